@@ -0,0 +1,70 @@
+use super::random;
+use na::{Isometry2, Point2, Vector2};
+use ncollide2d::bounding_volume::{self, BoundingVolume, DOP8, KDOP};
+use ncollide2d::partitioning::{BVH, BVT};
+use ncollide2d::query::visitors::CompositeClosestPointVisitor;
+use ncollide2d::query::{PointQuery, Ray, RayCast};
+use ncollide2d::shape::{CompositeShape, Cuboid, Polyline, SupportMap};
+
+#[test]
+fn dop8_bounding_volume_and_queries() {
+    let cuboid = Cuboid::new(Vector2::new(3.0, 0.5));
+    let id = Isometry2::identity();
+
+    for v in random(20 * 3, 42).chunks(3) {
+        let m = Isometry2::new(Vector2::new(v[0], v[1]) * 10.0, v[2] * 3.0);
+        let kdop: DOP8<f32> = bounding_volume::kdop(&cuboid, &m);
+        let aabb = bounding_volume::aabb(&cuboid, &m);
+        assert!(KDOP::from_aabb(&aabb.loosened(1.0e-4)).contains(&kdop));
+
+        let boundary: Vec<_> = random(100 * 2, 5)
+            .chunks(2)
+            .map(|d| {
+                let dir = Vector2::new(d[0], d[1]).normalize();
+                assert!(kdop
+                    .loosened(1.0e-4)
+                    .contains_local_point(&cuboid.support_point(&m, &dir)));
+
+                let ray = Ray::new(kdop.center(), dir);
+                let toi = kdop.toi_with_ray(&id, &ray, f32::MAX, false).unwrap();
+                ray.point_at(toi)
+            })
+            .collect();
+
+        for d in random(30 * 2, 11).chunks(2) {
+            let pt = m * (Point2::new(d[0], d[1]) * 8.0);
+            let proj = kdop.project_point(&id, &pt, true);
+            assert_eq!(proj.is_inside, kdop.contains_point(&id, &pt));
+
+            if !proj.is_inside {
+                // The projection on a convex set is characterized by this inequality.
+                assert!(kdop.loosened(1.0e-4).contains_local_point(&proj.point));
+
+                for b in &boundary {
+                    assert!((pt - proj.point).dot(&(b - proj.point)) < 1.0e-3);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn polyline_dop8_hierarchy() {
+    // A zigzag along the diagonal.
+    let points = (0..100)
+        .map(|i| {
+            let offset = if i % 2 == 0 { 0.1 } else { -0.1 };
+            Point2::new(i as f32 * 0.3 + offset, i as f32 * 0.3 - offset)
+        })
+        .collect();
+    let polyline = Polyline::new(points, None);
+    let kdop_tree: BVT<usize, DOP8<f32>> = polyline.build_bvt();
+
+    for v in random(40, 7).chunks(2) {
+        let pt = Point2::new(v[0], v[1]) * 20.0 + Vector2::repeat(15.0);
+        let expected = polyline.project_point(&Isometry2::identity(), &pt, true);
+        let mut visitor = CompositeClosestPointVisitor::new(&polyline, &pt, true);
+        let proj = kdop_tree.best_first_search(&mut visitor).unwrap().1;
+        assert!(na::distance(&proj.point, &expected.point) < 1.0e-4);
+    }
+}
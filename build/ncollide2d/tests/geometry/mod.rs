@@ -1,6 +1,17 @@
+use rand::{Rng, SeedableRng};
+use rand_isaac::IsaacRng;
+
 mod ball_ball_toi;
 mod ball_cuboid_contact;
 mod compound_penetration;
 mod epa2;
+mod kdop;
+mod obb;
 mod ray_cast;
 mod time_of_impact2;
+
+// Deterministic pseudo-random numbers in [-1, 1).
+pub fn random(n: usize, seed: u64) -> Vec<f32> {
+    let mut rng: IsaacRng = SeedableRng::seed_from_u64(seed);
+    (0..n).map(|_| rng.gen_range(-1.0, 1.0)).collect()
+}
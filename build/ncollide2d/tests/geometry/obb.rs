@@ -0,0 +1,61 @@
+use super::random;
+use na::{Isometry2, Point2, Vector2};
+use ncollide2d::bounding_volume::{BoundingVolume, OBB};
+use ncollide2d::partitioning::{BVH, BVT};
+use ncollide2d::query::visitors::CompositeClosestPointVisitor;
+use ncollide2d::query::{self, PointQuery};
+use ncollide2d::shape::{CompositeShape, Polyline};
+
+#[test]
+fn obb_bounding_volume() {
+    let obbs: Vec<_> = random(100 * 5, 42)
+        .chunks(5)
+        .map(|v| {
+            let position = Isometry2::new(Vector2::new(v[0], v[1]) * 3.0, v[2] * 3.0);
+            OBB::new(position, Vector2::new(v[3], v[4]).abs() * 2.0)
+        })
+        .collect();
+
+    for (i, obb1) in obbs.iter().enumerate() {
+        for obb2 in &obbs[i..] {
+            let dist = query::distance(
+                obb1.position(),
+                &obb1.cuboid(),
+                obb2.position(),
+                &obb2.cuboid(),
+            );
+
+            if dist > 1.0e-3 {
+                assert!(!obb1.intersects(obb2));
+            } else if dist == 0.0 {
+                assert!(obb1.intersects(obb2));
+            }
+
+            let merged = obb1.merged(obb2);
+            assert!(merged.contains(obb1));
+            assert!(merged.contains(obb2));
+        }
+    }
+}
+
+#[test]
+fn polyline_obb_hierarchy() {
+    // A zigzag along the diagonal.
+    let points = (0..100)
+        .map(|i| {
+            let offset = if i % 2 == 0 { 0.1 } else { -0.1 };
+            Point2::new(i as f32 * 0.3 + offset, i as f32 * 0.3 - offset)
+        })
+        .collect();
+    let polyline = Polyline::new(points, None);
+    let obb_tree: BVT<usize, OBB<f32>> = polyline.build_bvt();
+    assert!(obb_tree.sah_cost::<f32>() < polyline.bvt().sah_cost::<f32>());
+
+    for v in random(40, 7).chunks(2) {
+        let pt = Point2::new(v[0], v[1]) * 20.0 + Vector2::repeat(15.0);
+        let expected = polyline.project_point(&Isometry2::identity(), &pt, true);
+        let mut visitor = CompositeClosestPointVisitor::new(&polyline, &pt, true);
+        let proj = obb_tree.best_first_search(&mut visitor).unwrap().1;
+        assert!(na::distance(&proj.point, &expected.point) < 1.0e-4);
+    }
+}
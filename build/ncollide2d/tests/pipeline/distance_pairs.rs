@@ -0,0 +1,55 @@
+use na::{Isometry2, Point2, Vector2};
+use ncollide2d::pipeline::{CollisionGroups, CollisionWorld, DistanceEvent, GeometricQueryType};
+use ncollide2d::query::ClosestPoints;
+use ncollide2d::shape::{Ball, Cuboid, ShapeHandle};
+
+#[test]
+fn distance_pairs_track_closest_points() {
+    let mut world = CollisionWorld::new(0.0);
+    let ball = ShapeHandle::new(Ball::new(1.0));
+    let cuboid = ShapeHandle::new(Cuboid::new(Vector2::new(1.0, 1.0)));
+
+    let (sensor, _) = world.add(
+        Isometry2::new(Vector2::new(0.0, 0.0), 0.0),
+        ball,
+        CollisionGroups::new(),
+        GeometricQueryType::Distance(5.0),
+        (),
+    );
+    let (target, _) = world.add(
+        Isometry2::new(Vector2::new(4.0, 0.0), 0.0),
+        cuboid,
+        CollisionGroups::new(),
+        GeometricQueryType::Contacts(0.0, 0.0),
+        (),
+    );
+
+    world.update();
+
+    match world.distance_events().iter().next() {
+        Some(DistanceEvent::Started(..)) => {}
+        _ => panic!("A distance started event should have been generated."),
+    }
+
+    let (h1, _, pts) = world.distance_pair(sensor, target, true).unwrap();
+    let (p_sensor, p_target) = match *pts {
+        ClosestPoints::WithinMargin(p1, p2) if h1 == sensor => (p1, p2),
+        ClosestPoints::WithinMargin(p1, p2) => (p2, p1),
+        _ => panic!("The closest points should be within margin."),
+    };
+    assert_relative_eq!(p_sensor, Point2::new(1.0, 0.0), epsilon = 1.0e-5);
+    assert_relative_eq!(p_target, Point2::new(3.0, 0.0), epsilon = 1.0e-5);
+
+    // Move the target out of range.
+    world
+        .get_mut(target)
+        .unwrap()
+        .set_position(Isometry2::new(Vector2::new(10.0, 0.0), 0.0));
+    world.update();
+
+    match world.distance_events().iter().next() {
+        Some(DistanceEvent::Stopped(..)) => {}
+        _ => panic!("A distance stopped event should have been generated."),
+    }
+    assert!(world.distance_pair(sensor, target, true).is_none());
+}
@@ -1,2 +1,3 @@
+mod distance_pairs;
 mod is_send_sync;
 mod world_remove;
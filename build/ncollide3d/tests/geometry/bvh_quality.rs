@@ -0,0 +1,259 @@
+use super::random;
+use na::{Isometry3, Point3, Vector3};
+use ncollide3d::bounding_volume::{BoundingVolume, AABB};
+use ncollide3d::partitioning::{BVTNodeId, BVTRebuildPolicy, DBVTLeaf, DBVTLeafId, BVH, BVT, DBVT};
+use ncollide3d::procedural;
+use ncollide3d::query::visitors::BoundingVolumeInterferencesCollector;
+use ncollide3d::query::PointQuery;
+use ncollide3d::shape::{DeformableShape, TriMesh};
+
+// Small boxes packed in a few clusters, and a few large boxes, as in a typical scene.
+fn aabbs(n: usize, seed: u64) -> Vec<AABB<f32>> {
+    let values = random(n * 4, seed);
+
+    values
+        .chunks(4)
+        .enumerate()
+        .map(|(i, v)| {
+            let cluster = Vector3::new((i % 3) as f32 * 50.0, 0.0, (i % 5) as f32 * 20.0);
+            let center = Point3::new(v[0] * 10.0, v[1] * 2.0, v[2] * 10.0) + cluster;
+            let half_extents = if i % 20 == 0 {
+                Vector3::new(8.0, 0.5, 8.0)
+            } else {
+                Vector3::repeat(0.1 + v[3].abs() * 0.4)
+            };
+
+            AABB::from_half_extents(center, half_extents)
+        })
+        .collect()
+}
+
+// Checks every internal node encloses its children, and returns the leaves data.
+fn check_bvh<B: BVH<usize, AABB<f32>>>(bvh: &B) -> Vec<usize> {
+    let mut stack: Vec<_> = bvh.root().into_iter().collect();
+    let mut leaves = Vec::new();
+
+    while let Some(node) = stack.pop() {
+        let (bv, data) = bvh.content(node);
+
+        if let Some(data) = data {
+            leaves.push(*data);
+        }
+
+        for i in 0..bvh.num_children(node) {
+            let child = bvh.child(i, node);
+            assert!(bv.contains(bvh.content(child).0));
+            stack.push(child);
+        }
+    }
+
+    leaves.sort();
+    leaves
+}
+
+fn interferences<B: BVH<usize, AABB<f32>>>(bvh: &B, aabb: &AABB<f32>) -> Vec<usize> {
+    let mut result = Vec::new();
+    bvh.visit(&mut BoundingVolumeInterferencesCollector::new(
+        aabb,
+        &mut result,
+    ));
+    result.sort();
+    result
+}
+
+#[test]
+fn bvt_sah_construction() {
+    let aabbs = aabbs(500, 42);
+    let leaves: Vec<_> = aabbs.iter().cloned().enumerate().collect();
+    let balanced = BVT::new_balanced(leaves.clone());
+
+    for &max_leaf_size in &[1, 4, 16] {
+        let sah = BVT::new_sah(leaves.clone(), max_leaf_size);
+        assert_eq!(check_bvh(&sah), (0..aabbs.len()).collect::<Vec<_>>());
+        assert!(sah.sah_cost::<f32>() < balanced.sah_cost::<f32>());
+
+        for query in &aabbs[..20] {
+            let query = query.loosened(1.0);
+            let expected: Vec<_> = (0..aabbs.len())
+                .filter(|i| aabbs[*i].intersects(&query))
+                .collect();
+            assert_eq!(interferences(&sah, &query), expected);
+        }
+    }
+}
+
+#[test]
+fn bvt_sah_construction_with_identical_leaves() {
+    let aabb = AABB::new(Point3::origin(), Point3::new(1.0, 1.0, 1.0));
+    let bvt = BVT::new_sah((0..10).map(|i| (i, aabb.clone())).collect(), 1);
+    assert_eq!(check_bvh(&bvt), (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn dbvt_quality_after_many_updates() {
+    let aabbs = aabbs(500, 42);
+    let mut dbvt = DBVT::new();
+    let mut ids: Vec<DBVTLeafId> = aabbs
+        .iter()
+        .enumerate()
+        .map(|(i, aabb)| dbvt.insert(DBVTLeaf::new(aabb.clone(), i)))
+        .collect();
+
+    // Move the objects around by removing and reinserting them.
+    for (step, offsets) in random(10 * aabbs.len(), 7).chunks(aabbs.len()).enumerate() {
+        for (i, offset) in offsets.iter().enumerate() {
+            if (i + step) % 3 != 0 {
+                continue;
+            }
+
+            let shift = Vector3::new(offset * 2.0, 0.0, -offset * 2.0);
+            let leaf = dbvt.remove(ids[i]);
+            let aabb = AABB::new(
+                leaf.bounding_volume.mins() + shift,
+                leaf.bounding_volume.maxs() + shift,
+            );
+            ids[i] = dbvt.insert(DBVTLeaf::new(aabb, i));
+        }
+    }
+
+    assert_eq!(check_bvh(&dbvt), (0..aabbs.len()).collect::<Vec<_>>());
+
+    let moved: Vec<_> = ids
+        .iter()
+        .map(|id| dbvt[*id].bounding_volume.clone())
+        .collect();
+    let rebuilt = BVT::new_sah(moved.iter().cloned().enumerate().collect(), 1);
+    assert!(dbvt.sah_cost::<f32>() < rebuilt.sah_cost::<f32>() * 2.0);
+
+    for query in &moved[..20] {
+        let query = query.loosened(1.0);
+        let expected: Vec<_> = (0..moved.len())
+            .filter(|i| moved[*i].intersects(&query))
+            .collect();
+        assert_eq!(interferences(&dbvt, &query), expected);
+    }
+
+    // Removing the leaves keeps the tree valid.
+    for (i, id) in ids.iter().enumerate().filter(|(i, _)| i % 2 == 0) {
+        assert_eq!(dbvt.remove(*id).data, i);
+    }
+
+    let remaining: Vec<_> = (0..aabbs.len()).filter(|i| i % 2 == 1).collect();
+    assert_eq!(check_bvh(&dbvt), remaining);
+}
+
+#[test]
+fn bvt_rebuild_degraded_subtrees() {
+    let aabbs = aabbs(500, 42);
+    let mut bvt = BVT::new_balanced(aabbs.iter().cloned().enumerate().collect());
+    let mut policy = BVTRebuildPolicy::new(&bvt, 1.5);
+    assert!(policy.degraded_subtrees(&bvt).is_empty());
+
+    // Shuffle the leaves so that the refitted tree becomes very poor.
+    let moved: Vec<_> = (0..aabbs.len())
+        .map(|i| aabbs[(i * 7919) % aabbs.len()].clone())
+        .collect();
+    let data: Vec<_> = bvt.leaves().iter().map(|leaf| *leaf.data()).collect();
+
+    for (i, leaf_data) in data.iter().enumerate() {
+        bvt.set_leaf_bounding_volume(i, moved[*leaf_data].clone(), false);
+    }
+
+    bvt.refit(0.0);
+    let refitted_cost = bvt.sah_cost::<f32>();
+    assert!(!policy.degraded_subtrees(&bvt).is_empty());
+    assert!(policy.rebuild_degraded(&mut bvt) > 0);
+    assert!(policy.degraded_subtrees(&bvt).is_empty());
+    assert!(bvt.sah_cost::<f32>() < refitted_cost);
+
+    // The leaves kept their indices.
+    for (leaf, leaf_data) in bvt.leaves().iter().zip(&data) {
+        assert_eq!(leaf.data(), leaf_data);
+    }
+
+    assert_eq!(check_bvh(&bvt), (0..aabbs.len()).collect::<Vec<_>>());
+
+    for query in &moved[..20] {
+        let query = query.loosened(1.0);
+        let expected: Vec<_> = (0..moved.len())
+            .filter(|i| moved[*i].intersects(&query))
+            .collect();
+        assert_eq!(interferences(&bvt, &query), expected);
+    }
+
+    // The rebuilt tree can still be refitted incrementally.
+    let shifted = AABB::new(
+        moved[data[0]].mins() + Vector3::repeat(3.0),
+        moved[data[0]].maxs() + Vector3::repeat(3.0),
+    );
+    bvt.set_leaf_bounding_volume(0, shifted.clone(), true);
+    let root = bvt.root().unwrap();
+    assert!(bvt.content(root).0.contains(&shifted));
+    assert_eq!(check_bvh(&bvt), (0..aabbs.len()).collect::<Vec<_>>());
+
+    // Rebuilding the root is equivalent to a full rebuild.
+    policy.rebuild(&mut bvt, &[BVTNodeId::Internal(0)]);
+    assert_eq!(check_bvh(&bvt), (0..aabbs.len()).collect::<Vec<_>>());
+}
+
+#[test]
+fn trimesh_bvt_rebuild_after_deformations() {
+    let mut trimesh: TriMesh<f32> = procedural::quad(10.0, 10.0, 20, 20).into();
+    let mut rebuilt = trimesh.clone();
+    let policy = BVTRebuildPolicy::new(rebuilt.bvt(), 1.5);
+    rebuilt.set_bvt_rebuild_policy(Some(policy));
+
+    // Permute the vertices so that the original hierarchy becomes poor.
+    let n = trimesh.points().len();
+    let coords: Vec<f32> = (0..n)
+        .flat_map(|i| {
+            let pt = trimesh.points()[(i * 211) % n];
+            vec![pt.x, pt.y, pt.z]
+        })
+        .collect();
+
+    trimesh.set_deformations(&coords);
+    rebuilt.set_deformations(&coords);
+    assert!(rebuilt.bvt().sah_cost::<f32>() < trimesh.bvt().sah_cost::<f32>());
+
+    for pt in random(60, 7).chunks(3) {
+        let pt = Point3::new(pt[0] * 6.0, pt[1] * 2.0, pt[2] * 6.0);
+        let proj1 = trimesh.project_point(&Isometry3::identity(), &pt, true);
+        let proj2 = rebuilt.project_point(&Isometry3::identity(), &pt, true);
+        assert!((na::distance(&pt, &proj1.point) - na::distance(&pt, &proj2.point)).abs() < 1.0e-5);
+    }
+}
+
+#[cfg(feature = "parallel")]
+fn assert_same_layout(bvt1: &BVT<usize, AABB<f32>>, bvt2: &BVT<usize, AABB<f32>>) {
+    let mut stack = vec![(bvt1.root(), bvt2.root())];
+
+    while let Some((node1, node2)) = stack.pop() {
+        assert_eq!(node1, node2);
+
+        if let (Some(node1), Some(node2)) = (node1, node2) {
+            assert_eq!(bvt1.content(node1), bvt2.content(node2));
+
+            for i in 0..bvt1.num_children(node1) {
+                stack.push((Some(bvt1.child(i, node1)), Some(bvt2.child(i, node2))));
+            }
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "parallel")]
+fn parallel_bvt_construction() {
+    for &n in &[1, 100, 20_000] {
+        let leaves: Vec<_> = aabbs(n, 42).into_iter().enumerate().collect();
+
+        let balanced = BVT::new_balanced(leaves.clone());
+        let parallel = BVT::new_balanced_parallel(leaves.clone());
+        assert_same_layout(&balanced, &parallel);
+
+        let sah = |depth, leaves| BVT::sah_partitioning(8, 4, depth, leaves);
+        let sah_tree = BVT::from_partitioning(leaves.clone(), &mut |d, l| sah(d, l));
+        let parallel = BVT::from_partitioning_parallel(leaves, &sah);
+        assert_same_layout(&sah_tree, &parallel);
+    }
+}
@@ -0,0 +1,249 @@
+use na::{Isometry3, Point3, RealField, Translation3, Vector3};
+use ncollide3d::bounding_volume::{BoundingSphere, KDOPAxes, AABB, DOP14, DOP18, KDOP, OBB};
+use ncollide3d::partitioning::{BVH, BVT};
+use ncollide3d::procedural;
+use ncollide3d::query::{Ray, RayCast};
+use ncollide3d::shape::{Ball, CompositeShape, Compound, Cuboid, ShapeHandle, TriMesh};
+use ncollide3d::utils::{BinaryCodec, DecodingError};
+use std::fmt::Debug;
+
+// A 6-DOP, which is encoded with as many bytes as an AABB with twice more precise scalars.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct DOP6Axes;
+
+impl<N: RealField> KDOPAxes<N> for DOP6Axes {
+    type Bounds = [N; 3];
+    const AXES: &'static [[i8; 3]] = &[[1, 0, 0], [0, 1, 0], [0, 0, 1]];
+
+    fn bounds(value: N) -> Self::Bounds {
+        [value; 3]
+    }
+}
+
+fn sphere_mesh() -> TriMesh<f32> {
+    let mut mesh = procedural::sphere(4.0, 20, 20, true);
+    mesh.translate_by(&Translation3::new(1.0, 2.0, 3.0));
+    mesh.into()
+}
+
+fn assert_same_bvt<BV: PartialEq + Debug>(bvt1: &BVT<usize, BV>, bvt2: &BVT<usize, BV>) {
+    assert_eq!(bvt1.leaves().len(), bvt2.leaves().len());
+    let mut stack: Vec<_> = bvt1.root().into_iter().zip(bvt2.root()).collect();
+
+    while let Some((node1, node2)) = stack.pop() {
+        assert_eq!(bvt1.content(node1), bvt2.content(node2));
+        assert_eq!(bvt1.num_children(node1), bvt2.num_children(node2));
+
+        for i in 0..bvt1.num_children(node1) {
+            stack.push((bvt1.child(i, node1), bvt2.child(i, node2)));
+        }
+    }
+}
+
+fn round_trip<BV: BinaryCodec + PartialEq + Debug>(bvt: &BVT<usize, BV>) {
+    let bytes = bvt.to_bytes();
+    let decoded = BVT::from_bytes(&bytes).unwrap();
+    assert_same_bvt(bvt, &decoded);
+    assert_eq!(decoded.to_bytes(), bytes);
+}
+
+#[test]
+fn bvt_binary_round_trip() {
+    let mesh = sphere_mesh();
+    round_trip(mesh.bvt());
+    round_trip(&mesh.build_bvt::<OBB<f32>>());
+    round_trip(&mesh.build_bvt::<BoundingSphere<f32>>());
+    round_trip(&mesh.build_bvt::<DOP14<f32>>());
+    round_trip(&BVT::<usize, OBB<f32>>::new_balanced(Vec::new()));
+}
+
+#[test]
+fn trimesh_binary_round_trip() {
+    let mut mesh = sphere_mesh();
+    mesh.set_oriented(true);
+    let decoded = TriMesh::from_bytes(&mesh.to_bytes()).unwrap();
+    assert!(decoded.oriented());
+    let with_bvt = TriMesh::with_bvt(
+        mesh.points().to_vec(),
+        mesh.faces().iter().map(|f| f.indices).collect(),
+        mesh.uvs().map(|uvs| uvs.to_vec()),
+        BVT::from_bytes(&mesh.bvt().to_bytes()).unwrap(),
+    );
+
+    // The topology is decoded as it was encoded instead of being recomputed.
+    for (edge1, edge2) in decoded.edges().iter().zip(mesh.edges()) {
+        assert_eq!(edge1.indices, edge2.indices);
+        assert_eq!(edge1.adj_faces.0.face_id, edge2.adj_faces.0.face_id);
+        assert_eq!(edge1.adj_faces.1.face_id, edge2.adj_faces.1.face_id);
+    }
+
+    for (vtx1, vtx2) in decoded.vertices().iter().zip(mesh.vertices()) {
+        assert_eq!(vtx1.adj_faces, vtx2.adj_faces);
+        assert_eq!(vtx1.adj_vertices, vtx2.adj_vertices);
+    }
+
+    for other in &[decoded, with_bvt] {
+        assert_eq!(other.points(), mesh.points());
+        assert_eq!(other.uvs(), mesh.uvs());
+        assert_eq!(other.edges().len(), mesh.edges().len());
+        assert_same_bvt(other.bvt(), mesh.bvt());
+
+        for (face1, face2) in other.faces().iter().zip(mesh.faces()) {
+            assert_eq!(face1.indices, face2.indices);
+            assert_eq!(face1.edges, face2.edges);
+        }
+
+        for i in 0..20 {
+            let dir = Vector3::new(1.0, (i as f32).sin(), (i as f32).cos());
+            let ray = Ray::new(Point3::new(-10.0, 2.0, 3.0), dir);
+            let m = Isometry3::identity();
+            let inter1 = other.toi_and_normal_with_ray(&m, &ray, 100.0, true);
+            let inter2 = mesh.toi_and_normal_with_ray(&m, &ray, 100.0, true);
+            assert_eq!(inter1.map(|i| i.toi), inter2.map(|i| i.toi));
+            assert_eq!(inter1.map(|i| i.feature), inter2.map(|i| i.feature));
+        }
+    }
+}
+
+#[test]
+fn compound_with_bvt() {
+    let shapes = vec![
+        (
+            Isometry3::translation(1.0, 0.0, 0.0),
+            ShapeHandle::new(Ball::new(0.5f32)),
+        ),
+        (
+            Isometry3::translation(-1.0, 0.0, 0.0),
+            ShapeHandle::new(Cuboid::new(Vector3::repeat(0.5))),
+        ),
+        (
+            Isometry3::translation(0.0, 2.0, 0.0),
+            ShapeHandle::new(Ball::new(1.0)),
+        ),
+    ];
+    let compound = Compound::new(shapes.clone());
+    let bvt = BVT::from_bytes(&compound.bvt().to_bytes()).unwrap();
+    let decoded = Compound::with_bvt(shapes, bvt);
+
+    assert_same_bvt(decoded.bvt(), compound.bvt());
+    assert_eq!(decoded.aabb(), compound.aabb());
+
+    for i in 0..3 {
+        assert_eq!(decoded.aabb_at(i), compound.aabb_at(i));
+    }
+}
+
+#[test]
+#[should_panic]
+fn trimesh_with_mismatched_bvt() {
+    let mesh = sphere_mesh();
+    let other: TriMesh<f32> = procedural::cuboid(&Vector3::repeat(1.0)).into();
+    let _ = TriMesh::with_bvt(
+        mesh.points().to_vec(),
+        mesh.faces().iter().map(|f| f.indices).collect(),
+        None,
+        other.bvt().clone(),
+    );
+}
+
+#[test]
+fn binary_type_tags() {
+    assert_ne!(f32::TYPE_TAG, u32::TYPE_TAG);
+    assert_ne!(f64::TYPE_TAG, u64::TYPE_TAG);
+    assert_eq!(usize::TYPE_TAG, u64::TYPE_TAG);
+    assert_ne!(Vector3::<f32>::TYPE_TAG, Vector3::<u32>::TYPE_TAG);
+    assert_ne!(Point3::<f32>::TYPE_TAG, Vector3::<f32>::TYPE_TAG);
+    assert_ne!(AABB::<f64>::TYPE_TAG, KDOP::<f32, DOP6Axes>::TYPE_TAG);
+    assert_ne!(DOP14::<f32>::TYPE_TAG, DOP18::<f32>::TYPE_TAG);
+}
+
+#[test]
+fn invalid_binary_data() {
+    let mesh = sphere_mesh();
+    let bytes = mesh.bvt().to_bytes();
+    let mesh_bytes = mesh.to_bytes();
+
+    let mut invalid_magic = bytes.clone();
+    invalid_magic[0] = b'X';
+    assert_eq!(
+        BVT::<usize, AABB<f32>>::from_bytes(&invalid_magic).err(),
+        Some(DecodingError::InvalidMagic)
+    );
+    assert_eq!(
+        TriMesh::<f32>::from_bytes(&bytes).err(),
+        Some(DecodingError::InvalidMagic)
+    );
+
+    let mut new_version = bytes.clone();
+    new_version[4] += 1;
+    assert!(matches!(
+        BVT::<usize, AABB<f32>>::from_bytes(&new_version),
+        Err(DecodingError::UnsupportedVersion(_))
+    ));
+
+    // Scalar or bounding volume types which differ from the encoded ones.
+    assert_eq!(
+        BVT::<usize, AABB<f64>>::from_bytes(&bytes).err(),
+        Some(DecodingError::IncompatibleLayout)
+    );
+    assert_eq!(
+        BVT::<usize, BoundingSphere<f32>>::from_bytes(&bytes).err(),
+        Some(DecodingError::IncompatibleLayout)
+    );
+    assert_eq!(
+        TriMesh::<f64>::from_bytes(&mesh_bytes).err(),
+        Some(DecodingError::IncompatibleLayout)
+    );
+
+    // Data types with the same size as the encoded ones, but another encoding.
+    assert_eq!(
+        BVT::<f64, AABB<f32>>::from_bytes(&bytes).err(),
+        Some(DecodingError::IncompatibleLayout)
+    );
+    assert_eq!(
+        BVT::<usize, AABB<f64>>::from_bytes(&mesh.build_bvt::<KDOP<f32, DOP6Axes>>().to_bytes())
+            .err(),
+        Some(DecodingError::IncompatibleLayout)
+    );
+    assert!(BVT::<u64, AABB<f32>>::from_bytes(&bytes).is_ok());
+
+    // Truncated buffers.
+    for len in (0..100).chain((100..bytes.len()).step_by(101)) {
+        assert!(BVT::<usize, AABB<f32>>::from_bytes(&bytes[..len]).is_err());
+    }
+
+    for len in (0..100).chain((100..mesh_bytes.len()).step_by(101)) {
+        assert!(TriMesh::<f32>::from_bytes(&mesh_bytes[..len]).is_err());
+    }
+
+    // Trailing bytes.
+    let mut trailing = mesh_bytes.clone();
+    trailing.push(0);
+    assert_eq!(
+        TriMesh::<f32>::from_bytes(&trailing).err(),
+        Some(DecodingError::InvalidData)
+    );
+
+    // An out of bounds face index in the topology: the first adjacent face of the first vertex.
+    let mut invalid_topology = mesh_bytes.clone();
+    let num_points = mesh.points().len();
+    let num_faces = mesh.faces().len();
+    let uvs_len = mesh.uvs().map_or(0, |uvs| 8 + uvs.len() * 8);
+    let topology = 4 + 4 + 4 + 16 + 1 + 8 + num_points * 12 + 8 + num_faces * 24 + 1 + uvs_len;
+    let adj_face_list = topology + num_points * 32 + 8;
+    invalid_topology[adj_face_list..adj_face_list + 8]
+        .copy_from_slice(&(num_faces as u64).to_le_bytes());
+    assert_eq!(
+        TriMesh::<f32>::from_bytes(&invalid_topology).err(),
+        Some(DecodingError::InvalidData)
+    );
+
+    // A node referenced twice: set the right child of the first internal node to its left child.
+    let mut invalid_tree = bytes.clone();
+    let left = 4 + 4 + 4 + 2 * (8 + 8) + 9 + 8 + 24;
+    invalid_tree.copy_within(left..left + 9, left + 9);
+    assert_eq!(
+        BVT::<usize, AABB<f32>>::from_bytes(&invalid_tree).err(),
+        Some(DecodingError::InvalidData)
+    );
+}
@@ -0,0 +1,40 @@
+use na::{Isometry3, Point3, Vector3};
+use ncollide3d::shape::{Compound, Cuboid, ShapeHandle, TriMesh};
+
+fn parts() -> Vec<(Isometry3<f32>, ShapeHandle<f32>)> {
+    let mesh = TriMesh::new(
+        vec![
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(0.0, 0.0, 1.0),
+        ],
+        vec![Point3::new(0, 2, 1)],
+        None,
+    );
+
+    vec![
+        (
+            Isometry3::identity(),
+            ShapeHandle::new(Cuboid::new(Vector3::repeat(1.0))),
+        ),
+        (
+            Isometry3::translation(5.0, 0.0, 0.0),
+            ShapeHandle::new(mesh),
+        ),
+    ]
+}
+
+#[test]
+#[should_panic(expected = "Nested composite shapes are not allowed.")]
+fn compound_with_nested_composite_shape() {
+    let _ = Compound::new(parts());
+}
+
+#[test]
+#[should_panic(expected = "Nested composite shapes are not allowed.")]
+fn compound_with_bvt_and_nested_composite_shape() {
+    let parts = parts();
+    let convex_parts = vec![parts[0].clone(), parts[0].clone()];
+    let bvt = Compound::new(convex_parts).bvt().clone();
+    let _ = Compound::with_bvt(parts, bvt);
+}
@@ -1,6 +1,7 @@
 use na::{zero, Isometry3, Point3, Vector3};
 use ncollide3d::bounding_volume::*;
 use ncollide3d::pipeline::{CollisionGroups, CollisionWorld, GeometricQueryType};
+use ncollide3d::query::{Contact, ContactKinematic, ContactPreprocessor};
 use ncollide3d::shape::*;
 
 // Issue #182.
@@ -24,3 +25,45 @@ fn just_touching_nan3d() {
 
     world.update();
 }
+
+// Appends a digit to the first feature of the contacts, and rejects them if it is the first one.
+struct FeatureRemapper(usize);
+
+impl ContactPreprocessor<f32> for FeatureRemapper {
+    fn process_contact(
+        &self,
+        _: &mut Contact<f32>,
+        kinematic: &mut ContactKinematic<f32>,
+        _: bool,
+    ) -> bool {
+        match kinematic.feature1() {
+            FeatureId::Face(i) => {
+                kinematic.set_feature1(FeatureId::Face(i * 10 + self.0));
+                self.0 != 0
+            }
+            _ => false,
+        }
+    }
+}
+
+#[test]
+fn contact_preprocessor_pair_order() {
+    let mut contact = Contact::new(
+        Point3::origin(),
+        Point3::origin(),
+        Vector3::x_axis(),
+        0.0f32,
+    );
+    let mut kinematic = ContactKinematic::new();
+    kinematic.set_feature1(FeatureId::Face(1));
+
+    // The first preprocessor of the pair is applied first.
+    let pair = (Some(&FeatureRemapper(2)), &FeatureRemapper(3));
+    assert!(pair.process_contact(&mut contact, &mut kinematic, true));
+    assert_eq!(kinematic.feature1(), FeatureId::Face(123));
+
+    // The second preprocessor is not applied if the first one rejects the contact.
+    let pair = (Some(&FeatureRemapper(0)), &FeatureRemapper(4));
+    assert!(!pair.process_contact(&mut contact, &mut kinematic, true));
+    assert_eq!(kinematic.feature1(), FeatureId::Face(1230));
+}
@@ -0,0 +1,313 @@
+use super::random;
+use na::{Isometry3, Perspective3, Point3, Unit, Vector3};
+use ncollide3d::bounding_volume::{BoundingSphere, BoundingVolume, AABB, OBB};
+use ncollide3d::partitioning::{DBVTLeaf, BVT, DBVT};
+use ncollide3d::pipeline::broad_phase::{
+    BroadPhase, BroadPhaseInterferenceHandler, BroadPhaseProxyHandle, DBVTBroadPhase,
+};
+use ncollide3d::query::visitors::ConvexVolumeInterferencesCollector;
+use ncollide3d::query::{ConvexVolume, ConvexVolumeCulling, CullingStatus, Ray, RayIntersection};
+use std::collections::HashSet;
+
+// The frustum of a camera at the origin, looking toward the +x axis.
+fn frustum() -> (ConvexVolume<f32>, impl Fn(&Point3<f32>) -> bool) {
+    let view = Isometry3::look_at_rh(
+        &Point3::origin(),
+        &Point3::new(1.0, 0.0, 0.0),
+        &Vector3::z(),
+    );
+    let projection = Perspective3::new(1.5, 1.0, 0.5, 30.0);
+    let view_projection = projection.as_matrix() * view.to_homogeneous();
+    let frustum = ConvexVolume::from_view_projection(&view_projection);
+
+    let is_visible = move |pt: &Point3<f32>| {
+        let ndc = projection.project_point(&(view * pt));
+        view.transform_point(pt).z < 0.0 && ndc.coords.iter().all(|c| c.abs() <= 1.0)
+    };
+
+    (frustum, is_visible)
+}
+
+fn aabbs(n: usize, seed: u64) -> Vec<AABB<f32>> {
+    random(n * 6, seed)
+        .chunks(6)
+        .map(|v| {
+            let center = Point3::new(v[0] + 1.0, v[1], v[2]) * 20.0;
+            AABB::from_half_extents(center, Vector3::new(v[3], v[4], v[5]).abs() * 3.0)
+        })
+        .collect()
+}
+
+fn vertices(aabb: &AABB<f32>) -> Vec<Point3<f32>> {
+    (0..8)
+        .map(|i| {
+            let pick = |k: usize| {
+                if i & (1 << k) == 0 {
+                    aabb.mins()[k]
+                } else {
+                    aabb.maxs()[k]
+                }
+            };
+            Point3::new(pick(0), pick(1), pick(2))
+        })
+        .collect()
+}
+
+#[test]
+fn frustum_from_view_projection() {
+    let (frustum, is_visible) = frustum();
+    assert_eq!(frustum.planes().len(), 6);
+
+    for v in random(3000, 3).chunks(3) {
+        let pt = Point3::new(v[0] + 1.0, v[1], v[2]) * 20.0;
+
+        // Skip the points too close to the boundary.
+        let dist = frustum
+            .planes()
+            .iter()
+            .map(|(n, d)| (n.dot(&pt.coords) - d).abs())
+            .fold(f32::MAX, f32::min);
+
+        if dist > 1.0e-3 {
+            assert_eq!(frustum.contains_point(&pt), is_visible(&pt));
+        }
+    }
+}
+
+#[test]
+fn bounding_volume_culling_status() {
+    let (frustum, _) = frustum();
+    let mut num_inside = 0;
+    let mut num_outside = 0;
+
+    for aabb in aabbs(500, 7) {
+        let status = aabb.culling_status(&frustum);
+        let vertices = vertices(&aabb);
+
+        match status {
+            CullingStatus::Inside => {
+                num_inside += 1;
+                assert!(vertices.iter().all(|pt| frustum.contains_point(pt)));
+            }
+            CullingStatus::Outside => {
+                num_outside += 1;
+                assert!(vertices.iter().all(|pt| !frustum.contains_point(pt)));
+            }
+            CullingStatus::Intersecting => {}
+        }
+
+        // The bounding sphere and OBB of the AABB are located consistently.
+        let sphere = aabb.bounding_sphere();
+        let obb = OBB::from_aabb(&Isometry3::identity(), &aabb);
+        assert_eq!(obb.culling_status(&frustum), status);
+
+        if status == CullingStatus::Outside {
+            assert_ne!(sphere.culling_status(&frustum), CullingStatus::Inside);
+        } else {
+            assert_ne!(sphere.culling_status(&frustum), CullingStatus::Outside);
+        }
+    }
+
+    assert!(num_inside > 10);
+    assert!(num_outside > 10);
+
+    // A half-space.
+    let half_space = ConvexVolume::new(vec![(
+        Unit::new_normalize(Vector3::new(1.0, 1.0, 0.0)),
+        1.0,
+    )]);
+    let sphere = BoundingSphere::new(Point3::new(-1.0, -1.0, 0.0), 1.0);
+    assert_eq!(sphere.culling_status(&half_space), CullingStatus::Inside);
+    let sphere = BoundingSphere::new(Point3::new(2.0, 2.0, 0.0), 1.0);
+    assert_eq!(sphere.culling_status(&half_space), CullingStatus::Outside);
+    let sphere = BoundingSphere::new(Point3::new(1.0, 0.0, 0.0), 1.0);
+    assert_eq!(
+        sphere.culling_status(&half_space),
+        CullingStatus::Intersecting
+    );
+}
+
+#[test]
+fn convex_volume_aabb() {
+    let (frustum, _) = frustum();
+    let aabb = frustum.aabb();
+    assert_relative_eq!(aabb.mins().x, 0.5, epsilon = 1.0e-3);
+    assert_relative_eq!(aabb.maxs().x, 30.0, epsilon = 1.0e-3);
+    assert_relative_eq!(aabb.maxs().y, -aabb.mins().y, epsilon = 1.0e-3);
+    assert_relative_eq!(aabb.maxs().z, -aabb.mins().z, epsilon = 1.0e-3);
+
+    for v in random(3000, 3).chunks(3) {
+        let pt = Point3::new(v[0] + 1.0, v[1], v[2]) * 20.0;
+
+        if frustum.contains_point(&pt) {
+            assert!(aabb.contains_local_point(&pt));
+        }
+    }
+
+    // A cube.
+    let planes = [Vector3::x_axis(), Vector3::y_axis(), Vector3::z_axis()]
+        .iter()
+        .flat_map(|axis| vec![(*axis, 1.0), (-*axis, 1.0)])
+        .collect();
+    let aabb = ConvexVolume::new(planes).aabb();
+    assert_relative_eq!(aabb.mins(), &Point3::new(-1.0, -1.0, -1.0));
+    assert_relative_eq!(aabb.maxs(), &Point3::new(1.0, 1.0, 1.0));
+
+    // A half-space, and the corner of a cube.
+    let half_space = ConvexVolume::new(vec![(Vector3::x_axis(), 1.0)]);
+    let corner = ConvexVolume::new(vec![
+        (Vector3::x_axis(), 1.0),
+        (Vector3::y_axis(), 1.0),
+        (Vector3::z_axis(), 1.0),
+    ]);
+
+    for volume in &[half_space, corner] {
+        let aabb = volume.aabb();
+        assert!(aabb.mins().iter().all(|x| *x < -1.0e30));
+        assert!(aabb.maxs().iter().all(|x| *x > 1.0e30));
+    }
+}
+
+struct NoPairs;
+
+impl BroadPhaseInterferenceHandler<usize> for NoPairs {
+    fn is_interference_allowed(&mut self, _: &usize, _: &usize) -> bool {
+        false
+    }
+
+    fn interference_started(&mut self, _: &usize, _: &usize) {}
+
+    fn interference_stopped(&mut self, _: &usize, _: &usize) {}
+}
+
+#[test]
+fn bvh_and_broad_phase_frustum_culling() {
+    let (frustum, _) = frustum();
+    let aabbs = aabbs(500, 11);
+    let expected: HashSet<_> = (0..aabbs.len())
+        .filter(|i| aabbs[*i].culling_status(&frustum) != CullingStatus::Outside)
+        .collect();
+    assert!(!expected.is_empty() && expected.len() < aabbs.len());
+
+    let leaves = aabbs.iter().cloned().enumerate().collect();
+    let bvt = BVT::new_balanced(leaves);
+    let mut collected = Vec::new();
+    ConvexVolumeInterferencesCollector::new(&frustum, &mut collected).collect(&bvt);
+    assert_eq!(collected.len(), expected.len());
+    assert_eq!(collected.into_iter().collect::<HashSet<_>>(), expected);
+
+    let mut dbvt = DBVT::new();
+
+    for (i, aabb) in aabbs.iter().enumerate() {
+        let _ = dbvt.insert(DBVTLeaf::new(aabb.clone(), i));
+    }
+
+    let mut collected = Vec::new();
+    ConvexVolumeInterferencesCollector::new(&frustum, &mut collected).collect(&dbvt);
+    assert_eq!(collected.len(), expected.len());
+    assert_eq!(collected.into_iter().collect::<HashSet<_>>(), expected);
+
+    let mut broad_phase = DBVTBroadPhase::new(0.0);
+
+    for (i, aabb) in aabbs.iter().enumerate() {
+        let _ = broad_phase.create_proxy(aabb.clone(), i);
+    }
+
+    broad_phase.update(&mut NoPairs);
+    let mut collected = Vec::new();
+    broad_phase.culled_interferences_with_convex_volume(&frustum, &mut collected);
+    assert_eq!(collected.len(), expected.len());
+    assert_eq!(
+        collected.into_iter().cloned().collect::<HashSet<_>>(),
+        expected
+    );
+}
+
+// A broad phase relying on the default implementations of the optional queries.
+struct DefaultQueries(DBVTBroadPhase<f32, AABB<f32>, usize>);
+
+impl BroadPhase<f32, AABB<f32>, usize> for DefaultQueries {
+    fn create_proxy(&mut self, bv: AABB<f32>, data: usize) -> BroadPhaseProxyHandle {
+        self.0.create_proxy(bv, data)
+    }
+
+    fn proxy(&self, handle: BroadPhaseProxyHandle) -> Option<(&AABB<f32>, &usize)> {
+        self.0.proxy(handle)
+    }
+
+    fn remove(
+        &mut self,
+        handles: &[BroadPhaseProxyHandle],
+        removal_handler: &mut dyn FnMut(&usize, &usize),
+    ) {
+        self.0.remove(handles, removal_handler)
+    }
+
+    fn deferred_set_bounding_volume(&mut self, handle: BroadPhaseProxyHandle, bv: AABB<f32>) {
+        self.0.deferred_set_bounding_volume(handle, bv)
+    }
+
+    fn deferred_recompute_all_proximities_with(&mut self, handle: BroadPhaseProxyHandle) {
+        self.0.deferred_recompute_all_proximities_with(handle)
+    }
+
+    fn deferred_recompute_all_proximities(&mut self) {
+        self.0.deferred_recompute_all_proximities()
+    }
+
+    fn update(&mut self, handler: &mut dyn BroadPhaseInterferenceHandler<usize>) {
+        self.0.update(handler)
+    }
+
+    fn interferences_with_bounding_volume<'a>(&'a self, bv: &AABB<f32>, out: &mut Vec<&'a usize>) {
+        self.0.interferences_with_bounding_volume(bv, out)
+    }
+
+    fn interferences_with_ray<'a>(
+        &'a self,
+        ray: &Ray<f32>,
+        max_toi: f32,
+        out: &mut Vec<&'a usize>,
+    ) {
+        self.0.interferences_with_ray(ray, max_toi, out)
+    }
+
+    fn interferences_with_point<'a>(&'a self, point: &Point3<f32>, out: &mut Vec<&'a usize>) {
+        self.0.interferences_with_point(point, out)
+    }
+
+    fn first_interference_with_ray<'a, 'b>(
+        &'a self,
+        ray: &'b Ray<f32>,
+        max_toi: f32,
+        cost_fn: &'a dyn Fn(usize, &'b Ray<f32>, f32) -> Option<(usize, RayIntersection<f32>)>,
+    ) -> Option<(usize, RayIntersection<f32>)> {
+        self.0.first_interference_with_ray(ray, max_toi, cost_fn)
+    }
+}
+
+#[test]
+fn default_broad_phase_frustum_culling() {
+    let (frustum, _) = frustum();
+    let aabbs = aabbs(500, 11);
+    let frustum_aabb = frustum.aabb();
+    let mut broad_phase = DefaultQueries(DBVTBroadPhase::new(0.0));
+
+    for (i, aabb) in aabbs.iter().enumerate() {
+        let _ = broad_phase.create_proxy(aabb.clone(), i);
+    }
+
+    broad_phase.update(&mut NoPairs);
+    let mut collected = Vec::new();
+    broad_phase.interferences_with_convex_volume(&frustum, &mut collected);
+    let collected: HashSet<_> = collected.into_iter().cloned().collect();
+
+    // The default implementation is conservative.
+    for (i, aabb) in aabbs.iter().enumerate() {
+        if frustum.contains_point(&aabb.center()) {
+            assert!(collected.contains(&i));
+        }
+
+        assert_eq!(collected.contains(&i), aabb.intersects(&frustum_aabb));
+    }
+}
@@ -0,0 +1,244 @@
+use super::random;
+use na::{Isometry3, Point3, Translation3, UnitQuaternion, Vector3};
+use ncollide3d::bounding_volume::{
+    self, BoundingVolume, DOP14Axes, DOP18Axes, DOP26Axes, KDOPAxes, AABB, DOP14, DOP18, DOP26,
+    KDOP,
+};
+use ncollide3d::partitioning::{BVH, BVT};
+use ncollide3d::pipeline::broad_phase::{
+    BroadPhase, BroadPhaseInterferenceHandler, DBVTBroadPhase,
+};
+use ncollide3d::procedural;
+use ncollide3d::query::visitors::RayInterferencesCollector;
+use ncollide3d::query::{self, PointQuery, Ray, RayCast};
+use ncollide3d::shape::{Capsule, CompositeShape, Cuboid, SupportMap, TriMesh};
+use std::collections::HashSet;
+
+fn positions(n: usize, seed: u64) -> Vec<Isometry3<f32>> {
+    random(n * 6, seed)
+        .chunks(6)
+        .map(|v| {
+            Isometry3::new(
+                Vector3::new(v[0], v[1], v[2]) * 10.0,
+                Vector3::new(v[3], v[4], v[5]) * 3.0,
+            )
+        })
+        .collect()
+}
+
+fn directions(n: usize, seed: u64) -> Vec<Vector3<f32>> {
+    random(n * 3, seed)
+        .chunks(3)
+        .map(|v| Vector3::new(v[0], v[1], v[2]).normalize())
+        .collect()
+}
+
+fn check_bounds<A: KDOPAxes<f32>>(shape: &impl SupportMap<f32>, m: &Isometry3<f32>) {
+    let kdop: KDOP<f32, A> = bounding_volume::support_map_kdop(m, shape);
+    let aabb = bounding_volume::support_map_aabb(m, shape);
+
+    // The k-DOP contains the shape, and is contained by its AABB.
+    for dir in directions(50, 3) {
+        let pt = shape.support_point(m, &dir);
+        assert!(kdop.loosened(1.0e-4).contains_local_point(&pt));
+    }
+
+    assert!(KDOP::from_aabb(&aabb.loosened(1.0e-4)).contains(&kdop));
+    assert!(na::distance(kdop.aabb().mins(), aabb.mins()) < 1.0e-4);
+    assert!(na::distance(kdop.aabb().maxs(), aabb.maxs()) < 1.0e-4);
+
+    assert!(kdop.loosened(0.1).contains(&kdop));
+    assert!(!kdop.contains(&kdop.loosened(0.1)));
+    assert!(kdop
+        .loosened(0.1)
+        .tightened(0.1)
+        .contains(&kdop.tightened(1.0e-4)));
+    assert!(kdop.intersects(&kdop.loosened(0.1)));
+}
+
+#[test]
+fn kdop_bounding_volume() {
+    let cuboid = Cuboid::new(Vector3::new(3.0, 0.5, 1.0));
+    let capsule = Capsule::new(2.0, 0.3);
+
+    for m in positions(20, 42) {
+        check_bounds::<DOP14Axes>(&cuboid, &m);
+        check_bounds::<DOP18Axes>(&cuboid, &m);
+        check_bounds::<DOP26Axes>(&cuboid, &m);
+        check_bounds::<DOP14Axes>(&capsule, &m);
+        check_bounds::<DOP18Axes>(&capsule, &m);
+        check_bounds::<DOP26Axes>(&capsule, &m);
+    }
+}
+
+#[test]
+fn kdop_ray_cast_and_point_queries() {
+    let cuboid = Cuboid::new(Vector3::new(3.0, 0.5, 1.0));
+    let id = Isometry3::identity();
+
+    for m in positions(10, 7) {
+        let kdop: DOP26<f32> = bounding_volume::kdop(&cuboid, &m);
+        let center = kdop.center();
+
+        // Points on the boundary of the k-DOP.
+        let boundary: Vec<_> = directions(200, 5)
+            .into_iter()
+            .map(|dir| {
+                let ray = Ray::new(center, dir);
+                let inter = kdop
+                    .toi_and_normal_with_ray(&id, &ray, f32::MAX, false)
+                    .unwrap();
+                let pt = ray.point_at(inter.toi);
+                assert!(kdop.loosened(1.0e-4).contains_local_point(&pt));
+                assert!(!kdop.tightened(1.0e-3).contains_local_point(&pt));
+                assert!(inter.normal.dot(&dir) > 0.0);
+                assert_eq!(kdop.toi_with_ray(&id, &ray, f32::MAX, true), Some(0.0));
+                pt
+            })
+            .collect();
+
+        for v in random(90, 11).chunks(3) {
+            let pt = center + Vector3::new(v[0], v[1], v[2]) * 8.0;
+            let proj = kdop.project_point(&id, &pt, true);
+            assert_eq!(proj.is_inside, kdop.contains_point(&id, &pt));
+
+            if proj.is_inside {
+                assert_eq!(proj.point, pt);
+
+                let proj = kdop.project_point(&id, &pt, false);
+                assert!(proj.is_inside);
+                assert!(!kdop.tightened(1.0e-3).contains_local_point(&proj.point));
+
+                for b in &boundary {
+                    assert!(na::distance(&pt, &proj.point) <= na::distance(&pt, b) + 1.0e-4);
+                }
+            } else {
+                // The projection on a convex set is characterized by this inequality.
+                assert!(kdop.loosened(1.0e-4).contains_local_point(&proj.point));
+
+                for b in &boundary {
+                    assert!((pt - proj.point).dot(&(b - proj.point)) < 1.0e-3);
+                }
+
+                // A ray toward the projection hits the k-DOP at the projection.
+                let ray = Ray::new(pt, proj.point - pt);
+                let inter = kdop.toi_and_normal_with_ray(&id, &ray, 2.0, true).unwrap();
+                assert!((inter.toi - 1.0).abs() < 1.0e-3);
+                assert!(inter.normal.dot(&ray.dir) < 0.0);
+                assert!(!kdop.intersects_ray(&id, &ray, 0.99));
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct PairCollector {
+    pairs: HashSet<(usize, usize)>,
+}
+
+impl BroadPhaseInterferenceHandler<usize> for PairCollector {
+    fn is_interference_allowed(&mut self, a: &usize, b: &usize) -> bool {
+        a != b
+    }
+
+    fn interference_started(&mut self, a: &usize, b: &usize) {
+        let _ = self.pairs.insert((*a.min(b), *a.max(b)));
+    }
+
+    fn interference_stopped(&mut self, a: &usize, b: &usize) {
+        let _ = self.pairs.remove(&(*a.min(b), *a.max(b)));
+    }
+}
+
+#[test]
+fn kdop_broad_phase() {
+    let capsule = Capsule::new(3.0, 0.2);
+    let positions = positions(100, 3);
+    let mut aabb_broad_phase = DBVTBroadPhase::<f32, AABB<f32>, usize>::new(0.0);
+    let mut kdop_broad_phase = DBVTBroadPhase::<f32, DOP14<f32>, usize>::new(0.0);
+
+    for (i, m) in positions.iter().enumerate() {
+        let _ = aabb_broad_phase.create_proxy(bounding_volume::aabb(&capsule, m), i);
+        let _ = kdop_broad_phase.create_proxy(bounding_volume::kdop(&capsule, m), i);
+    }
+
+    let mut aabb_pairs = PairCollector::default();
+    let mut kdop_pairs = PairCollector::default();
+    aabb_broad_phase.update(&mut aabb_pairs);
+    kdop_broad_phase.update(&mut kdop_pairs);
+
+    // Every pair of intersecting capsules is found, with fewer false positives than with AABBs.
+    for i in 0..positions.len() {
+        for j in i + 1..positions.len() {
+            if query::distance(&positions[i], &capsule, &positions[j], &capsule) == 0.0 {
+                assert!(kdop_pairs.pairs.contains(&(i, j)));
+            }
+        }
+    }
+
+    assert!(kdop_pairs.pairs.len() < aabb_pairs.pairs.len());
+
+    let mut hits = Vec::new();
+    let ray = Ray::new(Point3::new(-20.0, 0.0, 0.0), Vector3::x());
+    kdop_broad_phase.interferences_with_ray(&ray, 40.0, &mut hits);
+
+    for (i, m) in positions.iter().enumerate() {
+        if capsule.intersects_ray(m, &ray, 40.0) {
+            assert!(hits.contains(&&i));
+        }
+    }
+}
+
+#[test]
+fn trimesh_kdop_hierarchy() {
+    let mut pipe = procedural::cylinder(1.0, 30.0, 12);
+    let rotation = UnitQuaternion::rotation_between(&Vector3::y(), &Vector3::repeat(1.0)).unwrap();
+    pipe.transform_by(&Isometry3::from_parts(Translation3::identity(), rotation));
+    let pipe: TriMesh<f32> = pipe.into();
+
+    let aabb_tree = pipe.bvt();
+    // Contrary to the 14-DOP, the 18-DOP has axes orthogonal to the pipe.
+    let kdop_tree: BVT<usize, DOP18<f32>> = pipe.build_bvt();
+    let pipe_kdop: DOP18<f32> = bounding_volume::local_kdop(&pipe);
+
+    for pt in pipe.points() {
+        assert!(pipe_kdop.contains_local_point(pt));
+    }
+
+    let mut num_aabb_hits = 0;
+    let mut num_kdop_hits = 0;
+
+    for v in random(60, 7).chunks(3) {
+        // A ray crossing the pipe.
+        let axis_pt = Point3::from(Vector3::repeat(v[0] * 8.0));
+        let dir = Vector3::new(v[1], v[2], -v[1] - v[2]).normalize();
+        let ray = Ray::new(axis_pt + dir * 3.0, -dir);
+        let mut aabb_hits = Vec::new();
+        let mut kdop_hits = Vec::new();
+        aabb_tree.visit(&mut RayInterferencesCollector::new(
+            &ray,
+            6.0,
+            &mut aabb_hits,
+        ));
+        kdop_tree.visit(&mut RayInterferencesCollector::new(
+            &ray,
+            6.0,
+            &mut kdop_hits,
+        ));
+
+        let hit_faces = |hits: &[usize]| {
+            hits.iter()
+                .filter(|i| {
+                    pipe.triangle_at(**i)
+                        .intersects_ray(&Isometry3::identity(), &ray, 6.0)
+                })
+                .count()
+        };
+        assert_eq!(hit_faces(&kdop_hits), 2);
+        assert_eq!(hit_faces(&aabb_hits), 2);
+        num_aabb_hits += aabb_hits.len();
+        num_kdop_hits += kdop_hits.len();
+    }
+
+    assert!(num_kdop_hits * 2 < num_aabb_hits);
+}
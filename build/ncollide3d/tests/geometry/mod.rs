@@ -1,11 +1,39 @@
+use na::Point3;
+use rand::{Rng, SeedableRng};
+use rand_isaac::IsaacRng;
+
 mod ball_ball_toi;
 mod ball_triangle_toi;
+mod bvh_quality;
+mod bvt_binary;
+mod compound;
 mod contact;
+mod convex_volume_culling;
 mod cuboid_ray_cast;
 mod cylinder_cuboid_contact;
 mod epa3;
 mod first_interference_with_ray;
 mod interferences_with_ray;
+mod kdop;
+mod nearest_objects;
+mod obb;
+mod qbvh;
+mod self_collision;
 mod still_objects_toi;
 mod time_of_impact3;
+mod traversal_stacks;
 mod trimesh_trimesh_toi;
+
+// Deterministic pseudo-random numbers in [-1, 1).
+pub fn random(n: usize, seed: u64) -> Vec<f32> {
+    let mut rng: IsaacRng = SeedableRng::seed_from_u64(seed);
+    (0..n).map(|_| rng.gen_range(-1.0, 1.0)).collect()
+}
+
+// Deterministic pseudo-random points in the cube [-10, 10)³.
+pub fn positions(n: usize, seed: u64) -> Vec<Point3<f32>> {
+    random(n * 3, seed)
+        .chunks(3)
+        .map(|v| Point3::new(v[0], v[1], v[2]) * 10.0)
+        .collect()
+}
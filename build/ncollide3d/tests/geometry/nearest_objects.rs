@@ -0,0 +1,228 @@
+use super::positions;
+use na::{Isometry3, Point3, Unit, Vector3};
+use ncollide3d::bounding_volume::{BoundingVolume, AABB};
+use ncollide3d::partitioning::{BestFirstVisitStatus, BestFirstVisitor, BVH, BVT};
+use ncollide3d::pipeline::{
+    CollisionGroups, CollisionObjectSlabHandle, CollisionWorld, GeometricQueryType,
+};
+use ncollide3d::query::{self, PointQuery};
+use ncollide3d::shape::{Ball, Compound, Cuboid, FeatureId, Shape, ShapeHandle};
+
+// Balls and cuboids at random positions, every third object being in the group 1.
+fn world() -> CollisionWorld<f32, ()> {
+    let mut world = CollisionWorld::new(0.0);
+    let query_type = GeometricQueryType::Contacts(0.0, 0.0);
+
+    for (i, pt) in positions(100, 42).into_iter().enumerate() {
+        let shape = if i % 2 == 0 {
+            ShapeHandle::new(Ball::new(0.5))
+        } else {
+            ShapeHandle::new(Cuboid::new(Vector3::new(0.2, 0.7, 0.4)))
+        };
+        let groups = CollisionGroups::new().with_membership(&[if i % 3 == 0 { 1 } else { 0 }]);
+        let _ = world.add(
+            Isometry3::translation(pt.x, pt.y, pt.z),
+            shape,
+            groups,
+            query_type,
+            (),
+        );
+    }
+
+    world.update();
+    world
+}
+
+fn brute_force_distances(
+    world: &CollisionWorld<f32, ()>,
+    groups: &CollisionGroups,
+    distance: impl Fn(&Isometry3<f32>, &dyn Shape<f32>) -> f32,
+) -> Vec<(CollisionObjectSlabHandle, f32)> {
+    let mut result: Vec<_> = world
+        .collision_objects()
+        .filter(|(_, co)| co.collision_groups().can_interact_with_groups(groups))
+        .map(|(handle, co)| (handle, distance(co.position(), co.shape().as_ref())))
+        .collect();
+    result.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+    result
+}
+
+#[test]
+fn k_nearest_to_point() {
+    let world = world();
+    let groups = CollisionGroups::new().with_blacklist(&[1]);
+
+    for point in positions(10, 7) {
+        let expected = brute_force_distances(&world, &groups, |m, shape| {
+            shape.distance_to_point(m, &point, true)
+        });
+
+        let nearest = world.k_nearest_to_point(&point, 5, f32::MAX, &groups);
+        assert_eq!(nearest.len(), 5);
+
+        for ((handle, _, proj), (expected_handle, expected_dist)) in nearest.iter().zip(&expected) {
+            assert_eq!(handle, expected_handle);
+            assert!((na::distance(&point, &proj.point) - expected_dist).abs() < 1.0e-5);
+        }
+
+        let (closest, _, _) = world
+            .closest_object_to_point(&point, f32::MAX, &groups)
+            .unwrap();
+        assert_eq!(closest, expected[0].0);
+
+        // The maximum distance cuts off the objects further than it.
+        let max_dist = (expected[1].1 + expected[2].1) / 2.0;
+        let nearest = world.k_nearest_to_point(&point, 5, max_dist, &groups);
+        assert_eq!(nearest.len(), 2);
+        assert!(world
+            .closest_object_to_point(&point, expected[0].1 * 0.9, &groups)
+            .is_none());
+    }
+}
+
+#[test]
+fn closest_object_to_shape() {
+    let world = world();
+    let groups = CollisionGroups::new().with_blacklist(&[1]);
+    let shape = Cuboid::new(Vector3::new(1.0, 0.2, 0.5));
+
+    for point in positions(10, 7) {
+        let pos = Isometry3::translation(point.x, point.y, point.z);
+        let expected = brute_force_distances(&world, &groups, |m, other| {
+            query::distance(&pos, &shape, m, other)
+        });
+
+        let (closest, _, dist) = world
+            .closest_object_to_shape(&pos, &shape, f32::MAX, &groups)
+            .unwrap();
+        // Several objects may intersect the shape.
+        assert!((dist - expected[0].1).abs() < 1.0e-4);
+        assert!(expected
+            .iter()
+            .any(|(handle, d)| *handle == closest && (d - dist).abs() < 1.0e-4));
+
+        if expected[0].1 > 0.0 {
+            assert!(world
+                .closest_object_to_shape(&pos, &shape, expected[0].1 * 0.9, &groups)
+                .is_none());
+        }
+    }
+}
+
+// A shape `query::distance` knows nothing about.
+#[derive(Clone)]
+struct UserShape;
+
+impl Shape<f32> for UserShape {
+    fn aabb(&self, m: &Isometry3<f32>) -> AABB<f32> {
+        Ball::new(0.5).aabb(m)
+    }
+
+    fn tangent_cone_contains_dir(
+        &self,
+        _: FeatureId,
+        _: &Isometry3<f32>,
+        _: Option<&[f32]>,
+        _: &Unit<Vector3<f32>>,
+    ) -> bool {
+        false
+    }
+}
+
+#[test]
+fn distance_to_unsupported_shapes_is_skipped() {
+    let mut world = CollisionWorld::new(0.0);
+    let query_type = GeometricQueryType::Distance(10.0);
+    let groups = CollisionGroups::new();
+    let user_compound = Compound::new(vec![(Isometry3::identity(), ShapeHandle::new(UserShape))]);
+
+    let _ = world.add(
+        Isometry3::identity(),
+        ShapeHandle::new(UserShape),
+        groups,
+        query_type,
+        (),
+    );
+    let _ = world.add(
+        Isometry3::translation(0.0, 0.0, 1.0),
+        ShapeHandle::new(user_compound),
+        groups,
+        query_type,
+        (),
+    );
+    let (ball, _) = world.add(
+        Isometry3::translation(3.0, 0.0, 0.0),
+        ShapeHandle::new(Ball::new(0.5)),
+        groups,
+        query_type,
+        (),
+    );
+
+    // The narrow phase does not create distance interactions it cannot compute.
+    world.update();
+    assert_eq!(world.distance_pairs(false).count(), 0);
+
+    let compound = Compound::new(vec![(
+        Isometry3::identity(),
+        ShapeHandle::new(Ball::new(0.5)),
+    )]);
+    let (closest, _, dist) = world
+        .closest_object_to_shape(&Isometry3::identity(), &compound, f32::MAX, &groups)
+        .unwrap();
+    assert_eq!(closest, ball);
+    assert!((dist - 2.0).abs() < 1.0e-5);
+}
+
+// Finds the leaves with the AABB closest to a point.
+struct AABBDistanceVisitor {
+    point: Point3<f32>,
+}
+
+impl BestFirstVisitor<f32, usize, AABB<f32>> for AABBDistanceVisitor {
+    type Result = usize;
+
+    fn visit(
+        &mut self,
+        best_cost_so_far: f32,
+        aabb: &AABB<f32>,
+        data: Option<&usize>,
+    ) -> BestFirstVisitStatus<f32, usize> {
+        let dist = aabb.distance_to_point(&Isometry3::identity(), &self.point, true);
+
+        if dist >= best_cost_so_far {
+            BestFirstVisitStatus::Stop
+        } else {
+            BestFirstVisitStatus::Continue {
+                cost: dist,
+                result: data.cloned(),
+            }
+        }
+    }
+}
+
+#[test]
+fn bvt_k_best_first_search() {
+    let aabbs: Vec<_> = positions(200, 42)
+        .into_iter()
+        .map(|pt| AABB::new(pt, pt + Vector3::new(0.3, 0.1, 0.2)).loosened(0.05))
+        .collect();
+    let bvt = BVT::new_balanced(aabbs.iter().cloned().enumerate().collect());
+
+    for point in positions(10, 7) {
+        let mut expected: Vec<_> = aabbs
+            .iter()
+            .map(|aabb| aabb.distance_to_point(&Isometry3::identity(), &point, true))
+            .enumerate()
+            .collect();
+        expected.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+
+        let mut visitor = AABBDistanceVisitor { point };
+        let result = bvt.k_best_first_search(7, &mut visitor);
+        let result: Vec<_> = result.into_iter().map(|(_, i)| i).collect();
+        let expected: Vec<_> = expected[..7].iter().map(|(i, _)| *i).collect();
+        assert_eq!(result, expected);
+
+        let best = bvt.best_first_search(&mut visitor).unwrap().1;
+        assert_eq!(best, expected[0]);
+    }
+}
@@ -0,0 +1,334 @@
+use super::random;
+use na::{Isometry3, Point3, Translation3, UnitQuaternion, Vector3};
+use ncollide3d::bounding_volume::{self, BoundingSphere, BoundingVolume, OBB};
+use ncollide3d::partitioning::{BVH, BVT};
+use ncollide3d::procedural;
+use ncollide3d::query::visitors::{CompositeClosestPointVisitor, RayInterferencesCollector};
+use ncollide3d::query::{self, PointQuery, Ray, RayCast};
+use ncollide3d::shape::{
+    CompositeShape, Compound, Cuboid, DeformableShape, Polyline, QueryBoundingVolume, ShapeHandle,
+    TriMesh,
+};
+
+fn obbs(n: usize, seed: u64) -> Vec<OBB<f32>> {
+    random(n * 9, seed)
+        .chunks(9)
+        .map(|v| {
+            let position = Isometry3::new(
+                Vector3::new(v[0], v[1], v[2]) * 3.0,
+                Vector3::new(v[3], v[4], v[5]) * 3.0,
+            );
+            let half_extents = Vector3::new(v[6], v[7], v[8]).abs() * 2.0;
+            OBB::new(position, half_extents)
+        })
+        .collect()
+}
+
+// A long pipe along the diagonal of the coordinate axes.
+fn diagonal_rotation() -> UnitQuaternion<f32> {
+    UnitQuaternion::rotation_between(&Vector3::y(), &Vector3::repeat(1.0)).unwrap()
+}
+
+fn diagonal_pipe() -> TriMesh<f32> {
+    let mut pipe = procedural::cylinder(1.0, 30.0, 12);
+    pipe.transform_by(&Isometry3::from_parts(
+        Translation3::identity(),
+        diagonal_rotation(),
+    ));
+    pipe.into()
+}
+
+#[test]
+fn obb_bounding_volume() {
+    let obbs = obbs(100, 42);
+
+    for (i, obb1) in obbs.iter().enumerate() {
+        let cuboid1 = obb1.cuboid();
+        let aabb = obb1.aabb().loosened(1.0e-5);
+
+        for k in 0..8 {
+            assert!(aabb.contains_local_point(&obb1.vertex(k)));
+        }
+
+        for obb2 in &obbs[i..] {
+            let cuboid2 = obb2.cuboid();
+            let dist = query::distance(obb1.position(), &cuboid1, obb2.position(), &cuboid2);
+
+            if dist > 1.0e-3 {
+                assert!(!obb1.intersects(obb2));
+            } else if dist == 0.0 {
+                assert!(obb1.intersects(obb2));
+            }
+
+            let merged = obb1.merged(obb2);
+            assert!(merged.contains(obb1));
+            assert!(merged.contains(obb2));
+            assert!(merged.intersects(obb1));
+        }
+
+        assert!(obb1.loosened(0.1).contains(obb1));
+        assert!(!obb1.contains(&obb1.loosened(0.1)));
+    }
+}
+
+#[test]
+fn obb_ray_cast_and_point_queries() {
+    for obb in obbs(20, 7) {
+        let cuboid = Cuboid::new(*obb.half_extents());
+
+        for v in random(30, 3).chunks(3) {
+            let pt = Point3::new(v[0], v[1], v[2]) * 6.0;
+            let m = Isometry3::identity();
+            let proj1 = obb.project_point(&m, &pt, true);
+            let proj2 = cuboid.project_point(obb.position(), &pt, true);
+            assert_eq!(proj1.point, proj2.point);
+            assert_eq!(proj1.is_inside, proj2.is_inside);
+            assert_eq!(
+                obb.contains_point(&m, &pt),
+                cuboid.contains_point(obb.position(), &pt)
+            );
+
+            let ray = Ray::new(pt, obb.center() - pt);
+            assert!(obb.intersects_ray(&m, &ray, 1.0));
+            assert_eq!(
+                obb.toi_with_ray(&m, &ray, 1.0, true),
+                cuboid.toi_with_ray(obb.position(), &ray, 1.0, true)
+            );
+        }
+    }
+}
+
+#[test]
+fn point_cloud_obb_is_tight() {
+    let pipe = diagonal_pipe();
+    let obb = bounding_volume::point_cloud_obb(pipe.points());
+    let aabb = bounding_volume::point_cloud_aabb(&Isometry3::identity(), pipe.points());
+
+    for pt in pipe.points() {
+        assert!(obb.contains_point(&Isometry3::identity(), pt));
+    }
+
+    assert!(obb.surface_area() < aabb.surface_area() / 5.0);
+}
+
+#[test]
+fn trimesh_obb_and_bounding_sphere_hierarchies() {
+    let pipe = diagonal_pipe();
+    let aabb_tree = pipe.bvt();
+    let obb_tree: BVT<usize, OBB<f32>> = pipe.build_bvt();
+    let sphere_tree: BVT<usize, BoundingSphere<f32>> = pipe.build_bvt();
+
+    assert_eq!(obb_tree.leaves().len(), pipe.faces().len());
+    assert_eq!(sphere_tree.leaves().len(), pipe.faces().len());
+
+    // Each face is bounded by its OBB.
+    for leaf in obb_tree.leaves() {
+        for pt in pipe.triangle_at(*leaf.data()).vertices() {
+            assert!(leaf
+                .bounding_volume()
+                .loosened(1.0e-5)
+                .contains_point(&Isometry3::identity(), pt));
+        }
+    }
+
+    let mut num_aabb_hits = 0;
+    let mut num_obb_hits = 0;
+
+    for v in random(60, 7).chunks(3) {
+        let pt = Point3::new(v[0], v[1], v[2]) * 12.0;
+        let expected = pipe.project_point(&Isometry3::identity(), &pt, false);
+
+        let mut visitor = CompositeClosestPointVisitor::new(&pipe, &pt, false);
+        let proj = obb_tree.best_first_search(&mut visitor).unwrap().1;
+        assert!(na::distance(&proj.point, &expected.point) < 1.0e-4);
+
+        let proj = sphere_tree.best_first_search(&mut visitor).unwrap().1;
+        assert!(na::distance(&proj.point, &expected.point) < 1.0e-4);
+
+        // A ray crossing the pipe touches fewer OBBs than AABBs.
+        let axis_pt = Point3::from(Vector3::repeat(v[0] * 8.0));
+        let dir = Vector3::new(v[1], v[2], -v[1] - v[2]).normalize();
+        let ray = Ray::new(axis_pt + dir * 3.0, -dir);
+        let mut aabb_hits = Vec::new();
+        let mut obb_hits = Vec::new();
+        aabb_tree.visit(&mut RayInterferencesCollector::new(
+            &ray,
+            6.0,
+            &mut aabb_hits,
+        ));
+        obb_tree.visit(&mut RayInterferencesCollector::new(
+            &ray,
+            6.0,
+            &mut obb_hits,
+        ));
+
+        let hit_faces = |hits: &[usize]| {
+            hits.iter()
+                .filter(|i| {
+                    pipe.triangle_at(**i)
+                        .intersects_ray(&Isometry3::identity(), &ray, 6.0)
+                })
+                .count()
+        };
+        assert_eq!(hit_faces(&obb_hits), 2);
+        assert_eq!(hit_faces(&aabb_hits), 2);
+        num_aabb_hits += aabb_hits.len();
+        num_obb_hits += obb_hits.len();
+
+        let hit_faces = |hits: &[usize]| {
+            hits.iter()
+                .filter(|i| {
+                    pipe.triangle_at(**i)
+                        .intersects_ray(&Isometry3::identity(), &ray, 1.0)
+                })
+                .count()
+        };
+        assert_eq!(hit_faces(&obb_hits), hit_faces(&aabb_hits));
+    }
+
+    assert!(num_obb_hits * 2 < num_aabb_hits);
+}
+
+#[test]
+fn trimesh_queries_traverse_the_selected_hierarchy() {
+    let aabb_pipe = diagonal_pipe();
+    let mut obb_pipe = aabb_pipe.clone();
+    let mut sphere_pipe = aabb_pipe.clone();
+    obb_pipe.set_query_bounding_volume(QueryBoundingVolume::OBB);
+    sphere_pipe.set_query_bounding_volume(QueryBoundingVolume::BoundingSphere);
+
+    assert_eq!(aabb_pipe.query_bounding_volume(), QueryBoundingVolume::AABB);
+    assert_eq!(obb_pipe.query_bounding_volume(), QueryBoundingVolume::OBB);
+    assert_eq!(
+        sphere_pipe.query_bounding_volume(),
+        QueryBoundingVolume::BoundingSphere
+    );
+
+    let m = Isometry3::new(Vector3::new(1.0, 2.0, 3.0), Vector3::new(0.1, 0.2, 0.3));
+
+    for v in random(90, 11).chunks(3) {
+        let pt = m * Point3::new(v[0], v[1], v[2]) * 12.0;
+        let ray = Ray::new(pt, m * Point3::origin() - pt);
+        let expected_proj = aabb_pipe.project_point(&m, &pt, false);
+        let expected_inter = aabb_pipe.toi_and_normal_with_ray(&m, &ray, 1.0, true);
+
+        for pipe in &[&obb_pipe, &sphere_pipe] {
+            let proj = pipe.project_point(&m, &pt, false);
+            assert!(na::distance(&proj.point, &expected_proj.point) < 1.0e-4);
+            assert_eq!(
+                pipe.contains_point(&m, &pt),
+                aabb_pipe.contains_point(&m, &pt)
+            );
+
+            let inter = pipe.toi_and_normal_with_ray(&m, &ray, 1.0, true);
+            assert_eq!(inter.is_some(), expected_inter.is_some());
+
+            if let (Some(inter), Some(expected)) = (inter, expected_inter) {
+                assert_relative_eq!(inter.toi, expected.toi, epsilon = 1.0e-5);
+                assert_eq!(inter.feature, expected.feature);
+            }
+        }
+    }
+}
+
+#[test]
+fn deformed_trimesh_refits_its_query_hierarchy() {
+    let mut pipe = diagonal_pipe();
+    pipe.set_query_bounding_volume(QueryBoundingVolume::OBB);
+
+    let shift = Vector3::new(0.0, 100.0, 0.0);
+    let mut coords = Vec::new();
+
+    for pt in pipe.points() {
+        coords.extend_from_slice((pt + shift).coords.as_slice());
+    }
+
+    pipe.set_deformations(&coords);
+    assert_eq!(pipe.query_bounding_volume(), QueryBoundingVolume::OBB);
+
+    let m = Isometry3::identity();
+    let old_ray = Ray::new(Point3::new(0.0, 0.0, -10.0), Vector3::z());
+    let new_ray = Ray::new(Point3::new(0.0, 100.0, -10.0), Vector3::z());
+    assert!(pipe.toi_with_ray(&m, &old_ray, 20.0, true).is_none());
+    assert!(pipe.toi_with_ray(&m, &new_ray, 20.0, true).is_some());
+}
+
+fn assert_same_queries<S: PointQuery<f32> + RayCast<f32>>(expected: &S, shape: &S, seed: u64) {
+    let m = Isometry3::new(Vector3::new(1.0, 2.0, 3.0), Vector3::new(0.1, 0.2, 0.3));
+
+    for v in random(90, seed).chunks(3) {
+        let pt = m * Point3::new(v[0], v[1], v[2]) * 12.0;
+        let ray = Ray::new(pt, m * Point3::origin() - pt);
+        let expected_proj = expected.project_point(&m, &pt, false);
+        let proj = shape.project_point(&m, &pt, false);
+        assert!(na::distance(&proj.point, &expected_proj.point) < 1.0e-4);
+
+        let expected_inter = expected.toi_and_normal_with_ray(&m, &ray, 1.0, true);
+        let inter = shape.toi_and_normal_with_ray(&m, &ray, 1.0, true);
+        assert_eq!(inter.is_some(), expected_inter.is_some());
+
+        if let (Some(inter), Some(expected)) = (inter, expected_inter) {
+            assert_relative_eq!(inter.toi, expected.toi, epsilon = 1.0e-5);
+        }
+    }
+}
+
+#[test]
+fn compound_and_polyline_queries_traverse_the_selected_hierarchy() {
+    let parts = (0..10)
+        .map(|i| {
+            let center = Vector3::repeat(i as f32 * 1.5 - 7.0);
+            let delta = Isometry3::from_parts(center.into(), diagonal_rotation());
+            let part = ShapeHandle::new(Cuboid::new(Vector3::new(0.2, 1.5, 0.2)));
+            (delta, part)
+        })
+        .collect();
+    let aabb_compound = Compound::new(parts);
+    let points = (0..30)
+        .map(|i| {
+            let t = i as f32 * 0.5 - 7.0;
+            Point3::new(t, t + t.sin(), t + t.cos())
+        })
+        .collect();
+    let aabb_polyline = Polyline::new(points, None);
+
+    for bv in &[
+        QueryBoundingVolume::OBB,
+        QueryBoundingVolume::BoundingSphere,
+    ] {
+        let mut compound = aabb_compound.clone();
+        let mut polyline = aabb_polyline.clone();
+        compound.set_query_bounding_volume(*bv);
+        polyline.set_query_bounding_volume(*bv);
+
+        assert_eq!(compound.query_bounding_volume(), *bv);
+        assert_eq!(polyline.query_bounding_volume(), *bv);
+        assert_same_queries(&aabb_compound, &compound, 12);
+        assert_same_queries(&aabb_polyline, &polyline, 13);
+    }
+}
+
+#[test]
+fn deformed_polyline_refits_its_query_hierarchy() {
+    let points = (0..30)
+        .map(|i| Point3::new(0.0, 0.0, i as f32 - 15.0))
+        .collect();
+    let mut polyline = Polyline::new(points, None);
+    polyline.set_query_bounding_volume(QueryBoundingVolume::OBB);
+
+    let shift = Vector3::new(0.0, 100.0, 0.0);
+    let mut coords = Vec::new();
+
+    for pt in polyline.points() {
+        coords.extend_from_slice((pt + shift).coords.as_slice());
+    }
+
+    polyline.set_deformations(&coords);
+    assert_eq!(polyline.query_bounding_volume(), QueryBoundingVolume::OBB);
+
+    let m = Isometry3::identity();
+    let old_ray = Ray::new(Point3::new(-10.0, 0.0, 0.5), Vector3::x());
+    let new_ray = Ray::new(Point3::new(-10.0, 100.0, 0.5), Vector3::x());
+    assert!(polyline.toi_with_ray(&m, &old_ray, 20.0, true).is_none());
+    assert!(polyline.toi_with_ray(&m, &new_ray, 20.0, true).is_some());
+}
@@ -0,0 +1,127 @@
+use super::positions;
+use na::{Isometry3, Vector3};
+use ncollide3d::bounding_volume::{BoundingVolume, AABB};
+use ncollide3d::partitioning::{BVH, QBVH};
+use ncollide3d::procedural;
+use ncollide3d::query::visitors::{
+    BoundingVolumeInterferencesCollector, CompositeClosestPointVisitor, RayInterferencesCollector,
+};
+use ncollide3d::query::{PointQuery, Ray, RayCast};
+use ncollide3d::shape::TriMesh;
+
+fn aabbs(n: usize) -> Vec<AABB<f32>> {
+    positions(n, 42)
+        .into_iter()
+        .enumerate()
+        .map(|(i, pt)| AABB::from_half_extents(pt, Vector3::repeat(0.2 + (i % 7) as f32 * 0.1)))
+        .collect()
+}
+
+fn sorted(mut values: Vec<usize>) -> Vec<usize> {
+    values.sort();
+    values
+}
+
+#[test]
+fn qbvh_interferences() {
+    for &n in &[0, 1, 3, 1000] {
+        let aabbs = aabbs(n);
+        let qbvh = QBVH::new(aabbs.iter().cloned().enumerate().collect());
+        assert_eq!(qbvh.leaves().len(), n);
+
+        let mut stack = Vec::new();
+        let mut out = Vec::new();
+
+        for pt in positions(20, 7) {
+            // Interferences with an AABB.
+            let query = AABB::from_half_extents(pt, Vector3::new(1.0, 2.0, 0.5));
+            let expected: Vec<_> = (0..n).filter(|i| aabbs[*i].intersects(&query)).collect();
+
+            out.clear();
+            qbvh.interferences_with_aabb(&query, &mut stack, &mut out);
+            assert_eq!(sorted(out.clone()), expected);
+
+            out.clear();
+            qbvh.visit(&mut BoundingVolumeInterferencesCollector::new(
+                &query, &mut out,
+            ));
+            assert_eq!(sorted(out.clone()), expected);
+
+            // Interferences with a point.
+            let expected: Vec<_> = (0..n)
+                .filter(|i| aabbs[*i].contains_local_point(&pt))
+                .collect();
+            out.clear();
+            qbvh.interferences_with_point(&pt, &mut stack, &mut out);
+            assert_eq!(sorted(out.clone()), expected);
+
+            // Interferences with rays, some of them axis-aligned.
+            for dir in &[
+                Vector3::new(1.0, 0.5, -0.2),
+                Vector3::new(0.0, 1.0, 0.0),
+                Vector3::new(0.0, -1.0, 1.0),
+            ] {
+                let ray = Ray::new(pt, *dir);
+                let expected: Vec<_> = (0..n)
+                    .filter(|i| aabbs[*i].intersects_ray(&Isometry3::identity(), &ray, 5.0))
+                    .collect();
+
+                out.clear();
+                qbvh.interferences_with_ray(&ray, 5.0, &mut stack, &mut out);
+                assert_eq!(sorted(out.clone()), expected);
+
+                let mut visitor_stack = Vec::new();
+                out.clear();
+                qbvh.visit_with_stack(
+                    &mut RayInterferencesCollector::new(&ray, 5.0, &mut out),
+                    &mut visitor_stack,
+                );
+                assert_eq!(sorted(out.clone()), expected);
+            }
+        }
+    }
+}
+
+#[test]
+fn qbvh_nodes_have_up_to_four_children() {
+    let qbvh = QBVH::new(aabbs(1000).into_iter().enumerate().collect());
+    let mut stack = vec![qbvh.root().unwrap()];
+    let mut num_leaves = 0;
+    let mut num_internals = 0;
+
+    while let Some(node) = stack.pop() {
+        let (aabb, data) = qbvh.content(node);
+        let num_children = qbvh.num_children(node);
+        assert!(num_children <= 4);
+
+        if data.is_some() {
+            num_leaves += 1;
+        } else {
+            num_internals += 1;
+        }
+
+        for i in 0..num_children {
+            let child = qbvh.child(i, node);
+            assert!(aabb.contains(qbvh.content(child).0));
+            stack.push(child);
+        }
+    }
+
+    assert_eq!(num_leaves, 1000);
+    // A binary tree would have 999 internal nodes.
+    assert!(num_internals < 500);
+}
+
+#[test]
+fn qbvh_closest_point_on_trimesh() {
+    let trimesh: TriMesh<f32> = procedural::sphere(4.0, 20, 20, false).into();
+    let qbvh = QBVH::from_bvh(trimesh.bvt());
+    assert_eq!(qbvh.leaves().len(), trimesh.faces().len());
+
+    for pt in positions(20, 7) {
+        let mut visitor = CompositeClosestPointVisitor::new(&trimesh, &pt, true);
+        let proj = qbvh.best_first_search(&mut visitor).unwrap().1;
+        let expected = trimesh.project_point(&Isometry3::identity(), &pt, true);
+        assert!(na::distance(&proj.point, &expected.point) < 1.0e-5);
+    }
+}
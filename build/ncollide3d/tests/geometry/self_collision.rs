@@ -0,0 +1,121 @@
+use na::{Isometry3, Point3};
+use ncollide3d::bounding_volume::{self, BoundingVolume, AABB};
+use ncollide3d::partitioning::DBVTLeaf;
+use ncollide3d::partitioning::{SimultaneousVisitor, VisitStatus, BVH, DBVT};
+use ncollide3d::procedural;
+use ncollide3d::query::visitors::SelfInterferencesCollector;
+use ncollide3d::shape::TriMesh;
+use std::collections::HashSet;
+
+// A sheet folded onto itself, like a piece of cloth.
+fn folded_sheet() -> TriMesh<f32> {
+    let mut sheet = procedural::quad(4.0f32, 2.0, 20, 10);
+
+    for pt in &mut sheet.coords {
+        *pt = Point3::new(pt.x.abs(), pt.y, pt.x * 0.05);
+    }
+
+    sheet.into()
+}
+
+fn share_vertex(mesh: &TriMesh<f32>, i: usize, j: usize) -> bool {
+    let face1 = &mesh.faces()[i].indices;
+    let face2 = &mesh.faces()[j].indices;
+    face1.iter().any(|v| face2.iter().any(|w| v == w))
+}
+
+fn sorted(pairs: &[(usize, usize)]) -> HashSet<(usize, usize)> {
+    let result: HashSet<_> = pairs.iter().map(|(i, j)| (*i.min(j), *i.max(j))).collect();
+    // Each pair is reported once.
+    assert_eq!(result.len(), pairs.len());
+    result
+}
+
+#[test]
+fn trimesh_self_interferences() {
+    let mesh = folded_sheet();
+    let aabbs: Vec<AABB<f32>> = (0..mesh.faces().len())
+        .map(|i| bounding_volume::aabb(&mesh.triangle_at(i), &Isometry3::identity()))
+        .collect();
+
+    let mut expected = HashSet::new();
+
+    for i in 0..aabbs.len() {
+        for j in i + 1..aabbs.len() {
+            if aabbs[i].intersects(&aabbs[j]) && !share_vertex(&mesh, i, j) {
+                let _ = expected.insert((i, j));
+            }
+        }
+    }
+
+    // The two layers of the sheet overlap.
+    assert!(!expected.is_empty());
+
+    let mut pairs = Vec::new();
+    let filter = |i: &usize, j: &usize| !share_vertex(&mesh, *i, *j);
+    mesh.bvt()
+        .visit_self_bvtt(&mut SelfInterferencesCollector::new(filter, &mut pairs));
+    assert_eq!(sorted(&pairs), expected);
+
+    let mut dbvt = DBVT::new();
+
+    for (i, aabb) in aabbs.iter().enumerate() {
+        let _ = dbvt.insert(DBVTLeaf::new(aabb.clone(), i));
+    }
+
+    pairs.clear();
+    dbvt.visit_self_bvtt(&mut SelfInterferencesCollector::new(filter, &mut pairs));
+    assert_eq!(sorted(&pairs), expected);
+
+    // Without any filter, the adjacent triangles are reported too.
+    pairs.clear();
+    mesh.bvt()
+        .visit_self_bvtt(&mut SelfInterferencesCollector::new(
+            |_: &usize, _: &usize| true,
+            &mut pairs,
+        ));
+    assert!(sorted(&pairs).is_superset(&expected));
+    assert!(pairs.iter().all(|(i, j)| i != j));
+    assert!(pairs.iter().any(|(i, j)| share_vertex(&mesh, *i, *j)));
+}
+
+struct FirstPair(Option<(usize, usize)>);
+
+impl SimultaneousVisitor<usize, AABB<f32>> for FirstPair {
+    fn visit(
+        &mut self,
+        left_bv: &AABB<f32>,
+        left_data: Option<&usize>,
+        right_bv: &AABB<f32>,
+        right_data: Option<&usize>,
+    ) -> VisitStatus {
+        if !left_bv.intersects(right_bv) {
+            return VisitStatus::Stop;
+        }
+
+        assert!(self.0.is_none());
+
+        if let (Some(i), Some(j)) = (left_data, right_data) {
+            self.0 = Some((*i, *j));
+            VisitStatus::ExitEarly
+        } else {
+            VisitStatus::Continue
+        }
+    }
+}
+
+#[test]
+fn self_interferences_exit_early() {
+    let mesh = folded_sheet();
+    let mut visitor = FirstPair(None);
+    mesh.bvt().visit_self_bvtt(&mut visitor);
+    let (i, j) = visitor.0.unwrap();
+    assert_ne!(i, j);
+
+    // A single leaf does not interfere with itself.
+    let mut dbvt = DBVT::new();
+    let _ = dbvt.insert(DBVTLeaf::new(mesh.aabb().clone(), 0));
+    let mut visitor = FirstPair(None);
+    dbvt.visit_self_bvtt(&mut visitor);
+    assert!(visitor.0.is_none());
+}
@@ -0,0 +1,107 @@
+use na::{Isometry3, Matrix3, Point3};
+use ncollide3d::partitioning::{BestFirstQueue, BVH};
+use ncollide3d::procedural;
+use ncollide3d::query::visitors::{
+    AABBSetsInterferencesCollector, CompositeClosestPointVisitor, SelfInterferencesCollector,
+};
+use ncollide3d::shape::TriMesh;
+
+fn sphere_mesh(diameter: f32) -> TriMesh<f32> {
+    procedural::sphere(diameter, 20, 20, false).into()
+}
+
+#[test]
+fn bvh_traversals_with_reused_stacks() {
+    let mesh1 = sphere_mesh(4.0);
+    let mesh2 = sphere_mesh(3.0);
+    let mut pair_stack = Vec::new();
+    let mut self_stack = Vec::new();
+    let mut queue = BestFirstQueue::new();
+    let mut k_best = Vec::new();
+    let mut capacities = Vec::new();
+
+    for _ in 0..2 {
+        for i in 0..10 {
+            let shift = i as f32 * 0.3;
+            let pos2 = Isometry3::translation(shift, 0.5, 0.0);
+            let abs_rot = Matrix3::identity();
+
+            let mut expected = Vec::new();
+            let mut pairs = Vec::new();
+            mesh1.bvt().visit_bvtt(
+                mesh2.bvt(),
+                &mut AABBSetsInterferencesCollector::new(0.0, &pos2, &abs_rot, &mut expected),
+            );
+            mesh1.bvt().visit_bvtt_with_stack(
+                mesh2.bvt(),
+                &mut AABBSetsInterferencesCollector::new(0.0, &pos2, &abs_rot, &mut pairs),
+                &mut pair_stack,
+            );
+            assert!(!pairs.is_empty());
+            assert_eq!(pairs, expected);
+
+            let filter = |_: &usize, _: &usize| true;
+            let mut expected = Vec::new();
+            let mut pairs = Vec::new();
+            mesh1
+                .bvt()
+                .visit_self_bvtt(&mut SelfInterferencesCollector::new(filter, &mut expected));
+            mesh1.bvt().visit_self_bvtt_with_stack(
+                &mut SelfInterferencesCollector::new(filter, &mut pairs),
+                &mut self_stack,
+            );
+            assert_eq!(pairs, expected);
+
+            let pt = Point3::new(shift, -shift, 1.0);
+            let mut visitor = CompositeClosestPointVisitor::new(&mesh1, &pt, false);
+            let expected = mesh1.bvt().best_first_search(&mut visitor).unwrap();
+            let proj = mesh1
+                .bvt()
+                .best_first_search_with_queue(&mut visitor, &mut queue)
+                .unwrap();
+            assert_eq!(proj.0, expected.0);
+            assert_eq!(proj.1.point, expected.1.point);
+            assert!((na::distance(&proj.1.point, &Point3::origin()) - 2.0).abs() < 0.1);
+
+            let expected = mesh1.bvt().k_best_first_search(5, &mut visitor);
+            mesh1
+                .bvt()
+                .k_best_first_search_with_queue(5, &mut visitor, &mut queue, &mut k_best);
+            assert_eq!(k_best.len(), 5);
+            assert_eq!(k_best.len(), expected.len());
+
+            for (best, expected) in k_best.iter().zip(expected.iter()) {
+                assert_eq!(best.0, expected.0);
+                assert_eq!(best.1.point, expected.1.point);
+            }
+        }
+
+        capacities.push((
+            pair_stack.capacity(),
+            self_stack.capacity(),
+            queue.capacity(),
+            k_best.capacity(),
+        ));
+    }
+
+    // The buffers don't grow once they are large enough for the queries.
+    assert_eq!(capacities[0], capacities[1]);
+
+    // A stack that is not empty at the start of a traversal is cleared.
+    let mut expected = Vec::new();
+    let mut pairs = Vec::new();
+    let pos2 = Isometry3::translation(100.0, 0.0, 0.0);
+    let abs_rot = Matrix3::identity();
+    mesh1.bvt().visit_bvtt(
+        mesh2.bvt(),
+        &mut AABBSetsInterferencesCollector::new(0.0, &pos2, &abs_rot, &mut expected),
+    );
+    pair_stack.push((mesh1.bvt().root().unwrap(), mesh2.bvt().root().unwrap()));
+    mesh1.bvt().visit_bvtt_with_stack(
+        mesh2.bvt(),
+        &mut AABBSetsInterferencesCollector::new(0.0, &pos2, &abs_rot, &mut pairs),
+        &mut pair_stack,
+    );
+    assert!(expected.is_empty());
+    assert!(pairs.is_empty());
+}
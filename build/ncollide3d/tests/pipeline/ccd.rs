@@ -0,0 +1,49 @@
+use na::{Isometry3, Vector3};
+use ncollide3d::pipeline::{CollisionGroups, CollisionWorld, GeometricQueryType};
+use ncollide3d::shape::{Ball, Cuboid, ShapeHandle};
+
+#[test]
+fn fast_ball_does_not_tunnel_through_thin_wall() {
+    let mut world = CollisionWorld::new(0.0);
+    let contacts_query = GeometricQueryType::Contacts(0.0, 0.0);
+
+    let wall = ShapeHandle::new(Cuboid::new(Vector3::new(0.05, 10.0, 10.0)));
+    let (wall_handle, _) = world.add(
+        Isometry3::identity(),
+        wall,
+        CollisionGroups::new(),
+        contacts_query,
+        (),
+    );
+
+    let ball = ShapeHandle::new(Ball::new(0.1));
+    let (ball_handle, ball_object) = world.add(
+        Isometry3::translation(-5.0, 0.0, 0.0),
+        ball,
+        CollisionGroups::new(),
+        contacts_query,
+        (),
+    );
+    ball_object.enable_ccd(true);
+    ball_object.set_position_with_prediction(
+        Isometry3::translation(-5.0, 0.0, 0.0),
+        Isometry3::translation(5.0, 0.0, 0.0),
+    );
+
+    world.update();
+
+    // No contact at the start nor at the end of the motion.
+    assert_eq!(world.contact_pairs(true).count(), 0);
+
+    let impacts = world.ccd_impacts();
+    assert_eq!(impacts.len(), 1);
+    assert!(impacts[0].collider1 == ball_handle);
+    assert!(impacts[0].collider2 == wall_handle);
+    // The ball hits the wall after moving 4.85 units out of 10.
+    assert_relative_eq!(impacts[0].toi.toi, 0.485, epsilon = 1.0e-3);
+
+    // Without CCD, no impact is reported.
+    world.get_mut(ball_handle).unwrap().enable_ccd(false);
+    world.update();
+    assert!(world.ccd_impacts().is_empty());
+}
@@ -0,0 +1,62 @@
+use super::grid;
+use na::{DMatrix, Isometry3, Vector3};
+use ncollide3d::pipeline::{CollisionGroups, CollisionWorld, GeometricQueryType};
+use ncollide3d::query::ContactId;
+use ncollide3d::shape::{Ball, HeightField, ShapeHandle};
+
+// Slides a ball across the given ground and returns the identifiers of its contact at each step.
+//
+// The ball penetrates the ground slightly and is never close to any edge of the ground
+// triangles when its position is sampled, so it always touches exactly one triangle.
+fn slide_ball_on(ground: ShapeHandle<f32>) -> Vec<ContactId> {
+    let mut world = CollisionWorld::new(0.0);
+    let contacts_query = GeometricQueryType::Contacts(0.0, 0.0);
+
+    let (ground_handle, _) = world.add(
+        Isometry3::identity(),
+        ground,
+        CollisionGroups::new(),
+        contacts_query,
+        (),
+    );
+    let (ball_handle, _) = world.add(
+        Isometry3::translation(-3.125, 0.495, 0.5),
+        ShapeHandle::new(Ball::new(0.5)),
+        CollisionGroups::new(),
+        contacts_query,
+        (),
+    );
+
+    let mut ids = Vec::new();
+
+    for i in 0..25 {
+        let x = -3.125 + i as f32 * 0.25;
+        world
+            .get_mut(ball_handle)
+            .unwrap()
+            .set_position(Isometry3::translation(x, 0.495, 0.5));
+        world.update();
+
+        let (_, _, _, manifold) = world
+            .contact_pair(ground_handle, ball_handle, true)
+            .expect("The ball must touch the ground.");
+        assert_eq!(manifold.len(), 1);
+        ids.push(manifold.contacts().next().unwrap().id);
+    }
+
+    ids
+}
+
+#[test]
+fn contact_id_is_preserved_across_trimesh_triangles() {
+    let ids = slide_ball_on(ShapeHandle::new(grid(10, 10.0)));
+    assert!(ids.iter().all(|id| *id == ids[0]));
+}
+
+#[test]
+fn contact_id_is_preserved_across_heightfield_triangles() {
+    let heights = DMatrix::zeros(11, 11);
+    let heightfield = HeightField::new(heights, Vector3::new(10.0, 1.0, 10.0));
+    let ids = slide_ball_on(ShapeHandle::new(heightfield));
+    assert!(ids.iter().all(|id| *id == ids[0]));
+}
@@ -0,0 +1,61 @@
+use na::Isometry3;
+use ncollide3d::pipeline::{CollisionGroups, CollisionWorld, ContactEvent, GeometricQueryType};
+use ncollide3d::shape::{Ball, ShapeHandle};
+
+#[test]
+fn contact_events_carry_contact_data() {
+    let mut world = CollisionWorld::new(0.0);
+    let contacts_query = GeometricQueryType::Contacts(0.0, 0.0);
+    let shape = ShapeHandle::new(Ball::new(1.0));
+
+    let (handle1, _) = world.add(
+        Isometry3::identity(),
+        shape.clone(),
+        CollisionGroups::new(),
+        contacts_query,
+        (),
+    );
+    let (handle2, _) = world.add(
+        Isometry3::translation(1.5, 0.0, 0.0),
+        shape,
+        CollisionGroups::new(),
+        contacts_query,
+        (),
+    );
+
+    world.update();
+
+    let events: Vec<_> = world.contact_events().iter().cloned().collect();
+    assert_eq!(events.len(), 1);
+    let (h1, h2) = events[0].handles();
+    assert!((h1, h2) == (handle1, handle2) || (h1, h2) == (handle2, handle1));
+    assert_eq!(events[0], ContactEvent::Started(h1, h2));
+    let data = world.contact_event_data(&events[0]).unwrap();
+    assert_eq!(data.timestamp, 0);
+    assert_eq!(data.num_contacts(), 1);
+    assert_eq!(world.contact_event_contacts(&events[0]).len(), 1);
+    assert_relative_eq!(data.deepest_contact.contact.depth, 0.5, epsilon = 1.0e-5);
+
+    // Move the objects slightly apart so that the contact is lost during the narrow phase.
+    world
+        .get_mut(handle2)
+        .unwrap()
+        .set_position(Isometry3::translation(2.5, 0.0, 0.0));
+    world.update();
+
+    let events: Vec<_> = world.contact_events().iter().cloned().collect();
+    assert_eq!(events, vec![ContactEvent::Stopped(h1, h2)]);
+
+    // The last known contact is reported.
+    let data = world.contact_event_data(&events[0]).unwrap();
+    assert_eq!(data.timestamp, 1);
+    assert_eq!(data.num_contacts(), 1);
+    assert_relative_eq!(data.deepest_contact.contact.depth, 0.5, epsilon = 1.0e-5);
+    let contacts = world.contact_event_contacts(&events[0]);
+    assert_relative_eq!(contacts[0].contact.depth, 0.5, epsilon = 1.0e-5);
+
+    // The data are discarded with the events.
+    world.clear_events();
+    assert!(world.contact_event_data(&events[0]).is_none());
+    assert!(world.contact_event_contacts(&events[0]).is_empty());
+}
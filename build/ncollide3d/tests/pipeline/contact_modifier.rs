@@ -0,0 +1,81 @@
+use na::{Isometry3, Vector3};
+use ncollide3d::pipeline::{
+    CollisionGroups, CollisionObject, CollisionObjectSlabHandle, CollisionWorld, ContactModifier,
+    GeometricQueryType,
+};
+use ncollide3d::query::ContactManifold;
+use ncollide3d::shape::{Ball, Cuboid, ShapeHandle};
+
+// Collision objects with `true` as user data are one-way platforms
+// that can only be hit from above.
+struct OneWayPlatforms;
+
+impl ContactModifier<f32, CollisionObject<f32, bool>, CollisionObjectSlabHandle>
+    for OneWayPlatforms
+{
+    fn modify_contacts(
+        &self,
+        co1: &CollisionObject<f32, bool>,
+        co2: &CollisionObject<f32, bool>,
+        _: CollisionObjectSlabHandle,
+        _: CollisionObjectSlabHandle,
+        manifold: &mut ContactManifold<f32>,
+    ) {
+        if *co1.data() {
+            manifold.retain(|c| c.contact.normal.y > 0.0)
+        } else if *co2.data() {
+            manifold.retain(|c| c.contact.normal.y < 0.0)
+        }
+    }
+}
+
+#[test]
+fn contact_modifier_drops_contacts() {
+    let mut world = CollisionWorld::new(0.0);
+    world.set_contact_modifier(Some(OneWayPlatforms));
+    let contacts_query = GeometricQueryType::Contacts(0.0, 0.0);
+
+    let platform = ShapeHandle::new(Cuboid::new(Vector3::new(10.0, 0.5, 10.0)));
+    let (platform_handle, _) = world.add(
+        Isometry3::identity(),
+        platform,
+        CollisionGroups::new(),
+        contacts_query,
+        true,
+    );
+
+    // A ball hitting the platform from below.
+    let ball = ShapeHandle::new(Ball::new(0.5));
+    let (ball_handle, _) = world.add(
+        Isometry3::translation(0.0, -0.9, 0.0),
+        ball,
+        CollisionGroups::new(),
+        contacts_query,
+        false,
+    );
+
+    world.update();
+    assert!(world
+        .contact_pair(platform_handle, ball_handle, true)
+        .is_none());
+    assert_eq!(world.contact_events().iter().count(), 0);
+
+    // The same ball landing on the platform.
+    world
+        .get_mut(ball_handle)
+        .unwrap()
+        .set_position(Isometry3::translation(0.0, 0.9, 0.0));
+    world.update();
+
+    let manifold = world
+        .contact_pair(platform_handle, ball_handle, true)
+        .unwrap()
+        .3;
+    assert_eq!(manifold.len(), 1);
+    assert_relative_eq!(
+        manifold.deepest_contact().unwrap().contact.depth,
+        0.1,
+        epsilon = 1.0e-5
+    );
+    assert_eq!(world.contact_events().iter().count(), 1);
+}
@@ -0,0 +1,96 @@
+use super::grid;
+use na::{Isometry3, Point3, Vector3};
+use ncollide3d::pipeline::{CollisionGroups, CollisionWorld, GeometricQueryType};
+use ncollide3d::query::{Contact, ContactKinematic, ContactManifold, ContactReduction};
+use ncollide3d::shape::{Cuboid, FeatureId, ShapeHandle};
+
+fn box_on_grid(reduction: Option<ContactReduction<f32>>) -> Vec<ContactManifold<f32>> {
+    let mut world = CollisionWorld::new(0.0);
+    world.set_contact_reduction(reduction);
+    let contacts_query = GeometricQueryType::Contacts(0.0, 0.0);
+
+    let _ = world.add(
+        Isometry3::identity(),
+        ShapeHandle::new(grid(10, 10.0)),
+        CollisionGroups::new(),
+        contacts_query,
+        (),
+    );
+    let _ = world.add(
+        Isometry3::translation(0.1, 0.9, 0.1),
+        ShapeHandle::new(Cuboid::new(Vector3::new(3.0, 1.0, 3.0))),
+        CollisionGroups::new(),
+        contacts_query,
+        (),
+    );
+
+    world.update();
+
+    world
+        .contact_pairs(true)
+        .map(|(_, _, _, manifold)| manifold.clone())
+        .collect()
+}
+
+#[test]
+fn contact_reduction_limits_manifold_size() {
+    let tolerance = 0.1;
+    let unreduced = box_on_grid(None);
+    assert_eq!(unreduced.len(), 1);
+    assert!(unreduced[0].len() > 4);
+
+    let reduced = box_on_grid(Some(ContactReduction::new(4, tolerance)));
+    assert_eq!(reduced.len(), 1);
+    assert_eq!(reduced[0].len(), 4);
+
+    // The deepest contact is always kept.
+    let deepest = unreduced[0].deepest_contact().unwrap().contact.depth;
+    let reduced_deepest = reduced[0].deepest_contact().unwrap().contact.depth;
+    assert!((deepest - reduced_deepest).abs() < 1.0e-5);
+}
+
+// A manifold with ten contacts with the normal `+y`, and three deeper contacts with the normal `+x`.
+fn two_clusters() -> ContactManifold<f32> {
+    let mut manifold = ContactManifold::new();
+    let normals = [Vector3::y_axis(), Vector3::x_axis()];
+    let mut id = 0;
+
+    for (k, (normal, n)) in normals.iter().zip(&[10, 3]).enumerate() {
+        for i in 0..*n {
+            let pt = Point3::new(i as f32, k as f32, (i * i) as f32);
+            let depth = 0.1 * (k + 1) as f32 + 0.01 * i as f32;
+            let mut kinematic = ContactKinematic::new();
+            kinematic.set_feature1(FeatureId::Vertex(id));
+            kinematic.set_feature2(FeatureId::Vertex(id));
+            id += 1;
+            let contact = Contact::new(pt, pt, *normal, depth);
+            let _ = manifold.push(contact, kinematic, pt, None, None);
+        }
+    }
+
+    manifold
+}
+
+#[test]
+fn contact_reduction_distributes_contacts_among_clusters() {
+    let tolerance = 0.1;
+    let unreduced = two_clusters();
+    assert_eq!(unreduced.len(), 13);
+    assert_eq!(unreduced.clusters(tolerance).len(), 2);
+    let deepest = unreduced.deepest_contact().unwrap().contact.depth;
+
+    for &(max_contacts, cluster_sizes) in &[(1, [1, 0]), (2, [1, 1]), (5, [3, 2]), (9, [3, 6])] {
+        let mut reduced = two_clusters();
+        reduced.reduce(&ContactReduction::new(max_contacts, tolerance));
+        assert_eq!(reduced.len(), max_contacts);
+
+        // The clusters are sorted by decreasing depth, so the `+x` cluster comes first.
+        let clusters = reduced.clusters(tolerance);
+        let sizes: Vec<_> = cluster_sizes.iter().cloned().filter(|n| *n != 0).collect();
+        assert_eq!(clusters.iter().map(|c| c.len()).collect::<Vec<_>>(), sizes);
+
+        // The deepest contact is always kept.
+        let reduced_deepest = reduced.deepest_contact().unwrap().contact.depth;
+        assert_eq!(reduced_deepest, deepest);
+    }
+}
@@ -0,0 +1,50 @@
+use na::{Isometry3, Perspective3, Point3, Vector3};
+use ncollide3d::pipeline::{CollisionGroups, CollisionWorld, GeometricQueryType};
+use ncollide3d::query::ConvexVolume;
+use ncollide3d::shape::{Ball, ShapeHandle};
+
+#[test]
+fn world_interferences_with_frustum() {
+    let mut world = CollisionWorld::new(0.0);
+    let query_type = GeometricQueryType::Contacts(0.0, 0.0);
+    let groups = CollisionGroups::new().with_membership(&[0]);
+    let ignored = CollisionGroups::new().with_membership(&[1]);
+    let ball = ShapeHandle::new(Ball::new(0.5f32));
+    let mut add = |x, y, groups| {
+        world
+            .add(
+                Isometry3::translation(x, y, 0.0),
+                ball.clone(),
+                groups,
+                query_type,
+                (),
+            )
+            .0
+    };
+
+    let visible = add(5.0, 0.0, groups);
+    let on_border = add(5.0, 3.6, groups);
+    let _behind = add(-5.0, 0.0, groups);
+    let _beside = add(5.0, 6.0, groups);
+    let _too_far = add(40.0, 0.0, groups);
+    let _filtered = add(5.0, 0.0, ignored);
+    world.update();
+
+    // The camera is at the origin, looking toward the +x axis.
+    let view = Isometry3::look_at_rh(
+        &Point3::origin(),
+        &Point3::new(1.0, 0.0, 0.0),
+        &Vector3::z(),
+    );
+    let projection = Perspective3::new(1.0, 1.2, 0.5, 30.0);
+    let frustum =
+        ConvexVolume::from_view_projection(&(projection.as_matrix() * view.to_homogeneous()));
+    let query_groups = CollisionGroups::new().with_blacklist(&[1]);
+
+    let mut handles: Vec<_> = world
+        .interferences_with_convex_volume(&frustum, &query_groups)
+        .map(|(handle, _)| handle)
+        .collect();
+    handles.sort();
+    assert_eq!(handles, vec![visible, on_border]);
+}
@@ -0,0 +1,103 @@
+use na::{Isometry3, Point3};
+use ncollide3d::bounding_volume::AABB;
+use ncollide3d::pipeline::{
+    CollisionGroupsU64, CollisionLayer, CollisionLayerMatrix, CollisionWorld, GeometricQueryType,
+};
+use ncollide3d::shape::{Ball, ShapeHandle};
+use std::sync::Arc;
+
+fn query_aabb() -> AABB<f32> {
+    AABB::new(Point3::new(-1.0, -1.0, -1.0), Point3::new(1.0, 1.0, 1.0))
+}
+
+#[test]
+fn collision_groups_with_64_groups() {
+    let mut world = CollisionWorld::new(0.0);
+    let contacts_query = GeometricQueryType::Contacts(0.0, 0.0);
+    let ball = ShapeHandle::new(Ball::new(0.5));
+    let groups1 = CollisionGroupsU64::new()
+        .with_membership(&[50])
+        .with_whitelist(&[62]);
+    let groups2 = CollisionGroupsU64::new().with_membership(&[62]);
+    let groups3 = CollisionGroupsU64::new()
+        .with_membership(&[40])
+        .with_blacklist(&[50]);
+
+    let (handle1, _) = world.add(
+        Isometry3::identity(),
+        ball.clone(),
+        groups1,
+        contacts_query,
+        (),
+    );
+    let (handle2, _) = world.add(
+        Isometry3::translation(0.5, 0.0, 0.0),
+        ball.clone(),
+        groups2,
+        contacts_query,
+        (),
+    );
+    let (handle3, _) = world.add(
+        Isometry3::translation(-0.4, 0.0, 0.0),
+        ball,
+        groups3,
+        contacts_query,
+        (),
+    );
+
+    world.update();
+    assert!(world.contact_pair(handle1, handle2, true).is_some());
+    assert!(world.contact_pair(handle1, handle3, false).is_none());
+    assert!(world.contact_pair(handle2, handle3, true).is_some());
+
+    let query_groups = CollisionGroupsU64::new()
+        .with_membership(&[40, 62])
+        .with_whitelist(&[40, 62]);
+    let mut found: Vec<_> = world
+        .interferences_with_aabb(&query_aabb(), &query_groups)
+        .map(|(h, _)| h)
+        .collect();
+    found.sort();
+    assert_eq!(found, vec![handle2, handle3]);
+}
+
+#[test]
+fn collision_layer_matrix() {
+    const PLAYER: usize = 0;
+    const ENEMY: usize = 1;
+    const GHOST: usize = 2;
+
+    let mut matrix = CollisionLayerMatrix::new(3);
+    matrix.set_interaction(GHOST, PLAYER, false);
+    matrix.set_interaction(GHOST, ENEMY, false);
+    matrix.set_interaction(ENEMY, ENEMY, false);
+    let matrix = Arc::new(matrix);
+    let layer = |l| CollisionLayer::new(l, matrix.clone());
+
+    let mut world = CollisionWorld::new(0.0);
+    let contacts_query = GeometricQueryType::Contacts(0.0, 0.0);
+    let ball = ShapeHandle::new(Ball::new(0.5));
+    let mut handles = Vec::new();
+
+    for (i, l) in [PLAYER, ENEMY, ENEMY, GHOST].iter().enumerate() {
+        let pos = Isometry3::translation(0.1 * i as f32, 0.0, 0.0);
+        handles.push(
+            world
+                .add(pos, ball.clone(), layer(*l), contacts_query, ())
+                .0,
+        );
+    }
+
+    world.update();
+    assert!(world.contact_pair(handles[0], handles[1], true).is_some());
+    assert!(world.contact_pair(handles[0], handles[2], true).is_some());
+    assert!(world.contact_pair(handles[1], handles[2], false).is_none());
+    assert!(world.contact_pair(handles[0], handles[3], false).is_none());
+    assert!(world.contact_pair(handles[1], handles[3], false).is_none());
+
+    let found: Vec<_> = world
+        .interferences_with_aabb(&query_aabb(), &layer(GHOST))
+        .map(|(h, _)| h)
+        .collect();
+    assert_eq!(found, vec![handles[3]]);
+}
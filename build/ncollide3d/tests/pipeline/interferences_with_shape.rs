@@ -0,0 +1,111 @@
+use na::{Isometry3, Vector3};
+use ncollide3d::pipeline::{
+    CollisionGroups, CollisionObjectSlabHandle, CollisionWorld, GeometricQueryType,
+};
+use ncollide3d::query::{ContactPrediction, Proximity};
+use ncollide3d::shape::{Ball, Cuboid, Shape, ShapeHandle};
+
+// A unit cuboid at the origin, a ball intersecting the query ball, a ball with an AABB intersecting
+// the AABB of the query ball but not the ball itself, and a ball in a group the query ignores.
+fn world() -> (CollisionWorld<f32, ()>, [CollisionObjectSlabHandle; 4]) {
+    let mut world = CollisionWorld::new(0.0);
+    let query_type = GeometricQueryType::Contacts(0.0, 0.0);
+    let groups = CollisionGroups::new().with_membership(&[0]);
+    let ignored = CollisionGroups::new().with_membership(&[1]);
+
+    let cuboid = world
+        .add(
+            Isometry3::identity(),
+            ShapeHandle::new(Cuboid::new(Vector3::repeat(1.0))),
+            groups,
+            query_type,
+            (),
+        )
+        .0;
+    let intersecting = world
+        .add(
+            Isometry3::translation(3.0, 1.7, 0.0),
+            ShapeHandle::new(Ball::new(0.5)),
+            groups,
+            query_type,
+            (),
+        )
+        .0;
+    let corner = world
+        .add(
+            Isometry3::translation(3.4, 2.9, 1.4),
+            ShapeHandle::new(Ball::new(0.5)),
+            groups,
+            query_type,
+            (),
+        )
+        .0;
+    let filtered = world
+        .add(
+            Isometry3::translation(1.5, 1.5, 0.0),
+            ShapeHandle::new(Ball::new(0.5)),
+            ignored,
+            query_type,
+            (),
+        )
+        .0;
+
+    world.update();
+    (world, [cuboid, intersecting, corner, filtered])
+}
+
+fn sorted(mut handles: Vec<CollisionObjectSlabHandle>) -> Vec<CollisionObjectSlabHandle> {
+    handles.sort();
+    handles
+}
+
+#[test]
+fn interferences_with_shape() {
+    let (world, [cuboid, intersecting, corner, _]) = world();
+    let pos = Isometry3::translation(2.0, 1.5, 0.0);
+    let ball = Ball::new(1.5);
+    let groups = CollisionGroups::new().with_blacklist(&[1]);
+
+    // All the balls have an AABB intersecting the AABB of the query ball.
+    assert_eq!(
+        world
+            .interferences_with_aabb(&ball.aabb(&pos), &CollisionGroups::new())
+            .count(),
+        4
+    );
+
+    let handles: Vec<_> = world
+        .interferences_with_shape(&pos, &ball, &groups)
+        .map(|(handle, _)| handle)
+        .collect();
+    assert_eq!(sorted(handles), sorted(vec![cuboid, intersecting]));
+
+    let proximities: Vec<_> = world
+        .proximities_with_shape(&pos, &ball, 0.5, &groups)
+        .map(|(handle, _, prox)| (handle, prox))
+        .collect();
+    assert_eq!(proximities.len(), 3);
+    assert!(proximities.contains(&(corner, Proximity::WithinMargin)));
+    assert!(proximities.contains(&(cuboid, Proximity::Intersecting)));
+    assert!(proximities.contains(&(intersecting, Proximity::Intersecting)));
+}
+
+#[test]
+fn contacts_with_shape() {
+    let (world, [cuboid, ..]) = world();
+    let pos = Isometry3::translation(0.0, 1.4, 0.0);
+    let ball = Ball::new(0.5);
+    let prediction = ContactPrediction::new(0.0, 0.0, 0.0);
+    let groups = CollisionGroups::new().with_blacklist(&[1]);
+
+    let contacts: Vec<_> = world
+        .contacts_with_shape(&pos, &ball, &prediction, &groups)
+        .collect();
+    assert_eq!(contacts.len(), 1);
+    assert_eq!(contacts[0].0, cuboid);
+
+    // The query shape is the first shape of the contact manifold.
+    let contact = contacts[0].2.deepest_contact().unwrap().contact;
+    assert!((contact.depth - 0.1).abs() < 1.0e-5);
+    assert!((contact.normal.y + 1.0).abs() < 1.0e-5);
+}
@@ -0,0 +1,81 @@
+use super::grid;
+use na::{DMatrix, Isometry3, Vector3};
+use ncollide3d::pipeline::{CollisionGroups, CollisionWorld, GeometricQueryType};
+use ncollide3d::shape::{Cuboid, HeightField, ShapeHandle};
+
+// Slides a box across the given flat ground and checks that all the contacts
+// have a vertical normal, i.e., that none of them is on an internal edge.
+// Contacts on the sides of the box are at the level of the ground, thus with
+// a zero penetration depth.
+fn slide_box_on(ground: ShapeHandle<f32>, ground_first: bool) {
+    let mut world = CollisionWorld::new(0.0);
+    let contacts_query = GeometricQueryType::Contacts(0.0, 0.0);
+    let cuboid = ShapeHandle::new(Cuboid::new(Vector3::new(0.5, 0.5, 0.5)));
+    let start = Isometry3::translation(-3.0, 0.45, 0.2);
+
+    let (ground_handle, box_handle) = if ground_first {
+        let ground_handle = world
+            .add(
+                Isometry3::identity(),
+                ground,
+                CollisionGroups::new(),
+                contacts_query,
+                (),
+            )
+            .0;
+        let box_handle = world
+            .add(start, cuboid, CollisionGroups::new(), contacts_query, ())
+            .0;
+        (ground_handle, box_handle)
+    } else {
+        let box_handle = world
+            .add(start, cuboid, CollisionGroups::new(), contacts_query, ())
+            .0;
+        let ground_handle = world
+            .add(
+                Isometry3::identity(),
+                ground,
+                CollisionGroups::new(),
+                contacts_query,
+                (),
+            )
+            .0;
+        (ground_handle, box_handle)
+    };
+
+    for i in 0..30 {
+        let x = -3.0 + i as f32 * 0.2;
+        world
+            .get_mut(box_handle)
+            .unwrap()
+            .set_position(Isometry3::translation(x, 0.45, 0.2));
+        world.update();
+
+        let (_, _, _, manifold) = world
+            .contact_pair(ground_handle, box_handle, true)
+            .expect("The box must touch the ground.");
+        assert!(manifold.len() != 0);
+
+        for c in manifold.contacts() {
+            assert!(c.contact.normal.y.abs() > 0.999);
+            assert!(c.contact.depth > -1.0e-4 && c.contact.depth < 0.05 + 1.0e-4);
+        }
+
+        let deepest = manifold.deepest_contact().unwrap();
+        assert!((deepest.contact.depth - 0.05).abs() < 1.0e-4);
+    }
+}
+
+#[test]
+fn box_does_not_catch_on_trimesh_internal_edges() {
+    slide_box_on(ShapeHandle::new(grid(10, 10.0)), true);
+    slide_box_on(ShapeHandle::new(grid(10, 10.0)), false);
+}
+
+#[test]
+fn box_does_not_catch_on_heightfield_internal_edges() {
+    let heights = DMatrix::zeros(11, 11);
+    let heightfield = HeightField::new(heights, Vector3::new(10.0, 1.0, 10.0));
+    slide_box_on(ShapeHandle::new(heightfield.clone()), true);
+    slide_box_on(ShapeHandle::new(heightfield), false);
+}
@@ -1,3 +1,51 @@
+use na::Point3;
+use ncollide3d::shape::TriMesh;
+
+mod ccd;
+mod composite_contact_tracking;
+mod contact_events;
+mod contact_modifier;
 mod contact_pairs;
+mod contact_reduction;
 mod duplicate_trimesh_on_world;
+mod frustum_culling;
+mod interaction_groups;
+mod interferences_with_shape;
+mod internal_edges;
 mod is_send_sync;
+mod narrow_phase_pair_filter;
+mod query_workspace;
+mod shape_registry;
+mod shared_static_geometry;
+mod sleeping;
+mod speculative_contacts;
+
+// A flat square triangle mesh of the given size on the `xz` plane, made of `n * n` cells.
+pub fn grid(n: usize, size: f32) -> TriMesh<f32> {
+    let mut points = Vec::new();
+    let mut indices = Vec::new();
+    let step = size / n as f32;
+
+    for i in 0..=n {
+        for j in 0..=n {
+            points.push(Point3::new(
+                -size / 2.0 + i as f32 * step,
+                0.0,
+                -size / 2.0 + j as f32 * step,
+            ));
+        }
+    }
+
+    for i in 0..n {
+        for j in 0..n {
+            let a = i * (n + 1) + j;
+            let b = a + 1;
+            let c = a + n + 1;
+            let d = c + 1;
+            indices.push(Point3::new(a, b, d));
+            indices.push(Point3::new(a, d, c));
+        }
+    }
+
+    TriMesh::new(points, indices, None)
+}
@@ -0,0 +1,87 @@
+use na::{Isometry3, Vector3};
+use ncollide3d::pipeline::{
+    CollisionGroups, CollisionObject, CollisionObjectSlabHandle, CollisionWorld,
+    GeometricQueryType, NarrowPhasePairFilter,
+};
+use ncollide3d::shape::{Ball, ShapeHandle};
+
+// Collision objects with `false` as user data don't generate contacts.
+struct ContactsEnabled;
+
+impl NarrowPhasePairFilter<f32, CollisionObject<f32, bool>, CollisionObjectSlabHandle>
+    for ContactsEnabled
+{
+    fn is_pair_valid(
+        &self,
+        co1: &CollisionObject<f32, bool>,
+        co2: &CollisionObject<f32, bool>,
+        _: CollisionObjectSlabHandle,
+        _: CollisionObjectSlabHandle,
+    ) -> bool {
+        *co1.data() && *co2.data()
+    }
+}
+
+fn add_ball(world: &mut CollisionWorld<f32, bool>, x: f32) -> CollisionObjectSlabHandle {
+    world
+        .add(
+            Isometry3::new(Vector3::new(x, 0.0, 0.0), na::zero()),
+            ShapeHandle::new(Ball::new(1.0)),
+            CollisionGroups::new(),
+            GeometricQueryType::Contacts(0.0, 0.0),
+            true,
+        )
+        .0
+}
+
+#[test]
+fn narrow_phase_pair_filter_disables_contacts() {
+    let mut world = CollisionWorld::new(0.0);
+    world.set_narrow_phase_pair_filter(Some(ContactsEnabled));
+    let handle1 = add_ball(&mut world, 0.0);
+    let handle2 = add_ball(&mut world, 1.5);
+
+    world.update();
+    assert!(world.contact_pair(handle1, handle2, true).is_some());
+
+    // Changing the user data is enough to disable the contacts, without moving the objects.
+    *world.get_mut(handle2).unwrap().data_mut() = false;
+    world.update();
+    assert!(world.contact_pair(handle1, handle2, true).is_none());
+    assert!(world.contact_pair(handle1, handle2, false).is_some());
+    assert_eq!(world.contact_events().iter().count(), 1);
+
+    *world.get_mut(handle2).unwrap().data_mut() = true;
+    world.update();
+    assert!(world.contact_pair(handle1, handle2, true).is_some());
+}
+
+#[test]
+fn excluded_pairs_never_interact() {
+    let mut world = CollisionWorld::new(0.0);
+    let handle1 = add_ball(&mut world, 0.0);
+    let handle2 = add_ball(&mut world, 1.5);
+    let handle3 = add_ball(&mut world, -1.5);
+
+    world.update();
+    assert!(world.contact_pair(handle1, handle2, true).is_some());
+
+    world.exclude_pair(handle2, handle1);
+    assert!(world.is_pair_excluded(handle1, handle2));
+    world.update();
+    assert!(world.contact_pair(handle1, handle2, false).is_none());
+    assert!(world.contact_pair(handle1, handle3, true).is_some());
+
+    // Moving the objects doesn't bring the pair back.
+    world
+        .get_mut(handle2)
+        .unwrap()
+        .set_position(Isometry3::translation(1.4, 0.0, 0.0));
+    world.update();
+    assert!(world.contact_pair(handle1, handle2, false).is_none());
+
+    world.include_pair(handle1, handle2);
+    assert!(!world.is_pair_excluded(handle1, handle2));
+    world.update();
+    assert!(world.contact_pair(handle1, handle2, true).is_some());
+}
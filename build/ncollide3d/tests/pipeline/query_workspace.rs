@@ -0,0 +1,142 @@
+use na::{Isometry3, Point3, Unit, Vector3};
+use ncollide3d::bounding_volume::AABB;
+use ncollide3d::pipeline::{
+    BroadPhaseWorkspace, CollisionGroups, CollisionWorld, GeometricQueryType,
+};
+use ncollide3d::query::Ray;
+use ncollide3d::shape::{Ball, Cuboid, ShapeHandle};
+
+#[test]
+fn world_queries_with_workspace() {
+    let mut world = CollisionWorld::new(0.0);
+    let query_type = GeometricQueryType::Contacts(0.0, 0.0);
+    let groups = CollisionGroups::new().with_membership(&[0]);
+    let ignored = CollisionGroups::new().with_membership(&[1]);
+    let ball = ShapeHandle::new(Ball::new(0.5f32));
+
+    for i in 0..10 {
+        for j in 0..10 {
+            let groups = if (i + j) % 7 == 0 { ignored } else { groups };
+            let pos = Isometry3::translation(i as f32 * 2.0, j as f32 * 2.0, 0.0);
+            let _ = world.add(pos, ball.clone(), groups, query_type, ());
+        }
+    }
+
+    world.update();
+
+    let query_groups = CollisionGroups::new().with_blacklist(&[1]);
+    let cube = Cuboid::new(Vector3::repeat(0.3f32));
+    let mut workspace = BroadPhaseWorkspace::new();
+    let mut ray_hits = Vec::new();
+    let mut sweep_hits = Vec::new();
+    let mut handles = Vec::new();
+    let mut capacities = Vec::new();
+
+    for _ in 0..2 {
+        for i in 0..10 {
+            let y = i as f32 * 2.0 + 0.2;
+
+            // Rays along the rows of balls.
+            let ray = Ray::new(Point3::new(-5.0, y, 0.0), Vector3::x());
+            let mut expected: Vec<_> = world
+                .interferences_with_ray(&ray, 100.0, &query_groups)
+                .map(|(handle, _, inter)| (handle, inter.toi))
+                .collect();
+            ray_hits.clear();
+            world.interferences_with_ray_with_workspace(
+                &ray,
+                100.0,
+                &query_groups,
+                &mut workspace,
+                &mut ray_hits,
+            );
+            let mut result: Vec<_> = ray_hits.iter().map(|(h, inter)| (*h, inter.toi)).collect();
+            expected.sort_by_key(|e| e.0);
+            result.sort_by_key(|e| e.0);
+            assert!(!result.is_empty());
+            assert_eq!(result, expected);
+
+            let expected = world
+                .first_interference_with_ray(&ray, 100.0, &query_groups)
+                .unwrap();
+            let first = world
+                .first_interference_with_ray_with_workspace(
+                    &ray,
+                    100.0,
+                    &query_groups,
+                    &mut workspace,
+                )
+                .unwrap();
+            assert_eq!(first.handle, expected.handle);
+            assert_eq!(first.inter.toi, expected.inter.toi);
+            assert_eq!(
+                first.inter.toi,
+                result.iter().map(|e| e.1).fold(f32::MAX, f32::min)
+            );
+
+            // Sweeps along the rows of balls.
+            let pos = Isometry3::translation(-5.0, y, 0.0);
+            let dir = Unit::new_normalize(Vector3::x());
+            let mut expected: Vec<_> = world
+                .sweep_test(&cube, &pos, &dir, 30.0, &query_groups)
+                .map(|(handle, toi)| (handle, toi.toi))
+                .collect();
+            sweep_hits.clear();
+            world.sweep_test_with_workspace(
+                &cube,
+                &pos,
+                &dir,
+                30.0,
+                &query_groups,
+                &mut workspace,
+                &mut sweep_hits,
+            );
+            let mut result: Vec<_> = sweep_hits.iter().map(|(h, toi)| (*h, toi.toi)).collect();
+            expected.sort_by_key(|e| e.0);
+            result.sort_by_key(|e| e.0);
+            assert!(!result.is_empty());
+            assert_eq!(result, expected);
+
+            // AABB and point queries.
+            let aabb = AABB::new(Point3::new(0.0, y - 1.0, -1.0), Point3::new(6.0, y, 1.0));
+            let mut expected: Vec<_> = world
+                .interferences_with_aabb(&aabb, &query_groups)
+                .map(|(handle, _)| handle)
+                .collect();
+            handles.clear();
+            world.interferences_with_aabb_with_workspace(
+                &aabb,
+                &query_groups,
+                &mut workspace,
+                &mut handles,
+            );
+            expected.sort();
+            handles.sort();
+            assert!(!handles.is_empty());
+            assert_eq!(handles, expected);
+
+            let point = Point3::new(4.0, y, 0.0);
+            let expected: Vec<_> = world
+                .interferences_with_point(&point, &query_groups)
+                .map(|(handle, _)| handle)
+                .collect();
+            handles.clear();
+            world.interferences_with_point_with_workspace(
+                &point,
+                &query_groups,
+                &mut workspace,
+                &mut handles,
+            );
+            assert_eq!(handles, expected);
+        }
+
+        capacities.push((
+            ray_hits.capacity(),
+            sweep_hits.capacity(),
+            handles.capacity(),
+        ));
+    }
+
+    // The output buffers don't grow once they are large enough for the queries.
+    assert_eq!(capacities[0], capacities[1]);
+}
@@ -0,0 +1,390 @@
+use na::{Isometry3, Unit, Vector3};
+use ncollide3d::bounding_volume::AABB;
+use ncollide3d::interpolation::RigidMotion;
+use ncollide3d::pipeline::{
+    CollisionGroups, CollisionWorld, ContactAlgorithm, ContactDispatcher, ContactManifoldGenerator,
+    GeometricQueryType, ProximityAlgorithm, ProximityDetector, ProximityDispatcher, ShapeKind,
+    ShapeRegistry,
+};
+use ncollide3d::query::{
+    ContactManifold, ContactPrediction, ContactPreprocessor, Proximity, TOIDispatcher, Unsupported,
+    TOI,
+};
+use ncollide3d::shape::{Ball, Compound, Cuboid, FeatureId, Shape, ShapeHandle};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+// A ball which is not known by the built-in dispatchers.
+#[derive(Clone)]
+struct SdfBall {
+    radius: f32,
+}
+
+impl SdfBall {
+    fn ball(&self) -> Ball<f32> {
+        Ball::new(self.radius)
+    }
+}
+
+impl Shape<f32> for SdfBall {
+    fn aabb(&self, m: &Isometry3<f32>) -> AABB<f32> {
+        self.ball().aabb(m)
+    }
+
+    fn tangent_cone_contains_dir(
+        &self,
+        _: FeatureId,
+        _: &Isometry3<f32>,
+        _: Option<&[f32]>,
+        _: &Unit<Vector3<f32>>,
+    ) -> bool {
+        false
+    }
+}
+
+// Computes contacts by replacing the `SdfBall` with a `Ball`.
+struct SdfBallShapeManifoldGenerator {
+    flip: bool,
+    sub_detector: Option<ContactAlgorithm<f32>>,
+}
+
+impl ContactManifoldGenerator<f32> for SdfBallShapeManifoldGenerator {
+    fn generate_contacts(
+        &mut self,
+        dispatcher: &dyn ContactDispatcher<f32>,
+        ma: &Isometry3<f32>,
+        a: &dyn Shape<f32>,
+        proc1: Option<&dyn ContactPreprocessor<f32>>,
+        mb: &Isometry3<f32>,
+        b: &dyn Shape<f32>,
+        proc2: Option<&dyn ContactPreprocessor<f32>>,
+        prediction: &ContactPrediction<f32>,
+        manifold: &mut ContactManifold<f32>,
+    ) -> bool {
+        let (a, b) = if !self.flip {
+            (
+                &a.as_shape::<SdfBall>().unwrap().ball() as &dyn Shape<f32>,
+                b,
+            )
+        } else {
+            (
+                a,
+                &b.as_shape::<SdfBall>().unwrap().ball() as &dyn Shape<f32>,
+            )
+        };
+
+        if self.sub_detector.is_none() {
+            self.sub_detector = dispatcher.get_contact_algorithm(a, b);
+        }
+
+        match &mut self.sub_detector {
+            Some(detector) => detector
+                .generate_contacts(dispatcher, ma, a, proc1, mb, b, proc2, prediction, manifold),
+            None => false,
+        }
+    }
+}
+
+// Computes proximities by replacing the `SdfBall` with a `Ball`.
+struct SdfBallShapeProximityDetector {
+    flip: bool,
+    sub_detector: Option<ProximityAlgorithm<f32>>,
+}
+
+impl ProximityDetector<f32> for SdfBallShapeProximityDetector {
+    fn update(
+        &mut self,
+        dispatcher: &dyn ProximityDispatcher<f32>,
+        ma: &Isometry3<f32>,
+        a: &dyn Shape<f32>,
+        mb: &Isometry3<f32>,
+        b: &dyn Shape<f32>,
+        margin: f32,
+    ) -> Option<Proximity> {
+        let (a, b) = if !self.flip {
+            (
+                &a.as_shape::<SdfBall>().unwrap().ball() as &dyn Shape<f32>,
+                b,
+            )
+        } else {
+            (
+                a,
+                &b.as_shape::<SdfBall>().unwrap().ball() as &dyn Shape<f32>,
+            )
+        };
+
+        if self.sub_detector.is_none() {
+            self.sub_detector = dispatcher.get_proximity_algorithm(a, b);
+        }
+
+        self.sub_detector
+            .as_mut()?
+            .update(dispatcher, ma, a, mb, b, margin)
+    }
+}
+
+// Computes times of impact by replacing the `SdfBall`, always given first, with a `Ball`.
+struct SdfBallShapeTOIDispatcher;
+
+impl TOIDispatcher<f32> for SdfBallShapeTOIDispatcher {
+    fn nonlinear_time_of_impact(
+        &self,
+        root_dispatcher: &dyn TOIDispatcher<f32>,
+        motion1: &dyn RigidMotion<f32>,
+        g1: &dyn Shape<f32>,
+        motion2: &dyn RigidMotion<f32>,
+        g2: &dyn Shape<f32>,
+        max_toi: f32,
+        target_distance: f32,
+    ) -> Result<Option<TOI<f32>>, Unsupported> {
+        let ball = g1.as_shape::<SdfBall>().unwrap().ball();
+        root_dispatcher.nonlinear_time_of_impact(
+            root_dispatcher,
+            motion1,
+            &ball,
+            motion2,
+            g2,
+            max_toi,
+            target_distance,
+        )
+    }
+
+    fn time_of_impact(
+        &self,
+        root_dispatcher: &dyn TOIDispatcher<f32>,
+        m1: &Isometry3<f32>,
+        vel1: &Vector3<f32>,
+        g1: &dyn Shape<f32>,
+        m2: &Isometry3<f32>,
+        vel2: &Vector3<f32>,
+        g2: &dyn Shape<f32>,
+        max_toi: f32,
+        target_distance: f32,
+    ) -> Result<Option<TOI<f32>>, Unsupported> {
+        let ball = g1.as_shape::<SdfBall>().unwrap().ball();
+        root_dispatcher.time_of_impact(
+            root_dispatcher,
+            m1,
+            vel1,
+            &ball,
+            m2,
+            vel2,
+            g2,
+            max_toi,
+            target_distance,
+        )
+    }
+}
+
+fn registry() -> ShapeRegistry<f32> {
+    let mut registry = ShapeRegistry::new();
+    let sdf_ball = ShapeKind::of::<f32, SdfBall>();
+
+    registry.register_contact_algorithm(sdf_ball, ShapeKind::Any, |flip| {
+        Box::new(SdfBallShapeManifoldGenerator {
+            flip,
+            sub_detector: None,
+        })
+    });
+    registry.register_proximity_algorithm(sdf_ball, ShapeKind::Any, |flip| {
+        Box::new(SdfBallShapeProximityDetector {
+            flip,
+            sub_detector: None,
+        })
+    });
+    registry.register_toi_dispatcher(sdf_ball, ShapeKind::Any, SdfBallShapeTOIDispatcher);
+    registry
+}
+
+// The contact manifolds between `shape` and a cuboid, with `true` if `shape` is the first shape of the manifold.
+fn manifolds_with_cuboid(
+    shape: ShapeHandle<f32>,
+    shape_first: bool,
+) -> Vec<(ContactManifold<f32>, bool)> {
+    let mut world = CollisionWorld::new(0.0);
+    world.set_shape_registry(registry());
+    let contacts_query = GeometricQueryType::Contacts(0.0, 0.0);
+    let cuboid = ShapeHandle::new(Cuboid::new(Vector3::repeat(1.0)));
+    let mut handles = Vec::new();
+    let mut shapes = vec![
+        (Isometry3::translation(0.0, 1.4, 0.0), shape),
+        (Isometry3::identity(), cuboid),
+    ];
+
+    if !shape_first {
+        shapes.reverse();
+    }
+
+    for (pos, shape) in shapes {
+        let (handle, _) = world.add(pos, shape, CollisionGroups::new(), contacts_query, ());
+        handles.push(handle);
+    }
+
+    let shape_handle = if shape_first { handles[0] } else { handles[1] };
+    world.update();
+
+    world
+        .contact_pairs(false)
+        .map(|(h1, _, _, manifold)| (manifold.clone(), h1 == shape_handle))
+        .collect()
+}
+
+#[test]
+fn registered_contact_algorithm() {
+    for shape_first in &[true, false] {
+        let manifolds =
+            manifolds_with_cuboid(ShapeHandle::new(SdfBall { radius: 0.5 }), *shape_first);
+        assert_eq!(manifolds.len(), 1);
+
+        let (manifold, ball_is_first) = &manifolds[0];
+        let contact = manifold.deepest_contact().unwrap().contact;
+        assert!((contact.depth - 0.1).abs() < 1.0e-5);
+        // The normal points from the first shape toward the second one.
+        let expected_y = if *ball_is_first { -1.0 } else { 1.0 };
+        assert!((contact.normal.y - expected_y).abs() < 1.0e-5);
+    }
+}
+
+#[test]
+fn registered_contact_algorithm_inside_composite_shape() {
+    let compound = Compound::new(vec![(
+        Isometry3::identity(),
+        ShapeHandle::new(SdfBall { radius: 0.5 }),
+    )]);
+
+    let manifolds = manifolds_with_cuboid(ShapeHandle::new(compound), true);
+    assert_eq!(manifolds.len(), 1);
+    let contact = manifolds[0].0.deepest_contact().unwrap().contact;
+    assert!((contact.depth - 0.1).abs() < 1.0e-5);
+}
+
+#[test]
+fn registered_contact_algorithm_with_composite_shape() {
+    // Registrations involving a concrete shape type take precedence over the built-in algorithms
+    // for composite shapes.
+    let mut registry = registry();
+    let num_created = Arc::new(AtomicUsize::new(0));
+    let counter = num_created.clone();
+
+    registry.register_contact_algorithm(
+        ShapeKind::of::<f32, SdfBall>(),
+        ShapeKind::CompositeShape,
+        move |flip| {
+            let _ = counter.fetch_add(1, Ordering::SeqCst);
+            Box::new(SdfBallShapeManifoldGenerator {
+                flip,
+                sub_detector: None,
+            })
+        },
+    );
+
+    let mut world = CollisionWorld::new(0.0);
+    world.set_shape_registry(registry);
+    let contacts_query = GeometricQueryType::Contacts(0.0, 0.0);
+    let compound = Compound::new(vec![(
+        Isometry3::identity(),
+        ShapeHandle::new(Cuboid::new(Vector3::repeat(1.0))),
+    )]);
+
+    for (pos, shape) in [
+        (Isometry3::identity(), ShapeHandle::new(compound)),
+        (
+            Isometry3::translation(0.0, 1.4, 0.0),
+            ShapeHandle::new(SdfBall { radius: 0.5 }),
+        ),
+    ] {
+        let _ = world.add(pos, shape, CollisionGroups::new(), contacts_query, ());
+    }
+
+    world.update();
+    assert_eq!(num_created.load(Ordering::SeqCst), 1);
+
+    let manifolds: Vec<_> = world.contact_pairs(true).map(|pair| pair.3).collect();
+    assert_eq!(manifolds.len(), 1);
+    let contact = manifolds[0].deepest_contact().unwrap().contact;
+    assert!((contact.depth - 0.1).abs() < 1.0e-5);
+}
+
+#[test]
+fn registered_proximity_algorithm() {
+    let mut world = CollisionWorld::new(0.0);
+    world.set_shape_registry(registry());
+    let proximity_query = GeometricQueryType::Proximity(0.0);
+
+    let _ = world.add(
+        Isometry3::identity(),
+        ShapeHandle::new(Cuboid::new(Vector3::repeat(1.0))),
+        CollisionGroups::new(),
+        proximity_query,
+        (),
+    );
+    let _ = world.add(
+        Isometry3::translation(1.4, 0.0, 0.0),
+        ShapeHandle::new(SdfBall { radius: 0.5 }),
+        CollisionGroups::new(),
+        proximity_query,
+        (),
+    );
+
+    world.update();
+
+    let proximities: Vec<_> = world
+        .proximity_pairs(false)
+        .map(|(_, _, _, prox)| prox)
+        .collect();
+    assert_eq!(proximities, vec![Proximity::Intersecting]);
+}
+
+#[test]
+fn registered_toi_dispatcher() {
+    let registry = registry();
+    let sdf_ball = SdfBall { radius: 0.5 };
+    let cuboid = Cuboid::new(Vector3::repeat(1.0));
+    let m1 = Isometry3::translation(-5.0, 0.0, 0.0);
+    let m2 = Isometry3::identity();
+    let vel1 = Vector3::new(1.0, 0.0, 0.0);
+    let vel2 = Vector3::zeros();
+
+    let toi = registry
+        .time_of_impact(
+            &registry, &m1, &vel1, &sdf_ball, &m2, &vel2, &cuboid, 10.0, 0.0,
+        )
+        .unwrap()
+        .unwrap();
+    assert!((toi.toi - 3.5).abs() < 1.0e-4);
+    assert!((toi.normal1.x - 1.0).abs() < 1.0e-4);
+
+    let toi = registry
+        .time_of_impact(
+            &registry, &m2, &vel2, &cuboid, &m1, &vel1, &sdf_ball, 10.0, 0.0,
+        )
+        .unwrap()
+        .unwrap();
+    assert!((toi.toi - 3.5).abs() < 1.0e-4);
+    assert!((toi.normal1.x + 1.0).abs() < 1.0e-4);
+}
+
+#[test]
+fn unregistered_shapes_are_unsupported() {
+    let registry = ShapeRegistry::new();
+    let sdf_ball = SdfBall { radius: 0.5 };
+    let cuboid = Cuboid::new(Vector3::repeat(1.0));
+
+    assert!(registry.get_contact_algorithm(&sdf_ball, &cuboid).is_none());
+    assert!(registry
+        .get_proximity_algorithm(&sdf_ball, &cuboid)
+        .is_none());
+    assert!(registry
+        .time_of_impact(
+            &registry,
+            &Isometry3::identity(),
+            &Vector3::zeros(),
+            &sdf_ball,
+            &Isometry3::identity(),
+            &Vector3::zeros(),
+            &cuboid,
+            1.0,
+            0.0,
+        )
+        .is_err());
+}
@@ -0,0 +1,107 @@
+use na::{Isometry3, Point3, Vector3};
+use ncollide3d::pipeline::{
+    CollisionGroups, CollisionWorld, ContactEvent, GeometricQueryType, StaticScene,
+};
+use ncollide3d::query::Ray;
+use ncollide3d::shape::{Ball, Cuboid, ShapeHandle, TriMesh};
+use std::sync::Arc;
+
+#[test]
+fn static_geometry_shared_among_worlds() {
+    // The static scene: a flat triangle mesh floor, and a box next to it.
+    let contacts_query = GeometricQueryType::Contacts(0.0, 0.0);
+    let mut scene = StaticScene::new();
+    let floor = scene.add(
+        Isometry3::identity(),
+        ShapeHandle::new(TriMesh::new(
+            vec![
+                Point3::new(-10.0, 0.0, -10.0),
+                Point3::new(0.0, 0.0, -10.0),
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(-10.0, 0.0, 0.0),
+            ],
+            vec![Point3::new(0, 2, 1), Point3::new(0, 3, 2)],
+            None,
+        )),
+        CollisionGroups::new(),
+        contacts_query,
+        (),
+    );
+    let block = scene.add(
+        Isometry3::translation(10.0, 0.0, 0.0),
+        ShapeHandle::new(Cuboid::new(Vector3::new(1.0, 1.0, 1.0))),
+        CollisionGroups::new(),
+        contacts_query,
+        (),
+    );
+    let scene_handles = [floor, block];
+    assert!(scene_handles.iter().all(|h| h.is_in_static_scene()));
+
+    let scene = Arc::new(scene);
+    let ball = ShapeHandle::new(Ball::new(0.5));
+    let mut worlds = Vec::new();
+
+    for ball_pos in &[Vector3::new(-5.0, 0.4, -5.0), Vector3::new(10.0, 1.4, 0.0)] {
+        let mut world = CollisionWorld::with_static_scene(0.0, scene.clone());
+        let (ball_handle, _) = world.add(
+            Isometry3::new(*ball_pos, na::zero()),
+            ball.clone(),
+            CollisionGroups::new(),
+            contacts_query,
+            (),
+        );
+        world.update();
+
+        // The static collision objects are not copied into the world.
+        assert_eq!(world.collision_objects().count(), 1);
+        assert!(world.get_mut(floor).is_none());
+        assert!(world
+            .collision_object(floor)
+            .unwrap()
+            .activation_status()
+            .is_static());
+        worlds.push((world, ball_handle));
+    }
+
+    for (i, (world, ball_handle)) in worlds.iter().enumerate() {
+        // Each ball only touches one part of the scene.
+        for (j, scene_handle) in scene_handles.iter().enumerate() {
+            let pair = world.contact_pair(*scene_handle, *ball_handle, true);
+            assert_eq!(pair.is_some(), i == j);
+
+            if let Some(pair) = pair {
+                let depth = pair.3.deepest_contact().unwrap().contact.depth;
+                assert_relative_eq!(depth, 0.1, epsilon = 1.0e-5);
+            }
+        }
+
+        let events: Vec<_> = world.contact_events().iter().cloned().collect();
+        assert_eq!(events.len(), 1);
+        assert!(
+            events[0] == ContactEvent::Started(scene_handles[i], *ball_handle)
+                || events[0] == ContactEvent::Started(*ball_handle, scene_handles[i])
+        );
+    }
+
+    // Queries see both the static scene and the collision objects of each world.
+    let (world, ball_handle) = &mut worlds[1];
+    let ray = Ray::new(Point3::new(10.0, 10.0, 0.0), -Vector3::y());
+    let hit = world
+        .first_interference_with_ray(&ray, 100.0, &CollisionGroups::new())
+        .unwrap();
+    assert_eq!(hit.handle, *ball_handle);
+
+    let hits: Vec<_> = world
+        .interferences_with_ray(&ray, 100.0, &CollisionGroups::new())
+        .map(|(handle, _, _)| handle)
+        .collect();
+    assert_eq!(hits.len(), 2);
+    assert!(hits.contains(&block) && hits.contains(ball_handle));
+
+    // Removing the ball removes its contact with the static scene, but not the scene itself.
+    world.remove(&[*ball_handle]);
+    world.update();
+    assert_eq!(world.contact_pairs(false).count(), 0);
+    assert!(world.collision_object(block).is_some());
+    assert_eq!(worlds[0].0.contact_pairs(true).count(), 1);
+}
@@ -0,0 +1,77 @@
+use na::{Isometry3, Vector3};
+use ncollide3d::pipeline::{ActivationStatus, CollisionGroups, CollisionWorld, GeometricQueryType};
+use ncollide3d::shape::{Ball, Cuboid, ShapeHandle};
+
+#[test]
+fn sleeping_pairs_keep_their_contacts_until_woken_up() {
+    let mut world = CollisionWorld::new(0.0);
+    let contacts_query = GeometricQueryType::Contacts(0.0, 0.0);
+
+    let ground = ShapeHandle::new(Cuboid::new(Vector3::new(10.0, 1.0, 10.0)));
+    let (ground_handle, ground_object) = world.add(
+        Isometry3::identity(),
+        ground,
+        CollisionGroups::new(),
+        contacts_query,
+        (),
+    );
+    ground_object.set_activation_status(ActivationStatus::Static);
+
+    let cube = ShapeHandle::new(Cuboid::new(Vector3::new(1.0, 1.0, 1.0)));
+    let (cube_handle, _) = world.add(
+        Isometry3::translation(0.0, 1.9, 0.0),
+        cube,
+        CollisionGroups::new(),
+        contacts_query,
+        (),
+    );
+
+    world.update();
+    let depth = |world: &CollisionWorld<f32, ()>| {
+        world
+            .contact_pair(ground_handle, cube_handle, true)
+            .unwrap()
+            .3
+            .deepest_contact()
+            .unwrap()
+            .contact
+            .depth
+    };
+    assert_relative_eq!(depth(&world), 0.1, epsilon = 1.0e-5);
+
+    // Contacts between a sleeping and a static object are not updated.
+    let cube_object = world.get_mut(cube_handle).unwrap();
+    cube_object.set_activation_status(ActivationStatus::Sleeping);
+    cube_object.set_position(Isometry3::translation(0.0, 1.8, 0.0));
+    world.update();
+    assert_relative_eq!(depth(&world), 0.1, epsilon = 1.0e-5);
+    assert!(world
+        .collision_object(cube_handle)
+        .unwrap()
+        .activation_status()
+        .is_sleeping());
+
+    // An active object moving close to the sleeping cube wakes it up.
+    let ball = ShapeHandle::new(Ball::new(0.5));
+    let (ball_handle, _) = world.add(
+        Isometry3::translation(0.0, 10.0, 0.0),
+        ball,
+        CollisionGroups::new(),
+        contacts_query,
+        (),
+    );
+    world.update();
+    world
+        .get_mut(ball_handle)
+        .unwrap()
+        .set_position(Isometry3::translation(0.0, 3.0, 0.0));
+    world.update();
+    assert!(world
+        .collision_object(cube_handle)
+        .unwrap()
+        .activation_status()
+        .is_active());
+
+    // The contacts of the cube with the ground are updated as soon as it is woken up.
+    assert_relative_eq!(depth(&world), 0.2, epsilon = 1.0e-5);
+}
@@ -0,0 +1,215 @@
+use na::{Isometry3, Unit, Vector3};
+use ncollide3d::bounding_volume::AABB;
+use ncollide3d::pipeline::{
+    CollisionGroups, CollisionWorld, ContactAlgorithm, ContactDispatcher, ContactManifoldGenerator,
+    GeometricQueryType, ShapeKind, ShapeRegistry, SpeculativeContacts,
+};
+use ncollide3d::query::{ContactManifold, ContactPrediction, ContactPreprocessor};
+use ncollide3d::shape::{Ball, Cuboid, FeatureId, Shape, ShapeHandle};
+use std::sync::{Arc, Mutex};
+
+fn fast_ball_near_cuboid(
+    speculative_contacts: SpeculativeContacts,
+    position: Isometry3<f32>,
+    predicted: Isometry3<f32>,
+) -> Vec<ContactManifold<f32>> {
+    let mut world = CollisionWorld::new(0.0);
+    world.set_speculative_contacts(speculative_contacts);
+    let contacts_query = GeometricQueryType::Contacts(0.0, 0.0);
+
+    let _ = world.add(
+        Isometry3::identity(),
+        ShapeHandle::new(Cuboid::new(Vector3::repeat(1.0))),
+        CollisionGroups::new(),
+        contacts_query,
+        (),
+    );
+    let (ball, _) = world.add(
+        position,
+        ShapeHandle::new(Ball::new(0.5)),
+        CollisionGroups::new(),
+        contacts_query,
+        (),
+    );
+
+    world
+        .get_mut(ball)
+        .unwrap()
+        .set_position_with_prediction(position, predicted);
+    world.update();
+
+    world
+        .contact_pairs(false)
+        .map(|(_, _, _, manifold)| manifold.clone())
+        .collect()
+}
+
+#[test]
+fn speculative_contacts_at_predicted_positions() {
+    let position = Isometry3::translation(-3.0, 0.0, 0.0);
+    let predicted = Isometry3::translation(-1.3, 0.0, 0.0);
+
+    let manifolds = fast_ball_near_cuboid(SpeculativeContacts::Disabled, position, predicted);
+    assert!(manifolds.iter().all(|m| m.len() == 0));
+
+    let manifolds =
+        fast_ball_near_cuboid(SpeculativeContacts::PredictedPositions, position, predicted);
+    assert_eq!(manifolds.len(), 1);
+    assert_eq!(manifolds[0].len(), 1);
+
+    let contact = manifolds[0].contacts().next().unwrap();
+    assert!(contact.speculative);
+    assert!(contact.contact.normal.x.abs() > 0.999);
+    assert!((contact.contact.depth + 1.5).abs() < 1.0e-4);
+}
+
+#[test]
+fn speculative_contacts_along_interpolated_motion() {
+    // The ball goes through the top of the cuboid and does not touch it at its predicted position.
+    let position = Isometry3::translation(-3.0, 1.2, 0.0);
+    let predicted = Isometry3::translation(3.0, 1.2, 0.0);
+
+    let manifolds =
+        fast_ball_near_cuboid(SpeculativeContacts::PredictedPositions, position, predicted);
+    assert!(manifolds.iter().all(|m| m.len() == 0));
+
+    let manifolds = fast_ball_near_cuboid(
+        SpeculativeContacts::InterpolatedMotion(4),
+        position,
+        predicted,
+    );
+    assert_eq!(manifolds.len(), 1);
+    assert!(manifolds[0].len() > 0);
+
+    for contact in manifolds[0].contacts() {
+        assert!(contact.speculative);
+        assert!(contact.contact.normal.y.abs() > 0.999);
+    }
+}
+
+// A ball which is not known by the built-in dispatchers.
+#[derive(Clone)]
+struct RecordedBall(Ball<f32>);
+
+impl Shape<f32> for RecordedBall {
+    fn aabb(&self, m: &Isometry3<f32>) -> AABB<f32> {
+        self.0.aabb(m)
+    }
+
+    fn tangent_cone_contains_dir(
+        &self,
+        _: FeatureId,
+        _: &Isometry3<f32>,
+        _: Option<&[f32]>,
+        _: &Unit<Vector3<f32>>,
+    ) -> bool {
+        false
+    }
+}
+
+// Computes contacts by replacing the `RecordedBall` with a `Ball`, and records the positions of
+// the ball it is called with.
+struct RecordingManifoldGenerator {
+    flip: bool,
+    positions: Arc<Mutex<Vec<f32>>>,
+    sub_detector: Option<ContactAlgorithm<f32>>,
+}
+
+impl ContactManifoldGenerator<f32> for RecordingManifoldGenerator {
+    fn generate_contacts(
+        &mut self,
+        dispatcher: &dyn ContactDispatcher<f32>,
+        ma: &Isometry3<f32>,
+        a: &dyn Shape<f32>,
+        proc1: Option<&dyn ContactPreprocessor<f32>>,
+        mb: &Isometry3<f32>,
+        b: &dyn Shape<f32>,
+        proc2: Option<&dyn ContactPreprocessor<f32>>,
+        prediction: &ContactPrediction<f32>,
+        manifold: &mut ContactManifold<f32>,
+    ) -> bool {
+        let (a, b) = if !self.flip {
+            self.positions.lock().unwrap().push(ma.translation.vector.x);
+            (
+                &a.as_shape::<RecordedBall>().unwrap().0 as &dyn Shape<f32>,
+                b,
+            )
+        } else {
+            self.positions.lock().unwrap().push(mb.translation.vector.x);
+            (
+                a,
+                &b.as_shape::<RecordedBall>().unwrap().0 as &dyn Shape<f32>,
+            )
+        };
+
+        if self.sub_detector.is_none() {
+            self.sub_detector = dispatcher.get_contact_algorithm(a, b);
+        }
+
+        match &mut self.sub_detector {
+            Some(detector) => detector
+                .generate_contacts(dispatcher, ma, a, proc1, mb, b, proc2, prediction, manifold),
+            None => false,
+        }
+    }
+}
+
+#[test]
+fn speculative_contacts_leave_the_pair_detector_untouched() {
+    // The positions recorded by each contact generator, in creation order.
+    let generators: Arc<Mutex<Vec<Arc<Mutex<Vec<f32>>>>>> = Arc::new(Mutex::new(Vec::new()));
+    let created = generators.clone();
+    let mut registry = ShapeRegistry::new();
+    registry.register_contact_algorithm(
+        ShapeKind::of::<f32, RecordedBall>(),
+        ShapeKind::Any,
+        move |flip| {
+            let positions = Arc::new(Mutex::new(Vec::new()));
+            created.lock().unwrap().push(positions.clone());
+            Box::new(RecordingManifoldGenerator {
+                flip,
+                positions,
+                sub_detector: None,
+            })
+        },
+    );
+
+    let mut world = CollisionWorld::new(0.0);
+    world.set_shape_registry(registry);
+    world.set_speculative_contacts(SpeculativeContacts::InterpolatedMotion(2));
+    let contacts_query = GeometricQueryType::Contacts(0.0, 0.0);
+
+    let (ball, _) = world.add(
+        Isometry3::translation(-1.4, 0.0, 0.0),
+        ShapeHandle::new(RecordedBall(Ball::new(0.5))),
+        CollisionGroups::new(),
+        contacts_query,
+        (),
+    );
+    let _ = world.add(
+        Isometry3::identity(),
+        ShapeHandle::new(Cuboid::new(Vector3::repeat(1.0))),
+        CollisionGroups::new(),
+        contacts_query,
+        (),
+    );
+    world.update();
+
+    for i in 0..3 {
+        let position = Isometry3::translation(-1.4 - i as f32, 0.0, 0.0);
+        let predicted = Isometry3::translation(5.0, 0.0, 0.0);
+        world
+            .get_mut(ball)
+            .unwrap()
+            .set_position_with_prediction(position, predicted);
+        world.update();
+    }
+
+    // The first generator created is the one of the pair: it only sees the current positions.
+    // The second one computes the speculative contacts, and is reused at each update.
+    let generators = generators.lock().unwrap();
+    assert_eq!(generators.len(), 2);
+    let positions = generators[0].lock().unwrap();
+    assert_eq!(*positions, vec![-1.4, -1.4, -2.4, -3.4]);
+    assert_eq!(generators[1].lock().unwrap().len(), 3 * 2);
+}
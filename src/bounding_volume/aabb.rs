@@ -129,6 +129,20 @@ impl<N: RealField> BoundingVolume<N> for AABB<N> {
         self.center()
     }
 
+    #[inline]
+    #[cfg(feature = "dim2")]
+    fn surface_area(&self) -> N {
+        let extents = self.extents();
+        (extents.x + extents.y) * na::convert(2.0)
+    }
+
+    #[inline]
+    #[cfg(feature = "dim3")]
+    fn surface_area(&self) -> N {
+        let extents = self.extents();
+        (extents.x * extents.y + extents.y * extents.z + extents.z * extents.x) * na::convert(2.0)
+    }
+
     #[inline]
     fn intersects(&self, other: &AABB<N>) -> bool {
         na::partial_le(&self.mins, &other.maxs) && na::partial_ge(&self.maxs, &other.mins)
@@ -67,6 +67,18 @@ impl<N: RealField> BoundingVolume<N> for BoundingSphere<N> {
         *self.center()
     }
 
+    #[inline]
+    #[cfg(feature = "dim2")]
+    fn surface_area(&self) -> N {
+        N::two_pi() * self.radius
+    }
+
+    #[inline]
+    #[cfg(feature = "dim3")]
+    fn surface_area(&self) -> N {
+        N::two_pi() * self.radius * self.radius * na::convert(2.0)
+    }
+
     #[inline]
     fn intersects(&self, other: &BoundingSphere<N>) -> bool {
         // FIXME: refactor that with the code from narrow_phase::ball_ball::collide(...) ?
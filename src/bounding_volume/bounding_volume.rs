@@ -22,6 +22,16 @@ pub trait BoundingVolume<N: RealField>: std::fmt::Debug {
     /// Returns a point inside of this bounding volume. This is ideally its center.
     fn center(&self) -> Point<N>;
 
+    /// The surface area of this bounding volume, or its perimeter in 2D.
+    ///
+    /// This is used by the surface area heuristic to estimate the quality of bounding volume
+    /// hierarchies. The default implementation returns zero, in which case the hierarchies built
+    /// or updated with this heuristic fall back to splitting or descending by the bounding volume
+    /// centers.
+    fn surface_area(&self) -> N {
+        N::zero()
+    }
+
     /// Checks if this bounding volume intersect with another one.
     fn intersects(&self, _: &Self) -> bool;
 
@@ -0,0 +1,370 @@
+//! Discrete Oriented Polytope.
+
+use crate::bounding_volume::{BoundingVolume, HasBoundingVolume, AABB};
+use crate::math::{Isometry, Point, Vector, DIM};
+use na::{self, RealField};
+use std::fmt::Debug;
+
+// Seems useful to help type inference. See issue #84.
+/// Computes the k-DOP of a shape `g` transformed by `m`.
+#[inline]
+pub fn kdop<N, A, G>(g: &G, m: &Isometry<N>) -> KDOP<N, A>
+where
+    N: RealField,
+    A: KDOPAxes<N>,
+    G: ?Sized + HasBoundingVolume<N, KDOP<N, A>>,
+{
+    g.bounding_volume(m)
+}
+
+// Seems useful to help type inference. See issue #84.
+/// Computes the k-DOP of a shape `g`.
+#[inline]
+pub fn local_kdop<N, A, G>(g: &G) -> KDOP<N, A>
+where
+    N: RealField,
+    A: KDOPAxes<N>,
+    G: ?Sized + HasBoundingVolume<N, KDOP<N, A>>,
+{
+    g.local_bounding_volume()
+}
+
+/// The set of axes along which a `KDOP` bounds an object.
+pub trait KDOPAxes<N: RealField>: 'static + Copy + Debug + PartialEq + Send + Sync {
+    /// The array of the bounds of a `KDOP` along each axis.
+    type Bounds: Copy + Debug + PartialEq + Send + Sync + AsRef<[N]> + AsMut<[N]>;
+
+    /// The axes, with integer coordinates equal to -1, 0 or 1.
+    ///
+    /// The first `DIM` axes are the coordinate axes.
+    const AXES: &'static [[i8; DIM]];
+
+    /// Bounds with all their components set to `value`.
+    fn bounds(value: N) -> Self::Bounds;
+}
+
+macro_rules! kdop_axes(
+    ($doc: expr, $name: ident, $alias: ident, $num_axes: expr, [$($axis: expr),*]) => {
+        #[doc = $doc]
+        #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+        pub struct $name;
+
+        impl<N: RealField> KDOPAxes<N> for $name {
+            type Bounds = [N; $num_axes];
+            const AXES: &'static [[i8; DIM]] = &[$($axis),*];
+
+            #[inline]
+            fn bounds(value: N) -> Self::Bounds {
+                [value; $num_axes]
+            }
+        }
+
+        #[doc = "A k-DOP bounding an object along the axes of `"]
+        #[doc = stringify!($name)]
+        #[doc = "`."]
+        pub type $alias<N> = KDOP<N, $name>;
+    }
+);
+
+#[cfg(feature = "dim2")]
+kdop_axes!(
+    "The axes of an 8-DOP: the coordinate axes and the diagonals.",
+    DOP8Axes,
+    DOP8,
+    4,
+    [[1, 0], [0, 1], [1, 1], [1, -1]]
+);
+
+#[cfg(feature = "dim3")]
+kdop_axes!(
+    "The axes of a 14-DOP: the coordinate axes and the diagonals of the cube.",
+    DOP14Axes,
+    DOP14,
+    7,
+    [
+        [1, 0, 0],
+        [0, 1, 0],
+        [0, 0, 1],
+        [1, 1, 1],
+        [1, 1, -1],
+        [1, -1, 1],
+        [-1, 1, 1]
+    ]
+);
+
+#[cfg(feature = "dim3")]
+kdop_axes!(
+    "The axes of an 18-DOP: the coordinate axes and the diagonals of the faces of the cube.",
+    DOP18Axes,
+    DOP18,
+    9,
+    [
+        [1, 0, 0],
+        [0, 1, 0],
+        [0, 0, 1],
+        [1, 1, 0],
+        [1, -1, 0],
+        [1, 0, 1],
+        [1, 0, -1],
+        [0, 1, 1],
+        [0, 1, -1]
+    ]
+);
+
+#[cfg(feature = "dim3")]
+kdop_axes!(
+    "The axes of a 26-DOP: the axes of both the 14-DOP and the 18-DOP.",
+    DOP26Axes,
+    DOP26,
+    13,
+    [
+        [1, 0, 0],
+        [0, 1, 0],
+        [0, 0, 1],
+        [1, 1, 1],
+        [1, 1, -1],
+        [1, -1, 1],
+        [-1, 1, 1],
+        [1, 1, 0],
+        [1, -1, 0],
+        [1, 0, 1],
+        [1, 0, -1],
+        [0, 1, 1],
+        [0, 1, -1]
+    ]
+);
+
+/// A Discrete Oriented Polytope.
+///
+/// A k-DOP is the intersection of `k / 2` slabs, each bounding an object along one of the fixed
+/// axes given by `A`. Because it also bounds the object along diagonal directions, it is much
+/// tighter than an `AABB` for objects which are not aligned with the coordinate axes, while
+/// keeping the cheap interference tests of an `AABB`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct KDOP<N: RealField, A: KDOPAxes<N>> {
+    mins: A::Bounds,
+    maxs: A::Bounds,
+}
+
+impl<N: RealField, A: KDOPAxes<N>> KDOP<N, A> {
+    /// Creates a new k-DOP from its bounds along each axis of `A::AXES`.
+    ///
+    /// The bounds are the extremal values of the dot products between the bounded points and the
+    /// (non-normalized) axes.
+    #[inline]
+    pub fn new(mins: A::Bounds, maxs: A::Bounds) -> Self {
+        KDOP { mins, maxs }
+    }
+
+    /// The smallest k-DOP containing the given AABB.
+    pub fn from_aabb(aabb: &AABB<N>) -> Self {
+        let mut mins = A::bounds(N::zero());
+        let mut maxs = A::bounds(N::zero());
+
+        for (i, axis) in A::AXES.iter().enumerate() {
+            for (k, component) in axis.iter().enumerate() {
+                // NOTE: the coordinates are not multiplied by the axis components so that
+                // infinite AABBs don't lead to NaNs.
+                if *component > 0 {
+                    mins.as_mut()[i] += aabb.mins()[k];
+                    maxs.as_mut()[i] += aabb.maxs()[k];
+                } else if *component < 0 {
+                    mins.as_mut()[i] -= aabb.maxs()[k];
+                    maxs.as_mut()[i] -= aabb.mins()[k];
+                }
+            }
+        }
+
+        KDOP::new(mins, maxs)
+    }
+
+    /// The axes of this k-DOP, with integer coordinates.
+    #[inline]
+    pub fn axes() -> &'static [[i8; DIM]] {
+        A::AXES
+    }
+
+    /// The `i`-th axis of this k-DOP, not normalized.
+    #[inline]
+    pub fn axis(i: usize) -> Vector<N> {
+        let mut result = Vector::zeros();
+
+        for k in 0..DIM {
+            result[k] = na::convert(A::AXES[i][k] as f64);
+        }
+
+        result
+    }
+
+    /// The dot product between `v` and the `i`-th axis of this k-DOP.
+    #[inline]
+    pub fn project_on_axis(i: usize, v: &Vector<N>) -> N {
+        let mut result = N::zero();
+
+        for k in 0..DIM {
+            if A::AXES[i][k] > 0 {
+                result += v[k]
+            } else if A::AXES[i][k] < 0 {
+                result -= v[k]
+            }
+        }
+
+        result
+    }
+
+    /// The lower bounds of this k-DOP along each of its axes.
+    #[inline]
+    pub fn mins(&self) -> &[N] {
+        self.mins.as_ref()
+    }
+
+    /// The upper bounds of this k-DOP along each of its axes.
+    #[inline]
+    pub fn maxs(&self) -> &[N] {
+        self.maxs.as_ref()
+    }
+
+    /// The smallest AABB containing this k-DOP.
+    #[inline]
+    pub fn aabb(&self) -> AABB<N> {
+        let mut mins = Point::origin();
+        let mut maxs = Point::origin();
+
+        for k in 0..DIM {
+            mins[k] = self.mins()[k];
+            maxs[k] = self.maxs()[k];
+        }
+
+        AABB::new(mins, maxs)
+    }
+
+    /// Tests if this k-DOP contains the given point.
+    #[inline]
+    pub fn contains_local_point(&self, point: &Point<N>) -> bool {
+        (0..A::AXES.len()).all(|i| {
+            let proj = Self::project_on_axis(i, &point.coords);
+            proj >= self.mins()[i] && proj <= self.maxs()[i]
+        })
+    }
+
+    /// Enlarges this k-DOP so that it contains `point`.
+    #[inline]
+    pub fn take_point(&mut self, point: &Point<N>) {
+        for i in 0..A::AXES.len() {
+            let proj = Self::project_on_axis(i, &point.coords);
+            self.mins.as_mut()[i] = self.mins()[i].min(proj);
+            self.maxs.as_mut()[i] = self.maxs()[i].max(proj);
+        }
+    }
+
+    /// The outward normal, not normalized, and the offset of the `i`-th bounding plane of this
+    /// k-DOP.
+    ///
+    /// The first `A::AXES.len()` planes bound this k-DOP from above along each axis, the
+    /// following ones from below.
+    #[inline]
+    pub(crate) fn plane(&self, i: usize) -> (Vector<N>, N) {
+        let num_axes = A::AXES.len();
+
+        if i < num_axes {
+            (Self::axis(i), self.maxs()[i])
+        } else {
+            (-Self::axis(i - num_axes), -self.mins()[i - num_axes])
+        }
+    }
+
+    // The factors converting a distance along each axis into a bound variation.
+    fn axis_norm(i: usize) -> N {
+        let num_nonzero = A::AXES[i].iter().filter(|c| **c != 0).count();
+        na::convert::<f64, N>(num_nonzero as f64).sqrt()
+    }
+}
+
+impl<N: RealField, A: KDOPAxes<N>> BoundingVolume<N> for KDOP<N, A> {
+    #[inline]
+    fn center(&self) -> Point<N> {
+        self.aabb().center()
+    }
+
+    /// The surface area of the AABB of this k-DOP, or its perimeter in 2D.
+    ///
+    /// This is an overestimation of the actual surface area of this k-DOP.
+    #[inline]
+    fn surface_area(&self) -> N {
+        self.aabb().surface_area()
+    }
+
+    #[inline]
+    fn intersects(&self, other: &Self) -> bool {
+        (0..A::AXES.len())
+            .all(|i| self.mins()[i] <= other.maxs()[i] && self.maxs()[i] >= other.mins()[i])
+    }
+
+    #[inline]
+    fn contains(&self, other: &Self) -> bool {
+        (0..A::AXES.len())
+            .all(|i| self.mins()[i] <= other.mins()[i] && self.maxs()[i] >= other.maxs()[i])
+    }
+
+    #[inline]
+    fn merge(&mut self, other: &Self) {
+        for i in 0..A::AXES.len() {
+            self.mins.as_mut()[i] = self.mins()[i].min(other.mins()[i]);
+            self.maxs.as_mut()[i] = self.maxs()[i].max(other.maxs()[i]);
+        }
+    }
+
+    #[inline]
+    fn merged(&self, other: &Self) -> Self {
+        let mut res = *self;
+        res.merge(other);
+        res
+    }
+
+    #[inline]
+    fn loosen(&mut self, amount: N) {
+        assert!(
+            amount >= na::zero(),
+            "The loosening margin must be positive."
+        );
+
+        for i in 0..A::AXES.len() {
+            let shift = amount * Self::axis_norm(i);
+            self.mins.as_mut()[i] -= shift;
+            self.maxs.as_mut()[i] += shift;
+        }
+    }
+
+    #[inline]
+    fn loosened(&self, amount: N) -> Self {
+        let mut res = *self;
+        res.loosen(amount);
+        res
+    }
+
+    #[inline]
+    fn tighten(&mut self, amount: N) {
+        assert!(
+            amount >= na::zero(),
+            "The tightening margin must be positive."
+        );
+
+        for i in 0..A::AXES.len() {
+            let shift = amount * Self::axis_norm(i);
+            self.mins.as_mut()[i] += shift;
+            self.maxs.as_mut()[i] -= shift;
+            assert!(
+                self.mins()[i] <= self.maxs()[i],
+                "The tightening margin is to large."
+            );
+        }
+    }
+
+    #[inline]
+    fn tightened(&self, amount: N) -> Self {
+        let mut res = *self;
+        res.tighten(amount);
+        res
+    }
+}
@@ -0,0 +1,157 @@
+use crate::bounding_volume::{self, BoundingVolume, HasBoundingVolume, KDOPAxes, KDOP};
+use crate::math::Isometry;
+#[cfg(feature = "dim2")]
+use crate::shape::ConvexPolygon;
+use crate::shape::{
+    Ball, Capsule, CompositeShape, Compound, Cuboid, HeightField, Plane, Polyline, Segment, Shape,
+    Triangle,
+};
+#[cfg(feature = "dim3")]
+use crate::shape::{Cone, ConvexHull, Cylinder, TriMesh};
+use na::RealField;
+
+macro_rules! impl_support_map_kdop(
+    ($($(#[$cfg: meta])* $shape: ident),*) => {$(
+        $(#[$cfg])*
+        impl<N: RealField, A: KDOPAxes<N>> HasBoundingVolume<N, KDOP<N, A>> for $shape<N> {
+            #[inline]
+            fn bounding_volume(&self, m: &Isometry<N>) -> KDOP<N, A> {
+                bounding_volume::support_map_kdop(m, self)
+            }
+
+            #[inline]
+            fn local_bounding_volume(&self) -> KDOP<N, A> {
+                bounding_volume::support_map_kdop(&Isometry::identity(), self)
+            }
+        }
+    )*}
+);
+
+impl_support_map_kdop!(
+    Ball,
+    Capsule,
+    Cuboid,
+    Segment,
+    Triangle,
+    #[cfg(feature = "dim3")]
+    Cone,
+    #[cfg(feature = "dim3")]
+    Cylinder
+);
+
+macro_rules! impl_point_cloud_kdop(
+    ($($(#[$cfg: meta])* $shape: ident),*) => {$(
+        $(#[$cfg])*
+        impl<N: RealField, A: KDOPAxes<N>> HasBoundingVolume<N, KDOP<N, A>> for $shape<N> {
+            #[inline]
+            fn bounding_volume(&self, m: &Isometry<N>) -> KDOP<N, A> {
+                bounding_volume::point_cloud_kdop(m, self.points())
+            }
+
+            #[inline]
+            fn local_bounding_volume(&self) -> KDOP<N, A> {
+                bounding_volume::point_cloud_kdop(&Isometry::identity(), self.points())
+            }
+        }
+    )*}
+);
+
+impl_point_cloud_kdop!(
+    Polyline,
+    #[cfg(feature = "dim2")]
+    ConvexPolygon,
+    #[cfg(feature = "dim3")]
+    ConvexHull,
+    #[cfg(feature = "dim3")]
+    TriMesh
+);
+
+impl<N: RealField, A: KDOPAxes<N>> HasBoundingVolume<N, KDOP<N, A>> for Compound<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> KDOP<N, A> {
+        composite_shape_kdop(m, self)
+    }
+
+    #[inline]
+    fn local_bounding_volume(&self) -> KDOP<N, A> {
+        composite_shape_kdop(&Isometry::identity(), self)
+    }
+}
+
+impl<N: RealField, A: KDOPAxes<N>> HasBoundingVolume<N, KDOP<N, A>> for HeightField<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> KDOP<N, A> {
+        KDOP::from_aabb(&self.aabb().transform_by(m))
+    }
+
+    #[inline]
+    fn local_bounding_volume(&self) -> KDOP<N, A> {
+        KDOP::from_aabb(self.aabb())
+    }
+}
+
+impl<N: RealField, A: KDOPAxes<N>> HasBoundingVolume<N, KDOP<N, A>> for Plane<N> {
+    #[inline]
+    fn bounding_volume(&self, _: &Isometry<N>) -> KDOP<N, A> {
+        self.local_bounding_volume()
+    }
+
+    #[inline]
+    fn local_bounding_volume(&self) -> KDOP<N, A> {
+        KDOP::from_aabb(&bounding_volume::local_aabb(self))
+    }
+}
+
+impl<N: RealField, A: KDOPAxes<N>> HasBoundingVolume<N, KDOP<N, A>> for dyn Shape<N> {
+    fn bounding_volume(&self, m: &Isometry<N>) -> KDOP<N, A> {
+        if let Some(s) = self.as_support_map() {
+            return bounding_volume::support_map_kdop(m, s);
+        }
+
+        // Meshes are bounded from their vertices instead of their parts.
+        if let Some(s) = self.as_shape::<Polyline<N>>() {
+            return s.bounding_volume(m);
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            if let Some(s) = self.as_shape::<TriMesh<N>>() {
+                return s.bounding_volume(m);
+            }
+        }
+
+        if let Some(s) = self.as_composite_shape() {
+            composite_shape_kdop(m, s)
+        } else {
+            KDOP::from_aabb(&self.aabb(m))
+        }
+    }
+
+    #[inline]
+    fn local_bounding_volume(&self) -> KDOP<N, A> {
+        self.bounding_volume(&Isometry::identity())
+    }
+}
+
+// The k-DOP of a composite shape, computed from the k-DOPs of its parts.
+fn composite_shape_kdop<N, A, S>(m: &Isometry<N>, shape: &S) -> KDOP<N, A>
+where
+    N: RealField,
+    A: KDOPAxes<N>,
+    S: ?Sized + CompositeShape<N>,
+{
+    let mut result: Option<KDOP<N, A>> = None;
+
+    for i in 0..shape.nparts() {
+        shape.map_part_at(i, m, &mut |m, part| {
+            let kdop = part.bounding_volume(m);
+
+            match &mut result {
+                Some(result) => result.merge(&kdop),
+                None => result = Some(kdop),
+            }
+        });
+    }
+
+    result.expect("Cannot compute the k-DOP of a composite shape without any part.")
+}
@@ -0,0 +1,47 @@
+use std::iter::IntoIterator;
+
+use crate::bounding_volume::{KDOPAxes, KDOP};
+use crate::math::{Isometry, Point};
+use crate::shape::SupportMap;
+use na::RealField;
+
+/// Computes the k-DOP of a support mapped shape.
+pub fn support_map_kdop<N, A, G>(m: &Isometry<N>, i: &G) -> KDOP<N, A>
+where
+    N: RealField,
+    A: KDOPAxes<N>,
+    G: ?Sized + SupportMap<N>,
+{
+    let mut mins = A::bounds(N::zero());
+    let mut maxs = A::bounds(N::zero());
+
+    for k in 0..A::AXES.len() {
+        let axis = KDOP::<N, A>::axis(k);
+        maxs.as_mut()[k] = i.support_point(m, &axis).coords.dot(&axis);
+        mins.as_mut()[k] = i.support_point(m, &-axis).coords.dot(&axis);
+    }
+
+    KDOP::new(mins, maxs)
+}
+
+/// Computes the k-DOP of a set of points transformed by `m`.
+pub fn point_cloud_kdop<'a, N, A, I>(m: &Isometry<N>, pts: I) -> KDOP<N, A>
+where
+    N: RealField,
+    A: KDOPAxes<N>,
+    I: IntoIterator<Item = &'a Point<N>>,
+{
+    let mut it = pts.into_iter();
+
+    let p0 = it.next().expect(
+        "Point cloud k-DOP construction: the input iterator should yield at least one point.",
+    );
+    let mut result = KDOP::new(A::bounds(N::max_value()), A::bounds(-N::max_value()));
+    result.take_point(&(m * p0));
+
+    for pt in it {
+        result.take_point(&(m * pt));
+    }
+
+    result
+}
@@ -17,6 +17,18 @@ pub use crate::bounding_volume::bounding_sphere_utils::{
 };
 #[doc(inline)]
 pub use crate::bounding_volume::bounding_volume::{BoundingVolume, HasBoundingVolume};
+#[doc(inline)]
+pub use crate::bounding_volume::kdop::{kdop, local_kdop, KDOPAxes, KDOP};
+#[cfg(feature = "dim3")]
+#[doc(inline)]
+pub use crate::bounding_volume::kdop::{DOP14Axes, DOP18Axes, DOP26Axes, DOP14, DOP18, DOP26};
+#[cfg(feature = "dim2")]
+#[doc(inline)]
+pub use crate::bounding_volume::kdop::{DOP8Axes, DOP8};
+pub use crate::bounding_volume::kdop_utils::{point_cloud_kdop, support_map_kdop};
+#[doc(inline)]
+pub use crate::bounding_volume::obb::{local_obb, obb, OBB};
+pub use crate::bounding_volume::obb_utils::point_cloud_obb;
 
 #[doc(hidden)]
 pub mod bounding_volume;
@@ -66,5 +78,18 @@ mod bounding_sphere_triangle;
 mod bounding_sphere_trimesh;
 mod bounding_sphere_utils;
 
+#[doc(hidden)]
+pub mod kdop;
+mod kdop_shape;
+mod kdop_utils;
+
+#[doc(hidden)]
+pub mod obb;
+mod obb_segment;
+mod obb_shape;
+#[cfg(feature = "dim3")]
+mod obb_triangle;
+mod obb_utils;
+
 pub(crate) mod circular_cone;
 mod spatialized_normal_cone;
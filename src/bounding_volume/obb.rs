@@ -0,0 +1,293 @@
+//! Oriented Bounding Box.
+
+use crate::bounding_volume::{BoundingVolume, HasBoundingVolume, AABB};
+use crate::math::{Isometry, Matrix, Point, Rotation, Translation, Vector, DIM};
+use crate::shape::Cuboid;
+use crate::utils::IsometryOps;
+use na::{self, RealField};
+
+// Seems useful to help type inference. See issue #84.
+/// Computes the oriented bounding box of a shape `g` transformed by `m`.
+///
+/// Same as `g.obb(m)`.
+#[inline]
+pub fn obb<N, G>(g: &G, m: &Isometry<N>) -> OBB<N>
+where
+    N: RealField,
+    G: ?Sized + HasBoundingVolume<N, OBB<N>>,
+{
+    g.bounding_volume(m)
+}
+
+// Seems useful to help type inference. See issue #84.
+/// Computes the oriented bounding box of a shape `g`.
+///
+/// Same as `g.local_obb()`.
+#[inline]
+pub fn local_obb<N, G>(g: &G) -> OBB<N>
+where
+    N: RealField,
+    G: ?Sized + HasBoundingVolume<N, OBB<N>>,
+{
+    g.local_bounding_volume()
+}
+
+/// An Oriented Bounding Box.
+///
+/// Contrary to an `AABB`, its axes follow the orientation of the object it bounds. This makes it
+/// much tighter than an `AABB` for long and thin objects which are not aligned with the coordinate
+/// axes.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Clone)]
+pub struct OBB<N: RealField> {
+    position: Isometry<N>,
+    half_extents: Vector<N>,
+}
+
+impl<N: RealField> OBB<N> {
+    /// Creates a new OBB centered at `position.translation` with axes rotated by
+    /// `position.rotation`.
+    #[inline]
+    pub fn new(position: Isometry<N>, half_extents: Vector<N>) -> OBB<N> {
+        OBB {
+            position,
+            half_extents,
+        }
+    }
+
+    /// Creates the OBB equal to `aabb` transformed by `m`.
+    #[inline]
+    pub fn from_aabb(m: &Isometry<N>, aabb: &AABB<N>) -> OBB<N> {
+        let position = m * Translation::from(aabb.center().coords);
+        OBB::new(position, aabb.half_extents())
+    }
+
+    /// Computes the smallest OBB oriented by `rotation` and containing all the given points.
+    ///
+    /// Panics if `points` is empty.
+    pub fn from_points_with_rotation(
+        rotation: Rotation<N>,
+        points: impl IntoIterator<Item = Point<N>>,
+    ) -> OBB<N> {
+        let mut points = points
+            .into_iter()
+            .map(|pt| rotation.inverse_transform_point(&pt));
+        let first = points
+            .next()
+            .expect("Cannot compute the OBB of an empty set of points.");
+        let (mut mins, mut maxs) = (first, first);
+
+        for pt in points {
+            mins = mins.inf(&pt);
+            maxs = maxs.sup(&pt);
+        }
+
+        let rotation = Isometry::from_parts(Translation::identity(), rotation);
+        OBB::from_aabb(&rotation, &AABB::new(mins, maxs))
+    }
+
+    /// The position of the center and the orientation of this OBB.
+    #[inline]
+    pub fn position(&self) -> &Isometry<N> {
+        &self.position
+    }
+
+    /// The half-extents of this OBB along its own axes.
+    #[inline]
+    pub fn half_extents(&self) -> &Vector<N> {
+        &self.half_extents
+    }
+
+    /// The center of this OBB.
+    #[inline]
+    pub fn center(&self) -> Point<N> {
+        Point::from(self.position.translation.vector)
+    }
+
+    /// The cuboid which, transformed by `self.position()`, is equal to this OBB.
+    #[inline]
+    pub fn cuboid(&self) -> Cuboid<N> {
+        Cuboid::new(self.half_extents)
+    }
+
+    /// Transforms this OBB by `m`.
+    #[inline]
+    pub fn transform_by(&self, m: &Isometry<N>) -> OBB<N> {
+        OBB::new(m * self.position, self.half_extents)
+    }
+
+    /// The smallest AABB containing this OBB.
+    #[inline]
+    pub fn aabb(&self) -> AABB<N> {
+        let half_extents = self.position.absolute_transform_vector(&self.half_extents);
+        AABB::from_half_extents(self.center(), half_extents)
+    }
+
+    /// The `i`-th vertex of this OBB, with `i < 2^DIM`.
+    #[inline]
+    pub fn vertex(&self, i: usize) -> Point<N> {
+        let mut local = Point::origin();
+
+        for k in 0..DIM {
+            local[k] = if i & (1 << k) == 0 {
+                -self.half_extents[k]
+            } else {
+                self.half_extents[k]
+            };
+        }
+
+        self.position * local
+    }
+
+    fn vertices(&self) -> impl Iterator<Item = Point<N>> + '_ {
+        (0..1 << DIM).map(move |i| self.vertex(i))
+    }
+
+    // The smallest OBB oriented as `self` and containing both `self` and `other`.
+    fn merged_with_own_rotation(&self, other: &OBB<N>) -> OBB<N> {
+        OBB::from_points_with_rotation(
+            self.position.rotation,
+            self.vertices().chain(other.vertices()),
+        )
+    }
+
+    // Tolerance for the numerical errors of the changes of frame.
+    fn tolerance(&self) -> N {
+        let scale = N::one() + self.position.translation.vector.amax() + self.half_extents.amax();
+        N::default_epsilon() * na::convert(100.0) * scale
+    }
+}
+
+// Builds a rotation from the given orthonormal basis, flipping its last axis if it is left-handed.
+pub(crate) fn rotation_from_basis<N: RealField>(mut basis: Matrix<N>) -> Rotation<N> {
+    if basis.determinant() < N::zero() {
+        let last = -basis.column(DIM - 1);
+        basis.set_column(DIM - 1, &last);
+    }
+
+    #[cfg(feature = "dim2")]
+    return Rotation::from_rotation_matrix(&na::Rotation2::from_matrix_unchecked(basis));
+    #[cfg(feature = "dim3")]
+    return Rotation::from_rotation_matrix(&na::Rotation3::from_matrix_unchecked(basis));
+}
+
+impl<N: RealField> BoundingVolume<N> for OBB<N> {
+    #[inline]
+    fn center(&self) -> Point<N> {
+        self.center()
+    }
+
+    #[inline]
+    #[cfg(feature = "dim2")]
+    fn surface_area(&self) -> N {
+        let he = self.half_extents;
+        (he.x + he.y) * na::convert(4.0)
+    }
+
+    #[inline]
+    #[cfg(feature = "dim3")]
+    fn surface_area(&self) -> N {
+        let he = self.half_extents;
+        (he.x * he.y + he.y * he.z + he.z * he.x) * na::convert(8.0)
+    }
+
+    fn intersects(&self, other: &OBB<N>) -> bool {
+        // Separating axis test.
+        let rot1 = self.position.rotation.to_rotation_matrix().into_inner();
+        let rot2 = other.position.rotation.to_rotation_matrix().into_inner();
+        let delta = other.position.translation.vector - self.position.translation.vector;
+
+        let is_separating = |axis: &Vector<N>| {
+            let radius1 = rot1.tr_mul(axis).abs().dot(&self.half_extents);
+            let radius2 = rot2.tr_mul(axis).abs().dot(&other.half_extents);
+            delta.dot(axis).abs() > radius1 + radius2
+        };
+
+        for i in 0..DIM {
+            if is_separating(&rot1.column(i).into_owned())
+                || is_separating(&rot2.column(i).into_owned())
+            {
+                return false;
+            }
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            for i in 0..DIM {
+                for j in 0..DIM {
+                    let axis = rot1.column(i).cross(&rot2.column(j));
+
+                    // Skip the axes generated by parallel edges.
+                    if axis.norm_squared() > N::default_epsilon() && is_separating(&axis) {
+                        return false;
+                    }
+                }
+            }
+        }
+
+        true
+    }
+
+    fn contains(&self, other: &OBB<N>) -> bool {
+        let tolerance = self.tolerance();
+        let limits = self.half_extents.add_scalar(tolerance);
+
+        other.vertices().all(|pt| {
+            let local = self.position.inverse_transform_point(&pt);
+            (0..DIM).all(|i| local[i].abs() <= limits[i])
+        })
+    }
+
+    #[inline]
+    fn merge(&mut self, other: &OBB<N>) {
+        *self = self.merged(other)
+    }
+
+    fn merged(&self, other: &OBB<N>) -> OBB<N> {
+        // Keep the orientation of either OBB, whichever gives the smallest result.
+        let candidate1 = self.merged_with_own_rotation(other);
+        let candidate2 = other.merged_with_own_rotation(self);
+
+        if candidate2.surface_area() < candidate1.surface_area() {
+            candidate2
+        } else {
+            candidate1
+        }
+    }
+
+    #[inline]
+    fn loosen(&mut self, amount: N) {
+        assert!(
+            amount >= na::zero(),
+            "The loosening margin must be positive."
+        );
+        self.half_extents = self.half_extents.add_scalar(amount)
+    }
+
+    #[inline]
+    fn loosened(&self, amount: N) -> OBB<N> {
+        let mut res = self.clone();
+        res.loosen(amount);
+        res
+    }
+
+    #[inline]
+    fn tighten(&mut self, amount: N) {
+        assert!(
+            amount >= na::zero(),
+            "The tightening margin must be positive."
+        );
+        assert!(
+            amount <= self.half_extents.min(),
+            "The tightening margin is to large."
+        );
+        self.half_extents = self.half_extents.add_scalar(-amount)
+    }
+
+    #[inline]
+    fn tightened(&self, amount: N) -> OBB<N> {
+        let mut res = self.clone();
+        res.tighten(amount);
+        res
+    }
+}
@@ -0,0 +1,31 @@
+use crate::bounding_volume::obb::{rotation_from_basis, OBB};
+use crate::bounding_volume::HasBoundingVolume;
+use crate::math::{Isometry, Matrix, Vector};
+use crate::shape::Segment;
+use na::RealField;
+
+impl<N: RealField> HasBoundingVolume<N, OBB<N>> for Segment<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> OBB<N> {
+        let bv: OBB<N> = self.local_bounding_volume();
+        bv.transform_by(m)
+    }
+
+    #[inline]
+    fn local_bounding_volume(&self) -> OBB<N> {
+        let mut basis = Matrix::identity();
+
+        // Align the first axis of the OBB with the segment.
+        if let Some(dir) = self.direction() {
+            let mut i = 0;
+            basis.set_column(0, dir.as_ref());
+            Vector::orthonormal_subspace_basis(&[dir.into_inner()], |axis| {
+                i += 1;
+                basis.set_column(i, axis);
+                true
+            });
+        }
+
+        OBB::from_points_with_rotation(rotation_from_basis(basis), vec![*self.a(), *self.b()])
+    }
+}
@@ -0,0 +1,38 @@
+use crate::bounding_volume::obb::{rotation_from_basis, OBB};
+use crate::bounding_volume::HasBoundingVolume;
+use crate::math::{Isometry, Matrix};
+use crate::shape::Triangle;
+use na::{RealField, Unit};
+
+impl<N: RealField> HasBoundingVolume<N, OBB<N>> for Triangle<N> {
+    #[inline]
+    fn bounding_volume(&self, m: &Isometry<N>) -> OBB<N> {
+        let bv: OBB<N> = self.local_bounding_volume();
+        bv.transform_by(m)
+    }
+
+    #[inline]
+    fn local_bounding_volume(&self) -> OBB<N> {
+        let mut basis = Matrix::identity();
+
+        // Align the OBB with the longest edge and the normal of the triangle.
+        let edges = self.edges_scaled_directions();
+        let longest = edges.iter().fold(edges[0], |longest, edge| {
+            if edge.norm_squared() > longest.norm_squared() {
+                *edge
+            } else {
+                longest
+            }
+        });
+
+        if let (Some(normal), Some(edge)) =
+            (self.normal(), Unit::try_new(longest, N::default_epsilon()))
+        {
+            basis.set_column(0, edge.as_ref());
+            basis.set_column(1, normal.as_ref());
+            basis.set_column(2, &edge.cross(&normal));
+        }
+
+        OBB::from_points_with_rotation(rotation_from_basis(basis), self.vertices().iter().cloned())
+    }
+}
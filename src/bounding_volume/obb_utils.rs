@@ -0,0 +1,20 @@
+use crate::bounding_volume::obb::{rotation_from_basis, OBB};
+use crate::math::{Matrix, Point};
+use crate::utils;
+use na::RealField;
+
+/// Computes an OBB of a set of points, oriented along their principal axes.
+///
+/// Panics if `pts` is empty.
+pub fn point_cloud_obb<N: RealField>(pts: &[Point<N>]) -> OBB<N> {
+    let center = utils::center(pts);
+    let mut covariance = Matrix::zeros();
+
+    for pt in pts {
+        let dpt = pt - center;
+        covariance += dpt * dpt.transpose();
+    }
+
+    let axes = covariance.symmetric_eigen().eigenvectors;
+    OBB::from_points_with_rotation(rotation_from_basis(axes), pts.iter().cloned())
+}
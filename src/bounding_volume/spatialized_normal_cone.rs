@@ -17,6 +17,10 @@ impl<N: RealField> BoundingVolume<N> for SpatializedNormalCone<N> {
         self.aabb.center()
     }
 
+    fn surface_area(&self) -> N {
+        self.aabb.surface_area()
+    }
+
     fn intersects(&self, other: &Self) -> bool {
         self.aabb.intersects(&other.aabb) && self.normals.double_cones_intersect(&other.normals)
     }
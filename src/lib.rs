@@ -50,6 +50,8 @@ And various traits for collision detectors and broad phase collision detection.
 #[cfg(feature = "serde")]
 #[macro_use]
 extern crate serde;
+#[cfg(feature = "parallel")]
+extern crate rayon;
 #[macro_use]
 extern crate approx;
 #[macro_use]
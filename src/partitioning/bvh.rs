@@ -1,3 +1,4 @@
+use crate::bounding_volume::BoundingVolume;
 use crate::partitioning::{
     BVTNodeId, BestFirstVisitStatus, BestFirstVisitor, DBVTNodeId, SimultaneousVisitor,
     VisitStatus, Visitor, BVT, DBVT,
@@ -22,8 +23,14 @@ pub trait BVH<T, BV> {
 
     /// Traverses this BVH using a visitor.
     fn visit(&self, visitor: &mut impl Visitor<T, BV>) {
-        // FIXME: find a way to avoid the allocation.
-        let mut stack = Vec::new();
+        self.visit_with_stack(visitor, &mut Vec::new())
+    }
+
+    /// Traverses this BVH using a visitor, and `stack` to store the nodes to visit.
+    ///
+    /// The same stack can be reused by several traversals to avoid any allocation.
+    fn visit_with_stack(&self, visitor: &mut impl Visitor<T, BV>, stack: &mut Vec<Self::Node>) {
+        stack.clear();
 
         if let Some(root) = self.root() {
             stack.push(root);
@@ -44,10 +51,53 @@ pub trait BVH<T, BV> {
         }
     }
 
+    /// Calls `f` on the data of every leaf of the subtree rooted at `node`.
+    ///
+    /// The bounding volumes of the subtree are not looked at.
+    fn visit_subtree_leaves(&self, node: Self::Node, f: &mut impl FnMut(&T)) {
+        self.visit_subtree_leaves_with_stack(node, f, &mut Vec::new())
+    }
+
+    /// Calls `f` on the data of every leaf of the subtree rooted at `node`, using `stack` to store
+    /// the nodes to visit.
+    ///
+    /// The same stack can be reused by several traversals to avoid any allocation.
+    fn visit_subtree_leaves_with_stack(
+        &self,
+        node: Self::Node,
+        f: &mut impl FnMut(&T),
+        stack: &mut Vec<Self::Node>,
+    ) {
+        stack.clear();
+        stack.push(node);
+
+        while let Some(node) = stack.pop() {
+            if let Some(data) = self.content(node).1 {
+                f(data)
+            }
+
+            for i in 0..self.num_children(node) {
+                stack.push(self.child(i, node))
+            }
+        }
+    }
+
     /// Visits the bounding volume test tree implicitly formed with `other`.
     fn visit_bvtt(&self, other: &impl BVH<T, BV>, visitor: &mut impl SimultaneousVisitor<T, BV>) {
-        // FIXME: find a way to avoid the allocation.
-        let mut stack = Vec::new();
+        self.visit_bvtt_with_stack(other, visitor, &mut Vec::new())
+    }
+
+    /// Visits the bounding volume test tree implicitly formed with `other`, using `stack` to store
+    /// the pairs of nodes to visit.
+    ///
+    /// The same stack can be reused by several traversals to avoid any allocation.
+    fn visit_bvtt_with_stack<B: BVH<T, BV>>(
+        &self,
+        other: &B,
+        visitor: &mut impl SimultaneousVisitor<T, BV>,
+        stack: &mut Vec<(Self::Node, B::Node)>,
+    ) {
+        stack.clear();
 
         if let (Some(root1), Some(root2)) = (self.root(), other.root()) {
             stack.push((root1, root2));
@@ -94,6 +144,125 @@ pub trait BVH<T, BV> {
         }
     }
 
+    /// Visits the bounding volume test tree implicitly formed with itself.
+    ///
+    /// This is the traversal used for self-collision detection. Contrary to
+    /// `self.visit_bvtt(self, ...)`, each unordered pair of distinct nodes is given at most once to
+    /// the visitor, and a node is never paired with itself or with one of its ancestors. Thus, each
+    /// pair of overlapping leaves is reported once, with the leaf data given in an arbitrary order.
+    fn visit_self_bvtt(&self, visitor: &mut impl SimultaneousVisitor<T, BV>) {
+        self.visit_self_bvtt_with_stack(visitor, &mut Vec::new())
+    }
+
+    /// Visits the bounding volume test tree implicitly formed with itself, using `stack` to store
+    /// the pairs of nodes to visit.
+    ///
+    /// The boolean of each element of the stack is `true` for the pairs of identical nodes. The
+    /// same stack can be reused by several traversals to avoid any allocation.
+    fn visit_self_bvtt_with_stack(
+        &self,
+        visitor: &mut impl SimultaneousVisitor<T, BV>,
+        stack: &mut Vec<(Self::Node, Self::Node, bool)>,
+    ) {
+        stack.clear();
+
+        if let Some(root) = self.root() {
+            stack.push((root, root, true));
+
+            while let Some((node1, node2, same)) = stack.pop() {
+                if same {
+                    // Visit the pairs of children, and each child with itself.
+                    let nchild = self.num_children(node1);
+
+                    for i in 0..nchild {
+                        let n1 = self.child(i, node1);
+                        stack.push((n1, n1, true));
+
+                        for j in i + 1..nchild {
+                            stack.push((n1, self.child(j, node1), false))
+                        }
+                    }
+
+                    continue;
+                }
+
+                let content1 = self.content(node1);
+                let content2 = self.content(node2);
+
+                match visitor.visit(content1.0, content1.1, content2.0, content2.1) {
+                    VisitStatus::Continue => {
+                        let nchild1 = self.num_children(node1);
+                        let nchild2 = self.num_children(node2);
+
+                        // The two nodes are roots of disjoint subtrees.
+                        match (nchild1, nchild2) {
+                            (0, 0) => {}
+                            (0, _) => {
+                                for j in 0..nchild2 {
+                                    stack.push((node1, self.child(j, node2), false))
+                                }
+                            }
+                            (_, 0) => {
+                                for i in 0..nchild1 {
+                                    stack.push((self.child(i, node1), node2, false))
+                                }
+                            }
+                            (_, _) => {
+                                for i in 0..nchild1 {
+                                    let n1 = self.child(i, node1);
+
+                                    for j in 0..nchild2 {
+                                        stack.push((n1, self.child(j, node2), false))
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    VisitStatus::ExitEarly => return,
+                    VisitStatus::Stop => {}
+                }
+            }
+        }
+    }
+
+    /// The surface area heuristic cost of this BVH.
+    ///
+    /// This is the sum of the surface areas of the internal nodes divided by the surface area of
+    /// the root. It measures the quality of the BVH for diagnostics: the lower it is, the fewer
+    /// nodes are expected to be traversed by queries. It is zero if the BVH has less than two leaves.
+    fn sah_cost<N>(&self) -> N
+    where
+        N: RealField,
+        BV: BoundingVolume<N>,
+    {
+        let mut stack = Vec::new();
+        let mut cost = N::zero();
+        let mut root_area = N::zero();
+
+        if let Some(root) = self.root() {
+            root_area = self.content(root).0.surface_area();
+            stack.push(root);
+        }
+
+        while let Some(node) = stack.pop() {
+            let num_children = self.num_children(node);
+
+            if num_children != 0 {
+                cost += self.content(node).0.surface_area();
+
+                for i in 0..num_children {
+                    stack.push(self.child(i, node))
+                }
+            }
+        }
+
+        if root_area.is_zero() {
+            N::zero()
+        } else {
+            cost / root_area
+        }
+    }
+
     /// Performs a best-first-search on the BVH.
     ///
     /// Returns the content of the leaf with the smallest associated cost, and a result of
@@ -103,7 +272,23 @@ pub trait BVH<T, BV> {
         N: RealField,
         BFS: BestFirstVisitor<N, T, BV>,
     {
-        let mut queue: BinaryHeap<WeightedValue<N, Self::Node>> = BinaryHeap::new();
+        self.best_first_search_with_queue(visitor, &mut BestFirstQueue::new())
+    }
+
+    /// Performs a best-first-search on the BVH, using `queue` to store the nodes to visit.
+    ///
+    /// The same queue can be reused by several searches to avoid any allocation.
+    fn best_first_search_with_queue<N, BFS>(
+        &self,
+        visitor: &mut BFS,
+        queue: &mut BestFirstQueue<N, Self::Node>,
+    ) -> Option<(Self::Node, BFS::Result)>
+    where
+        N: RealField,
+        BFS: BestFirstVisitor<N, T, BV>,
+    {
+        let queue = &mut queue.heap;
+        queue.clear();
         // The lowest cost collision with actual scene geometry.
         let mut best_cost = N::max_value();
         let mut best_result = None;
@@ -158,6 +343,110 @@ pub trait BVH<T, BV> {
 
         best_result
     }
+
+    /// Performs a best-first-search on the BVH to find the `k` leaves with the smallest costs.
+    ///
+    /// The visitor is given the largest cost among the `k` best results found so far, or `N::max_value()`
+    /// if less than `k` results have been found. Returns the content of the leaves with the smallest
+    /// associated costs, and results of user-defined type, sorted by increasing cost. If the visitor
+    /// exits early, the result it returns (if any) is appended to the results found so far.
+    fn k_best_first_search<N, BFS>(
+        &self,
+        k: usize,
+        visitor: &mut BFS,
+    ) -> Vec<(Self::Node, BFS::Result)>
+    where
+        N: RealField,
+        BFS: BestFirstVisitor<N, T, BV>,
+    {
+        let mut results = Vec::new();
+        self.k_best_first_search_with_queue(k, visitor, &mut BestFirstQueue::new(), &mut results);
+        results
+    }
+
+    /// Performs a best-fist-search on the BVH to find the `k` leaves with the smallest costs, using
+    /// `queue` to store the nodes to visit.
+    ///
+    /// This is the same as `k_best_first_search` except that `out` is cleared and filled with the
+    /// results. The same queue and output buffer can be reused by several searches to avoid any
+    /// allocation.
+    fn k_best_first_search_with_queue<N, BFS>(
+        &self,
+        k: usize,
+        visitor: &mut BFS,
+        queue: &mut BestFirstQueue<N, Self::Node>,
+        out: &mut Vec<(Self::Node, BFS::Result)>,
+    ) where
+        N: RealField,
+        BFS: BestFirstVisitor<N, T, BV>,
+    {
+        let BestFirstQueue {
+            heap: queue,
+            nodes: to_visit,
+            costs: best_costs,
+        } = queue;
+        queue.clear();
+        to_visit.clear();
+        // The costs of the best results found so far, stored in `out` by increasing cost.
+        best_costs.clear();
+        out.clear();
+
+        let worst_cost = |best_costs: &Vec<N>| {
+            if best_costs.len() < k {
+                N::max_value()
+            } else {
+                best_costs[k - 1]
+            }
+        };
+
+        if k == 0 {
+            return;
+        }
+
+        to_visit.extend(self.root());
+
+        loop {
+            for node in to_visit.drain(..) {
+                let worst = worst_cost(best_costs);
+                let (bv, data) = self.content(node);
+
+                match visitor.visit(worst, bv, data) {
+                    BestFirstVisitStatus::Continue { cost, result } => {
+                        if cost < worst {
+                            if let Some(res) = result {
+                                let i = best_costs
+                                    .iter()
+                                    .position(|best| cost < *best)
+                                    .unwrap_or(best_costs.len());
+                                best_costs.insert(i, cost);
+                                best_costs.truncate(k);
+                                out.insert(i, (node, res));
+                                out.truncate(k);
+                            }
+
+                            // The node may have a child with a lower cost, evaluate it next.
+                            queue.push(WeightedValue::new(node, -cost))
+                        }
+                    }
+                    BestFirstVisitStatus::ExitEarly(result) => {
+                        out.extend(result.map(|res| (node, res)));
+                        return;
+                    }
+                    BestFirstVisitStatus::Stop => {}
+                }
+            }
+
+            match queue.pop() {
+                // The remaining BVs may contain a leaf with a lower cost than the worst best result.
+                Some(entry) if -entry.cost < worst_cost(best_costs) => {
+                    for i in 0..self.num_children(entry.value) {
+                        to_visit.push(self.child(i, entry.value))
+                    }
+                }
+                _ => break,
+            }
+        }
+    }
 }
 
 /// An enum grouping references to all the BVH implementations on ncollide.
@@ -218,6 +507,16 @@ impl<'a, N: RealField, T, BV> BVHImpl<'a, N, T, BV> {
         }
     }
 
+    /// Visits the bounding volume traversal tree implicitly formed with itself.
+    ///
+    /// See `BVH::visit_self_bvtt` for details.
+    pub fn visit_self_bvtt(self, visitor: &mut impl SimultaneousVisitor<T, BV>) {
+        match self {
+            BVHImpl::BVT(bvt) => bvt.visit_self_bvtt(visitor),
+            BVHImpl::DBVT(dbvt) => dbvt.visit_self_bvtt(visitor),
+        }
+    }
+
     fn visit_bvtt_dispatch(
         self,
         bvh2: &impl BVH<T, BV>,
@@ -246,8 +545,79 @@ impl<'a, N: RealField, T, BV> BVHImpl<'a, N, T, BV> {
                 .map(|res| (BVHNodeId::DBVTNodeId(res.0), res.1)),
         }
     }
+
+    /// Performs a best-fist-search on the tree to find the `k` leaves with the smallest costs.
+    ///
+    /// Returns the content of the leaves with the smallest associated costs, and results of
+    /// user-defined type, sorted by increasing cost.
+    pub fn k_best_first_search<BFS>(
+        self,
+        k: usize,
+        visitor: &mut BFS,
+    ) -> Vec<(BVHNodeId, BFS::Result)>
+    where
+        BFS: BestFirstVisitor<N, T, BV>,
+    {
+        match self {
+            BVHImpl::BVT(bvt) => bvt
+                .k_best_first_search(k, visitor)
+                .into_iter()
+                .map(|res| (BVHNodeId::BVTNodeId(res.0), res.1))
+                .collect(),
+            BVHImpl::DBVT(dbvt) => dbvt
+                .k_best_first_search(k, visitor)
+                .into_iter()
+                .map(|res| (BVHNodeId::DBVTNodeId(res.0), res.1))
+                .collect(),
+        }
+    }
+}
+
+/// A priority queue storing the nodes to visit during a best-first search on a BVH.
+///
+/// The same queue can be given to several calls to `BVH::best_first_search_with_queue` or
+/// `BVH::k_best_first_search_with_queue` to avoid allocating a new queue for each search.
+#[derive(Clone)]
+pub struct BestFirstQueue<N, Node> {
+    heap: BinaryHeap<WeightedValue<N, Node>>,
+    // The nodes to visit before popping the next node of `heap`.
+    nodes: Vec<Node>,
+    // The costs of the best results of a k-best-first search.
+    costs: Vec<N>,
+}
+
+impl<N: PartialOrd, Node> BestFirstQueue<N, Node> {
+    /// Creates an empty queue.
+    pub fn new() -> Self {
+        BestFirstQueue {
+            heap: BinaryHeap::new(),
+            nodes: Vec::new(),
+            costs: Vec::new(),
+        }
+    }
+
+    /// Creates an empty queue able to store `capacity` nodes without reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        BestFirstQueue {
+            heap: BinaryHeap::with_capacity(capacity),
+            nodes: Vec::new(),
+            costs: Vec::new(),
+        }
+    }
+
+    /// The number of nodes this queue can store without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.heap.capacity()
+    }
+}
+
+impl<N: PartialOrd, Node> Default for BestFirstQueue<N, Node> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
+#[derive(Clone)]
 struct WeightedValue<N, T> {
     pub value: T,
     pub cost: N,
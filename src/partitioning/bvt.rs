@@ -3,12 +3,18 @@
 use crate::bounding_volume::BoundingVolume;
 use crate::math::{Point, DIM};
 use crate::partitioning::BVH;
-use crate::utils;
+use crate::utils::{self, BinaryCodec, DecodingError};
 use simba::scalar::RealField;
 use std::collections::VecDeque;
 use std::iter;
 use std::usize;
 
+// The number of bins used by `BVT::new_sah`.
+const SAH_NUM_BINS: usize = 16;
+// The number of leaves under which a subtree is built on a single thread by the parallel builder.
+#[cfg(feature = "parallel")]
+const PARALLEL_BUILD_MIN_LEAVES: usize = 1024;
+
 /// A Bounding Volume Tree.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone)]
@@ -72,6 +78,106 @@ struct BVTDeformationInfo {
     timestamp: usize,
 }
 
+/// A policy deciding which subtrees of a `BVT` should be rebuilt after it has been refitted.
+///
+/// Refitting a `BVT` preserves its topology, so its quality degrades as its leaves move away from
+/// their initial arrangement. This policy records the surface area heuristic cost of every subtree
+/// of a `BVT` and reports the subtrees which cost grew by more than a given factor since. The same
+/// policy must only be used with the `BVT` it has been created for.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone)]
+pub struct BVTRebuildPolicy<N: RealField> {
+    max_cost_growth: N,
+    // The reference cost of the subtree rooted at each internal node.
+    reference_costs: Vec<N>,
+}
+
+impl<N: RealField> BVTRebuildPolicy<N> {
+    /// Creates a policy reporting the subtrees of `bvt` with a surface area heuristic cost more
+    /// than `max_cost_growth` times larger than their current cost.
+    pub fn new<T, BV: BoundingVolume<N>>(bvt: &BVT<T, BV>, max_cost_growth: N) -> Self {
+        assert!(
+            max_cost_growth >= N::one(),
+            "The maximum cost growth must be at least 1."
+        );
+
+        BVTRebuildPolicy {
+            max_cost_growth,
+            reference_costs: bvt.subtree_sah_costs(),
+        }
+    }
+
+    /// The factor by which the cost of a subtree must grow for this subtree to be rebuilt.
+    pub fn max_cost_growth(&self) -> N {
+        self.max_cost_growth
+    }
+
+    /// The roots of the largest subtrees of `bvt` which cost grew too much.
+    pub fn degraded_subtrees<T, BV: BoundingVolume<N>>(&self, bvt: &BVT<T, BV>) -> Vec<BVTNodeId> {
+        assert_eq!(
+            bvt.internals.len(),
+            self.reference_costs.len(),
+            "This policy was not created for the given BVT."
+        );
+
+        let costs = bvt.subtree_sah_costs();
+        let mut result = Vec::new();
+        let mut stack: Vec<_> = bvt.root().into_iter().collect();
+
+        while let Some(node) = stack.pop() {
+            if let BVTNodeId::Internal(i) = node {
+                if costs[i] > self.reference_costs[i] * self.max_cost_growth {
+                    result.push(node)
+                } else {
+                    stack.push(bvt.internals[i].left);
+                    stack.push(bvt.internals[i].right);
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Rebuilds the given subtrees of `bvt` and records their new cost.
+    pub fn rebuild<T, BV>(&mut self, bvt: &mut BVT<T, BV>, subtrees: &[BVTNodeId])
+    where
+        BV: BoundingVolume<N> + Clone,
+    {
+        if subtrees.is_empty() {
+            return;
+        }
+
+        for subtree in subtrees {
+            bvt.rebuild_subtree(*subtree);
+        }
+
+        // Only the rebuilt nodes get a new reference cost, so that
+        // the degradation of the other subtrees is still tracked.
+        let costs = bvt.subtree_sah_costs();
+        let mut stack = subtrees.to_vec();
+
+        while let Some(node) = stack.pop() {
+            if let BVTNodeId::Internal(i) = node {
+                self.reference_costs[i] = costs[i];
+                stack.push(bvt.internals[i].left);
+                stack.push(bvt.internals[i].right);
+            }
+        }
+    }
+
+    /// Rebuilds all the subtrees of `bvt` which cost grew too much.
+    ///
+    /// Returns the number of rebuilt subtrees.
+    pub fn rebuild_degraded<T, BV>(&mut self, bvt: &mut BVT<T, BV>) -> usize
+    where
+        BV: BoundingVolume<N> + Clone,
+    {
+        let subtrees = self.degraded_subtrees(bvt);
+        self.rebuild(bvt, &subtrees);
+        subtrees.len()
+    }
+}
+
 /// Result of a binary partition.
 pub enum BinaryPartition<T, BV> {
     /// Result of the partitioning of one element.
@@ -244,6 +350,136 @@ impl<T, BV> BVT<T, BV> {
         }
     }
 
+    /// Rebuilds the subtree rooted at `node` using the surface area heuristic.
+    ///
+    /// This is useful when the leaves moved too much for the refitted tree to remain efficient.
+    /// The leaves keep their indices, so the indices given to `.set_leaf_bounding_volume(...)`
+    /// remain valid. This should be called after `.refit(...)` if some leaf bounding volumes changed.
+    pub fn rebuild_subtree<N>(&mut self, node: BVTNodeId)
+    where
+        N: RealField,
+        BV: BoundingVolume<N> + Clone,
+    {
+        let root = match node {
+            BVTNodeId::Internal(i) => i,
+            BVTNodeId::Leaf(_) => return,
+        };
+
+        // Collect the internal nodes and the leaves of the subtree. The subtree root is
+        // placed last so it is reused for the root of the rebuilt subtree.
+        let mut slots = Vec::new();
+        let mut elements = Vec::new();
+        let mut stack = vec![node];
+
+        while let Some(node) = stack.pop() {
+            match node {
+                BVTNodeId::Internal(i) => {
+                    if i != root {
+                        slots.push(i);
+                    }
+
+                    stack.push(self.internals[i].left);
+                    stack.push(self.internals[i].right);
+                }
+                BVTNodeId::Leaf(i) => elements.push((i, self.leaves[i].bounding_volume.clone())),
+            }
+        }
+
+        slots.push(root);
+
+        let mut internals = Vec::with_capacity(slots.len());
+        let mut leaves = Vec::with_capacity(elements.len());
+        let _ = BVT::_from_partitioning(
+            0,
+            elements,
+            &mut internals,
+            &mut leaves,
+            &mut |depth, elements| BVT::sah_partitioning(SAH_NUM_BINS, 1, depth, elements),
+        );
+
+        let remap = |id| match id {
+            BVTNodeId::Internal(i) => BVTNodeId::Internal(slots[i]),
+            BVTNodeId::Leaf(i) => BVTNodeId::Leaf(leaves[i].data),
+        };
+
+        for (slot, internal) in slots.iter().zip(internals) {
+            self.internals[*slot] = BVTInternal {
+                bounding_volume: internal.bounding_volume,
+                left: remap(internal.left),
+                right: remap(internal.right),
+            };
+        }
+
+        if !self.deformation_infos.is_empty() {
+            let num_internals = self.internals.len();
+
+            for slot in &slots {
+                let internal = &self.internals[*slot];
+
+                for child in &[internal.left, internal.right] {
+                    match *child {
+                        BVTNodeId::Internal(j) => self.deformation_infos[j].parent = *slot,
+                        BVTNodeId::Leaf(j) => {
+                            self.deformation_infos[num_internals + j].parent = *slot
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // The surface area heuristic cost of the subtree rooted at each internal node.
+    fn subtree_sah_costs<N>(&self) -> Vec<N>
+    where
+        N: RealField,
+        BV: BoundingVolume<N>,
+    {
+        let mut costs = vec![N::zero(); self.internals.len()];
+        let mut order = Vec::with_capacity(self.internals.len());
+        let mut stack = Vec::new();
+
+        if let BVTNodeId::Internal(root) = self.root {
+            if !self.leaves.is_empty() {
+                stack.push(root);
+            }
+        }
+
+        while let Some(i) = stack.pop() {
+            order.push(i);
+
+            for child in &[self.internals[i].left, self.internals[i].right] {
+                if let BVTNodeId::Internal(j) = *child {
+                    stack.push(j)
+                }
+            }
+        }
+
+        // Sum the surface areas of the internal nodes of each subtree, children first.
+        for i in order.into_iter().rev() {
+            let mut cost = self.internals[i].bounding_volume.surface_area();
+
+            for child in &[self.internals[i].left, self.internals[i].right] {
+                if let BVTNodeId::Internal(j) = *child {
+                    cost += costs[j]
+                }
+            }
+
+            costs[i] = cost;
+        }
+
+        for (cost, internal) in costs.iter_mut().zip(self.internals.iter()) {
+            let area = internal.bounding_volume.surface_area();
+
+            *cost = if area.is_zero() {
+                N::one()
+            } else {
+                *cost / area
+            };
+        }
+
+        costs
+    }
+
     fn init_deformation_infos(&mut self) {
         if self.deformation_infos.is_empty() {
             self.deformation_infos = iter::repeat(BVTDeformationInfo {
@@ -357,6 +593,148 @@ impl<T, BV> BVT<T, BV> {
         Self::median_partitioning_with_centers(depth, leaves, &mut |_, bv| bv.center())
     }
 
+    /// Creates a `BVT` using the binned surface area heuristic.
+    ///
+    /// This is slower than `BVT::new_balanced` but results in faster queries, especially when
+    /// the leaves have very different sizes or are unevenly distributed. The sets of at most
+    /// `max_leaf_size` leaves are split at their median instead, which is faster for the bottom
+    /// levels of the tree where the surface area heuristic makes little difference.
+    pub fn new_sah<N>(leaves: Vec<(T, BV)>, max_leaf_size: usize) -> BVT<T, BV>
+    where
+        N: RealField,
+        BV: BoundingVolume<N> + Clone,
+    {
+        BVT::from_partitioning(leaves, &mut |depth, leaves| {
+            Self::sah_partitioning(SAH_NUM_BINS, max_leaf_size, depth, leaves)
+        })
+    }
+
+    /// Construction function for a BVT based on the binned surface area heuristic, to be used with
+    /// `BVT::from_partitioning`.
+    ///
+    /// The leaves are binned along each axis into `num_bins` bins depending on their center, and split
+    /// at the bin boundary minimizing the surface area heuristic cost. The sets of at most
+    /// `max_leaf_size` leaves are split with `BVT::median_partitioning` instead.
+    pub fn sah_partitioning<N>(
+        num_bins: usize,
+        max_leaf_size: usize,
+        depth: usize,
+        leaves: Vec<(T, BV)>,
+    ) -> (BV, BinaryPartition<T, BV>)
+    where
+        N: RealField,
+        BV: BoundingVolume<N> + Clone,
+    {
+        assert!(
+            num_bins >= 2,
+            "The surface area heuristic requires at least two bins."
+        );
+
+        if leaves.len() <= max_leaf_size.max(2) {
+            return Self::median_partitioning(depth, leaves);
+        }
+
+        let centers: Vec<_> = leaves.iter().map(|l| l.1.center()).collect();
+        let mut mins = centers[0];
+        let mut maxs = centers[0];
+
+        for center in &centers[1..] {
+            mins = mins.inf(center);
+            maxs = maxs.sup(center);
+        }
+
+        let bin = |center: &Point<N>, axis: usize| {
+            let extent = maxs[axis] - mins[axis];
+            let i = (center[axis] - mins[axis]) / extent * na::convert(num_bins as f64);
+            (na::convert_unchecked::<N, f64>(i) as usize).min(num_bins - 1)
+        };
+
+        // The best split as its axis and first bin on the right side.
+        let mut best_cost = N::max_value();
+        let mut best_split = None;
+        let mut bins: Vec<(usize, Option<BV>)> = Vec::with_capacity(num_bins);
+        let mut right_costs = vec![N::zero(); num_bins];
+
+        for axis in 0..DIM {
+            if maxs[axis] == mins[axis] {
+                continue;
+            }
+
+            bins.clear();
+            bins.resize(num_bins, (0, None));
+
+            for (center, leaf) in centers.iter().zip(leaves.iter()) {
+                let bin = &mut bins[bin(center, axis)];
+                bin.0 += 1;
+                merge_optional(&mut bin.1, &leaf.1);
+            }
+
+            let mut count = 0;
+            let mut bv = None;
+
+            for i in (1..num_bins).rev() {
+                count += bins[i].0;
+
+                if let Some(bin_bv) = &bins[i].1 {
+                    merge_optional(&mut bv, bin_bv);
+                }
+
+                right_costs[i] = bv.as_ref().map_or(N::zero(), |bv| {
+                    bv.surface_area() * na::convert(count as f64)
+                });
+            }
+
+            count = 0;
+            bv = None;
+
+            for i in 1..num_bins {
+                count += bins[i - 1].0;
+
+                if let Some(bin_bv) = &bins[i - 1].1 {
+                    merge_optional(&mut bv, bin_bv);
+                }
+
+                if count == 0 || count == leaves.len() {
+                    continue;
+                }
+
+                let cost = bv.as_ref().unwrap().surface_area() * na::convert(count as f64)
+                    + right_costs[i];
+
+                if cost < best_cost {
+                    best_cost = cost;
+                    best_split = Some((axis, i));
+                }
+            }
+        }
+
+        match best_split {
+            // A zero cost means the bounding volumes have no surface area.
+            Some((axis, split)) if !best_cost.is_zero() => {
+                let mut left = Vec::new();
+                let mut right = Vec::new();
+                let mut bounding_bounding_volume = leaves[0].1.clone();
+
+                for (center, leaf) in centers.iter().zip(leaves) {
+                    bounding_bounding_volume.merge(&leaf.1);
+
+                    if bin(center, axis) < split {
+                        left.push(leaf)
+                    } else {
+                        right.push(leaf)
+                    }
+                }
+
+                (
+                    bounding_bounding_volume,
+                    BinaryPartition::Parts(left, right),
+                )
+            }
+            // All the leaves have the same center, or no surface area.
+            _ => Self::median_partitioning(depth, leaves),
+        }
+    }
+
     fn _from_partitioning<F: FnMut(usize, Vec<(T, BV)>) -> (BV, BinaryPartition<T, BV>)>(
         depth: usize,
         leaves: Vec<(T, BV)>,
@@ -400,6 +778,335 @@ impl<T, BV> BVT<T, BV> {
     }
 }
 
+#[cfg(feature = "parallel")]
+impl<T: Send, BV: Send> BVT<T, BV> {
+    /// Creates a balanced `BVT` on several threads.
+    ///
+    /// The result is identical to the one of `BVT::new_balanced`.
+    pub fn new_balanced_parallel<N>(leaves: Vec<(T, BV)>) -> BVT<T, BV>
+    where
+        N: RealField,
+        BV: BoundingVolume<N> + Clone,
+    {
+        BVT::from_partitioning_parallel(leaves, &Self::median_partitioning)
+    }
+
+    /// Builds a bounding volume tree on several threads using the specified partitioning function.
+    ///
+    /// The subtrees are built in parallel once the partitioning function split the elements. The
+    /// result is identical to the one of `BVT::from_partitioning` with the same partitioning function.
+    pub fn from_partitioning_parallel(
+        elements: Vec<(T, BV)>,
+        partitioning: &(impl Fn(usize, Vec<(T, BV)>) -> (BV, BinaryPartition<T, BV>) + Sync),
+    ) -> BVT<T, BV> {
+        if elements.len() <= PARALLEL_BUILD_MIN_LEAVES {
+            return BVT::from_partitioning(elements, &mut |depth, elements| {
+                partitioning(depth, elements)
+            });
+        }
+
+        let num_leaves = elements.len();
+        let mut internals: Vec<_> = iter::repeat_with(|| None).take(num_leaves - 1).collect();
+        let mut leaves: Vec<_> = iter::repeat_with(|| None).take(num_leaves).collect();
+        let root = Self::_from_partitioning_parallel(
+            0,
+            elements,
+            (0, &mut internals),
+            (0, &mut leaves),
+            partitioning,
+        );
+
+        BVT {
+            root,
+            internals: internals.into_iter().map(Option::unwrap).collect(),
+            leaves: leaves.into_iter().map(Option::unwrap).collect(),
+            deformation_timestamp: 1,
+            deformation_infos: Vec::new(),
+            parents_to_update: VecDeque::new(),
+        }
+    }
+
+    // Builds the subtree containing `leaves` into the given slices, each given with the index of
+    // its first element on the whole tree.
+    //
+    // A subtree with `n` leaves has `n - 1` internal nodes, so the slices have exactly the size
+    // of the subtree and the nodes are placed where `BVT::_from_partitioning` would push them.
+    fn _from_partitioning_parallel(
+        depth: usize,
+        leaves: Vec<(T, BV)>,
+        (internals_start, out_internals): (usize, &mut [Option<BVTInternal<BV>>]),
+        (leaves_start, out_leaves): (usize, &mut [Option<BVTLeaf<T, BV>>]),
+        partitioning: &(impl Fn(usize, Vec<(T, BV)>) -> (BV, BinaryPartition<T, BV>) + Sync),
+    ) -> BVTNodeId {
+        if leaves.len() <= PARALLEL_BUILD_MIN_LEAVES {
+            let mut internals = Vec::with_capacity(out_internals.len());
+            let mut subtree_leaves = Vec::with_capacity(out_leaves.len());
+            let root = Self::_from_partitioning(
+                depth,
+                leaves,
+                &mut internals,
+                &mut subtree_leaves,
+                &mut |depth, elements| partitioning(depth, elements),
+            );
+            assert_eq!(
+                subtree_leaves.len(),
+                out_leaves.len(),
+                "The partitioning function must neither add nor remove elements."
+            );
+
+            let offset = |id| match id {
+                BVTNodeId::Internal(i) => BVTNodeId::Internal(internals_start + i),
+                BVTNodeId::Leaf(i) => BVTNodeId::Leaf(leaves_start + i),
+            };
+
+            for (out, mut internal) in out_internals.iter_mut().zip(internals) {
+                internal.left = offset(internal.left);
+                internal.right = offset(internal.right);
+                *out = Some(internal);
+            }
+
+            for (out, leaf) in out_leaves.iter_mut().zip(subtree_leaves) {
+                *out = Some(leaf);
+            }
+
+            return offset(root);
+        }
+
+        let (bv, partitions) = partitioning(depth, leaves);
+
+        match partitions {
+            BinaryPartition::Part(b) => {
+                assert_eq!(
+                    out_leaves.len(),
+                    1,
+                    "The partitioning function must neither add nor remove elements."
+                );
+                out_leaves[0] = Some(BVTLeaf {
+                    bounding_volume: bv,
+                    data: b,
+                });
+                BVTNodeId::Leaf(leaves_start)
+            }
+            BinaryPartition::Parts(left, right) => {
+                assert!(
+                    !left.is_empty() && !right.is_empty(),
+                    "The partitioning function must not produce empty partitions."
+                );
+                assert_eq!(
+                    left.len() + right.len(),
+                    out_leaves.len(),
+                    "The partitioning function must neither add nor remove elements."
+                );
+
+                let num_left = left.len();
+                let node_id = internals_start + out_internals.len() - 1;
+                let (left_internals, right_internals) = out_internals.split_at_mut(num_left - 1);
+                let (right_internals, node) =
+                    right_internals.split_at_mut(right_internals.len() - 1);
+                let (left_leaves, right_leaves) = out_leaves.split_at_mut(num_left);
+
+                let (left, right) = rayon::join(
+                    || {
+                        Self::_from_partitioning_parallel(
+                            depth + 1,
+                            left,
+                            (internals_start, left_internals),
+                            (leaves_start, left_leaves),
+                            partitioning,
+                        )
+                    },
+                    || {
+                        Self::_from_partitioning_parallel(
+                            depth + 1,
+                            right,
+                            (internals_start + num_left - 1, right_internals),
+                            (leaves_start + num_left, right_leaves),
+                            partitioning,
+                        )
+                    },
+                );
+
+                node[0] = Some(BVTInternal {
+                    bounding_volume: bv,
+                    left,
+                    right,
+                });
+                BVTNodeId::Internal(node_id)
+            }
+        }
+    }
+}
+
+// The magic number starting the binary encoding of a BVT.
+const BVT_MAGIC: &[u8; 4] = b"NBVT";
+
+impl<T: BinaryCodec, BV: BinaryCodec> BVT<T, BV> {
+    /// Encodes this BVT into a compact and versioned binary buffer.
+    ///
+    /// This allows the trees of static geometries to be built offline and loaded with
+    /// `BVT::from_bytes` at a cost much lower than their construction. The deformation-tracking
+    /// data of this BVT are not encoded.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.encode(&mut out);
+        out
+    }
+
+    /// Appends the binary encoding of this BVT to `out`.
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        let layout = [utils::layout_of::<BV>(), utils::layout_of::<T>()];
+        utils::encode_header(BVT_MAGIC, &layout, out);
+        out.reserve(
+            self.internals.len() * (BV::ENCODED_SIZE + BVTNodeId::ENCODED_SIZE * 2)
+                + self.leaves.len() * (BV::ENCODED_SIZE + T::ENCODED_SIZE),
+        );
+        self.root.encode(out);
+
+        self.internals.len().encode(out);
+        for internal in &self.internals {
+            internal.bounding_volume.encode(out);
+            internal.left.encode(out);
+            internal.right.encode(out);
+        }
+
+        self.leaves.len().encode(out);
+        for leaf in &self.leaves {
+            leaf.bounding_volume.encode(out);
+            leaf.data.encode(out);
+        }
+    }
+
+    /// Decodes a BVT encoded with `BVT::to_bytes`.
+    ///
+    /// The nodes are read in a single pass from `bytes`, which may, e.g., be a memory-mapped
+    /// file. They are copied into the returned BVT, which is then checked to be a valid tree,
+    /// so decoding takes a time linear in the number of nodes. Fails if `bytes` does not contain
+    /// exactly one BVT with the same bounding volume and data types, or if it is not a valid
+    /// tree.
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, DecodingError> {
+        let result = Self::decode(&mut bytes)?;
+
+        if bytes.is_empty() {
+            Ok(result)
+        } else {
+            Err(DecodingError::InvalidData)
+        }
+    }
+
+    /// Decodes a BVT from the beginning of `bytes`, and advances `bytes` past it.
+    pub fn decode(bytes: &mut &[u8]) -> Result<Self, DecodingError> {
+        let layout = [utils::layout_of::<BV>(), utils::layout_of::<T>()];
+        utils::decode_header(BVT_MAGIC, &layout, bytes)?;
+        let root = BVTNodeId::decode(bytes)?;
+
+        let num_internals =
+            utils::decode_len(bytes, BV::ENCODED_SIZE + BVTNodeId::ENCODED_SIZE * 2)?;
+        let mut internals = Vec::with_capacity(num_internals);
+
+        for _ in 0..num_internals {
+            internals.push(BVTInternal {
+                bounding_volume: BV::decode(bytes)?,
+                left: BVTNodeId::decode(bytes)?,
+                right: BVTNodeId::decode(bytes)?,
+            });
+        }
+
+        let num_leaves = utils::decode_len(bytes, BV::ENCODED_SIZE + T::ENCODED_SIZE)?;
+        let mut leaves = Vec::with_capacity(num_leaves);
+
+        for _ in 0..num_leaves {
+            leaves.push(BVTLeaf {
+                bounding_volume: BV::decode(bytes)?,
+                data: T::decode(bytes)?,
+            });
+        }
+
+        let result = BVT {
+            root,
+            internals,
+            leaves,
+            deformation_timestamp: 1,
+            deformation_infos: Vec::new(),
+            parents_to_update: VecDeque::new(),
+        };
+
+        if result.is_valid_tree() {
+            Ok(result)
+        } else {
+            Err(DecodingError::InvalidData)
+        }
+    }
+
+    // Checks that every node of this BVT is reachable from its root exactly once.
+    fn is_valid_tree(&self) -> bool {
+        if self.leaves.is_empty() {
+            return self.internals.is_empty();
+        }
+
+        let mut visited_internals = vec![false; self.internals.len()];
+        let mut visited_leaves = vec![false; self.leaves.len()];
+        let mut stack = vec![self.root];
+
+        while let Some(node) = stack.pop() {
+            let visited = match node {
+                BVTNodeId::Internal(i) => visited_internals.get_mut(i),
+                BVTNodeId::Leaf(i) => visited_leaves.get_mut(i),
+            };
+
+            match visited {
+                Some(visited) if !*visited => *visited = true,
+                _ => return false,
+            }
+
+            if let BVTNodeId::Internal(i) = node {
+                stack.push(self.internals[i].left);
+                stack.push(self.internals[i].right);
+            }
+        }
+
+        visited_internals
+            .iter()
+            .chain(visited_leaves.iter())
+            .all(|v| *v)
+    }
+}
+
+impl BinaryCodec for BVTNodeId {
+    const ENCODED_SIZE: usize = u8::ENCODED_SIZE + usize::ENCODED_SIZE;
+    const TYPE_TAG: u64 = utils::type_tag("BVTNodeId", &[]);
+
+    #[inline]
+    fn encode(&self, out: &mut Vec<u8>) {
+        match *self {
+            BVTNodeId::Internal(i) => {
+                0u8.encode(out);
+                i.encode(out);
+            }
+            BVTNodeId::Leaf(i) => {
+                1u8.encode(out);
+                i.encode(out);
+            }
+        }
+    }
+
+    #[inline]
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodingError> {
+        match u8::decode(bytes)? {
+            0 => Ok(BVTNodeId::Internal(usize::decode(bytes)?)),
+            1 => Ok(BVTNodeId::Leaf(usize::decode(bytes)?)),
+            _ => Err(DecodingError::InvalidData),
+        }
+    }
+}
+
+// Merges `bv` into `acc`, or sets `acc` to `bv` if it is `None`.
+fn merge_optional<N: RealField, BV: BoundingVolume<N> + Clone>(acc: &mut Option<BV>, bv: &BV) {
+    match acc {
+        Some(acc) => acc.merge(bv),
+        None => *acc = Some(bv.clone()),
+    }
+}
+
 impl<'a, T, BV> BVH<T, BV> for BVT<T, BV> {
     type Node = BVTNodeId;
 
@@ -24,12 +24,6 @@ impl DBVTLeafId {
     }
 }
 
-#[derive(Copy, Clone, Debug)]
-enum UpdateStatus {
-    NeedsShrink,
-    UpToDate,
-}
-
 #[derive(Copy, Clone, Debug, Hash)]
 enum DBVTInternalId {
     RightChildOf(usize),
@@ -51,7 +45,7 @@ pub enum DBVTNodeId {
 pub struct DBVT<N: RealField, T, BV> {
     root: DBVTNodeId,
     leaves: Slab<DBVTLeaf<N, T, BV>>,
-    internals: Slab<DBVTInternal<N, BV>>,
+    internals: Slab<DBVTInternal<BV>>,
 }
 
 /// Leaf of a Dynamic Bounding Volume Tree.
@@ -69,19 +63,15 @@ pub struct DBVTLeaf<N: RealField, T, BV> {
 
 /// Internal node of a DBVT. An internal node always has two children.
 #[derive(Clone)]
-struct DBVTInternal<N: RealField, BV> {
+struct DBVTInternal<BV> {
     /// The bounding volume of this node. It always encloses both its children bounding volumes.
     bounding_volume: BV,
-    /// The center of this node bounding volume.
-    center: Point<N>,
     /// This node left child.
     left: DBVTNodeId,
     /// This node right child.
     right: DBVTNodeId,
     /// This node parent.
     parent: DBVTInternalId,
-
-    state: UpdateStatus,
 }
 
 impl<N: RealField, T, BV: BoundingVolume<N>> DBVTLeaf<N, T, BV> {
@@ -104,21 +94,19 @@ impl<N: RealField, T, BV: BoundingVolume<N>> DBVTLeaf<N, T, BV> {
     }
 }
 
-impl<N: RealField, BV: BoundingVolume<N>> DBVTInternal<N, BV> {
+impl<BV> DBVTInternal<BV> {
     /// Creates a new internal node.
     fn new(
         bounding_volume: BV,
         parent: DBVTInternalId,
         left: DBVTNodeId,
         right: DBVTNodeId,
-    ) -> DBVTInternal<N, BV> {
+    ) -> DBVTInternal<BV> {
         DBVTInternal {
-            center: bounding_volume.center(),
             bounding_volume: bounding_volume,
             left: left,
             right: right,
             parent: parent,
-            state: UpdateStatus::UpToDate,
         }
     }
 }
@@ -155,6 +143,10 @@ impl<N: RealField, T, BV: BoundingVolume<N>> DBVT<N, T, BV> {
     }
 
     /// Inserts a leaf into this DBVT.
+    ///
+    /// The leaf is inserted next to the node minimizing the increase of the surface area heuristic
+    /// cost of the tree. The ancestors of the new leaf are then refitted and rotated whenever this
+    /// reduces their surface area.
     pub fn insert(&mut self, leaf: DBVTLeaf<N, T, BV>) -> DBVTLeafId {
         if self.is_empty() {
             let new_id = self.leaves.insert(leaf);
@@ -164,160 +156,207 @@ impl<N: RealField, T, BV: BoundingVolume<N>> DBVT<N, T, BV> {
             return DBVTLeafId(new_id);
         }
 
-        match self.root {
-            DBVTNodeId::Internal(_) => {
-                let mut curr = self.root;
-
-                loop {
-                    match curr {
-                        DBVTNodeId::Internal(id) => {
-                            // FIXME: we could avoid the systematic merge
-                            let (left, right) = {
-                                let node = &mut self.internals[id];
-                                node.bounding_volume.merge(&leaf.bounding_volume);
-                                (node.left, node.right)
-                            };
-
-                            let dist1 = match left {
-                                DBVTNodeId::Leaf(l) => {
-                                    na::distance_squared(&self.leaves[l].center, &leaf.center)
-                                }
-                                DBVTNodeId::Internal(i) => {
-                                    na::distance_squared(&self.internals[i].center, &leaf.center)
-                                }
-                            };
-
-                            let dist2 = match right {
-                                DBVTNodeId::Leaf(l) => {
-                                    na::distance_squared(&self.leaves[l].center, &leaf.center)
-                                }
-                                DBVTNodeId::Internal(i) => {
-                                    na::distance_squared(&self.internals[i].center, &leaf.center)
-                                }
-                            };
-
-                            curr = if dist1 < dist2 { left } else { right };
-                        }
-                        DBVTNodeId::Leaf(id) => {
-                            let parent_bv = self.leaves[id]
-                                .bounding_volume
-                                .merged(&leaf.bounding_volume);
-                            let grand_parent = self.leaves[id].parent;
-
-                            let new_id = self.leaves.insert(leaf);
-                            let parent = DBVTInternal::new(
-                                parent_bv,
-                                grand_parent,
-                                curr,
-                                DBVTNodeId::Leaf(new_id),
-                            );
-                            let parent_id = self.internals.insert(parent);
-                            self.leaves[id].parent = DBVTInternalId::LeftChildOf(parent_id);
-                            self.leaves[new_id].parent = DBVTInternalId::RightChildOf(parent_id);
-
-                            match grand_parent {
-                                DBVTInternalId::LeftChildOf(pp) => {
-                                    self.internals[pp].left = DBVTNodeId::Internal(parent_id)
-                                }
-                                DBVTInternalId::RightChildOf(pp) => {
-                                    self.internals[pp].right = DBVTNodeId::Internal(parent_id)
-                                }
-                                _ => unreachable!(),
-                            }
-
-                            break DBVTLeafId(new_id);
-                        }
+        let sibling = self.best_sibling(&leaf.bounding_volume);
+        let grand_parent = self.parent(sibling);
+        let parent_bv = self.bounding_volume(sibling).merged(&leaf.bounding_volume);
+
+        let new_id = self.leaves.insert(leaf);
+        let parent = DBVTInternal::new(parent_bv, grand_parent, sibling, DBVTNodeId::Leaf(new_id));
+        let parent_id = self.internals.insert(parent);
+        self.attach(sibling, DBVTInternalId::LeftChildOf(parent_id));
+        self.attach(
+            DBVTNodeId::Leaf(new_id),
+            DBVTInternalId::RightChildOf(parent_id),
+        );
+        self.attach(DBVTNodeId::Internal(parent_id), grand_parent);
+        self.refit_ancestors(grand_parent);
+
+        DBVTLeafId(new_id)
+    }
+
+    // Finds the node to which a leaf with the bounding volume `bv` should become a sibling.
+    //
+    // This descends greedily toward the child with the smallest surface area heuristic cost, or
+    // toward the closest child if both costs are equal.
+    fn best_sibling(&self, bv: &BV) -> DBVTNodeId {
+        let two: N = na::convert(2.0);
+        let mut curr = self.root;
+
+        while let DBVTNodeId::Internal(id) = curr {
+            let node = &self.internals[id];
+            let area = node.bounding_volume.surface_area();
+            let merged_area = node.bounding_volume.merged(bv).surface_area();
+
+            // Cost of creating a new parent for this node and the new leaf.
+            let cost = merged_area * two;
+            // Cost of the enlargement of this node if the new leaf is inserted deeper.
+            let inheritance_cost = (merged_area - area) * two;
+
+            let descent_cost = |child| {
+                let child_bv = self.bounding_volume(child);
+                let merged_area = child_bv.merged(bv).surface_area();
+
+                match child {
+                    DBVTNodeId::Leaf(_) => merged_area + inheritance_cost,
+                    DBVTNodeId::Internal(_) => {
+                        merged_area - child_bv.surface_area() + inheritance_cost
                     }
                 }
+            };
+
+            let left_cost = descent_cost(node.left);
+            let right_cost = descent_cost(node.right);
+
+            if cost < left_cost && cost < right_cost {
+                break;
             }
-            DBVTNodeId::Leaf(id) => {
-                let new_id = self.leaves.insert(leaf);
-
-                // Create a common parent which is the new root.
-                let root_bv = self.leaves[id]
-                    .bounding_volume
-                    .merged(&self.leaves[new_id].bounding_volume);
-                let root = DBVTInternal::new(
-                    root_bv,
-                    DBVTInternalId::Root,
-                    DBVTNodeId::Leaf(id),
-                    DBVTNodeId::Leaf(new_id),
-                );
-
-                let root_id = self.internals.insert(root);
-                self.leaves[id].parent = DBVTInternalId::LeftChildOf(root_id);
-                self.leaves[new_id].parent = DBVTInternalId::RightChildOf(root_id);
-                self.root = DBVTNodeId::Internal(root_id);
-
-                DBVTLeafId(new_id)
-            }
+
+            curr = if left_cost < right_cost {
+                node.left
+            } else if right_cost < left_cost {
+                node.right
+            } else {
+                // Happens if the bounding volumes have no surface area.
+                let center = bv.center();
+                let left_dist =
+                    na::distance_squared(&self.bounding_volume(node.left).center(), &center);
+                let right_dist =
+                    na::distance_squared(&self.bounding_volume(node.right).center(), &center);
+
+                if left_dist < right_dist {
+                    node.left
+                } else {
+                    node.right
+                }
+            };
         }
+
+        curr
     }
 
-    /// Removes a leaf from this DBVT.
-    ///
-    /// Panics if the provided leaf is not attached to this DBVT.
-    pub fn remove(&mut self, leaf_id: DBVTLeafId) -> DBVTLeaf<N, T, BV> {
-        let DBVTLeafId(leaf_id) = leaf_id;
-        let leaf = self.leaves.remove(leaf_id);
+    // Refits and rotates all the internal nodes from `node` to the root.
+    fn refit_ancestors(&mut self, mut node: DBVTInternalId) {
+        while let DBVTInternalId::LeftChildOf(id) | DBVTInternalId::RightChildOf(id) = node {
+            self.refit(id);
+            self.rotate(id);
+            node = self.internals[id].parent;
+        }
+    }
 
-        if !leaf.is_root() {
-            let p;
-            let other;
+    // Recomputes the bounding volume of an internal node from its children.
+    fn refit(&mut self, id: usize) {
+        let node = &self.internals[id];
+        let bv = self
+            .bounding_volume(node.left)
+            .merged(self.bounding_volume(node.right));
+        self.internals[id].bounding_volume = bv;
+    }
 
-            match leaf.parent {
-                DBVTInternalId::RightChildOf(parent) => {
-                    other = self.internals[parent].left;
-                    p = parent;
+    // Swaps a child of the internal node `id` with a grandchild from its other child, if this
+    // reduces the surface area of the latter. Only the best rotation is applied.
+    fn rotate(&mut self, id: usize) {
+        let node = &self.internals[id];
+        let mut best_cost = N::zero();
+        let mut best_rotation = None;
+
+        for &(child, other) in &[
+            (DBVTInternalId::LeftChildOf(id), node.right),
+            (DBVTInternalId::RightChildOf(id), node.left),
+        ] {
+            if let DBVTNodeId::Internal(other_id) = other {
+                let child_bv = self.bounding_volume(self.child(child));
+                let other = &self.internals[other_id];
+                let area = other.bounding_volume.surface_area();
+
+                for &(nephew, remaining) in &[
+                    (DBVTInternalId::LeftChildOf(other_id), other.right),
+                    (DBVTInternalId::RightChildOf(other_id), other.left),
+                ] {
+                    let cost = child_bv
+                        .merged(self.bounding_volume(remaining))
+                        .surface_area()
+                        - area;
+
+                    if cost < best_cost {
+                        best_cost = cost;
+                        best_rotation = Some((child, nephew));
+                    }
                 }
-                DBVTInternalId::LeftChildOf(parent) => {
-                    other = self.internals[parent].right;
-                    p = parent;
+            }
+        }
+
+        if let Some((child, nephew)) = best_rotation {
+            let child_node = self.child(child);
+            let nephew_node = self.child(nephew);
+            self.attach(nephew_node, child);
+            self.attach(child_node, nephew);
+
+            match nephew {
+                DBVTInternalId::LeftChildOf(other) | DBVTInternalId::RightChildOf(other) => {
+                    self.refit(other)
                 }
                 DBVTInternalId::Root => unreachable!(),
             }
+        }
+    }
 
-            match self.internals[p].parent {
-                DBVTInternalId::RightChildOf(pp) => {
-                    match other {
-                        DBVTNodeId::Internal(id) => {
-                            self.internals[id].parent = DBVTInternalId::RightChildOf(pp)
-                        }
-                        DBVTNodeId::Leaf(id) => {
-                            self.leaves[id].parent = DBVTInternalId::RightChildOf(pp)
-                        }
-                    }
+    // The bounding volume of the given node.
+    fn bounding_volume(&self, node: DBVTNodeId) -> &BV {
+        match node {
+            DBVTNodeId::Leaf(i) => &self.leaves[i].bounding_volume,
+            DBVTNodeId::Internal(i) => &self.internals[i].bounding_volume,
+        }
+    }
 
-                    self.internals[pp].right = other;
-                    self.internals[pp].state = UpdateStatus::NeedsShrink;
-                }
-                DBVTInternalId::LeftChildOf(pp) => {
-                    match other {
-                        DBVTNodeId::Internal(id) => {
-                            self.internals[id].parent = DBVTInternalId::LeftChildOf(pp)
-                        }
-                        DBVTNodeId::Leaf(id) => {
-                            self.leaves[id].parent = DBVTInternalId::LeftChildOf(pp)
-                        }
-                    }
+    // The position of the given node in the tree.
+    fn parent(&self, node: DBVTNodeId) -> DBVTInternalId {
+        match node {
+            DBVTNodeId::Leaf(i) => self.leaves[i].parent,
+            DBVTNodeId::Internal(i) => self.internals[i].parent,
+        }
+    }
 
-                    self.internals[pp].left = other;
-                    self.internals[pp].state = UpdateStatus::NeedsShrink;
-                }
-                DBVTInternalId::Root => {
-                    // The root changes to the other child.
-                    match other {
-                        DBVTNodeId::Leaf(id) => self.leaves[id].parent = DBVTInternalId::Root,
-                        DBVTNodeId::Internal(id) => {
-                            self.internals[id].parent = DBVTInternalId::Root
-                        }
-                    }
+    // The node at the given position in the tree.
+    fn child(&self, position: DBVTInternalId) -> DBVTNodeId {
+        match position {
+            DBVTInternalId::LeftChildOf(p) => self.internals[p].left,
+            DBVTInternalId::RightChildOf(p) => self.internals[p].right,
+            DBVTInternalId::Root => self.root,
+        }
+    }
 
-                    self.root = other;
-                }
-            }
+    // Moves the given node to the given position in the tree.
+    fn attach(&mut self, node: DBVTNodeId, position: DBVTInternalId) {
+        match position {
+            DBVTInternalId::LeftChildOf(p) => self.internals[p].left = node,
+            DBVTInternalId::RightChildOf(p) => self.internals[p].right = node,
+            DBVTInternalId::Root => self.root = node,
+        }
+
+        match node {
+            DBVTNodeId::Leaf(i) => self.leaves[i].parent = position,
+            DBVTNodeId::Internal(i) => self.internals[i].parent = position,
+        }
+    }
+
+    /// Removes a leaf from this DBVT.
+    ///
+    /// Panics if the provided leaf is not attached to this DBVT.
+    pub fn remove(&mut self, leaf_id: DBVTLeafId) -> DBVTLeaf<N, T, BV> {
+        let DBVTLeafId(leaf_id) = leaf_id;
+        let leaf = self.leaves.remove(leaf_id);
+
+        if !leaf.is_root() {
+            let (p, other) = match leaf.parent {
+                DBVTInternalId::RightChildOf(parent) => (parent, self.internals[parent].left),
+                DBVTInternalId::LeftChildOf(parent) => (parent, self.internals[parent].right),
+                DBVTInternalId::Root => unreachable!(),
+            };
 
-            let _ = self.internals.remove(p);
+            // The sibling of the removed leaf replaces their parent.
+            let grand_parent = self.internals.remove(p).parent;
+            self.attach(other, grand_parent);
+            self.refit_ancestors(grand_parent);
         } else {
             // The tree is now empty.
             self.leaves.clear();
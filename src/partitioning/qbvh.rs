@@ -0,0 +1,394 @@
+//! A read-only Bounding Volume Hierarchy with four children per node.
+
+use crate::bounding_volume::{BoundingVolume, AABB};
+use crate::math::{Point, DIM};
+use crate::partitioning::{BVH, BVT};
+use crate::query::Ray;
+use na::RealField;
+use std::mem;
+
+/// A read-only Bounding Volume Hierarchy with four children per node.
+///
+/// The AABBs of the children of each node are stored together, with one array per coordinate,
+/// so that they can be tested at once and without jumping through memory. This makes queries on
+/// static geometry faster than with a binary `BVT`.
+///
+/// Besides the `BVH` trait, it provides specialized queries which do not allocate any memory when
+/// they are given the same stack repeatedly.
+#[derive(Clone)]
+pub struct QBVH<N: RealField, T> {
+    nodes: Vec<QBVHNode<N>>,
+    leaves: Vec<QBVHLeaf<N, T>>,
+}
+
+/// The identifier of a QBVH node.
+#[derive(Copy, Clone, Hash, PartialEq, Eq, Debug)]
+pub enum QBVHNodeId {
+    /// Identifier of an internal node.
+    Internal(usize),
+    /// Identifier of a leaf node.
+    Leaf(usize),
+}
+
+#[derive(Clone)]
+struct QBVHNode<N: RealField> {
+    aabb: AABB<N>,
+    // The AABBs of the children, with one lane per child for each coordinate.
+    // The unused lanes contain empty AABBs.
+    mins: [[N; 4]; DIM],
+    maxs: [[N; 4]; DIM],
+    children: [QBVHNodeId; 4],
+    num_children: usize,
+}
+
+/// A leaf of the QBVH.
+#[derive(Clone)]
+pub struct QBVHLeaf<N: RealField, T> {
+    aabb: AABB<N>,
+    data: T,
+}
+
+impl<N: RealField, T> QBVHLeaf<N, T> {
+    /// The AABB stored on this leaf.
+    #[inline]
+    pub fn bounding_volume(&self) -> &AABB<N> {
+        &self.aabb
+    }
+
+    /// The user-data stored on this leaf.
+    #[inline]
+    pub fn data(&self) -> &T {
+        &self.data
+    }
+}
+
+impl<N: RealField> QBVHNode<N> {
+    fn new(aabb: AABB<N>) -> Self {
+        QBVHNode {
+            aabb,
+            mins: [[N::max_value(); 4]; DIM],
+            maxs: [[-N::max_value(); 4]; DIM],
+            children: [QBVHNodeId::Leaf(0); 4],
+            num_children: 0,
+        }
+    }
+
+    fn push_child(&mut self, child: QBVHNodeId, aabb: &AABB<N>) {
+        let lane = self.num_children;
+
+        for i in 0..DIM {
+            self.mins[i][lane] = aabb.mins()[i];
+            self.maxs[i][lane] = aabb.maxs()[i];
+        }
+
+        self.children[lane] = child;
+        self.num_children += 1;
+    }
+
+    fn intersecting_children(&self, aabb: &AABB<N>) -> [bool; 4] {
+        let mut result = [true; 4];
+
+        for i in 0..DIM {
+            for (lane, result) in result.iter_mut().enumerate() {
+                *result &=
+                    self.mins[i][lane] <= aabb.maxs()[i] && self.maxs[i][lane] >= aabb.mins()[i];
+            }
+        }
+
+        result
+    }
+
+    fn children_containing_point(&self, point: &Point<N>) -> [bool; 4] {
+        let mut result = [true; 4];
+
+        for i in 0..DIM {
+            for (lane, result) in result.iter_mut().enumerate() {
+                *result &= self.mins[i][lane] <= point[i] && self.maxs[i][lane] >= point[i];
+            }
+        }
+
+        result
+    }
+
+    fn children_intersecting_ray(&self, ray: &Ray<N>, max_toi: N) -> [bool; 4] {
+        let mut tmin = [N::zero(); 4];
+        let mut tmax = [max_toi; 4];
+        let mut result = [true; 4];
+
+        for i in 0..DIM {
+            if ray.dir[i].is_zero() {
+                for (lane, result) in result.iter_mut().enumerate() {
+                    *result &=
+                        self.mins[i][lane] <= ray.origin[i] && self.maxs[i][lane] >= ray.origin[i];
+                }
+            } else {
+                let denom = N::one() / ray.dir[i];
+
+                for lane in 0..4 {
+                    let mut near = (self.mins[i][lane] - ray.origin[i]) * denom;
+                    let mut far = (self.maxs[i][lane] - ray.origin[i]) * denom;
+
+                    if near > far {
+                        mem::swap(&mut near, &mut far)
+                    }
+
+                    tmin[lane] = tmin[lane].max(near);
+                    tmax[lane] = tmax[lane].min(far);
+                }
+            }
+        }
+
+        for lane in 0..4 {
+            result[lane] &= tmin[lane] <= tmax[lane];
+        }
+
+        result
+    }
+}
+
+impl<N: RealField, T> QBVH<N, T> {
+    /// Builds a QBVH containing the given leaves, using the surface area heuristic.
+    pub fn new(leaves: Vec<(T, AABB<N>)>) -> Self {
+        let mut data = Vec::with_capacity(leaves.len());
+        let mut aabbs = Vec::with_capacity(leaves.len());
+
+        for (i, (t, aabb)) in leaves.into_iter().enumerate() {
+            data.push(Some(t));
+            aabbs.push((i, aabb));
+        }
+
+        let bvt = BVT::new_sah(aabbs, 1);
+        Self::from_bvh_with(&bvt, &mut |i| data[*i].take().unwrap())
+    }
+
+    /// Builds a QBVH with the same leaves as the given BVH.
+    ///
+    /// The nodes of `bvh` are collapsed so that the nodes of the QBVH have up to four children.
+    /// Panics if a node of `bvh` has more than four children.
+    pub fn from_bvh(bvh: &impl BVH<T, AABB<N>>) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_bvh_with(bvh, &mut |t| t.clone())
+    }
+
+    fn from_bvh_with<U, B: BVH<U, AABB<N>>>(bvh: &B, leaf_data: &mut impl FnMut(&U) -> T) -> Self {
+        let mut result = QBVH {
+            nodes: Vec::new(),
+            leaves: Vec::new(),
+        };
+
+        if let Some(root) = bvh.root() {
+            if bvh.num_children(root) == 0 {
+                // The root must be an internal node.
+                let (aabb, data) = bvh.content(root);
+                let mut node = QBVHNode::new(aabb.clone());
+                node.push_child(QBVHNodeId::Leaf(0), aabb);
+                result.nodes.push(node);
+                result.leaves.push(QBVHLeaf {
+                    aabb: aabb.clone(),
+                    data: leaf_data(data.expect("A BVH leaf must contain some data.")),
+                });
+            } else {
+                let _ = result.collapse(bvh, root, leaf_data);
+            }
+        }
+
+        result.nodes.shrink_to_fit();
+        result.leaves.shrink_to_fit();
+        result
+    }
+
+    // Adds to this QBVH the subtree of `bvh` rooted at `node`.
+    fn collapse<U, B: BVH<U, AABB<N>>>(
+        &mut self,
+        bvh: &B,
+        node: B::Node,
+        leaf_data: &mut impl FnMut(&U) -> T,
+    ) -> QBVHNodeId {
+        let (aabb, data) = bvh.content(node);
+        let num_children = bvh.num_children(node);
+
+        if num_children == 0 {
+            self.leaves.push(QBVHLeaf {
+                aabb: aabb.clone(),
+                data: leaf_data(data.expect("A BVH leaf must contain some data.")),
+            });
+            return QBVHNodeId::Leaf(self.leaves.len() - 1);
+        }
+
+        assert!(
+            num_children <= 4,
+            "Cannot build a QBVH from a BVH with more than four children per node."
+        );
+
+        // Replace the largest internal children by their own children while they fit.
+        let mut children: Vec<_> = (0..num_children).map(|i| bvh.child(i, node)).collect();
+
+        loop {
+            let mut largest = None;
+            let mut largest_area = -N::one();
+
+            for (i, child) in children.iter().enumerate() {
+                let num_grand_children = bvh.num_children(*child);
+
+                if num_grand_children != 0 && children.len() - 1 + num_grand_children <= 4 {
+                    let area = bvh.content(*child).0.surface_area();
+
+                    if area > largest_area {
+                        largest = Some(i);
+                        largest_area = area;
+                    }
+                }
+            }
+
+            match largest {
+                Some(i) => {
+                    let child = children.remove(i);
+
+                    for j in 0..bvh.num_children(child) {
+                        children.insert(i + j, bvh.child(j, child));
+                    }
+                }
+                None => break,
+            }
+        }
+
+        let id = self.nodes.len();
+        self.nodes.push(QBVHNode::new(aabb.clone()));
+
+        for child in children {
+            let child_id = self.collapse(bvh, child, leaf_data);
+            self.nodes[id].push_child(child_id, bvh.content(child).0);
+        }
+
+        QBVHNodeId::Internal(id)
+    }
+
+    /// The set of leaves on this QBVH.
+    #[inline]
+    pub fn leaves(&self) -> &[QBVHLeaf<N, T>] {
+        &self.leaves
+    }
+
+    /// Reference to the AABB of the QBVH root.
+    pub fn root_bounding_volume(&self) -> Option<&AABB<N>> {
+        self.nodes.first().map(|root| &root.aabb)
+    }
+
+    /// Collects the data of the leaves with an AABB intersecting `aabb`.
+    ///
+    /// The `stack` is used to store the nodes to visit. It can be reused by several queries
+    /// to avoid any allocation.
+    pub fn interferences_with_aabb(&self, aabb: &AABB<N>, stack: &mut Vec<usize>, out: &mut Vec<T>)
+    where
+        T: Clone,
+    {
+        self.traverse(stack, |node| node.intersecting_children(aabb), out)
+    }
+
+    /// Collects the data of the leaves with an AABB containing `point`.
+    ///
+    /// The `stack` is used to store the nodes to visit. It can be reused by several queries
+    /// to avoid any allocation.
+    pub fn interferences_with_point(
+        &self,
+        point: &Point<N>,
+        stack: &mut Vec<usize>,
+        out: &mut Vec<T>,
+    ) where
+        T: Clone,
+    {
+        self.traverse(stack, |node| node.children_containing_point(point), out)
+    }
+
+    /// Collects the data of the leaves with an AABB intersecting `ray` before `max_toi`.
+    ///
+    /// The `stack` is used to store the nodes to visit. It can be reused by several queries
+    /// to avoid any allocation.
+    pub fn interferences_with_ray(
+        &self,
+        ray: &Ray<N>,
+        max_toi: N,
+        stack: &mut Vec<usize>,
+        out: &mut Vec<T>,
+    ) where
+        T: Clone,
+    {
+        self.traverse(
+            stack,
+            |node| node.children_intersecting_ray(ray, max_toi),
+            out,
+        )
+    }
+
+    fn traverse(
+        &self,
+        stack: &mut Vec<usize>,
+        test: impl Fn(&QBVHNode<N>) -> [bool; 4],
+        out: &mut Vec<T>,
+    ) where
+        T: Clone,
+    {
+        if self.nodes.is_empty() {
+            return;
+        }
+
+        stack.clear();
+        stack.push(0);
+
+        while let Some(id) = stack.pop() {
+            let node = &self.nodes[id];
+            let hits = test(node);
+
+            for (child, hit) in node.children[..node.num_children].iter().zip(&hits) {
+                if *hit {
+                    match *child {
+                        QBVHNodeId::Internal(i) => stack.push(i),
+                        QBVHNodeId::Leaf(i) => out.push(self.leaves[i].data.clone()),
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<N: RealField, T> BVH<T, AABB<N>> for QBVH<N, T> {
+    type Node = QBVHNodeId;
+
+    fn root(&self) -> Option<Self::Node> {
+        if self.nodes.is_empty() {
+            None
+        } else {
+            Some(QBVHNodeId::Internal(0))
+        }
+    }
+
+    fn num_children(&self, node: Self::Node) -> usize {
+        match node {
+            QBVHNodeId::Internal(i) => self.nodes[i].num_children,
+            QBVHNodeId::Leaf(_) => 0,
+        }
+    }
+
+    fn child(&self, i: usize, node: Self::Node) -> Self::Node {
+        match node {
+            QBVHNodeId::Internal(node_id) => {
+                let node = &self.nodes[node_id];
+                assert!(i < node.num_children, "QBVH child index out of bounds.");
+                node.children[i]
+            }
+            QBVHNodeId::Leaf(_) => panic!("QBVH child index out of bounds."),
+        }
+    }
+
+    fn content(&self, node: Self::Node) -> (&AABB<N>, Option<&T>) {
+        match node {
+            QBVHNodeId::Internal(i) => (&self.nodes[i].aabb, None),
+            QBVHNodeId::Leaf(i) => {
+                let leaf = &self.leaves[i];
+                (&leaf.aabb, Some(&leaf.data))
+            }
+        }
+    }
+}
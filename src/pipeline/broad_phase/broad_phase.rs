@@ -1,8 +1,15 @@
 use na::RealField;
 use std::any::Any;
+use std::cmp::Ordering;
 
-use crate::math::Point;
-use crate::query::{Ray, RayIntersection};
+use crate::bounding_volume::HasBoundingVolume;
+use crate::math::{Isometry, Point, Translation};
+use crate::partitioning::{BestFirstQueue, DBVTNodeId};
+use crate::query::{ConvexVolume, Ray, RayIntersection};
+use crate::shape::{Ball, Cuboid};
+
+// The bit set on the handles of the proxies of a `DBVTStaticLayer`.
+pub(crate) const STATIC_LAYER_BIT: usize = !(usize::MAX >> 1);
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct BroadPhaseProxyHandle(pub usize);
@@ -22,6 +29,45 @@ impl BroadPhaseProxyHandle {
     pub fn uid(&self) -> usize {
         self.0
     }
+
+    /// Checks if this handle identifies a proxy of a `DBVTStaticLayer`.
+    #[inline]
+    pub fn is_in_static_layer(&self) -> bool {
+        !self.is_invalid() && self.0 & STATIC_LAYER_BIT != 0
+    }
+}
+
+/// Reusable buffers for the queries of a broad phase.
+///
+/// Giving the same workspace to several `visit_interferences_with_*` or
+/// `first_interference_with_ray_with_workspace` queries of a broad phase avoids any allocation
+/// once its buffers are large enough.
+///
+/// Only the broad phases overriding those queries actually use the workspace, e.g.,
+/// `DBVTBroadPhase`. The default implementations of the `BroadPhase` trait ignore it and allocate
+/// the same temporary buffers as the queries without workspace.
+#[derive(Clone)]
+pub struct BroadPhaseWorkspace<N> {
+    pub(crate) proxies: Vec<BroadPhaseProxyHandle>,
+    pub(crate) stack: Vec<DBVTNodeId>,
+    pub(crate) queue: BestFirstQueue<N, DBVTNodeId>,
+}
+
+impl<N: PartialOrd> BroadPhaseWorkspace<N> {
+    /// Creates a workspace with empty buffers.
+    pub fn new() -> Self {
+        BroadPhaseWorkspace {
+            proxies: Vec::new(),
+            stack: Vec::new(),
+            queue: BestFirstQueue::new(),
+        }
+    }
+}
+
+impl<N: PartialOrd> Default for BroadPhaseWorkspace<N> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// Proximity handling for BroadPhase updates.
@@ -77,10 +123,142 @@ pub trait BroadPhase<N: RealField, BV, T>: Any + Sync + Send {
     /// Collects every object which might contain a given point.
     fn interferences_with_point<'a>(&'a self, point: &Point<N>, out: &mut Vec<&'a T>);
 
+    /// Calls `f` on every object which might intersect a given bounding volume.
+    ///
+    /// The temporary buffers of the query are taken from `workspace` instead of being allocated.
+    /// The default implementation ignores `workspace` and allocates a temporary `Vec`.
+    fn visit_interferences_with_bounding_volume(
+        &self,
+        bv: &BV,
+        workspace: &mut BroadPhaseWorkspace<N>,
+        f: &mut dyn FnMut(&T),
+    ) {
+        let _ = workspace;
+        let mut out = Vec::new();
+        self.interferences_with_bounding_volume(bv, &mut out);
+        out.into_iter().for_each(f)
+    }
+
+    /// Calls `f` on every object which might intersect a given ray.
+    ///
+    /// The temporary buffers of the query are taken from `workspace` instead of being allocated.
+    /// The default implementation ignores `workspace` and allocates a temporary `Vec`.
+    fn visit_interferences_with_ray(
+        &self,
+        ray: &Ray<N>,
+        max_toi: N,
+        workspace: &mut BroadPhaseWorkspace<N>,
+        f: &mut dyn FnMut(&T),
+    ) {
+        let _ = workspace;
+        let mut out = Vec::new();
+        self.interferences_with_ray(ray, max_toi, &mut out);
+        out.into_iter().for_each(f)
+    }
+
+    /// Calls `f` on every object which might contain a given point.
+    ///
+    /// The temporary buffers of the query are taken from `workspace` instead of being allocated.
+    /// The default implementation ignores `workspace` and allocates a temporary `Vec`.
+    fn visit_interferences_with_point(
+        &self,
+        point: &Point<N>,
+        workspace: &mut BroadPhaseWorkspace<N>,
+        f: &mut dyn FnMut(&T),
+    ) {
+        let _ = workspace;
+        let mut out = Vec::new();
+        self.interferences_with_point(point, &mut out);
+        out.into_iter().for_each(f)
+    }
+
+    /// Collects every object which might intersect a given convex volume, e.g., a camera frustum.
+    ///
+    /// The default implementation collects every object which might intersect the bounding volume
+    /// of the AABB of `volume`. See `DBVTBroadPhase::culled_interferences_with_convex_volume` for a
+    /// tighter culling.
+    fn interferences_with_convex_volume<'a>(
+        &'a self,
+        volume: &ConvexVolume<N>,
+        out: &mut Vec<&'a T>,
+    ) where
+        Cuboid<N>: HasBoundingVolume<N, BV>,
+    {
+        let aabb = volume.aabb();
+        let cuboid = Cuboid::new(aabb.half_extents());
+        let bv = cuboid.bounding_volume(&Isometry::from_parts(
+            Translation::from(aabb.center().coords),
+            na::one(),
+        ));
+        self.interferences_with_bounding_volume(&bv, out)
+    }
+
     fn first_interference_with_ray<'a, 'b>(
         &'a self,
         ray: &'b Ray<N>,
         max_toi: N,
         cost_fn: &'a dyn Fn(T, &'b Ray<N>, N) -> Option<(T, RayIntersection<N>)>,
     ) -> Option<(T, RayIntersection<N>)>;
+
+    /// Returns the first object that interferes with a ray, taking the temporary buffers of the
+    /// query from `workspace` instead of allocating them.
+    ///
+    /// The default implementation ignores `workspace` and calls `first_interference_with_ray`.
+    #[allow(clippy::type_complexity)]
+    fn first_interference_with_ray_with_workspace<'a, 'b>(
+        &'a self,
+        ray: &'b Ray<N>,
+        max_toi: N,
+        cost_fn: &'a dyn Fn(T, &'b Ray<N>, N) -> Option<(T, RayIntersection<N>)>,
+        workspace: &mut BroadPhaseWorkspace<N>,
+    ) -> Option<(T, RayIntersection<N>)> {
+        let _ = workspace;
+        self.first_interference_with_ray(ray, max_toi, cost_fn)
+    }
+
+    /// Finds the `k` objects with the smallest costs among the objects closer than `max_dist` to the
+    /// ball with the given `center` and `radius`.
+    ///
+    /// The cost of each object is computed by `cost_fn` and must be greater than or equal to the
+    /// distance between the ball and the object's bounding volume. Objects for which `cost_fn` returns
+    /// `None`, or a cost greater than `max_dist`, are ignored. The result is sorted by increasing cost.
+    ///
+    /// The default implementation evaluates `cost_fn` on every object intersecting the bounding
+    /// volume of the ball enlarged by `max_dist`.
+    fn k_nearest_with_ball<'a>(
+        &'a self,
+        center: &Point<N>,
+        radius: N,
+        max_dist: N,
+        k: usize,
+        cost_fn: &'a dyn Fn(&T) -> Option<N>,
+    ) -> Vec<(T, N)>
+    where
+        T: Clone,
+        Ball<N>: HasBoundingVolume<N, BV>,
+    {
+        let ball = Ball::new(radius + max_dist);
+        let bv = ball.bounding_volume(&Isometry::from_parts(
+            Translation::from(center.coords),
+            na::one(),
+        ));
+        let mut candidates = Vec::new();
+        self.interferences_with_bounding_volume(&bv, &mut candidates);
+
+        let mut res: Vec<_> = candidates
+            .into_iter()
+            .filter_map(|data| {
+                let cost = cost_fn(data)?;
+                if cost <= max_dist {
+                    Some((data.clone(), cost))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        res.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
+        res.truncate(k);
+        res
+    }
 }
@@ -1,25 +1,30 @@
 use crate::bounding_volume::BoundingVolume;
 use crate::math::Point;
 use crate::partitioning::{DBVTLeaf, DBVTLeafId, BVH, DBVT};
+use crate::pipeline::broad_phase::broad_phase::STATIC_LAYER_BIT;
 use crate::pipeline::broad_phase::{
-    BroadPhase, BroadPhaseInterferenceHandler, BroadPhaseProxyHandle,
+    BroadPhase, BroadPhaseInterferenceHandler, BroadPhaseProxyHandle, BroadPhaseWorkspace,
 };
 use crate::query::visitors::{
-    BoundingVolumeInterferencesCollector, PointInterferencesCollector, RayInterferencesCollector,
+    BoundingVolumeInterferencesCollector, ConvexVolumeInterferencesCollector,
+    DistanceCostFnVisitor, PointInterferencesCollector, RayInterferencesCollector,
     RayIntersectionCostFnVisitor,
 };
-use crate::query::{PointQuery, Ray, RayCast, RayIntersection};
+use crate::query::{ConvexVolume, ConvexVolumeCulling, PointQuery, Ray, RayCast, RayIntersection};
 use crate::utils::{DeterministicState, SortedPair};
 use na::RealField;
 use slab::Slab;
 use std::any::Any;
+use std::cmp::Ordering;
 use std::collections::hash_map::Entry;
 use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 enum ProxyStatus {
     OnStaticTree(DBVTLeafId),
     OnDynamicTree(DBVTLeafId, usize),
+    OnStaticLayer(DBVTLeafId),
     // The usize is the location of the corresponding on proxies_to_update
     Detached(Option<usize>),
     Deleted,
@@ -50,16 +55,113 @@ impl<T> DBVTBroadPhaseProxy<T> {
 
 const DEACTIVATION_THRESHOLD: usize = 100;
 
+/// A read-only set of static proxies that can be shared among several `DBVTBroadPhase`.
+///
+/// Every broad phase created by `DBVTBroadPhase::with_static_layer` reports the interferences
+/// between its own proxies and the proxies of this layer, without duplicating its tree. The
+/// proxies of a static layer never move, and the interferences between two of them are never
+/// reported.
+pub struct DBVTStaticLayer<N: RealField, BV, T> {
+    tree: DBVT<N, BroadPhaseProxyHandle, BV>,
+    proxies: Vec<DBVTBroadPhaseProxy<T>>,
+}
+
+impl<N, BV, T> DBVTStaticLayer<N, BV, T>
+where
+    N: RealField,
+    BV: 'static + BoundingVolume<N> + Clone,
+{
+    /// Creates an empty static layer.
+    pub fn new() -> DBVTStaticLayer<N, BV, T> {
+        DBVTStaticLayer {
+            tree: DBVT::new(),
+            proxies: Vec::new(),
+        }
+    }
+
+    /// Adds to this layer a static proxy with the given bounding volume and data.
+    ///
+    /// The returned handle identifies this proxy in every broad phase sharing this layer.
+    pub fn create_proxy(&mut self, bv: BV, data: T) -> BroadPhaseProxyHandle {
+        let handle = BroadPhaseProxyHandle(self.proxies.len() | STATIC_LAYER_BIT);
+        let leaf = self.tree.insert(DBVTLeaf::new(bv, handle));
+        self.proxies.push(DBVTBroadPhaseProxy {
+            data,
+            status: ProxyStatus::OnStaticLayer(leaf),
+            updated: false,
+        });
+        handle
+    }
+
+    /// Retrieves the bounding volume and data associated to the given proxy of this layer.
+    pub fn proxy(&self, handle: BroadPhaseProxyHandle) -> Option<(&BV, &T)> {
+        let proxy = self.get(handle)?;
+        match proxy.status {
+            ProxyStatus::OnStaticLayer(id) => {
+                Some((&self.tree.get(id)?.bounding_volume, &proxy.data))
+            }
+            _ => None,
+        }
+    }
+
+    /// The number of proxies of this layer.
+    pub fn len(&self) -> usize {
+        self.proxies.len()
+    }
+
+    /// Returns `true` if this layer does not contain any proxy.
+    pub fn is_empty(&self) -> bool {
+        self.proxies.is_empty()
+    }
+}
+
+impl<N: RealField, BV, T> DBVTStaticLayer<N, BV, T> {
+    fn get(&self, handle: BroadPhaseProxyHandle) -> Option<&DBVTBroadPhaseProxy<T>> {
+        if handle.is_in_static_layer() {
+            self.proxies.get(handle.uid() & !STATIC_LAYER_BIT)
+        } else {
+            None
+        }
+    }
+}
+
+// Retrieves the proxy identified by `handle`, which is either one of `proxies` or a proxy of the
+// static layer.
+fn get_proxy<'a, N: RealField, BV, T>(
+    proxies: &'a Slab<DBVTBroadPhaseProxy<T>>,
+    static_layer: Option<&'a DBVTStaticLayer<N, BV, T>>,
+    handle: BroadPhaseProxyHandle,
+) -> Option<&'a DBVTBroadPhaseProxy<T>> {
+    if handle.is_in_static_layer() {
+        static_layer?.get(handle)
+    } else {
+        proxies.get(handle.uid())
+    }
+}
+
+impl<N, BV, T> Default for DBVTStaticLayer<N, BV, T>
+where
+    N: RealField,
+    BV: 'static + BoundingVolume<N> + Clone,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Broad phase based on a Dynamic Bounding Volume Tree.
 ///
 /// It uses two separate trees: one for static objects and which is never updated, and one for
-/// moving objects.
+/// moving objects. It may also share a third read-only tree of static objects, a
+/// `DBVTStaticLayer`, with other broad phases.
 pub struct DBVTBroadPhase<N: RealField, BV, T> {
     proxies: Slab<DBVTBroadPhaseProxy<T>>,
     // DBVT for moving objects.
     tree: DBVT<N, BroadPhaseProxyHandle, BV>,
     // DBVT for static objects.
     stree: DBVT<N, BroadPhaseProxyHandle, BV>,
+    // Static objects shared with other broad phases.
+    static_layer: Option<Arc<DBVTStaticLayer<N, BV, T>>>,
     // Pairs detected.
     pairs: HashMap<SortedPair<BroadPhaseProxyHandle>, bool, DeterministicState>,
     // The margin added to each bounding volume.
@@ -83,6 +185,7 @@ where
             proxies: Slab::new(),
             tree: DBVT::new(),
             stree: DBVT::new(),
+            static_layer: None,
             pairs: HashMap::with_hasher(DeterministicState::new()),
             purge_all: false,
             collector: Vec::new(),
@@ -92,41 +195,76 @@ where
         }
     }
 
+    /// Creates a new broad phase that also reports the interferences with the proxies of the
+    /// given static layer.
+    ///
+    /// The static layer is not copied, so it can be shared by many broad phases. Its proxies are
+    /// identified by the handles returned by `DBVTStaticLayer::create_proxy` and cannot be
+    /// modified nor removed from this broad phase.
+    pub fn with_static_layer(
+        margin: N,
+        static_layer: Arc<DBVTStaticLayer<N, BV, T>>,
+    ) -> DBVTBroadPhase<N, BV, T> {
+        let mut result = Self::new(margin);
+        result.static_layer = Some(static_layer);
+        result
+    }
+
+    /// The static layer shared by this broad phase, if any.
+    pub fn static_layer(&self) -> Option<&Arc<DBVTStaticLayer<N, BV, T>>> {
+        self.static_layer.as_ref()
+    }
+
     /// Number of interferences detected by this broad phase.
     #[inline]
     pub fn num_interferences(&self) -> usize {
         self.pairs.len()
     }
 
+    /// The surface area heuristic cost of the tree containing the moving objects.
+    ///
+    /// See `BVH::sah_cost` for details.
+    pub fn dynamic_tree_sah_cost(&self) -> N {
+        self.tree.sah_cost()
+    }
+
+    /// The surface area heuristic cost of the tree containing the static objects.
+    ///
+    /// See `BVH::sah_cost` for details.
+    pub fn static_tree_sah_cost(&self) -> N {
+        self.stree.sah_cost()
+    }
+
     fn purge_some_contact_pairs(&mut self, handler: &mut dyn BroadPhaseInterferenceHandler<T>) {
         let purge_all = self.purge_all;
         let proxies = &self.proxies;
         let stree = &self.stree;
         let tree = &self.tree;
+        let static_layer = self.static_layer.as_deref();
         self.pairs.retain(|pair, up_to_date| {
             let mut retain = true;
 
             if purge_all || !*up_to_date {
                 *up_to_date = true;
 
-                let proxy1 = proxies
-                    .get(pair.0.uid())
+                let proxy1 = get_proxy(proxies, static_layer, pair.0)
                     .expect("DBVT broad phase: internal error.");
-                let proxy2 = proxies
-                    .get(pair.1.uid())
+                let proxy2 = get_proxy(proxies, static_layer, pair.1)
                     .expect("DBVT broad phase: internal error.");
 
                 if purge_all || proxy1.updated || proxy2.updated {
                     if handler.is_interference_allowed(&proxy1.data, &proxy2.data) {
-                        let l1 = match proxy1.status {
-                            ProxyStatus::OnStaticTree(leaf) => &stree[leaf],
-                            ProxyStatus::OnDynamicTree(leaf, _) => &tree[leaf],
+                        let l1 = match (proxy1.status, static_layer) {
+                            (ProxyStatus::OnStaticTree(leaf), _) => &stree[leaf],
+                            (ProxyStatus::OnDynamicTree(leaf, _), _) => &tree[leaf],
+                            (ProxyStatus::OnStaticLayer(leaf), Some(layer)) => &layer.tree[leaf],
                             _ => panic!("DBVT broad phase: internal error."),
                         };
 
-                        let l2 = match proxy2.status {
-                            ProxyStatus::OnStaticTree(leaf) => &stree[leaf],
-                            ProxyStatus::OnDynamicTree(leaf, _) => &tree[leaf],
+                        let l2 = match (proxy2.status, static_layer) {
+                            (ProxyStatus::OnStaticTree(leaf), _) => &stree[leaf],
+                            (ProxyStatus::OnDynamicTree(leaf, _), _) => &tree[leaf],
+                            (ProxyStatus::OnStaticLayer(leaf), Some(layer)) => &layer.tree[leaf],
                             _ => panic!("DBVT broad phase: internal error."),
                         };
 
@@ -146,6 +284,12 @@ where
         });
     }
 
+    fn proxy_data(&self, handle: BroadPhaseProxyHandle) -> &T {
+        &get_proxy(&self.proxies, self.static_layer.as_deref(), handle)
+            .expect("DBVT broad phase: internal error.")
+            .data
+    }
+
     fn update_activation_states(&mut self) {
         /*
          * Update activation states.
@@ -165,6 +309,39 @@ where
     }
 }
 
+impl<N, BV, T> DBVTBroadPhase<N, BV, T>
+where
+    N: RealField,
+    BV: 'static + BoundingVolume<N> + ConvexVolumeCulling<N> + Clone,
+{
+    /// Collects every object which might intersect a given convex volume, e.g., a camera frustum.
+    ///
+    /// Unlike `BroadPhase::interferences_with_convex_volume`, this culls the nodes of the trees
+    /// against the planes of `volume` instead of its AABB, so fewer objects are collected.
+    pub fn culled_interferences_with_convex_volume<'a>(
+        &'a self,
+        volume: &ConvexVolume<N>,
+        out: &mut Vec<&'a T>,
+    ) {
+        let mut collector = Vec::new();
+
+        {
+            let mut visitor = ConvexVolumeInterferencesCollector::new(volume, &mut collector);
+
+            visitor.collect(&self.tree);
+            visitor.collect(&self.stree);
+
+            if let Some(layer) = &self.static_layer {
+                visitor.collect(&layer.tree);
+            }
+        }
+
+        for l in collector.into_iter() {
+            out.push(self.proxy_data(l))
+        }
+    }
+}
+
 impl<N, BV, T> BroadPhase<N, BV, T> for DBVTBroadPhase<N, BV, T>
 where
     N: RealField,
@@ -201,6 +378,9 @@ where
                     ProxyStatus::Deleted => {
                         panic!("DBVT broad phase internal error: the proxy was deleted.")
                     }
+                    ProxyStatus::OnStaticLayer(_) => {
+                        panic!("DBVT broad phase internal error: the proxy is static.")
+                    }
                 }
 
                 proxy.updated = true;
@@ -226,11 +406,17 @@ where
 
                     self.tree.visit(&mut visitor);
                     self.stree.visit(&mut visitor);
+
+                    if let Some(layer) = &self.static_layer {
+                        layer.tree.visit(&mut visitor);
+                    }
                 }
 
                 // Event generation.
                 for proxy_key2 in self.collector.iter() {
-                    let proxy2 = &self.proxies[proxy_key2.uid()];
+                    let proxy2 =
+                        get_proxy(&self.proxies, self.static_layer.as_deref(), *proxy_key2)
+                            .expect("DBVT broad phase: internal error.");
 
                     if handler.is_interference_allowed(&proxy1.data, &proxy2.data) {
                         match self.pairs.entry(SortedPair::new(leaf.data, *proxy_key2)) {
@@ -260,6 +446,10 @@ where
 
     /// Retrieves the bounding volume and data associated to the given proxy.
     fn proxy(&self, handle: BroadPhaseProxyHandle) -> Option<(&BV, &T)> {
+        if handle.is_in_static_layer() {
+            return self.static_layer.as_ref()?.proxy(handle);
+        }
+
         let proxy = self.proxies.get(handle.uid())?;
         match proxy.status {
             ProxyStatus::OnDynamicTree(id, _) => {
@@ -300,12 +490,11 @@ where
 
         {
             let proxies = &self.proxies;
+            let static_layer = self.static_layer.as_deref();
             self.pairs.retain(|pair, _| {
-                let proxy1 = proxies
-                    .get(pair.0.uid())
+                let proxy1 = get_proxy(proxies, static_layer, pair.0)
                     .expect("DBVT broad phase: internal error.");
-                let proxy2 = proxies
-                    .get(pair.1.uid())
+                let proxy2 = get_proxy(proxies, static_layer, pair.1)
                     .expect("DBVT broad phase: internal error.");
 
                 if proxy1.status == ProxyStatus::Deleted || proxy2.status == ProxyStatus::Deleted {
@@ -332,7 +521,7 @@ where
                     !self.tree[leaf].bounding_volume.contains(&bounding_volume)
                 }
                 ProxyStatus::Detached(_) => true,
-                ProxyStatus::Deleted => {
+                ProxyStatus::Deleted | ProxyStatus::OnStaticLayer(_) => {
                     panic!("DBVT broad phase: internal error, proxy not found.")
                 }
             };
@@ -352,7 +541,7 @@ where
                 ProxyStatus::OnStaticTree(leaf) => self.stree[leaf].bounding_volume.clone(),
                 ProxyStatus::OnDynamicTree(leaf, _) => self.tree[leaf].bounding_volume.clone(),
                 ProxyStatus::Detached(_) => return,
-                ProxyStatus::Deleted => {
+                ProxyStatus::Deleted | ProxyStatus::OnStaticLayer(_) => {
                     panic!("DBVT broad phase: internal error, proxy not found.")
                 }
             };
@@ -372,7 +561,7 @@ where
                     bv = self.tree[leaf].bounding_volume.clone();
                 }
                 ProxyStatus::Detached(_) => continue,
-                ProxyStatus::Deleted => {
+                ProxyStatus::Deleted | ProxyStatus::OnStaticLayer(_) => {
                     panic!("DBVT broad phase: internal error, proxy not found.")
                 }
             }
@@ -392,10 +581,14 @@ where
 
             self.tree.visit(&mut visitor);
             self.stree.visit(&mut visitor);
+
+            if let Some(layer) = &self.static_layer {
+                layer.tree.visit(&mut visitor);
+            }
         }
 
         for l in collector.into_iter() {
-            out.push(&self.proxies[l.uid()].data)
+            out.push(self.proxy_data(l))
         }
     }
 
@@ -407,10 +600,14 @@ where
 
             self.tree.visit(&mut visitor);
             self.stree.visit(&mut visitor);
+
+            if let Some(layer) = &self.static_layer {
+                layer.tree.visit(&mut visitor);
+            }
         }
 
         for l in collector.into_iter() {
-            out.push(&self.proxies[l.uid()].data)
+            out.push(self.proxy_data(l))
         }
     }
 
@@ -422,10 +619,99 @@ where
 
             self.tree.visit(&mut visitor);
             self.stree.visit(&mut visitor);
+
+            if let Some(layer) = &self.static_layer {
+                layer.tree.visit(&mut visitor);
+            }
         }
 
         for l in collector.into_iter() {
-            out.push(&self.proxies[l.uid()].data)
+            out.push(self.proxy_data(l))
+        }
+    }
+
+    fn visit_interferences_with_bounding_volume(
+        &self,
+        bv: &BV,
+        workspace: &mut BroadPhaseWorkspace<N>,
+        f: &mut dyn FnMut(&T),
+    ) {
+        workspace.proxies.clear();
+
+        {
+            let mut visitor = BoundingVolumeInterferencesCollector::new(bv, &mut workspace.proxies);
+
+            self.tree
+                .visit_with_stack(&mut visitor, &mut workspace.stack);
+            self.stree
+                .visit_with_stack(&mut visitor, &mut workspace.stack);
+
+            if let Some(layer) = &self.static_layer {
+                layer
+                    .tree
+                    .visit_with_stack(&mut visitor, &mut workspace.stack);
+            }
+        }
+
+        for l in &workspace.proxies {
+            f(self.proxy_data(*l))
+        }
+    }
+
+    fn visit_interferences_with_ray(
+        &self,
+        ray: &Ray<N>,
+        max_toi: N,
+        workspace: &mut BroadPhaseWorkspace<N>,
+        f: &mut dyn FnMut(&T),
+    ) {
+        workspace.proxies.clear();
+
+        {
+            let mut visitor = RayInterferencesCollector::new(ray, max_toi, &mut workspace.proxies);
+
+            self.tree
+                .visit_with_stack(&mut visitor, &mut workspace.stack);
+            self.stree
+                .visit_with_stack(&mut visitor, &mut workspace.stack);
+
+            if let Some(layer) = &self.static_layer {
+                layer
+                    .tree
+                    .visit_with_stack(&mut visitor, &mut workspace.stack);
+            }
+        }
+
+        for l in &workspace.proxies {
+            f(self.proxy_data(*l))
+        }
+    }
+
+    fn visit_interferences_with_point(
+        &self,
+        point: &Point<N>,
+        workspace: &mut BroadPhaseWorkspace<N>,
+        f: &mut dyn FnMut(&T),
+    ) {
+        workspace.proxies.clear();
+
+        {
+            let mut visitor = PointInterferencesCollector::new(point, &mut workspace.proxies);
+
+            self.tree
+                .visit_with_stack(&mut visitor, &mut workspace.stack);
+            self.stree
+                .visit_with_stack(&mut visitor, &mut workspace.stack);
+
+            if let Some(layer) = &self.static_layer {
+                layer
+                    .tree
+                    .visit_with_stack(&mut visitor, &mut workspace.stack);
+            }
+        }
+
+        for l in &workspace.proxies {
+            f(self.proxy_data(*l))
         }
     }
 
@@ -435,20 +721,55 @@ where
         ray: &'b Ray<N>,
         max_toi: N,
         cost_fn: &'a dyn Fn(T, &'b Ray<N>, N) -> Option<(T, RayIntersection<N>)>,
+    ) -> Option<(T, RayIntersection<N>)> {
+        self.first_interference_with_ray_with_workspace(
+            ray,
+            max_toi,
+            cost_fn,
+            &mut BroadPhaseWorkspace::new(),
+        )
+    }
+
+    fn first_interference_with_ray_with_workspace<'a, 'b>(
+        &'a self,
+        ray: &'b Ray<N>,
+        max_toi: N,
+        cost_fn: &'a dyn Fn(T, &'b Ray<N>, N) -> Option<(T, RayIntersection<N>)>,
+        workspace: &mut BroadPhaseWorkspace<N>,
     ) -> Option<(T, RayIntersection<N>)> {
         let res = {
             let mut visitor =
                 RayIntersectionCostFnVisitor::<'a, 'b, N, T, BV>::new(ray, max_toi, self, cost_fn);
 
-            let dynamic_hit = self.tree.best_first_search(&mut visitor);
-            let static_hit = self.stree.best_first_search(&mut visitor);
+            let dynamic_hit = self
+                .tree
+                .best_first_search_with_queue(&mut visitor, &mut workspace.queue);
+            let static_hit = self
+                .stree
+                .best_first_search_with_queue(&mut visitor, &mut workspace.queue);
 
             // The static hit must be better than the dynamic hit as it uses the
             // same visitor so give it priority
-            if static_hit.is_some() {
+            let hit = if static_hit.is_some() {
                 static_hit
             } else {
                 dynamic_hit
+            };
+            let layer_hit = self.static_layer.as_ref().and_then(|layer| {
+                layer
+                    .tree
+                    .best_first_search_with_queue(&mut visitor, &mut workspace.queue)
+            });
+
+            match (hit, layer_hit) {
+                (Some(hit), Some(layer_hit)) => {
+                    if (layer_hit.1).1.toi < (hit.1).1.toi {
+                        Some(layer_hit)
+                    } else {
+                        Some(hit)
+                    }
+                }
+                (hit, layer_hit) => hit.or(layer_hit),
             }
         };
 
@@ -458,4 +779,33 @@ where
             None
         }
     }
+
+    fn k_nearest_with_ball<'a>(
+        &'a self,
+        center: &Point<N>,
+        radius: N,
+        max_dist: N,
+        k: usize,
+        cost_fn: &'a dyn Fn(&T) -> Option<N>,
+    ) -> Vec<(T, N)> {
+        let mut visitor =
+            DistanceCostFnVisitor::<N, T, BV>::new(center, radius, max_dist, self, cost_fn);
+
+        let mut res: Vec<_> = self
+            .tree
+            .k_best_first_search(k, &mut visitor)
+            .into_iter()
+            .chain(self.stree.k_best_first_search(k, &mut visitor))
+            .chain(
+                self.static_layer
+                    .iter()
+                    .flat_map(|layer| layer.tree.k_best_first_search(k, &mut visitor)),
+            )
+            .map(|(_node, res)| res)
+            .collect();
+
+        res.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
+        res.truncate(k);
+        res
+    }
 }
@@ -1,9 +1,11 @@
 //! Broad phases.
 
 #[doc(inline)]
-pub use self::broad_phase::{BroadPhase, BroadPhaseInterferenceHandler, BroadPhaseProxyHandle};
+pub use self::broad_phase::{
+    BroadPhase, BroadPhaseInterferenceHandler, BroadPhaseProxyHandle, BroadPhaseWorkspace,
+};
 pub use self::broad_phase_pair_filter::BroadPhasePairFilter;
-pub use self::dbvt_broad_phase::DBVTBroadPhase;
+pub use self::dbvt_broad_phase::{DBVTBroadPhase, DBVTStaticLayer};
 
 #[doc(hidden)]
 pub mod broad_phase;
@@ -0,0 +1,130 @@
+use na::RealField;
+
+use crate::bounding_volume::AABB;
+use crate::interpolation::{InterpolatedRigidMotion, RigidMotion};
+use crate::pipeline::broad_phase::{BroadPhase, BroadPhasePairFilter};
+use crate::pipeline::object::{CollisionGroupsPairFilter, CollisionObjectRef, CollisionObjectSet};
+use crate::query::{TOIDispatcher, TOI};
+
+/// The earliest impact found by the continuous collision detection stage for a collision object.
+#[derive(Clone, Debug)]
+pub struct CCDImpact<N: RealField, Handle> {
+    /// The collision object with continuous collision detection enabled.
+    pub collider1: Handle,
+    /// The first collision object hit by `collider1`.
+    pub collider2: Handle,
+    /// The time of impact between both collision objects.
+    ///
+    /// The time `toi.toi` lies in `[0, 1]` where `0` is the current position of the collision objects and `1`
+    /// is their predicted position.
+    pub toi: TOI<N>,
+}
+
+/// Performs the continuous collision detection stage.
+///
+/// For each collision object with continuous collision detection enabled and a predicted position,
+/// this computes the earliest time of impact between this collision object and every other collision
+/// object with a `GeometricQueryType::Contacts(..)` query type whose broad-phase bounding volume
+/// intersects its swept AABB. The motion of every collision object is interpolated between its current
+/// position and its predicted position (if any). The earliest impact found for each collision object
+/// is pushed to `impacts`. A `pair_filters` can be provided to filter out pairs of object that should not
+/// be considered.
+///
+/// Pairs of shapes for which `toi_dispatcher` does not support the computation of nonlinear times of
+/// impact are not checked by this stage: their collisions are only detected by the narrow phase, at
+/// the positions of the collision objects.
+pub fn perform_ccd<N, Objects>(
+    objects: &Objects,
+    broad_phase: &(impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    toi_dispatcher: &dyn TOIDispatcher<N>,
+    pair_filters: Option<
+        &(impl BroadPhasePairFilter<N, Objects::CollisionObject, Objects::CollisionObjectHandle>
+              + ?Sized),
+    >,
+    impacts: &mut Vec<CCDImpact<N, Objects::CollisionObjectHandle>>,
+) where
+    N: RealField,
+    Objects: CollisionObjectSet<N>,
+{
+    let filter_by_groups = CollisionGroupsPairFilter;
+    let mut candidates = Vec::new();
+
+    objects.foreach(|handle1, co1| {
+        if !co1.is_ccd_enabled() || !co1.query_type().is_contacts_query() {
+            return;
+        }
+
+        let predicted_pos1 = try_ret!(co1.predicted_position());
+        let motion1 = InterpolatedRigidMotion::new(*co1.position(), *predicted_pos1);
+        let swept_aabb = co1.compute_swept_aabb();
+        let mut earliest: Option<CCDImpact<N, Objects::CollisionObjectHandle>> = None;
+
+        candidates.clear();
+        broad_phase.interferences_with_bounding_volume(&swept_aabb, &mut candidates);
+
+        for handle2 in candidates.drain(..) {
+            let handle2 = *handle2;
+
+            if handle2 == handle1 {
+                continue;
+            }
+
+            let co2 = match objects.collision_object(handle2) {
+                Some(co2) => co2,
+                None => continue,
+            };
+
+            if !co2.query_type().is_contacts_query()
+                || !filter_by_groups.is_pair_valid(co1, co2, handle1, handle2)
+                || !pair_filters
+                    .map(|f| f.is_pair_valid(co1, co2, handle1, handle2))
+                    .unwrap_or(true)
+            {
+                continue;
+            }
+
+            let max_toi = earliest.as_ref().map(|e| e.toi.toi).unwrap_or(N::one());
+            let toi = match co2.predicted_position() {
+                Some(predicted_pos2) => {
+                    let motion2 = InterpolatedRigidMotion::new(*co2.position(), *predicted_pos2);
+                    nonlinear_toi(toi_dispatcher, &motion1, co1, &motion2, co2, max_toi)
+                }
+                None => nonlinear_toi(toi_dispatcher, &motion1, co1, co2.position(), co2, max_toi),
+            };
+
+            if let Some(toi) = toi {
+                earliest = Some(CCDImpact {
+                    collider1: handle1,
+                    collider2: handle2,
+                    toi,
+                });
+            }
+        }
+
+        if let Some(impact) = earliest {
+            impacts.push(impact);
+        }
+    });
+}
+
+fn nonlinear_toi<N: RealField>(
+    toi_dispatcher: &dyn TOIDispatcher<N>,
+    motion1: &dyn RigidMotion<N>,
+    co1: &impl CollisionObjectRef<N>,
+    motion2: &dyn RigidMotion<N>,
+    co2: &impl CollisionObjectRef<N>,
+    max_toi: N,
+) -> Option<TOI<N>> {
+    let toi = toi_dispatcher.nonlinear_time_of_impact(
+        toi_dispatcher,
+        motion1,
+        co1.shape(),
+        motion2,
+        co2.shape(),
+        max_toi,
+        N::zero(),
+    );
+
+    // Unsupported pairs are left to the narrow phase.
+    toi.ok().flatten()
+}
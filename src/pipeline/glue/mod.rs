@@ -1,16 +1,24 @@
 //! Glue code between each part of the collision-detection pipeline.
 
+pub use self::ccd::{perform_ccd, CCDImpact};
 pub use self::query::{
-    first_interference_with_ray, interferences_with_aabb, interferences_with_point,
-    interferences_with_ray, FirstInterferenceWithRay, InterferencesWithAABB,
-    InterferencesWithPoint, InterferencesWithRay,
+    closest_object_to_point, closest_object_to_shape, contacts_with_shape,
+    first_interference_with_ray, first_interference_with_ray_with_workspace,
+    interferences_with_aabb, interferences_with_convex_volume, interferences_with_point,
+    interferences_with_ray, interferences_with_shape, k_nearest_to_point, proximities_with_shape,
+    ContactsWithShape, FirstInterferenceWithRay, InterferencesWithAABB,
+    InterferencesWithConvexVolume, InterferencesWithPoint, InterferencesWithRay,
+    InterferencesWithShape, ProximitiesWithShape,
 };
 pub use setup::{
     create_proxies, default_broad_phase, default_interaction_graph, default_narrow_phase,
     remove_proxies,
 };
-pub use update::{perform_all_pipeline, perform_broad_phase, perform_narrow_phase};
+pub use update::{
+    collect_objects_to_wake_up, perform_all_pipeline, perform_broad_phase, perform_narrow_phase,
+};
 
+mod ccd;
 mod query;
 mod setup;
 mod update;
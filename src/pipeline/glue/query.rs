@@ -1,11 +1,16 @@
 use na::RealField;
 use std::vec::IntoIter;
 
-use crate::bounding_volume::AABB;
-use crate::math::Point;
-use crate::pipeline::broad_phase::BroadPhase;
-use crate::pipeline::object::{CollisionGroups, CollisionObjectRef, CollisionObjectSet};
-use crate::query::{PointQuery, Ray, RayCast, RayIntersection};
+use crate::bounding_volume::{BoundingVolume, AABB};
+use crate::math::{Isometry, Point};
+use crate::pipeline::broad_phase::{BroadPhase, BroadPhaseWorkspace};
+use crate::pipeline::narrow_phase::{ContactDispatcher, ProximityDispatcher};
+use crate::pipeline::object::{CollisionObjectRef, CollisionObjectSet, InteractionGroups};
+use crate::query::{
+    self, ContactManifold, ContactPrediction, ConvexVolume, ConvexVolumeCulling, CullingStatus,
+    PointProjection, PointQuery, Proximity, Ray, RayCast, RayIntersection,
+};
+use crate::shape::Shape;
 
 /// Returns an iterator yielding all the collision objects intersecting with the given ray.
 ///
@@ -15,7 +20,7 @@ pub fn interferences_with_ray<'a, 'b, N, Objects>(
     broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
     ray: &'b Ray<N>,
     max_toi: N,
-    groups: &'b CollisionGroups,
+    groups: &'b <Objects::CollisionObject as CollisionObjectRef<N>>::Groups,
 ) -> InterferencesWithRay<'a, 'b, N, Objects>
 where
     N: RealField,
@@ -38,7 +43,7 @@ pub struct InterferencesWithRay<'a, 'b, N: RealField, Objects: CollisionObjectSe
     ray: &'b Ray<N>,
     max_toi: N,
     objects: &'a Objects,
-    groups: &'b CollisionGroups,
+    groups: &'b <Objects::CollisionObject as CollisionObjectRef<N>>::Groups,
     handles: IntoIter<&'a Objects::CollisionObjectHandle>,
 }
 
@@ -55,11 +60,11 @@ where
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(handle) = self.handles.next() {
+        for handle in self.handles.by_ref() {
             if let Some(co) = self.objects.collision_object(*handle) {
                 if co.collision_groups().can_interact_with_groups(self.groups) {
                     let inter = co.shape().toi_and_normal_with_ray(
-                        &co.position(),
+                        co.position(),
                         self.ray,
                         self.max_toi,
                         true,
@@ -83,7 +88,7 @@ pub fn interferences_with_point<'a, 'b, N, Objects>(
     objects: &'a Objects,
     broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
     point: &'b Point<N>,
-    groups: &'b CollisionGroups,
+    groups: &'b <Objects::CollisionObject as CollisionObjectRef<N>>::Groups,
 ) -> InterferencesWithPoint<'a, 'b, N, Objects>
 where
     N: RealField,
@@ -104,7 +109,7 @@ where
 pub struct InterferencesWithPoint<'a, 'b, N: RealField, Objects: CollisionObjectSet<N>> {
     point: &'b Point<N>,
     objects: &'a Objects,
-    groups: &'b CollisionGroups,
+    groups: &'b <Objects::CollisionObject as CollisionObjectRef<N>>::Groups,
     handles: IntoIter<&'a Objects::CollisionObjectHandle>,
 }
 
@@ -117,10 +122,10 @@ where
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(handle) = self.handles.next() {
+        for handle in self.handles.by_ref() {
             if let Some(co) = self.objects.collision_object(*handle) {
                 if co.collision_groups().can_interact_with_groups(self.groups)
-                    && co.shape().contains_point(&co.position(), self.point)
+                    && co.shape().contains_point(co.position(), self.point)
                 {
                     return Some((*handle, co));
                 }
@@ -138,7 +143,7 @@ pub fn interferences_with_aabb<'a, 'b, N, Objects>(
     objects: &'a Objects,
     broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
     aabb: &AABB<N>,
-    groups: &'b CollisionGroups,
+    groups: &'b <Objects::CollisionObject as CollisionObjectRef<N>>::Groups,
 ) -> InterferencesWithAABB<'a, 'b, N, Objects>
 where
     N: RealField,
@@ -157,7 +162,7 @@ where
 /// Iterator through all the objects on the world which bounding volume intersects a specific AABB.
 pub struct InterferencesWithAABB<'a, 'b, N: RealField, Objects: CollisionObjectSet<N>> {
     objects: &'a Objects,
-    groups: &'b CollisionGroups,
+    groups: &'b <Objects::CollisionObject as CollisionObjectRef<N>>::Groups,
     handles: IntoIter<&'a Objects::CollisionObjectHandle>,
 }
 
@@ -168,7 +173,7 @@ impl<'a, 'b, N: RealField, Objects: CollisionObjectSet<N>> Iterator
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(handle) = self.handles.next() {
+        for handle in self.handles.by_ref() {
             if let Some(co) = self.objects.collision_object(*handle) {
                 if co.collision_groups().can_interact_with_groups(self.groups) {
                     return Some((*handle, co));
@@ -180,6 +185,62 @@ impl<'a, 'b, N: RealField, Objects: CollisionObjectSet<N>> Iterator
     }
 }
 
+/// Returns an iterator yielding all the collision objects with an AABB which may intersect the
+/// given convex volume.
+///
+/// The result will only include collision objects in a group that can interact with the given `groups`.
+pub fn interferences_with_convex_volume<'a, 'b, N, Objects>(
+    objects: &'a Objects,
+    broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    volume: &'b ConvexVolume<N>,
+    groups: &'b <Objects::CollisionObject as CollisionObjectRef<N>>::Groups,
+) -> InterferencesWithConvexVolume<'a, 'b, N, Objects>
+where
+    N: RealField,
+    Objects: CollisionObjectSet<N>,
+{
+    let mut handles = Vec::new();
+    broad_phase.interferences_with_convex_volume(volume, &mut handles);
+
+    InterferencesWithConvexVolume {
+        volume,
+        groups,
+        objects,
+        handles: handles.into_iter(),
+    }
+}
+
+/// Iterator through all the objects on the world which AABB may intersect a specific convex
+/// volume.
+pub struct InterferencesWithConvexVolume<'a, 'b, N: RealField, Objects: CollisionObjectSet<N>> {
+    volume: &'b ConvexVolume<N>,
+    objects: &'a Objects,
+    groups: &'b <Objects::CollisionObject as CollisionObjectRef<N>>::Groups,
+    handles: IntoIter<&'a Objects::CollisionObjectHandle>,
+}
+
+impl<'a, 'b, N: RealField, Objects: CollisionObjectSet<N>> Iterator
+    for InterferencesWithConvexVolume<'a, 'b, N, Objects>
+{
+    type Item = (Objects::CollisionObjectHandle, &'a Objects::CollisionObject);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        for handle in self.handles.by_ref() {
+            if let Some(co) = self.objects.collision_object(*handle) {
+                // The broad phase may report objects which AABB is outside of the volume.
+                if co.collision_groups().can_interact_with_groups(self.groups)
+                    && co.compute_aabb().culling_status(self.volume) != CullingStatus::Outside
+                {
+                    return Some((*handle, co));
+                }
+            }
+        }
+
+        None
+    }
+}
+
 /// Return structure for `first_interference_with_ray`
 ///
 /// Contains the handle of the closest object along the ray along with its
@@ -202,7 +263,34 @@ pub fn first_interference_with_ray<'a, 'b, N: RealField, Objects: CollisionObjec
     broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
     ray: &'b Ray<N>,
     max_toi: N,
-    groups: &'b CollisionGroups,
+    groups: &'b <Objects::CollisionObject as CollisionObjectRef<N>>::Groups,
+) -> Option<FirstInterferenceWithRay<'a, N, Objects>> {
+    first_interference_with_ray_with_workspace(
+        objects,
+        broad_phase,
+        ray,
+        max_toi,
+        groups,
+        &mut BroadPhaseWorkspace::new(),
+    )
+}
+
+/// Returns an the closest collision object intersecting with the given ray, taking the temporary
+/// buffers of the query from `workspace`.
+///
+/// The result will only include collision objects in a group that can interact with the given `groups`.
+pub fn first_interference_with_ray_with_workspace<
+    'a,
+    'b,
+    N: RealField,
+    Objects: CollisionObjectSet<N>,
+>(
+    objects: &'a Objects,
+    broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    ray: &'b Ray<N>,
+    max_toi: N,
+    groups: &'b <Objects::CollisionObject as CollisionObjectRef<N>>::Groups,
+    workspace: &mut BroadPhaseWorkspace<N>,
 ) -> Option<FirstInterferenceWithRay<'a, N, Objects>> {
     // Narrow phase
     let narrow_phase = move |handle: Objects::CollisionObjectHandle, ray: &Ray<N>, max_toi: N| {
@@ -210,7 +298,7 @@ pub fn first_interference_with_ray<'a, 'b, N: RealField, Objects: CollisionObjec
         if co.collision_groups().can_interact_with_groups(groups) {
             let inter = co
                 .shape()
-                .toi_and_normal_with_ray(&co.position(), ray, max_toi, true);
+                .toi_and_normal_with_ray(co.position(), ray, max_toi, true);
 
             inter.map(|inter| (handle, inter))
         } else {
@@ -218,7 +306,356 @@ pub fn first_interference_with_ray<'a, 'b, N: RealField, Objects: CollisionObjec
         }
     };
 
-    let (handle, inter) = broad_phase.first_interference_with_ray(ray, max_toi, &narrow_phase)?;
+    let (handle, inter) = broad_phase.first_interference_with_ray_with_workspace(
+        ray,
+        max_toi,
+        &narrow_phase,
+        workspace,
+    )?;
     let co = objects.collision_object(handle)?;
     Some(FirstInterferenceWithRay { handle, co, inter })
 }
+
+/// Returns an iterator yielding all the collision objects intersecting with the given shape.
+///
+/// The exact intersection tests are performed by the proximity detectors selected by the given
+/// `dispatcher`. Collision objects with a shape not supported by the `dispatcher` are ignored.
+/// The result will only include collision objects in a group that can interact with the given `groups`.
+pub fn interferences_with_shape<'a, 'b, N, Objects>(
+    objects: &'a Objects,
+    broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    dispatcher: &'a dyn ProximityDispatcher<N>,
+    isometry: &'b Isometry<N>,
+    shape: &'b dyn Shape<N>,
+    groups: &'b <Objects::CollisionObject as CollisionObjectRef<N>>::Groups,
+) -> InterferencesWithShape<'a, 'b, N, Objects>
+where
+    N: RealField,
+    Objects: CollisionObjectSet<N>,
+{
+    InterferencesWithShape {
+        proximities: proximities_with_shape(
+            objects,
+            broad_phase,
+            dispatcher,
+            isometry,
+            shape,
+            N::zero(),
+            groups,
+        ),
+    }
+}
+
+/// Iterator through all the objects on the world that intersect a specific shape.
+pub struct InterferencesWithShape<'a, 'b, N: RealField, Objects: CollisionObjectSet<N>> {
+    proximities: ProximitiesWithShape<'a, 'b, N, Objects>,
+}
+
+impl<'a, 'b, N: RealField, Objects: CollisionObjectSet<N>> Iterator
+    for InterferencesWithShape<'a, 'b, N, Objects>
+{
+    type Item = (Objects::CollisionObjectHandle, &'a Objects::CollisionObject);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        for (handle, co, prox) in self.proximities.by_ref() {
+            if prox == Proximity::Intersecting {
+                return Some((handle, co));
+            }
+        }
+
+        None
+    }
+}
+
+/// Returns an iterator yielding all the collision objects intersecting with the given shape, or
+/// separated from it by a distance smaller than `margin`.
+///
+/// The proximities are computed by the proximity detectors selected by the given `dispatcher`.
+/// Collision objects with a shape not supported by the `dispatcher` are ignored.
+/// The result will only include collision objects in a group that can interact with the given `groups`.
+pub fn proximities_with_shape<'a, 'b, N, Objects>(
+    objects: &'a Objects,
+    broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    dispatcher: &'a dyn ProximityDispatcher<N>,
+    isometry: &'b Isometry<N>,
+    shape: &'b dyn Shape<N>,
+    margin: N,
+    groups: &'b <Objects::CollisionObject as CollisionObjectRef<N>>::Groups,
+) -> ProximitiesWithShape<'a, 'b, N, Objects>
+where
+    N: RealField,
+    Objects: CollisionObjectSet<N>,
+{
+    let mut handles = Vec::new();
+    let aabb = shape.aabb(isometry).loosened(margin);
+    broad_phase.interferences_with_bounding_volume(&aabb, &mut handles);
+
+    ProximitiesWithShape {
+        dispatcher,
+        isometry,
+        shape,
+        margin,
+        groups,
+        objects,
+        handles: handles.into_iter(),
+    }
+}
+
+/// Iterator through all the objects on the world in close proximity with a specific shape.
+pub struct ProximitiesWithShape<'a, 'b, N: RealField, Objects: CollisionObjectSet<N>> {
+    dispatcher: &'a dyn ProximityDispatcher<N>,
+    isometry: &'b Isometry<N>,
+    shape: &'b dyn Shape<N>,
+    margin: N,
+    objects: &'a Objects,
+    groups: &'b <Objects::CollisionObject as CollisionObjectRef<N>>::Groups,
+    handles: IntoIter<&'a Objects::CollisionObjectHandle>,
+}
+
+impl<'a, 'b, N: RealField, Objects: CollisionObjectSet<N>> Iterator
+    for ProximitiesWithShape<'a, 'b, N, Objects>
+{
+    type Item = (
+        Objects::CollisionObjectHandle,
+        &'a Objects::CollisionObject,
+        Proximity,
+    );
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        for handle in self.handles.by_ref() {
+            if let Some(co) = self.objects.collision_object(*handle) {
+                if !co.collision_groups().can_interact_with_groups(self.groups) {
+                    continue;
+                }
+
+                let shape = co.shape();
+                let detector = self.dispatcher.get_proximity_algorithm(self.shape, shape);
+
+                if let Some(mut detector) = detector {
+                    let prox = detector.update(
+                        self.dispatcher,
+                        self.isometry,
+                        self.shape,
+                        co.position(),
+                        shape,
+                        self.margin,
+                    );
+
+                    match prox {
+                        Some(Proximity::Disjoint) | None => {}
+                        Some(prox) => return Some((*handle, co, prox)),
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Returns an iterator yielding the contacts between the given shape and all the collision objects
+/// closer to it than `prediction.linear()`.
+///
+/// The contacts are computed by the contact manifold generators selected by the given `dispatcher`,
+/// with the given shape as the first shape. Collision objects with a shape not supported by the
+/// `dispatcher` are ignored.
+/// The result will only include collision objects in a group that can interact with the given `groups`.
+pub fn contacts_with_shape<'a, 'b, N, Objects>(
+    objects: &'a Objects,
+    broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    dispatcher: &'a dyn ContactDispatcher<N>,
+    isometry: &'b Isometry<N>,
+    shape: &'b dyn Shape<N>,
+    prediction: &'b ContactPrediction<N>,
+    groups: &'b <Objects::CollisionObject as CollisionObjectRef<N>>::Groups,
+) -> ContactsWithShape<'a, 'b, N, Objects>
+where
+    N: RealField,
+    Objects: CollisionObjectSet<N>,
+{
+    let mut handles = Vec::new();
+    let aabb = shape.aabb(isometry).loosened(prediction.linear());
+    broad_phase.interferences_with_bounding_volume(&aabb, &mut handles);
+
+    ContactsWithShape {
+        dispatcher,
+        isometry,
+        shape,
+        prediction,
+        groups,
+        objects,
+        handles: handles.into_iter(),
+    }
+}
+
+/// Iterator through the contacts between a specific shape and the objects on the world.
+pub struct ContactsWithShape<'a, 'b, N: RealField, Objects: CollisionObjectSet<N>> {
+    dispatcher: &'a dyn ContactDispatcher<N>,
+    isometry: &'b Isometry<N>,
+    shape: &'b dyn Shape<N>,
+    prediction: &'b ContactPrediction<N>,
+    objects: &'a Objects,
+    groups: &'b <Objects::CollisionObject as CollisionObjectRef<N>>::Groups,
+    handles: IntoIter<&'a Objects::CollisionObjectHandle>,
+}
+
+impl<'a, 'b, N: RealField, Objects: CollisionObjectSet<N>> Iterator
+    for ContactsWithShape<'a, 'b, N, Objects>
+{
+    type Item = (
+        Objects::CollisionObjectHandle,
+        &'a Objects::CollisionObject,
+        ContactManifold<N>,
+    );
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        for handle in self.handles.by_ref() {
+            if let Some(co) = self.objects.collision_object(*handle) {
+                if !co.collision_groups().can_interact_with_groups(self.groups) {
+                    continue;
+                }
+
+                let shape = co.shape();
+                let generator = self.dispatcher.get_contact_algorithm(self.shape, shape);
+
+                if let Some(mut generator) = generator {
+                    let mut manifold = generator.init_manifold();
+                    let _ = generator.generate_contacts(
+                        self.dispatcher,
+                        self.isometry,
+                        self.shape,
+                        None,
+                        co.position(),
+                        shape,
+                        None,
+                        self.prediction,
+                        &mut manifold,
+                    );
+
+                    if manifold.len() != 0 {
+                        return Some((*handle, co, manifold));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Returns the collision object closest to the given point, if it is not further than `max_dist`.
+///
+/// The result will only include collision objects in a group that can interact with the given `groups`
+/// and with a shape supporting point queries.
+pub fn closest_object_to_point<'a, N: RealField, Objects: CollisionObjectSet<N>>(
+    objects: &'a Objects,
+    broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    point: &Point<N>,
+    max_dist: N,
+    groups: &<Objects::CollisionObject as CollisionObjectRef<N>>::Groups,
+) -> Option<(
+    Objects::CollisionObjectHandle,
+    &'a Objects::CollisionObject,
+    PointProjection<N>,
+)> {
+    k_nearest_to_point(objects, broad_phase, point, 1, max_dist, groups)
+        .into_iter()
+        .next()
+}
+
+/// Returns the `k` collision objects closest to the given point and not further than `max_dist`, sorted
+/// by increasing distance.
+///
+/// The result will only include collision objects in a group that can interact with the given `groups`
+/// and with a shape supporting point queries.
+pub fn k_nearest_to_point<'a, N: RealField, Objects: CollisionObjectSet<N>>(
+    objects: &'a Objects,
+    broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    point: &Point<N>,
+    k: usize,
+    max_dist: N,
+    groups: &<Objects::CollisionObject as CollisionObjectRef<N>>::Groups,
+) -> Vec<(
+    Objects::CollisionObjectHandle,
+    &'a Objects::CollisionObject,
+    PointProjection<N>,
+)> {
+    let project = |handle: &Objects::CollisionObjectHandle| {
+        let co = objects.collision_object(*handle)?;
+
+        if co.collision_groups().can_interact_with_groups(groups) {
+            let proj = co
+                .shape()
+                .as_point_query()?
+                .project_point(co.position(), point, true);
+            Some((co, proj))
+        } else {
+            None
+        }
+    };
+    let cost_fn = |handle: &Objects::CollisionObjectHandle| {
+        let (_, proj) = project(handle)?;
+
+        if proj.is_inside {
+            Some(N::zero())
+        } else {
+            Some(na::distance(point, &proj.point))
+        }
+    };
+
+    broad_phase
+        .k_nearest_with_ball(point, N::zero(), max_dist, k, &cost_fn)
+        .into_iter()
+        .filter_map(|(handle, _)| {
+            let (co, proj) = project(&handle)?;
+            Some((handle, co, proj))
+        })
+        .collect()
+}
+
+/// Returns the collision object closest to the given shape, and their distance, if it is not further
+/// than `max_dist`.
+///
+/// The result will only include collision objects in a group that can interact with the given `groups`
+/// and with a shape for which the distance to the given shape can be computed by `query::distance`.
+pub fn closest_object_to_shape<'a, N: RealField, Objects: CollisionObjectSet<N>>(
+    objects: &'a Objects,
+    broad_phase: &'a (impl BroadPhase<N, AABB<N>, Objects::CollisionObjectHandle> + ?Sized),
+    isometry: &Isometry<N>,
+    shape: &dyn Shape<N>,
+    max_dist: N,
+    groups: &<Objects::CollisionObject as CollisionObjectRef<N>>::Groups,
+) -> Option<(
+    Objects::CollisionObjectHandle,
+    &'a Objects::CollisionObject,
+    N,
+)> {
+    let cost_fn = |handle: &Objects::CollisionObjectHandle| {
+        let co = objects.collision_object(*handle)?;
+
+        if co.collision_groups().can_interact_with_groups(groups)
+            && query::is_distance_supported(shape, co.shape())
+        {
+            Some(query::distance(isometry, shape, co.position(), co.shape()))
+        } else {
+            None
+        }
+    };
+
+    let bounding_sphere = shape.bounding_sphere(isometry);
+    let (handle, dist) = broad_phase
+        .k_nearest_with_ball(
+            bounding_sphere.center(),
+            bounding_sphere.radius(),
+            max_dist,
+            1,
+            &cost_fn,
+        )
+        .into_iter()
+        .next()?;
+    let co = objects.collision_object(handle)?;
+    Some((handle, co, dist))
+}
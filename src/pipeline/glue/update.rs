@@ -4,7 +4,9 @@ use crate::bounding_volume::AABB;
 use crate::pipeline::broad_phase::{
     BroadPhase, BroadPhaseInterferenceHandler, BroadPhasePairFilter,
 };
-use crate::pipeline::narrow_phase::{InteractionGraph, NarrowPhase};
+use crate::pipeline::narrow_phase::{
+    ContactModifier, InteractionGraph, NarrowPhase, NarrowPhasePairFilter,
+};
 use crate::pipeline::object::{CollisionGroupsPairFilter, CollisionObjectRef, CollisionObjectSet};
 
 struct CollisionWorldInterferenceHandler<'a, 'b, N, Objects, Filter>
@@ -106,15 +108,29 @@ pub fn perform_broad_phase<N: RealField, Objects>(
 ///
 /// This will update all interactions in the interaction graph by computing new contacts,
 /// and proximities.
+/// A `narrow_phase_pair_filter` can be provided to disable contact generation between some pairs of objects.
+/// A `contact_modifier` can be provided to modify the contacts computed for each pair of objects.
 pub fn perform_narrow_phase<N, Objects>(
     objects: &Objects,
     narrow_phase: &mut NarrowPhase<N, Objects::CollisionObjectHandle>,
     interactions: &mut InteractionGraph<N, Objects::CollisionObjectHandle>,
+    narrow_phase_pair_filter: Option<
+        &(impl NarrowPhasePairFilter<N, Objects::CollisionObject, Objects::CollisionObjectHandle>
+              + ?Sized),
+    >,
+    contact_modifier: Option<
+        &(impl ContactModifier<N, Objects::CollisionObject, Objects::CollisionObjectHandle> + ?Sized),
+    >,
 ) where
     N: RealField,
     Objects: CollisionObjectSet<N>,
 {
-    narrow_phase.update(interactions, objects);
+    narrow_phase.update(
+        interactions,
+        objects,
+        narrow_phase_pair_filter,
+        contact_modifier,
+    );
 }
 
 /// Performs the broad-phase and the narrow-phase.
@@ -130,6 +146,13 @@ pub fn perform_all_pipeline<'a, N, Objects>(
         &'a (impl BroadPhasePairFilter<N, Objects::CollisionObject, Objects::CollisionObjectHandle>
                  + ?Sized),
     >,
+    narrow_phase_pair_filter: Option<
+        &'a (impl NarrowPhasePairFilter<N, Objects::CollisionObject, Objects::CollisionObjectHandle>
+                 + ?Sized),
+    >,
+    contact_modifier: Option<
+        &'a (impl ContactModifier<N, Objects::CollisionObject, Objects::CollisionObjectHandle> + ?Sized),
+    >,
 ) where
     N: RealField,
     Objects: CollisionObjectSet<N>,
@@ -141,5 +164,46 @@ pub fn perform_all_pipeline<'a, N, Objects>(
         interactions,
         pair_filters,
     );
-    perform_narrow_phase(objects, narrow_phase, interactions);
+    perform_narrow_phase(
+        objects,
+        narrow_phase,
+        interactions,
+        narrow_phase_pair_filter,
+        contact_modifier,
+    );
+}
+
+/// Collects the sleeping collision objects that should be woken up.
+///
+/// A sleeping collision object should be woken up whenever an active collision object it interacts with
+/// (according to the interaction graph) has been changed in a way that justify a narrow-phase update.
+/// The handles of those sleeping collision objects are pushed to `out`.
+pub fn collect_objects_to_wake_up<N, Objects>(
+    objects: &Objects,
+    interactions: &InteractionGraph<N, Objects::CollisionObjectHandle>,
+    out: &mut Vec<Objects::CollisionObjectHandle>,
+) where
+    N: RealField,
+    Objects: CollisionObjectSet<N>,
+{
+    objects.foreach(|handle, co| {
+        if !co.activation_status().is_sleeping() {
+            return;
+        }
+
+        let id = objects
+            .graph_index(handle)
+            .expect(crate::NOT_REGISTERED_ERROR);
+
+        for neighbor in interactions.0.neighbors(id) {
+            if let Some(neighbor) = objects.collision_object(interactions.0[neighbor]) {
+                if neighbor.activation_status().is_active()
+                    && neighbor.update_flags().needs_narrow_phase_update()
+                {
+                    out.push(handle);
+                    return;
+                }
+            }
+        }
+    });
 }
@@ -0,0 +1,113 @@
+use super::convex_mesh_manifold_generator::ConvexMeshManifoldGenerator;
+use crate::bounding_volume::{self, BoundingVolume};
+use crate::math::Isometry;
+use crate::pipeline::narrow_phase::{ContactDispatcher, ContactManifoldGenerator};
+use crate::query::{ContactManifold, ContactPrediction, ContactPreprocessor, ContactTrackingMode};
+use crate::shape::{HeightField, Shape};
+use na::RealField;
+
+/// Collision detector between an heightfield and a convex shape.
+///
+/// Contrary to the `HeightFieldShapeManifoldGenerator`, all the triangles close to the convex
+/// shape are processed at once: contacts on internal edges of the heightfield are corrected to use
+/// the normal of the adjacent triangles, and duplicate contacts found on adjacent coplanar triangles
+/// are merged. This prevents convex shapes sliding on the heightfield from catching on its internal edges.
+pub struct ConvexHeightFieldManifoldGenerator<N: RealField> {
+    generator: ConvexMeshManifoldGenerator<N>,
+    flip: bool,
+}
+
+impl<N: RealField> ConvexHeightFieldManifoldGenerator<N> {
+    /// Creates a new collision detector between an heightfield and a convex shape.
+    ///
+    /// If `flip` is `true`, the heightfield is expected to be the second shape.
+    pub fn new(flip: bool) -> ConvexHeightFieldManifoldGenerator<N> {
+        ConvexHeightFieldManifoldGenerator {
+            generator: ConvexMeshManifoldGenerator::new(),
+            flip,
+        }
+    }
+
+    fn do_update(
+        &mut self,
+        dispatcher: &dyn ContactDispatcher<N>,
+        m1: &Isometry<N>,
+        g1: &HeightField<N>,
+        proc1: Option<&dyn ContactPreprocessor<N>>,
+        m2: &Isometry<N>,
+        g2: &dyn Shape<N>,
+        proc2: Option<&dyn ContactPreprocessor<N>>,
+        prediction: &ContactPrediction<N>,
+        manifold: &mut ContactManifold<N>,
+        flip: bool,
+    ) {
+        // The contacts are computed with the heightfield as the first shape.
+        let prediction = if flip {
+            ContactPrediction::new(
+                prediction.linear(),
+                prediction.angular2(),
+                prediction.angular1(),
+            )
+        } else {
+            prediction.clone()
+        };
+
+        // Find new collisions
+        let ls_m2 = m1.inverse() * m2.clone();
+        let ls_aabb2 = bounding_volume::aabb(g2, &ls_m2).loosened(prediction.linear());
+
+        let generator = &mut self.generator;
+        generator.begin();
+
+        g1.map_elements_in_local_aabb(&ls_aabb2, &mut |i, triangle, part_proc| {
+            generator.add_triangle(
+                dispatcher,
+                g1,
+                i,
+                triangle,
+                part_proc,
+                m1,
+                m2,
+                g2,
+                &prediction,
+            )
+        });
+
+        generator.end(g1, m1, proc1, proc2, &prediction, manifold, flip);
+    }
+}
+
+impl<N: RealField> ContactManifoldGenerator<N> for ConvexHeightFieldManifoldGenerator<N> {
+    fn generate_contacts(
+        &mut self,
+        d: &dyn ContactDispatcher<N>,
+        ma: &Isometry<N>,
+        a: &dyn Shape<N>,
+        proc1: Option<&dyn ContactPreprocessor<N>>,
+        mb: &Isometry<N>,
+        b: &dyn Shape<N>,
+        proc2: Option<&dyn ContactPreprocessor<N>>,
+        prediction: &ContactPrediction<N>,
+        manifold: &mut ContactManifold<N>,
+    ) -> bool {
+        if !self.flip {
+            if let Some(hf) = a.as_shape::<HeightField<N>>() {
+                self.do_update(d, ma, hf, proc1, mb, b, proc2, prediction, manifold, false);
+                return true;
+            }
+        } else {
+            if let Some(hf) = b.as_shape::<HeightField<N>>() {
+                self.do_update(d, mb, hf, proc2, ma, a, proc1, prediction, manifold, true);
+                return true;
+            }
+        }
+
+        return false;
+    }
+
+    fn init_manifold(&self) -> ContactManifold<N> {
+        let mut res = ContactManifold::new();
+        res.set_tracking_mode(ContactTrackingMode::FeatureBased);
+        res
+    }
+}
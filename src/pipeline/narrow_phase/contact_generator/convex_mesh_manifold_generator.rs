@@ -0,0 +1,359 @@
+use crate::math::Isometry;
+use crate::pipeline::narrow_phase::{ContactAlgorithm, ContactDispatcher};
+use crate::query::{
+    Contact, ContactKinematic, ContactManifold, ContactPrediction, ContactPreprocessor,
+    NeighborhoodGeometry,
+};
+use crate::shape::{FeatureId, HeightField, Shape, TriMesh, Triangle};
+use crate::utils::{DeterministicState, IsometryOps};
+use na::{self, RealField};
+use std::collections::{hash_map::Entry, HashMap};
+
+/// The topological informations about a triangle mesh required to detect internal edges.
+pub(crate) trait TriangleMeshTopology<N: RealField> {
+    /// The global identifiers of the vertices of the given feature, followed by their number.
+    fn feature_vertices(&self, feature: FeatureId) -> ([usize; 3], usize);
+    /// Tests whether two distinct features of this mesh share at least one vertex.
+    fn are_features_adjacent(&self, feature1: FeatureId, feature2: FeatureId) -> bool;
+    /// The global identifier of the front or back face of the given triangle.
+    fn face(&self, triangle: usize, front: bool) -> FeatureId;
+}
+
+impl<N: RealField> TriangleMeshTopology<N> for TriMesh<N> {
+    fn feature_vertices(&self, feature: FeatureId) -> ([usize; 3], usize) {
+        TriMesh::feature_vertices(self, feature)
+    }
+
+    fn are_features_adjacent(&self, feature1: FeatureId, feature2: FeatureId) -> bool {
+        TriMesh::are_features_adjacent(self, feature1, feature2)
+    }
+
+    fn face(&self, triangle: usize, front: bool) -> FeatureId {
+        if front {
+            FeatureId::Face(triangle)
+        } else {
+            FeatureId::Face(triangle + self.faces().len())
+        }
+    }
+}
+
+impl<N: RealField> TriangleMeshTopology<N> for HeightField<N> {
+    fn feature_vertices(&self, feature: FeatureId) -> ([usize; 3], usize) {
+        HeightField::feature_vertices(self, feature)
+    }
+
+    fn are_features_adjacent(&self, feature1: FeatureId, feature2: FeatureId) -> bool {
+        HeightField::are_features_adjacent(self, feature1, feature2)
+    }
+
+    fn face(&self, triangle: usize, front: bool) -> FeatureId {
+        if front {
+            FeatureId::Face(triangle)
+        } else {
+            FeatureId::Face(triangle + self.num_triangles())
+        }
+    }
+}
+
+// A contact preprocessor that only exposes the adjacency informations of a mesh.
+// The contacts it is applied to already have their features expressed in terms of the mesh.
+struct MeshAdjacency<'a, M: ?Sized>(&'a M);
+
+impl<'a, N: RealField, M: ?Sized + TriangleMeshTopology<N>> ContactPreprocessor<N>
+    for MeshAdjacency<'a, M>
+{
+    fn process_contact(&self, _: &mut Contact<N>, _: &mut ContactKinematic<N>, _: bool) -> bool {
+        true
+    }
+
+    fn are_features_adjacent(&self, feature1: FeatureId, feature2: FeatureId) -> bool {
+        self.0.are_features_adjacent(feature1, feature2)
+    }
+}
+
+struct LocalTriangle<N: RealField> {
+    id: usize,
+    vertices: [usize; 3],
+    triangle: Triangle<N>,
+}
+
+/// Contact generation between a convex shape and all the triangles of a mesh at once.
+///
+/// Contacts are first computed independently for each triangle of the mesh close to the convex shape.
+/// Then, the contacts with a normal outside of the normal cone of the mesh feature they are located
+/// on (computed from the triangles adjacent to this feature) are corrected to use the normal of the
+/// adjacent triangle closest to the contact normal. Finally, duplicate contacts found by
+/// adjacent coplanar triangles are merged.
+pub(crate) struct ConvexMeshManifoldGenerator<N: RealField> {
+    sub_detectors: HashMap<usize, (ContactAlgorithm<N>, usize), DeterministicState>,
+    timestamp: usize,
+    triangles: Vec<LocalTriangle<N>>,
+    candidates: Vec<(Contact<N>, ContactKinematic<N>)>,
+    workspace: ContactManifold<N>,
+}
+
+impl<N: RealField> ConvexMeshManifoldGenerator<N> {
+    pub fn new() -> Self {
+        ConvexMeshManifoldGenerator {
+            sub_detectors: HashMap::with_hasher(DeterministicState),
+            timestamp: 0,
+            triangles: Vec::new(),
+            candidates: Vec::new(),
+            workspace: ContactManifold::new(),
+        }
+    }
+
+    /// Starts a new contact generation.
+    pub fn begin(&mut self) {
+        self.timestamp += 1;
+        self.triangles.clear();
+        self.candidates.clear();
+    }
+
+    /// Computes the contacts between the `triangle`-th triangle of the mesh and the convex shape.
+    ///
+    /// The triangle is expressed in the local-space of the mesh and the contacts are always computed
+    /// with the mesh as the first shape, so `prediction` must be given accordingly.
+    pub fn add_triangle(
+        &mut self,
+        dispatcher: &dyn ContactDispatcher<N>,
+        mesh: &dyn TriangleMeshTopology<N>,
+        triangle_id: usize,
+        triangle: &Triangle<N>,
+        triangle_proc: &dyn ContactPreprocessor<N>,
+        m1: &Isometry<N>,
+        m2: &Isometry<N>,
+        g2: &dyn Shape<N>,
+        prediction: &ContactPrediction<N>,
+    ) {
+        let detector = match self.sub_detectors.entry(triangle_id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => match dispatcher.get_contact_algorithm(triangle, g2) {
+                Some(detector) => entry.insert((detector, self.timestamp)),
+                None => return,
+            },
+        };
+
+        self.workspace.clear();
+
+        if detector.0.generate_contacts(
+            dispatcher,
+            m1,
+            triangle,
+            Some(triangle_proc),
+            m2,
+            g2,
+            None,
+            prediction,
+            &mut self.workspace,
+        ) {
+            detector.1 = self.timestamp;
+        }
+
+        for c in self.workspace.contacts() {
+            self.candidates.push((c.contact, c.kinematic));
+        }
+
+        let (vertices, _) = mesh.feature_vertices(mesh.face(triangle_id, true));
+        self.triangles.push(LocalTriangle {
+            id: triangle_id,
+            vertices,
+            triangle: triangle.clone(),
+        });
+    }
+
+    /// Corrects and merges all the contacts computed since the last call to `self.begin`, and adds
+    /// them to the `manifold`.
+    ///
+    /// The `prediction` must be the same as the one given to `self.add_triangle`. If `flip` is `true`,
+    /// the contacts are flipped so that the mesh becomes the second shape.
+    pub fn end(
+        &mut self,
+        mesh: &dyn TriangleMeshTopology<N>,
+        m1: &Isometry<N>,
+        proc1: Option<&dyn ContactPreprocessor<N>>,
+        proc2: Option<&dyn ContactPreprocessor<N>>,
+        prediction: &ContactPrediction<N>,
+        manifold: &mut ContactManifold<N>,
+        flip: bool,
+    ) {
+        let timestamp = self.timestamp;
+        self.sub_detectors
+            .retain(|_, detector| detector.1 == timestamp);
+
+        let sin_tol = prediction.sin_angular1() + N::default_epsilon().sqrt();
+
+        for (contact, kinematic) in &mut self.candidates {
+            Self::correct_internal_edge(&self.triangles, mesh, m1, sin_tol, contact, kinematic)
+        }
+
+        self.merge_duplicates();
+
+        let adjacency = MeshAdjacency(mesh);
+        let mesh_proc = (proc1, &adjacency);
+
+        for (mut contact, mut kinematic) in self.candidates.drain(..) {
+            if !flip {
+                let local2 = kinematic.local2();
+                let _ = manifold.push(contact, kinematic, local2, Some(&mesh_proc), proc2);
+            } else {
+                contact.flip();
+                flip_kinematic(&mut kinematic);
+                let local1 = kinematic.local1();
+                let _ = manifold.push(contact, kinematic, local1, proc2, Some(&mesh_proc));
+            }
+        }
+    }
+
+    fn correct_internal_edge(
+        triangles: &[LocalTriangle<N>],
+        mesh: &dyn TriangleMeshTopology<N>,
+        m1: &Isometry<N>,
+        sin_tol: N,
+        contact: &mut Contact<N>,
+        kinematic: &mut ContactKinematic<N>,
+    ) {
+        let (feature_vertices, nvertices) = mesh.feature_vertices(kinematic.feature1());
+
+        if nvertices == 0 {
+            return;
+        }
+
+        let feature_vertices = &feature_vertices[..nvertices];
+        let local_normal = m1.inverse_transform_unit_vector(&contact.normal);
+        let mut origin = None;
+        let mut is_valid = true;
+        let mut best = None::<(N, &LocalTriangle<N>)>;
+
+        // The contact normal is valid if it lies inside of the polar of the tangent cone of
+        // the feature, i.e., if it is orthogonal to the feature and no vertex of the adjacent
+        // triangles is above the feature.
+        for tri in triangles {
+            if !feature_vertices.iter().all(|v| tri.vertices.contains(v)) {
+                continue;
+            }
+
+            let pts = tri.triangle.vertices();
+            let origin = *origin.get_or_insert_with(|| {
+                let i = tri.vertices.iter().position(|v| *v == feature_vertices[0]);
+                pts[i.unwrap()]
+            });
+
+            for (pt, id) in pts.iter().zip(tri.vertices.iter()) {
+                let dpt = pt - origin;
+                let mut dot = dpt.dot(&local_normal);
+
+                // The normal must be orthogonal to the feature itself.
+                if feature_vertices.contains(id) {
+                    dot = dot.abs();
+                }
+
+                if dot > sin_tol * dpt.norm() {
+                    is_valid = false;
+                }
+            }
+
+            if let Some(n) = tri.triangle.normal() {
+                let dot = n.dot(&local_normal).abs();
+                if best.map(|b| dot > b.0) != Some(false) {
+                    best = Some((dot, tri))
+                }
+            }
+        }
+
+        if is_valid {
+            return;
+        }
+
+        if let Some((_, tri)) = best {
+            let mut normal = tri.triangle.normal().unwrap();
+            let front = normal.dot(&local_normal) >= N::zero();
+
+            if !front {
+                normal = -normal;
+            }
+
+            let world_normal = m1 * normal;
+            let depth = world_normal.dot(&(contact.world1 - contact.world2));
+            let world1 = contact.world2 + *world_normal * depth;
+
+            *contact = Contact::new(world1, contact.world2, world_normal, depth);
+            kinematic.set_approx1(
+                mesh.face(tri.id, front),
+                m1.inverse_transform_point(&world1),
+                NeighborhoodGeometry::Plane(normal),
+            );
+        }
+    }
+
+    // Removes the contacts found by several triangles with the same normal at the same point of the convex shape.
+    fn merge_duplicates(&mut self) {
+        let eps = N::default_epsilon().sqrt();
+        let mut i = 0;
+
+        while i < self.candidates.len() {
+            let (ci, ki) = self.candidates[i];
+            let duplicate = self.candidates[i + 1..].iter().position(|(cj, kj)| {
+                ki.feature2() != FeatureId::Unknown
+                    && ki.feature2() == kj.feature2()
+                    && ci.normal.dot(&cj.normal) >= N::one() - eps
+                    && na::distance_squared(&ci.world2, &cj.world2) <= eps * eps
+            });
+
+            if let Some(j) = duplicate {
+                let j = i + 1 + j;
+
+                // Keep the deepest contact at the position `i`.
+                if self.candidates[j].0.depth > ci.depth {
+                    self.candidates.swap(i, j);
+                }
+
+                let _ = self.candidates.swap_remove(j);
+            } else {
+                i += 1;
+            }
+        }
+    }
+}
+
+fn flip_kinematic<N: RealField>(kinematic: &mut ContactKinematic<N>) {
+    let approx1 = *kinematic.approx1();
+    let approx2 = *kinematic.approx2();
+    let dilation1 = kinematic.dilation1();
+    let dilation2 = kinematic.dilation2();
+
+    kinematic.set_approx1(approx2.feature, approx2.point, approx2.geometry);
+    kinematic.set_approx2(approx1.feature, approx1.point, approx1.geometry);
+    kinematic.set_dilation1(dilation2);
+    kinematic.set_dilation2(dilation1);
+}
+
+#[cfg(test)]
+mod test {
+    use super::ConvexMeshManifoldGenerator;
+    use crate::math::{Point, Vector};
+    use crate::query::{Contact, ContactKinematic};
+    use crate::shape::FeatureId;
+    use na::Unit;
+
+    #[test]
+    fn merge_duplicates_keeps_the_deepest_contact() {
+        let normal = Unit::new_normalize(Vector::<f64>::y());
+        let pt = Point::origin();
+        let mut kinematic = ContactKinematic::new();
+        kinematic.set_feature2(FeatureId::Vertex(0));
+
+        for depths in &[[0.1, 0.2], [0.2, 0.1]] {
+            let mut generator = ConvexMeshManifoldGenerator::new();
+
+            for depth in depths {
+                let contact = Contact::new(pt, pt, normal, *depth);
+                generator.candidates.push((contact, kinematic));
+            }
+
+            generator.merge_duplicates();
+            assert_eq!(generator.candidates.len(), 1);
+            assert_eq!(generator.candidates[0].0.depth, 0.2);
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use super::convex_mesh_manifold_generator::ConvexMeshManifoldGenerator;
+use crate::bounding_volume::{self, BoundingVolume};
+use crate::math::Isometry;
+use crate::pipeline::narrow_phase::{ContactDispatcher, ContactManifoldGenerator};
+use crate::query::{
+    visitors::BoundingVolumeInterferencesCollector, ContactManifold, ContactPrediction,
+    ContactPreprocessor, ContactTrackingMode,
+};
+use crate::shape::{CompositeShape, Shape, TriMesh, Triangle};
+use na::RealField;
+
+/// Collision detector between a triangle mesh and a convex shape.
+///
+/// Contrary to the `CompositeShapeShapeManifoldGenerator`, all the triangles close to the convex
+/// shape are processed at once: contacts on internal edges of the mesh are corrected to use the
+/// normal of the adjacent triangles, and duplicate contacts found on adjacent coplanar triangles
+/// are merged. This prevents convex shapes sliding on the mesh from catching on its internal edges.
+pub struct ConvexTriMeshManifoldGenerator<N: RealField> {
+    generator: ConvexMeshManifoldGenerator<N>,
+    interferences: Vec<usize>,
+    flip: bool,
+}
+
+impl<N: RealField> ConvexTriMeshManifoldGenerator<N> {
+    /// Creates a new collision detector between a triangle mesh and a convex shape.
+    ///
+    /// If `flip` is `true`, the triangle mesh is expected to be the second shape.
+    pub fn new(flip: bool) -> ConvexTriMeshManifoldGenerator<N> {
+        ConvexTriMeshManifoldGenerator {
+            generator: ConvexMeshManifoldGenerator::new(),
+            interferences: Vec::new(),
+            flip,
+        }
+    }
+
+    fn do_update(
+        &mut self,
+        dispatcher: &dyn ContactDispatcher<N>,
+        m1: &Isometry<N>,
+        g1: &TriMesh<N>,
+        proc1: Option<&dyn ContactPreprocessor<N>>,
+        m2: &Isometry<N>,
+        g2: &dyn Shape<N>,
+        proc2: Option<&dyn ContactPreprocessor<N>>,
+        prediction: &ContactPrediction<N>,
+        manifold: &mut ContactManifold<N>,
+        flip: bool,
+    ) {
+        // The contacts are computed with the mesh as the first shape.
+        let prediction = if flip {
+            ContactPrediction::new(
+                prediction.linear(),
+                prediction.angular2(),
+                prediction.angular1(),
+            )
+        } else {
+            prediction.clone()
+        };
+
+        // Find new collisions
+        let ls_m2 = m1.inverse() * m2.clone();
+        let ls_aabb2 = bounding_volume::aabb(g2, &ls_m2).loosened(prediction.linear());
+
+        {
+            let mut visitor =
+                BoundingVolumeInterferencesCollector::new(&ls_aabb2, &mut self.interferences);
+            g1.bvh().visit(&mut visitor);
+        }
+
+        let generator = &mut self.generator;
+        generator.begin();
+
+        for i in self.interferences.drain(..) {
+            g1.map_part_and_preprocessor_at(i, m1, &prediction, &mut |m1, part, part_proc| {
+                if let Some(triangle) = part.as_shape::<Triangle<N>>() {
+                    generator.add_triangle(
+                        dispatcher,
+                        g1,
+                        i,
+                        triangle,
+                        part_proc,
+                        m1,
+                        m2,
+                        g2,
+                        &prediction,
+                    )
+                }
+            });
+        }
+
+        generator.end(g1, m1, proc1, proc2, &prediction, manifold, flip);
+    }
+}
+
+impl<N: RealField> ContactManifoldGenerator<N> for ConvexTriMeshManifoldGenerator<N> {
+    fn generate_contacts(
+        &mut self,
+        d: &dyn ContactDispatcher<N>,
+        ma: &Isometry<N>,
+        a: &dyn Shape<N>,
+        proc1: Option<&dyn ContactPreprocessor<N>>,
+        mb: &Isometry<N>,
+        b: &dyn Shape<N>,
+        proc2: Option<&dyn ContactPreprocessor<N>>,
+        prediction: &ContactPrediction<N>,
+        manifold: &mut ContactManifold<N>,
+    ) -> bool {
+        if !self.flip {
+            if let Some(mesh) = a.as_shape::<TriMesh<N>>() {
+                self.do_update(
+                    d, ma, mesh, proc1, mb, b, proc2, prediction, manifold, false,
+                );
+                return true;
+            }
+        } else {
+            if let Some(mesh) = b.as_shape::<TriMesh<N>>() {
+                self.do_update(d, mb, mesh, proc2, ma, a, proc1, prediction, manifold, true);
+                return true;
+            }
+        }
+
+        return false;
+    }
+
+    fn init_manifold(&self) -> ContactManifold<N> {
+        let mut res = ContactManifold::new();
+        res.set_tracking_mode(ContactTrackingMode::FeatureBased);
+        res
+    }
+}
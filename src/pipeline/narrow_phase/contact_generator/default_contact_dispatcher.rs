@@ -1,5 +1,8 @@
 #[cfg(feature = "dim3")]
-use crate::pipeline::narrow_phase::TriMeshTriMeshManifoldGenerator;
+use crate::pipeline::narrow_phase::{
+    ConvexHeightFieldManifoldGenerator, ConvexTriMeshManifoldGenerator,
+    TriMeshTriMeshManifoldGenerator,
+};
 use crate::pipeline::{
     BallBallManifoldGenerator, BallConvexPolyhedronManifoldGenerator,
     CapsuleCapsuleManifoldGenerator, CapsuleShapeManifoldGenerator,
@@ -45,6 +48,16 @@ impl<N: RealField> ContactDispatcher<N> for DefaultContactDispatcher {
 
             if a_is_trimesh && b_is_trimesh {
                 return Some(Box::new(TriMeshTriMeshManifoldGenerator::<N>::new()));
+            } else if a_is_trimesh && b.is_support_map() {
+                return Some(Box::new(ConvexTriMeshManifoldGenerator::<N>::new(false)));
+            } else if b_is_trimesh && a.is_support_map() {
+                return Some(Box::new(ConvexTriMeshManifoldGenerator::<N>::new(true)));
+            } else if a_is_heightfield && b.is_support_map() {
+                return Some(Box::new(ConvexHeightFieldManifoldGenerator::<N>::new(
+                    false,
+                )));
+            } else if b_is_heightfield && a.is_support_map() {
+                return Some(Box::new(ConvexHeightFieldManifoldGenerator::<N>::new(true)));
             }
         }
 
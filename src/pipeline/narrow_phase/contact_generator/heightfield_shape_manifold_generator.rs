@@ -1,6 +1,8 @@
 use crate::bounding_volume::{self, BoundingVolume};
 use crate::math::Isometry;
 use crate::pipeline::{ContactAlgorithm, ContactDispatcher, ContactManifoldGenerator};
+#[cfg(feature = "dim3")]
+use crate::query::ContactTrackingMode;
 use crate::query::{ContactManifold, ContactPrediction, ContactPreprocessor};
 use crate::shape::{HeightField, Shape};
 use crate::utils::DeterministicState;
@@ -151,9 +153,11 @@ impl<N: RealField> ContactManifoldGenerator<N> for HeightFieldShapeManifoldGener
         return false;
     }
 
-    //    fn init_manifold(&self) -> ContactManifold<N> {
-    //        let mut res = ContactManifold::new();
-    //        res.set_tracking_mode(ContactTrackingMode::FeatureBased);
-    //        res
-    //    }
+    // The feature ids of the 2D heightfield are not remapped yet.
+    #[cfg(feature = "dim3")]
+    fn init_manifold(&self) -> ContactManifold<N> {
+        let mut res = ContactManifold::new();
+        res.set_tracking_mode(ContactTrackingMode::FeatureBased);
+        res
+    }
 }
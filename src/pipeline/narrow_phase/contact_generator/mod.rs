@@ -9,7 +9,11 @@ pub use self::composite_shape_shape_manifold_generator::CompositeShapeShapeManif
 pub use self::contact_manifold_generator::{
     ContactAlgorithm, ContactDispatcher, ContactManifoldGenerator,
 };
+#[cfg(feature = "dim3")]
+pub use self::convex_heightfield_manifold_generator::ConvexHeightFieldManifoldGenerator;
 pub use self::convex_polyhedron_convex_polyhedron_manifold_generator::ConvexPolyhedronConvexPolyhedronManifoldGenerator;
+#[cfg(feature = "dim3")]
+pub use self::convex_trimesh_manifold_generator::ConvexTriMeshManifoldGenerator;
 pub use self::default_contact_dispatcher::DefaultContactDispatcher;
 pub use self::heightfield_shape_manifold_generator::HeightFieldShapeManifoldGenerator;
 pub use self::plane_ball_manifold_generator::PlaneBallManifoldGenerator;
@@ -26,7 +30,13 @@ mod composite_shape_composite_shape_manifold_generator;
 mod composite_shape_shape_manifold_generator;
 #[doc(hidden)]
 pub mod contact_manifold_generator;
+#[cfg(feature = "dim3")]
+mod convex_heightfield_manifold_generator;
+#[cfg(feature = "dim3")]
+mod convex_mesh_manifold_generator;
 mod convex_polyhedron_convex_polyhedron_manifold_generator;
+#[cfg(feature = "dim3")]
+mod convex_trimesh_manifold_generator;
 mod default_contact_dispatcher;
 mod heightfield_shape_manifold_generator;
 mod plane_ball_manifold_generator;
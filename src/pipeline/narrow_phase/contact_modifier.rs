@@ -0,0 +1,32 @@
+use crate::query::ContactManifold;
+use na::RealField;
+use std::any::Any;
+
+/// A user-defined hook for modifying the contacts computed between two collision objects.
+pub trait ContactModifier<N: RealField, Object, Handle>: Any + Send + Sync {
+    /// Modifies the contact manifold computed by the narrow-phase for a pair of collision objects.
+    ///
+    /// This is called right after the contact manifold generator updated `manifold`, and before any
+    /// contact event is generated for this pair. Contacts can be edited with `manifold.contacts_mut()`,
+    /// dropped with `manifold.retain(...)`, or added with `manifold.push(...)`.
+    fn modify_contacts(
+        &self,
+        co1: &Object,
+        co2: &Object,
+        h1: Handle,
+        h2: Handle,
+        manifold: &mut ContactManifold<N>,
+    );
+}
+
+impl<N: RealField, Object, Handle> ContactModifier<N, Object, Handle> for () {
+    fn modify_contacts(
+        &self,
+        _: &Object,
+        _: &Object,
+        _: Handle,
+        _: Handle,
+        _: &mut ContactManifold<N>,
+    ) {
+    }
+}
@@ -1,6 +1,8 @@
 //! Structures for describing and storing collision-related events.
 
-use crate::query::Proximity;
+use crate::query::{Proximity, TrackedContact};
+use crate::shape::Shape;
+use na::RealField;
 use std::iter::IntoIterator;
 use std::slice::Iter;
 
@@ -15,6 +17,8 @@ pub struct EventPool<E> {
 pub type ContactEvents<Handle> = EventPool<ContactEvent<Handle>>;
 /// A set of proximity events.
 pub type ProximityEvents<Handle> = EventPool<ProximityEvent<Handle>>;
+/// A set of distance events.
+pub type DistanceEvents<Handle> = EventPool<DistanceEvent<Handle>>;
 
 impl<E> EventPool<E> {
     /// Creates a new empty set of events.
@@ -61,8 +65,10 @@ impl<'a, E> IntoIterator for &'a EventPool<E> {
     }
 }
 
-#[derive(Copy, Clone, Hash, Debug)]
+#[derive(Copy, Clone, Hash, PartialEq, Eq, Debug)]
 /// Events occuring when two collision objects start or stop being in contact (or penetration).
+///
+/// The contacts involved in each event can be retrieved with `NarrowPhase::contact_event_data`.
 pub enum ContactEvent<Handle> {
     /// Event occuring when two collision objects start being in contact.
     ///
@@ -74,6 +80,94 @@ pub enum ContactEvent<Handle> {
     Stopped(Handle, Handle),
 }
 
+impl<Handle: Copy> ContactEvent<Handle> {
+    /// The handles of the two collision objects involved in this event.
+    #[inline]
+    pub fn handles(&self) -> (Handle, Handle) {
+        match self {
+            ContactEvent::Started(h1, h2) | ContactEvent::Stopped(h1, h2) => (*h1, *h2),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+/// Contact information attached to a contact event.
+pub struct ContactEventData<N: RealField> {
+    /// The number of narrow-phase updates completed before this event was generated.
+    pub timestamp: usize,
+    /// The contact with the deepest penetration depth.
+    pub deepest_contact: TrackedContact<N>,
+    /// The sub-shape of the first collision object's shape containing the first feature of the deepest contact.
+    pub subshape1: usize,
+    /// The sub-shape of the second collision object's shape containing the second feature of the deepest contact.
+    pub subshape2: usize,
+    // The range of the contacts of this event in the contact buffer of the narrow-phase.
+    first_contact: usize,
+    num_contacts: usize,
+}
+
+impl<N: RealField> ContactEventData<N> {
+    /// Collects the given contacts between two shapes into a new contact event data.
+    ///
+    /// The contacts are appended to `buffer`. Returns `None` if `contacts` is empty.
+    pub(crate) fn new<'a>(
+        timestamp: usize,
+        g1: &dyn Shape<N>,
+        g2: &dyn Shape<N>,
+        contacts: impl Iterator<Item = &'a TrackedContact<N>>,
+        buffer: &mut Vec<TrackedContact<N>>,
+    ) -> Option<Self> {
+        let first_contact = buffer.len();
+        buffer.extend(contacts);
+        let contacts = &buffer[first_contact..];
+        let mut deepest = contacts.first()?;
+
+        for c in contacts {
+            if c.contact.depth > deepest.contact.depth {
+                deepest = c;
+            }
+        }
+
+        Some(ContactEventData {
+            timestamp,
+            deepest_contact: *deepest,
+            subshape1: g1.subshape_containing_feature(deepest.kinematic.feature1()),
+            subshape2: g2.subshape_containing_feature(deepest.kinematic.feature2()),
+            first_contact,
+            num_contacts: contacts.len(),
+        })
+    }
+
+    /// The contacts between the two collision objects, taken from the contact buffer of the
+    /// narrow-phase which generated this data.
+    ///
+    /// For a `ContactEvent::Stopped`, those are the last contacts known before the two collision objects
+    /// stopped being in contact.
+    #[inline]
+    pub(crate) fn contacts<'a>(&self, buffer: &'a [TrackedContact<N>]) -> &'a [TrackedContact<N>] {
+        &buffer[self.first_contact..self.first_contact + self.num_contacts]
+    }
+
+    /// The number of contacts between the two collision objects.
+    #[inline]
+    pub fn num_contacts(&self) -> usize {
+        self.num_contacts
+    }
+}
+
+#[derive(Copy, Clone, Hash, Debug)]
+/// Events occuring when two collision objects start or stop being closer than their distance query limit.
+pub enum DistanceEvent<Handle> {
+    /// Event occuring when two collision objects start being closer than their distance query limit.
+    ///
+    /// This event is generated whenever the narrow-phase finds the closest points between two collision objects that were too far apart at the last update.
+    Started(Handle, Handle),
+    /// Event occuring when two collision objects stop being closer than their distance query limit.
+    ///
+    /// This event is generated whenever the narrow-phase finds two collision objects too far apart while they had closest points tracked at the last update.
+    Stopped(Handle, Handle),
+}
+
 #[derive(Copy, Clone, Debug)]
 /// Events occuring when two collision objects start or stop being in close proximity, contact, or disjoint.
 pub struct ProximityEvent<Handle> {
@@ -85,6 +179,8 @@ pub struct ProximityEvent<Handle> {
     pub prev_status: Proximity,
     /// The new state of proximity between the two collision objects.
     pub new_status: Proximity,
+    /// The number of narrow-phase updates completed before this event was generated.
+    pub timestamp: usize,
 }
 
 impl<Handle> ProximityEvent<Handle> {
@@ -96,6 +192,7 @@ impl<Handle> ProximityEvent<Handle> {
         collider2: Handle,
         prev_status: Proximity,
         new_status: Proximity,
+        timestamp: usize,
     ) -> Self {
         assert_ne!(
             prev_status, new_status,
@@ -106,6 +203,7 @@ impl<Handle> ProximityEvent<Handle> {
             collider2,
             prev_status,
             new_status,
+            timestamp,
         }
     }
 }
@@ -1,7 +1,5 @@
 //! Persistent collision detection algorithms to compute contact points.
 
-#[cfg(feature = "dim3")]
-pub use self::contact_generator::TriMeshTriMeshManifoldGenerator;
 #[doc(inline)]
 pub use self::contact_generator::{
     BallBallManifoldGenerator, BallConvexPolyhedronManifoldGenerator,
@@ -12,22 +10,38 @@ pub use self::contact_generator::{
     HeightFieldShapeManifoldGenerator, PlaneBallManifoldGenerator,
     PlaneConvexPolyhedronManifoldGenerator,
 };
-pub use self::events::{ContactEvent, ContactEvents, EventPool, ProximityEvent, ProximityEvents};
+#[cfg(feature = "dim3")]
+pub use self::contact_generator::{
+    ConvexHeightFieldManifoldGenerator, ConvexTriMeshManifoldGenerator,
+    TriMeshTriMeshManifoldGenerator,
+};
+pub use self::contact_modifier::ContactModifier;
+pub use self::events::{
+    ContactEvent, ContactEventData, ContactEvents, DistanceEvent, DistanceEvents, EventPool,
+    ProximityEvent, ProximityEvents,
+};
 pub use self::interaction_graph::{
     CollisionObjectGraphIndex, Interaction, InteractionGraph, TemporaryInteractionIndex,
 };
 pub use self::narrow_phase::NarrowPhase;
+pub use self::narrow_phase_pair_filter::NarrowPhasePairFilter;
 #[doc(inline)]
 pub use self::proximity_detector::{
     BallBallProximityDetector, CompositeShapeShapeProximityDetector, DefaultProximityDispatcher,
     PlaneSupportMapProximityDetector, ProximityAlgorithm, ProximityDetector, ProximityDispatcher,
     SupportMapPlaneProximityDetector, SupportMapSupportMapProximityDetector,
 };
+pub use self::shape_registry::{ShapeKind, ShapeRegistry};
+pub use self::speculative_contacts::SpeculativeContacts;
 
 #[doc(hidden)]
 pub mod contact_generator;
+mod contact_modifier;
 mod events;
 mod interaction_graph;
 mod narrow_phase;
+mod narrow_phase_pair_filter;
 #[doc(hidden)]
 pub mod proximity_detector;
+mod shape_registry;
+mod speculative_contacts;
@@ -1,22 +1,46 @@
 use na::RealField;
 use slotmap::{Key, SlotMap};
+use std::collections::{HashMap, HashSet};
 
+use crate::interpolation::{InterpolatedRigidMotion, RigidMotion};
 use crate::pipeline::narrow_phase::{
-    ContactDispatcher, ContactEvent, ContactEvents, ContactManifoldGenerator, Interaction,
-    InteractionGraph, ProximityDetector, ProximityDispatcher, ProximityEvent, ProximityEvents,
+    CollisionObjectGraphIndex, ContactDispatcher, ContactEvent, ContactEventData, ContactEvents,
+    ContactManifoldGenerator, ContactModifier, DistanceEvent, DistanceEvents, Interaction,
+    InteractionGraph, NarrowPhasePairFilter, ProximityDetector, ProximityDispatcher,
+    ProximityEvent, ProximityEvents, SpeculativeContacts,
 };
 use crate::pipeline::object::{
     CollisionObjectHandle, CollisionObjectRef, CollisionObjectSet, GeometricQueryType,
 };
-use crate::query::{ContactId, ContactManifold, Proximity};
+use crate::query::{
+    self, ClosestPoints, ContactId, ContactManifold, ContactPrediction, ContactReduction,
+    Proximity, TrackedContact,
+};
+use crate::shape::Shape;
+use crate::utils::DeterministicState;
 
 /// Collision detector dispatcher for collision objects.
 pub struct NarrowPhase<N: RealField, Handle: CollisionObjectHandle> {
     contact_dispatcher: Box<dyn ContactDispatcher<N>>,
     proximity_dispatcher: Box<dyn ProximityDispatcher<N>>,
     contact_events: ContactEvents<Handle>,
+    // The data of the contact events, and the contacts they refer to.
+    contact_event_data: HashMap<ContactEvent<Handle>, ContactEventData<N>, DeterministicState>,
+    contact_event_contacts: Vec<TrackedContact<N>>,
     proximity_events: ProximityEvents<Handle>,
+    distance_events: DistanceEvents<Handle>,
     id_allocator: SlotMap<ContactId, bool>,
+    timestamp: usize,
+    // Pairs with contacts disabled by the narrow-phase pair filter.
+    filtered_pairs: HashSet<(Handle, Handle), DeterministicState>,
+    // Pairs that never interact. Both orderings of each pair are stored.
+    excluded_pairs: HashSet<(Handle, Handle), DeterministicState>,
+    // Pairs that must be updated during the next update, even if their objects did not change.
+    // Both orderings of each pair are stored.
+    pairs_to_update: HashSet<(Handle, Handle), DeterministicState>,
+    contact_reduction: Option<ContactReduction<N>>,
+    speculative_contacts: SpeculativeContacts,
+    speculative_workspace: ContactManifold<N>,
 }
 
 impl<N: RealField, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
@@ -29,11 +53,70 @@ impl<N: RealField, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
             contact_dispatcher,
             proximity_dispatcher,
             contact_events: ContactEvents::new(),
+            contact_event_data: HashMap::with_hasher(DeterministicState::new()),
+            contact_event_contacts: Vec::new(),
             proximity_events: ProximityEvents::new(),
+            distance_events: DistanceEvents::new(),
             id_allocator: SlotMap::with_key(),
+            timestamp: 0,
+            filtered_pairs: HashSet::with_hasher(DeterministicState::new()),
+            excluded_pairs: HashSet::with_hasher(DeterministicState::new()),
+            pairs_to_update: HashSet::with_hasher(DeterministicState::new()),
+            contact_reduction: None,
+            speculative_contacts: SpeculativeContacts::Disabled,
+            speculative_workspace: ContactManifold::new(),
         }
     }
 
+    /// The dispatcher used to select the contact manifold generators of new interactions.
+    pub fn contact_dispatcher(&self) -> &dyn ContactDispatcher<N> {
+        &*self.contact_dispatcher
+    }
+
+    /// The dispatcher used to select the proximity detectors of new interactions.
+    pub fn proximity_dispatcher(&self) -> &dyn ProximityDispatcher<N> {
+        &*self.proximity_dispatcher
+    }
+
+    /// Sets the dispatcher used to select the contact manifold generators of new interactions.
+    ///
+    /// The interactions that already exist keep using the algorithm they have been created with.
+    pub fn set_contact_dispatcher(&mut self, contact_dispatcher: Box<dyn ContactDispatcher<N>>) {
+        self.contact_dispatcher = contact_dispatcher
+    }
+
+    /// Sets the dispatcher used to select the proximity detectors of new interactions.
+    ///
+    /// The interactions that already exist keep using the algorithm they have been created with.
+    pub fn set_proximity_dispatcher(
+        &mut self,
+        proximity_dispatcher: Box<dyn ProximityDispatcher<N>>,
+    ) {
+        self.proximity_dispatcher = proximity_dispatcher
+    }
+
+    /// The strategy used to compute speculative contacts.
+    pub fn speculative_contacts(&self) -> SpeculativeContacts {
+        self.speculative_contacts
+    }
+
+    /// Sets the strategy used to compute speculative contacts.
+    pub fn set_speculative_contacts(&mut self, speculative_contacts: SpeculativeContacts) {
+        self.speculative_contacts = speculative_contacts
+    }
+
+    /// The parameters used to reduce the number of contacts of each contact manifold, if any.
+    pub fn contact_reduction(&self) -> Option<&ContactReduction<N>> {
+        self.contact_reduction.as_ref()
+    }
+
+    /// Sets the parameters used to reduce the number of contacts of each contact manifold.
+    ///
+    /// If `None`, all the contacts computed by the contact manifold generators are kept.
+    pub fn set_contact_reduction(&mut self, reduction: Option<ContactReduction<N>>) {
+        self.contact_reduction = reduction
+    }
+
     fn garbage_collect_ids(&mut self, interactions: &mut InteractionGraph<N, Handle>) {
         for interaction in interactions.0.edge_weights_mut() {
             match interaction {
@@ -44,7 +127,7 @@ impl<N: RealField, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
                         }
                     }
                 }
-                Interaction::Proximity(..) => {}
+                Interaction::Proximity(..) | Interaction::Distance(..) => {}
             }
         }
 
@@ -53,14 +136,20 @@ impl<N: RealField, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
     }
 
     /// Update the specified contact manifold between two collision objects.
-    pub fn update_contact(
+    ///
+    /// If speculative contacts are enabled, they are added to the manifold before any contact
+    /// reduction. If a contact reduction is set, the computed manifold is reduced before being given to the
+    /// `contact_modifier`. If a `contact_modifier` is provided, it is given the opportunity to modify
+    /// the contacts computed by the `detector` before any contact event is generated.
+    pub fn update_contact<Object: CollisionObjectRef<N>>(
         &mut self,
-        co1: &impl CollisionObjectRef<N>,
-        co2: &impl CollisionObjectRef<N>,
+        co1: &Object,
+        co2: &Object,
         handle1: Handle,
         handle2: Handle,
         detector: &mut dyn ContactManifoldGenerator<N>,
         manifold: &mut ContactManifold<N>,
+        contact_modifier: Option<&(impl ContactModifier<N, Object, Handle> + ?Sized)>,
     ) {
         let had_contacts = manifold.len() != 0;
 
@@ -81,6 +170,16 @@ impl<N: RealField, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
                 manifold,
             );
 
+            self.add_speculative_contacts(co1, co2, manifold, &prediction);
+
+            if let Some(reduction) = &self.contact_reduction {
+                manifold.reduce(reduction);
+            }
+
+            if let Some(modifier) = contact_modifier {
+                modifier.modify_contacts(co1, co2, handle1, handle2, manifold);
+            }
+
             for contact in manifold.contacts_mut() {
                 if contact.id.is_null() {
                     contact.id = self.id_allocator.insert(false)
@@ -92,15 +191,144 @@ impl<N: RealField, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
 
         if manifold.len() == 0 {
             if had_contacts {
-                self.contact_events
-                    .push(ContactEvent::Stopped(handle1, handle2));
+                let event = ContactEvent::Stopped(handle1, handle2);
+                let contacts = manifold.stale_contacts();
+                self.emit_contact_event(event, co1.shape(), co2.shape(), contacts);
             }
         } else {
             if !had_contacts {
-                self.contact_events
-                    .push(ContactEvent::Started(handle1, handle2));
+                let event = ContactEvent::Started(handle1, handle2);
+                let contacts = manifold.contacts();
+                self.emit_contact_event(event, co1.shape(), co2.shape(), contacts);
+            }
+        }
+    }
+
+    // Pushes a contact event, and records the contacts involved.
+    fn emit_contact_event<'a>(
+        &mut self,
+        event: ContactEvent<Handle>,
+        g1: &dyn Shape<N>,
+        g2: &dyn Shape<N>,
+        contacts: impl Iterator<Item = &'a TrackedContact<N>>,
+    ) {
+        let data = ContactEventData::new(
+            self.timestamp,
+            g1,
+            g2,
+            contacts,
+            &mut self.contact_event_contacts,
+        );
+
+        match data {
+            Some(data) => {
+                let _ = self.contact_event_data.insert(event, data);
+            }
+            None => {
+                let _ = self.contact_event_data.remove(&event);
+            }
+        }
+
+        self.contact_events.push(event);
+    }
+
+    // The speculative contacts are computed with a scratch detector so the state of the
+    // detector of the pair, which is only valid at the current positions, is left untouched.
+    // This scratch detector is kept by the manifold to be reused at the next update.
+    fn add_speculative_contacts<Object: CollisionObjectRef<N>>(
+        &mut self,
+        co1: &Object,
+        co2: &Object,
+        manifold: &mut ContactManifold<N>,
+        prediction: &ContactPrediction<N>,
+    ) {
+        let nsteps = match self.speculative_contacts {
+            SpeculativeContacts::Disabled => return,
+            SpeculativeContacts::PredictedPositions => 1,
+            SpeculativeContacts::InterpolatedMotion(nsteps) => nsteps,
+        };
+
+        if co1.predicted_position().is_none() && co2.predicted_position().is_none() {
+            return;
+        }
+
+        let mut detector = match manifold.speculative_detector_mut().take() {
+            Some(detector) => detector,
+            None => try_ret!(self
+                .contact_dispatcher
+                .get_contact_algorithm(co1.shape(), co2.shape())),
+        };
+
+        let pos1 = co1.position();
+        let pos2 = co2.position();
+        let motion1 =
+            InterpolatedRigidMotion::new(*pos1, *co1.predicted_position().unwrap_or(pos1));
+        let motion2 =
+            InterpolatedRigidMotion::new(*pos2, *co2.predicted_position().unwrap_or(pos2));
+
+        for step in 1..=nsteps {
+            let t = N::from_usize(step).unwrap() / N::from_usize(nsteps).unwrap();
+            let workspace = &mut self.speculative_workspace;
+            workspace.clear();
+
+            let _ = detector.generate_contacts(
+                &*self.contact_dispatcher,
+                &motion1.position_at_time(t),
+                co1.shape(),
+                None,
+                &motion2.position_at_time(t),
+                co2.shape(),
+                None,
+                prediction,
+                workspace,
+            );
+
+            for c in workspace.contacts() {
+                let features = (c.kinematic.feature1(), c.kinematic.feature2());
+
+                if manifold
+                    .contacts()
+                    .any(|c| (c.kinematic.feature1(), c.kinematic.feature2()) == features)
+                {
+                    continue;
+                }
+
+                // Express the contact at the current positions.
+                if let Some(contact) = c.kinematic.contact(
+                    pos1,
+                    co1.shape(),
+                    None,
+                    pos2,
+                    co2.shape(),
+                    None,
+                    &c.contact.normal,
+                ) {
+                    let local1 = c.kinematic.local1();
+                    let _ = manifold.push_speculative(contact, c.kinematic, local1, None, None);
+                }
             }
         }
+
+        *manifold.speculative_detector_mut() = Some(detector);
+    }
+
+    /// Removes all the contacts of the specified contact manifold between two collision objects.
+    ///
+    /// A `ContactEvent::Stopped` is generated if the manifold was not empty.
+    pub fn discard_contacts(
+        &mut self,
+        co1: &impl CollisionObjectRef<N>,
+        co2: &impl CollisionObjectRef<N>,
+        handle1: Handle,
+        handle2: Handle,
+        manifold: &mut ContactManifold<N>,
+    ) {
+        if manifold.len() != 0 {
+            let event = ContactEvent::Stopped(handle1, handle2);
+            self.emit_contact_event(event, co1.shape(), co2.shape(), manifold.contacts());
+        }
+
+        manifold.clear();
     }
 
     // FIXME: the fact this is public is only useful for nphysics.
@@ -114,8 +342,13 @@ impl<N: RealField, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
         new_prox: Proximity,
     ) {
         if prev_prox != new_prox {
-            self.proximity_events
-                .push(ProximityEvent::new(handle1, handle2, prev_prox, new_prox));
+            self.proximity_events.push(ProximityEvent::new(
+                handle1,
+                handle2,
+                prev_prox,
+                new_prox,
+                self.timestamp,
+            ));
         }
     }
 
@@ -142,33 +375,86 @@ impl<N: RealField, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
         }
     }
 
-    /// Update the specified interaction between two collision objects.
-    pub fn update_interaction(
+    /// Update the specified closest points between two collision objects.
+    pub fn update_distance(
         &mut self,
         co1: &impl CollisionObjectRef<N>,
         co2: &impl CollisionObjectRef<N>,
         handle1: Handle,
         handle2: Handle,
+        closest_points: &mut ClosestPoints<N>,
+    ) {
+        let new_closest_points = query::closest_points(
+            &co1.position(),
+            co1.shape(),
+            &co2.position(),
+            co2.shape(),
+            co1.query_type().query_limit() + co2.query_type().query_limit(),
+        );
+
+        let was_in_range = *closest_points != ClosestPoints::Disjoint;
+        let is_in_range = new_closest_points != ClosestPoints::Disjoint;
+
+        if was_in_range && !is_in_range {
+            self.distance_events
+                .push(DistanceEvent::Stopped(handle1, handle2));
+        } else if !was_in_range && is_in_range {
+            self.distance_events
+                .push(DistanceEvent::Started(handle1, handle2));
+        }
+
+        *closest_points = new_closest_points;
+    }
+
+    /// Update the specified interaction between two collision objects.
+    pub fn update_interaction<Object: CollisionObjectRef<N>>(
+        &mut self,
+        co1: &Object,
+        co2: &Object,
+        handle1: Handle,
+        handle2: Handle,
         interaction: &mut Interaction<N>,
+        contact_modifier: Option<&(impl ContactModifier<N, Object, Handle> + ?Sized)>,
     ) {
         match interaction {
-            Interaction::Contact(detector, manifold) => {
-                self.update_contact(co1, co2, handle1, handle2, &mut **detector, manifold)
-            }
+            Interaction::Contact(detector, manifold) => self.update_contact(
+                co1,
+                co2,
+                handle1,
+                handle2,
+                &mut **detector,
+                manifold,
+                contact_modifier,
+            ),
             Interaction::Proximity(detector, prox) => {
                 self.update_proximity(co1, co2, handle1, handle2, &mut **detector, prox)
             }
+            Interaction::Distance(closest_points) => {
+                self.update_distance(co1, co2, handle1, handle2, closest_points)
+            }
         }
     }
 
     /// Updates the narrow-phase by actually computing contact points and proximities between the
     /// interactions pairs reported by the broad-phase.
     ///
+    /// Interactions between two collision objects that are not active are ignored.
+    ///
     /// This will push relevant events to `contact_events` and `proximity_events`.
+    /// A `pair_filter` can be provided to disable contact generation between some pairs of collision objects.
+    /// A `contact_modifier` can be provided to modify the contacts computed for each pair of collision objects.
     pub fn update<Objects>(
         &mut self,
         interactions: &mut InteractionGraph<N, Objects::CollisionObjectHandle>,
         objects: &Objects,
+        pair_filter: Option<
+            &(impl NarrowPhasePairFilter<N, Objects::CollisionObject, Objects::CollisionObjectHandle>
+                  + ?Sized),
+        >,
+        contact_modifier: Option<
+            &(impl ContactModifier<N, Objects::CollisionObject, Objects::CollisionObjectHandle>
+                  + ?Sized),
+        >,
     ) where
         Objects: CollisionObjectSet<N, CollisionObjectHandle = Handle>,
     {
@@ -178,22 +464,60 @@ impl<N: RealField, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
             let handle2 = interactions.0[id2];
             let co1 = objects.collision_object(handle1).unwrap();
             let co2 = objects.collision_object(handle2).unwrap();
+
+            if !co1.activation_status().is_active() && !co2.activation_status().is_active() {
+                // Keep the last contacts and proximities of non-active pairs.
+                continue;
+            }
+
             let flags1 = co1.update_flags();
             let flags2 = co2.update_flags();
+            let mut needs_update = flags1.needs_narrow_phase_update()
+                || flags2.needs_narrow_phase_update()
+                || self.pairs_to_update.contains(&(handle1, handle2));
+
+            if let Interaction::Contact(_, manifold) = interactions.0.edge_weight_mut(eid).unwrap()
+            {
+                let is_valid = pair_filter
+                    .map(|f| f.is_pair_valid(co1, co2, handle1, handle2))
+                    .unwrap_or(true);
+
+                if !is_valid {
+                    if self.filtered_pairs.insert((handle1, handle2)) {
+                        self.discard_contacts(co1, co2, handle1, handle2, manifold);
+                    }
 
-            if flags1.needs_narrow_phase_update() || flags2.needs_narrow_phase_update() {
+                    continue;
+                }
+
+                // Contacts must be recomputed if they were disabled by the filter.
+                needs_update = self.filtered_pairs.remove(&(handle1, handle2)) || needs_update;
+            }
+
+            if needs_update {
                 self.update_interaction(
                     co1,
                     co2,
                     handle1,
                     handle2,
                     interactions.0.edge_weight_mut(eid).unwrap(),
+                    contact_modifier,
                 )
             }
         }
 
+        self.pairs_to_update.clear();
+
         // FIXME: don't do this at each update?
-        self.garbage_collect_ids(interactions)
+        self.garbage_collect_ids(interactions);
+        self.timestamp += 1;
+    }
+
+    /// The number of times `self.update` has been called so far.
+    ///
+    /// This is the timestamp attached to every event generated until the next call to `self.update` completes.
+    pub fn timestamp(&self) -> usize {
+        self.timestamp
     }
 
     /// Handles a pair of collision objects detected as either started or stopped interacting.
@@ -207,14 +531,37 @@ impl<N: RealField, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
     ) where
         Objects: CollisionObjectSet<N, CollisionObjectHandle = Handle>,
     {
+        if started && self.is_pair_excluded(handle1, handle2) {
+            return;
+        }
+
+        let co1_handle = handle1;
         let co1 = objects.collision_object(handle1).unwrap();
         let co2 = objects.collision_object(handle2).unwrap();
-        let id1 = co1.graph_index().expect(crate::NOT_REGISTERED_ERROR);
-        let id2 = co2.graph_index().expect(crate::NOT_REGISTERED_ERROR);
+        let id1 = objects
+            .graph_index(handle1)
+            .expect(crate::NOT_REGISTERED_ERROR);
+        let id2 = objects
+            .graph_index(handle2)
+            .expect(crate::NOT_REGISTERED_ERROR);
 
         if started {
             if !interactions.0.contains_edge(id1, id2) {
                 match (co1.query_type(), co2.query_type()) {
+                    (_, GeometricQueryType::Proximity(_))
+                    | (GeometricQueryType::Proximity(_), _) => {
+                        let dispatcher = &self.proximity_dispatcher;
+
+                        if let Some(detector) =
+                            dispatcher.get_proximity_algorithm(co1.shape(), co2.shape())
+                        {
+                            let _ = interactions.0.add_edge(
+                                id1,
+                                id2,
+                                Interaction::Proximity(detector, Proximity::Disjoint),
+                            );
+                        }
+                    }
                     (GeometricQueryType::Contacts(..), GeometricQueryType::Contacts(..)) => {
                         let dispatcher = &self.contact_dispatcher;
 
@@ -229,17 +576,12 @@ impl<N: RealField, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
                             );
                         }
                     }
-                    (_, GeometricQueryType::Proximity(_))
-                    | (GeometricQueryType::Proximity(_), _) => {
-                        let dispatcher = &self.proximity_dispatcher;
-
-                        if let Some(detector) =
-                            dispatcher.get_proximity_algorithm(co1.shape(), co2.shape())
-                        {
+                    (_, GeometricQueryType::Distance(_)) | (GeometricQueryType::Distance(_), _) => {
+                        if query::is_distance_supported(co1.shape(), co2.shape()) {
                             let _ = interactions.0.add_edge(
                                 id1,
                                 id2,
-                                Interaction::Proximity(detector, Proximity::Disjoint),
+                                Interaction::Distance(ClosestPoints::Disjoint),
                             );
                         }
                     }
@@ -250,14 +592,22 @@ impl<N: RealField, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
                 let endpoints = interactions.0.edge_endpoints(eid).unwrap();
                 let handle1 = *interactions.0.node_weight(endpoints.0).unwrap();
                 let handle2 = *interactions.0.node_weight(endpoints.1).unwrap();
+                let _ = self.filtered_pairs.remove(&(handle1, handle2));
 
                 if let Some(detector) = interactions.0.remove_edge(eid) {
                     match detector {
                         Interaction::Contact(_, mut manifold) => {
                             // Register a collision lost event if there was a contact.
                             if manifold.len() != 0 {
-                                self.contact_events
-                                    .push(ContactEvent::Stopped(handle1, handle2));
+                                // NOTE: the endpoints order may not match `co1` and `co2`.
+                                let (g1, g2) = if handle1 == co1_handle {
+                                    (co1.shape(), co2.shape())
+                                } else {
+                                    (co2.shape(), co1.shape())
+                                };
+
+                                let event = ContactEvent::Stopped(handle1, handle2);
+                                self.emit_contact_event(event, g1, g2, manifold.contacts());
                             }
 
                             manifold.clear();
@@ -271,25 +621,114 @@ impl<N: RealField, Handle: CollisionObjectHandle> NarrowPhase<N, Handle> {
                                 Proximity::Disjoint,
                             );
                         }
+                        Interaction::Distance(closest_points) => {
+                            // Register a distance lost event if they were in range.
+                            if closest_points != ClosestPoints::Disjoint {
+                                self.distance_events
+                                    .push(DistanceEvent::Stopped(handle1, handle2));
+                            }
+                        }
                     }
                 }
             }
         }
     }
 
+    /// Excludes the given pair of collision objects from any interaction.
+    ///
+    /// This does not remove any interaction already existing between those objects from
+    /// the interaction graph: this should be done with `self.handle_interaction(..., false)`.
+    /// Returns `false` if this pair was already excluded.
+    pub fn exclude_pair(&mut self, handle1: Handle, handle2: Handle) -> bool {
+        let _ = self.excluded_pairs.insert((handle2, handle1));
+        self.excluded_pairs.insert((handle1, handle2))
+    }
+
+    /// Removes the given pair of collision objects from the set of excluded pairs.
+    ///
+    /// The interaction between those objects, if any is created in the meantime by
+    /// `self.handle_interaction`, will be updated during the next call to `self.update`.
+    /// Returns `false` if this pair was not excluded.
+    pub fn include_pair(&mut self, handle1: Handle, handle2: Handle) -> bool {
+        let _ = self.excluded_pairs.remove(&(handle2, handle1));
+
+        if self.excluded_pairs.remove(&(handle1, handle2)) {
+            let _ = self.pairs_to_update.insert((handle1, handle2));
+            let _ = self.pairs_to_update.insert((handle2, handle1));
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Forces the update of all the interactions involving the given collision object during the next
+    /// call to `self.update`, even if the collision objects involved did not change.
+    pub fn update_interactions_with(
+        &mut self,
+        interactions: &InteractionGraph<N, Handle>,
+        id: CollisionObjectGraphIndex,
+    ) {
+        let handle = interactions.0[id];
+
+        for neighbor in interactions.0.neighbors(id) {
+            let neighbor = interactions.0[neighbor];
+            let _ = self.pairs_to_update.insert((handle, neighbor));
+            let _ = self.pairs_to_update.insert((neighbor, handle));
+        }
+    }
+
+    /// Checks if the given pair of collision objects is excluded from any interaction.
+    pub fn is_pair_excluded(&self, handle1: Handle, handle2: Handle) -> bool {
+        self.excluded_pairs.contains(&(handle1, handle2))
+    }
+
+    /// Removes from the set of excluded pairs all the pairs involving the given collision object.
+    pub fn include_all_pairs_with(&mut self, handle: Handle) {
+        self.excluded_pairs
+            .retain(|pair| pair.0 != handle && pair.1 != handle)
+    }
+
     /// The set of contact events generated by this narrow-phase.
     pub fn contact_events(&self) -> &ContactEvents<Handle> {
         &self.contact_events
     }
 
+    /// The contact information attached to the given contact event.
+    ///
+    /// Returns `None` if `event` has not been generated since the last call to `self.clear_events`,
+    /// or if no contact was known when it was generated. If the same event has been generated several
+    /// times, this is the data of the last one.
+    pub fn contact_event_data(&self, event: &ContactEvent<Handle>) -> Option<&ContactEventData<N>> {
+        self.contact_event_data.get(event)
+    }
+
+    /// The contacts involved in the given contact event.
+    ///
+    /// For a `ContactEvent::Stopped`, those are the last contacts known before the two collision
+    /// objects stopped being in contact. This is empty if `self.contact_event_data(event)` is `None`.
+    pub fn contact_event_contacts(&self, event: &ContactEvent<Handle>) -> &[TrackedContact<N>] {
+        match self.contact_event_data.get(event) {
+            Some(data) => data.contacts(&self.contact_event_contacts),
+            None => &[],
+        }
+    }
+
     /// The set of proximity events generated by this narrow-phase.
     pub fn proximity_events(&self) -> &ProximityEvents<Handle> {
         &self.proximity_events
     }
 
+    /// The set of distance events generated by this narrow-phase.
+    pub fn distance_events(&self) -> &DistanceEvents<Handle> {
+        &self.distance_events
+    }
+
     /// Clear the events generated by this narrow-phase.
     pub fn clear_events(&mut self) {
         self.contact_events.clear();
+        self.contact_event_data.clear();
+        self.contact_event_contacts.clear();
         self.proximity_events.clear();
+        self.distance_events.clear();
     }
 }
@@ -0,0 +1,19 @@
+use na::RealField;
+use std::any::Any;
+
+/// A user-defined filter that can disable contact generation between two collision objects.
+pub trait NarrowPhasePairFilter<N: RealField, Object, Handle>: Any + Send + Sync {
+    /// Checks if contacts should be computed between the two given collision objects.
+    ///
+    /// This is evaluated by the narrow-phase at each update for every pair of collision objects
+    /// with a contact interaction. If this returns `false`, the contacts of this pair are discarded but
+    /// the interaction itself is kept alive, so contacts are computed again as soon as this filter
+    /// returns `true` without requiring any broad-phase update.
+    fn is_pair_valid(&self, co1: &Object, co2: &Object, h1: Handle, h2: Handle) -> bool;
+}
+
+impl<N: RealField, Object, Handle> NarrowPhasePairFilter<N, Object, Handle> for () {
+    fn is_pair_valid(&self, _: &Object, _: &Object, _: Handle, _: Handle) -> bool {
+        true
+    }
+}
@@ -0,0 +1,348 @@
+use crate::interpolation::RigidMotion;
+use crate::math::{Isometry, Vector};
+use crate::pipeline::narrow_phase::{
+    ContactAlgorithm, ContactDispatcher, DefaultContactDispatcher, DefaultProximityDispatcher,
+    ProximityAlgorithm, ProximityDispatcher,
+};
+use crate::query::{DefaultTOIDispatcher, TOIDispatcher, Unsupported, TOI};
+use crate::shape::Shape;
+use na::RealField;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A set of shapes an algorithm can be registered for on a `ShapeRegistry`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ShapeKind {
+    /// The shapes with the given concrete type.
+    Type(TypeId),
+    /// The shapes that can be represented as a convex polyhedron.
+    ConvexPolyhedron,
+    /// The shapes that can be represented as a support map.
+    SupportMap,
+    /// The shapes that can be represented as a composite shape.
+    CompositeShape,
+    /// All the shapes.
+    Any,
+}
+
+impl ShapeKind {
+    /// The kind matching only the shapes of type `S`.
+    pub fn of<N: RealField, S: Shape<N>>() -> Self {
+        ShapeKind::Type(TypeId::of::<S>())
+    }
+
+    /// Tests if `shape` belongs to this kind.
+    pub fn matches<N: RealField>(&self, shape: &dyn Shape<N>) -> bool {
+        match self {
+            ShapeKind::Type(id) => Any::type_id(shape.as_any()) == *id,
+            ShapeKind::ConvexPolyhedron => shape.is_convex_polyhedron(),
+            ShapeKind::SupportMap => shape.is_support_map(),
+            ShapeKind::CompositeShape => shape.is_composite_shape(),
+            ShapeKind::Any => true,
+        }
+    }
+
+    // All the kinds `shape` belongs to, from the most specific to the least specific.
+    fn kinds_of<N: RealField>(shape: &dyn Shape<N>) -> [Option<ShapeKind>; 5] {
+        let kinds = [
+            ShapeKind::Type(Any::type_id(shape.as_any())),
+            ShapeKind::ConvexPolyhedron,
+            ShapeKind::SupportMap,
+            ShapeKind::CompositeShape,
+            ShapeKind::Any,
+        ];
+        let mut result = [None; 5];
+
+        for (kind, res) in kinds.iter().zip(result.iter_mut()) {
+            if kind.matches(shape) {
+                *res = Some(*kind)
+            }
+        }
+
+        result
+    }
+}
+
+type ContactAlgorithmFactory<N> = dyn Fn(bool) -> ContactAlgorithm<N> + Send + Sync;
+type ProximityAlgorithmFactory<N> = dyn Fn(bool) -> ProximityAlgorithm<N> + Send + Sync;
+type Registrations<T> = HashMap<(ShapeKind, ShapeKind), Arc<T>>;
+
+/// A registry of algorithms for user-defined shapes.
+///
+/// Algorithms are registered for a pair of `ShapeKind`, e.g., for a user-defined shape and any
+/// support map. The registry implements the `ContactDispatcher`, `ProximityDispatcher` and
+/// `TOIDispatcher` traits: the algorithms registered for at least one `ShapeKind::Type` are used
+/// first, then the built-in algorithms of the default dispatchers, and finally the algorithms
+/// registered for generic kinds only, e.g., `ShapeKind::SupportMap`. If several registered pairs of
+/// kinds match a pair of shapes, the one with the most specific kinds is used.
+///
+/// A registry is cheap to clone so the same registry can be given to every dispatch site, e.g., with
+/// `CollisionWorld::set_shape_registry`.
+pub struct ShapeRegistry<N: RealField> {
+    contact_algorithms: Registrations<ContactAlgorithmFactory<N>>,
+    proximity_algorithms: Registrations<ProximityAlgorithmFactory<N>>,
+    toi_dispatchers: Registrations<dyn TOIDispatcher<N>>,
+}
+
+impl<N: RealField> Clone for ShapeRegistry<N> {
+    fn clone(&self) -> Self {
+        ShapeRegistry {
+            contact_algorithms: self.contact_algorithms.clone(),
+            proximity_algorithms: self.proximity_algorithms.clone(),
+            toi_dispatchers: self.toi_dispatchers.clone(),
+        }
+    }
+}
+
+impl<N: RealField> Default for ShapeRegistry<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: RealField> ShapeRegistry<N> {
+    /// Creates a registry without any registered algorithm.
+    pub fn new() -> Self {
+        ShapeRegistry {
+            contact_algorithms: HashMap::new(),
+            proximity_algorithms: HashMap::new(),
+            toi_dispatchers: HashMap::new(),
+        }
+    }
+
+    /// Registers a contact manifold generator for the shapes of kinds `kind1` and `kind2`.
+    ///
+    /// The `factory` is given `true` if the shapes are given to the generator in reverse order, i.e.,
+    /// if the shape of kind `kind1` is the second shape.
+    pub fn register_contact_algorithm(
+        &mut self,
+        kind1: ShapeKind,
+        kind2: ShapeKind,
+        factory: impl Fn(bool) -> ContactAlgorithm<N> + Send + Sync + 'static,
+    ) {
+        let _ = self
+            .contact_algorithms
+            .insert((kind1, kind2), Arc::new(factory));
+    }
+
+    /// Registers a proximity detector for the shapes of kinds `kind1` and `kind2`.
+    ///
+    /// The `factory` is given `true` if the shapes are given to the detector in reverse order, i.e.,
+    /// if the shape of kind `kind1` is the second shape.
+    pub fn register_proximity_algorithm(
+        &mut self,
+        kind1: ShapeKind,
+        kind2: ShapeKind,
+        factory: impl Fn(bool) -> ProximityAlgorithm<N> + Send + Sync + 'static,
+    ) {
+        let _ = self
+            .proximity_algorithms
+            .insert((kind1, kind2), Arc::new(factory));
+    }
+
+    /// Registers a time-of-impact dispatcher for the shapes of kinds `kind1` and `kind2`.
+    ///
+    /// The `dispatcher` is always given the shape of kind `kind1` first. The results are swapped
+    /// automatically if the shapes were given in reverse order to the registry.
+    pub fn register_toi_dispatcher(
+        &mut self,
+        kind1: ShapeKind,
+        kind2: ShapeKind,
+        dispatcher: impl TOIDispatcher<N> + 'static,
+    ) {
+        let _ = self
+            .toi_dispatchers
+            .insert((kind1, kind2), Arc::new(dispatcher));
+    }
+
+    // Finds the registration with the most specific kinds matching the given shapes.
+    //
+    // Only the registrations involving at least one `ShapeKind::Type` are considered if
+    // `with_type` is `true`, and only the other ones otherwise.
+    // Returns `true` alongside the registration if it has been registered for the shapes in reverse order.
+    fn find<'a, T: ?Sized>(
+        registrations: &'a Registrations<T>,
+        a: &dyn Shape<N>,
+        b: &dyn Shape<N>,
+        with_type: bool,
+    ) -> Option<(&'a T, bool)> {
+        if registrations.is_empty() {
+            return None;
+        }
+
+        let kinds_a = ShapeKind::kinds_of(a);
+        let kinds_b = ShapeKind::kinds_of(b);
+        let n = kinds_a.len();
+
+        for specificity in 0..2 * n - 1 {
+            let first = specificity.saturating_sub(n - 1);
+            let last = n.min(specificity + 1);
+
+            for (i, ka) in kinds_a.iter().enumerate().take(last).skip(first) {
+                let j = specificity - i;
+
+                if let (Some(ka), Some(kb)) = (*ka, kinds_b[j]) {
+                    let has_type =
+                        matches!(ka, ShapeKind::Type(_)) || matches!(kb, ShapeKind::Type(_));
+
+                    if has_type != with_type {
+                        continue;
+                    }
+
+                    if let Some(registration) = registrations.get(&(ka, kb)) {
+                        return Some((&**registration, false));
+                    }
+
+                    if let Some(registration) = registrations.get(&(kb, ka)) {
+                        return Some((&**registration, true));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl<N: RealField> ContactDispatcher<N> for ShapeRegistry<N> {
+    fn get_contact_algorithm(
+        &self,
+        a: &dyn Shape<N>,
+        b: &dyn Shape<N>,
+    ) -> Option<ContactAlgorithm<N>> {
+        let registered = |with_type| {
+            Self::find(&self.contact_algorithms, a, b, with_type)
+                .map(|(factory, flip)| factory(flip))
+        };
+
+        registered(true)
+            .or_else(|| DefaultContactDispatcher::new().get_contact_algorithm(a, b))
+            .or_else(|| registered(false))
+    }
+}
+
+impl<N: RealField> ProximityDispatcher<N> for ShapeRegistry<N> {
+    fn get_proximity_algorithm(
+        &self,
+        a: &dyn Shape<N>,
+        b: &dyn Shape<N>,
+    ) -> Option<ProximityAlgorithm<N>> {
+        let registered = |with_type| {
+            Self::find(&self.proximity_algorithms, a, b, with_type)
+                .map(|(factory, flip)| factory(flip))
+        };
+
+        registered(true)
+            .or_else(|| DefaultProximityDispatcher::new().get_proximity_algorithm(a, b))
+            .or_else(|| registered(false))
+    }
+}
+
+impl<N: RealField> TOIDispatcher<N> for ShapeRegistry<N> {
+    fn nonlinear_time_of_impact(
+        &self,
+        root_dispatcher: &dyn TOIDispatcher<N>,
+        motion1: &dyn RigidMotion<N>,
+        g1: &dyn Shape<N>,
+        motion2: &dyn RigidMotion<N>,
+        g2: &dyn Shape<N>,
+        max_toi: N,
+        target_distance: N,
+    ) -> Result<Option<TOI<N>>, Unsupported> {
+        let registered = |with_type| match Self::find(&self.toi_dispatchers, g1, g2, with_type) {
+            Some((dispatcher, false)) => dispatcher.nonlinear_time_of_impact(
+                root_dispatcher,
+                motion1,
+                g1,
+                motion2,
+                g2,
+                max_toi,
+                target_distance,
+            ),
+            Some((dispatcher, true)) => dispatcher
+                .nonlinear_time_of_impact(
+                    root_dispatcher,
+                    motion2,
+                    g2,
+                    motion1,
+                    g1,
+                    max_toi,
+                    target_distance,
+                )
+                .map(|toi| toi.map(TOI::swapped)),
+            None => Err(Unsupported),
+        };
+
+        registered(true)
+            .or_else(|Unsupported| {
+                DefaultTOIDispatcher.nonlinear_time_of_impact(
+                    root_dispatcher,
+                    motion1,
+                    g1,
+                    motion2,
+                    g2,
+                    max_toi,
+                    target_distance,
+                )
+            })
+            .or_else(|Unsupported| registered(false))
+    }
+
+    fn time_of_impact(
+        &self,
+        root_dispatcher: &dyn TOIDispatcher<N>,
+        m1: &Isometry<N>,
+        vel1: &Vector<N>,
+        g1: &dyn Shape<N>,
+        m2: &Isometry<N>,
+        vel2: &Vector<N>,
+        g2: &dyn Shape<N>,
+        max_toi: N,
+        target_distance: N,
+    ) -> Result<Option<TOI<N>>, Unsupported> {
+        let registered = |with_type| match Self::find(&self.toi_dispatchers, g1, g2, with_type) {
+            Some((dispatcher, false)) => dispatcher.time_of_impact(
+                root_dispatcher,
+                m1,
+                vel1,
+                g1,
+                m2,
+                vel2,
+                g2,
+                max_toi,
+                target_distance,
+            ),
+            Some((dispatcher, true)) => dispatcher
+                .time_of_impact(
+                    root_dispatcher,
+                    m2,
+                    vel2,
+                    g2,
+                    m1,
+                    vel1,
+                    g1,
+                    max_toi,
+                    target_distance,
+                )
+                .map(|toi| toi.map(TOI::swapped)),
+            None => Err(Unsupported),
+        };
+
+        registered(true)
+            .or_else(|Unsupported| {
+                DefaultTOIDispatcher.time_of_impact(
+                    root_dispatcher,
+                    m1,
+                    vel1,
+                    g1,
+                    m2,
+                    vel2,
+                    g2,
+                    max_toi,
+                    target_distance,
+                )
+            })
+            .or_else(|Unsupported| registered(false))
+    }
+}
@@ -0,0 +1,27 @@
+/// The strategy used by the narrow phase to compute speculative contacts.
+///
+/// Speculative contacts are computed only for pairs of collision objects where at least one has a
+/// predicted position (set with `.set_position_with_prediction` or `.set_predicted_position`). They
+/// are computed at the predicted positions of the collision objects, and then expressed at their current
+/// positions using the contact kinematics. Thus, they usually have a negative penetration depth equal to
+/// the distance the collision objects can travel before touching. Because they are part of the contact
+/// manifolds, they generate contact events just like the contacts resulting from the contact prediction
+/// margins. They are flagged with `TrackedContact::speculative`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SpeculativeContacts {
+    /// No speculative contact is computed.
+    Disabled,
+    /// Speculative contacts are computed at the predicted positions of the collision objects.
+    PredictedPositions,
+    /// Speculative contacts are computed at the given number of positions evenly spaced along the
+    /// motion interpolated between the current and predicted positions of the collision objects.
+    ///
+    /// The last of those positions is the predicted one.
+    InterpolatedMotion(usize),
+}
+
+impl Default for SpeculativeContacts {
+    fn default() -> Self {
+        SpeculativeContacts::Disabled
+    }
+}
@@ -1,15 +1,76 @@
 use crate::pipeline::broad_phase::BroadPhasePairFilter;
 use crate::pipeline::object::{CollisionObjectHandle, CollisionObjectRef};
 use na::RealField;
+use std::fmt::Debug;
+use std::ops::{BitAnd, BitOr, Not};
+
+/// Data attached to a collision object to decide with which other collision objects it may interact.
+pub trait InteractionGroups: Clone + Debug + Send + Sync + 'static {
+    /// Tests whether the owners of `self` and `other` may interact.
+    fn can_interact_with_groups(&self, other: &Self) -> bool;
+    /// Tests whether the owner of `self` may interact with itself.
+    fn can_interact_with_self(&self) -> bool;
+}
+
+/// A bit mask type usable to represent collision groups.
+///
+/// The most significant bit of the mask is reserved for self-interaction.
+pub trait CollisionGroupsMask:
+    Copy
+    + Debug
+    + PartialEq
+    + PartialOrd
+    + Send
+    + Sync
+    + 'static
+    + BitAnd<Output = Self>
+    + BitOr<Output = Self>
+    + Not<Output = Self>
+{
+    /// The number of groups representable by this mask.
+    const NUM_GROUPS: usize;
+    /// The mask containing no group.
+    const NO_GROUP: Self;
+    /// The mask containing all the groups.
+    const ALL_GROUPS: Self;
+    /// The bit reserved for self-interaction.
+    const SELF_COLLISION: Self;
+
+    /// The mask containing only the given group.
+    fn group(group_id: usize) -> Self;
+}
 
-const SELF_COLLISION: u32 = 1 << 31;
-const ALL_GROUPS: u32 = (1 << 30) - 1;
-const NO_GROUP: u32 = 0;
+macro_rules! impl_collision_groups_mask(
+    ($($t: ty, $num_groups: expr);*) => {$(
+        impl CollisionGroupsMask for $t {
+            const NUM_GROUPS: usize = $num_groups;
+            const NO_GROUP: Self = 0;
+            const ALL_GROUPS: Self = (1 << $num_groups) - 1;
+            const SELF_COLLISION: Self = 1 << (<$t>::max_value().count_ones() - 1);
+
+            #[inline]
+            fn group(group_id: usize) -> Self {
+                1 << group_id
+            }
+        }
+    )*}
+);
+
+// NOTE: the `u32` mask only has 30 groups for backward compatibility.
+impl_collision_groups_mask!(u32, 30; u64, 63; u128, 127);
+
+/// Collision groups with at most 30 groups indexed from 0 to 29 (included).
+pub type CollisionGroups = GenericCollisionGroups<u32>;
+/// Collision groups stored in a `u64`, with at most 63 groups indexed from 0 to 62 (included).
+pub type CollisionGroupsU64 = GenericCollisionGroups<u64>;
+/// Collision groups stored in a `u128`, with at most 127 groups indexed from 0 to 126 (included).
+pub type CollisionGroupsU128 = GenericCollisionGroups<u128>;
 
 /// Groups of collision used to filter which object interact with which other one.
 ///
-/// There are at most 30 groups indexed from 0 to 29 (included). This identifies collidable
-/// entities by combining three attributes:
+/// The number of groups depends on the mask type `M`, e.g., there are at most 30 groups indexed from
+/// 0 to 29 (included) for the `CollisionGroups` alias. This identifies collidable entities by
+/// combining three attributes:
 ///    * A set of group this structure is member of.
 ///    * A collision group whitelist.
 ///    * A collision group blacklist.
@@ -36,36 +97,36 @@ const NO_GROUP: u32 = 0;
 ///
 /// Then we have:
 ///    * A and C can interact because A whitelists the group 6 (which C is part of), and,
-///    reciprocally, C whitelists the group 3 (which A is part of).
+///      reciprocally, C whitelists the group 3 (which A is part of).
 ///    * A and B will **not** interact because B is part of the group 1 which is blacklisted by A.
 ///    * Finally, B and C will **not** interact either because, even if C whitelists the group 3
-///    (which B is part of), B does not whitelists the groups 6 nor 9 (which B is part of).
+///      (which B is part of), B does not whitelists the groups 6 nor 9 (which B is part of).
 #[derive(Clone, Debug, Copy)]
-pub struct CollisionGroups {
-    membership: u32,
-    whitelist: u32,
-    blacklist: u32,
+pub struct GenericCollisionGroups<M: CollisionGroupsMask> {
+    membership: M,
+    whitelist: M,
+    blacklist: M,
 }
 
-impl CollisionGroups {
+impl<M: CollisionGroupsMask> GenericCollisionGroups<M> {
     /// Creates a new `CollisionGroups` that enables interactions with everything except
     /// self-interaction.
     #[inline]
-    pub fn new() -> CollisionGroups {
-        CollisionGroups {
-            membership: ALL_GROUPS,
-            whitelist: ALL_GROUPS,
-            blacklist: NO_GROUP,
+    pub fn new() -> Self {
+        GenericCollisionGroups {
+            membership: M::ALL_GROUPS,
+            whitelist: M::ALL_GROUPS,
+            blacklist: M::NO_GROUP,
         }
     }
 
     /// Creates a new `CollisionGroups` that disables interactions with everything.
     #[inline]
-    pub fn empty() -> CollisionGroups {
-        CollisionGroups {
-            membership: NO_GROUP,
-            whitelist: NO_GROUP,
-            blacklist: NO_GROUP,
+    pub fn empty() -> Self {
+        GenericCollisionGroups {
+            membership: M::NO_GROUP,
+            whitelist: M::NO_GROUP,
+            blacklist: M::NO_GROUP,
         }
     }
 
@@ -81,8 +142,8 @@ impl CollisionGroups {
     /// assert!(groups.is_member_of(GROUP_B));
     /// ```
     #[inline]
-    pub fn with_membership(mut self, groups: &[usize]) -> CollisionGroups {
-        CollisionGroups::set_mask(&mut self.membership, groups);
+    pub fn with_membership(mut self, groups: &[usize]) -> Self {
+        Self::set_mask(&mut self.membership, groups);
         self
     }
 
@@ -98,8 +159,8 @@ impl CollisionGroups {
     /// assert!(group_a.is_group_whitelisted(GROUP_B));
     /// ```
     #[inline]
-    pub fn with_whitelist(mut self, groups: &[usize]) -> CollisionGroups {
-        CollisionGroups::set_mask(&mut self.whitelist, groups);
+    pub fn with_whitelist(mut self, groups: &[usize]) -> Self {
+        Self::set_mask(&mut self.whitelist, groups);
         self
     }
 
@@ -115,80 +176,86 @@ impl CollisionGroups {
     /// assert!(group_a.is_group_blacklisted(GROUP_B));
     /// ```
     #[inline]
-    pub fn with_blacklist(mut self, groups: &[usize]) -> CollisionGroups {
-        CollisionGroups::set_mask(&mut self.blacklist, groups);
+    pub fn with_blacklist(mut self, groups: &[usize]) -> Self {
+        Self::set_mask(&mut self.blacklist, groups);
         self
     }
 
     /// The maximum allowed group identifier.
     #[inline]
     pub fn max_group_id() -> usize {
-        29
+        M::NUM_GROUPS - 1
     }
 
     #[inline]
-    fn modify_mask(mask: &mut u32, group_id: usize, add: bool) {
+    fn assert_group_id(group_id: usize) {
         assert!(
-            group_id < 30,
-            "There are at most 30 groups indexed from 0 to 29 (included)."
+            group_id < M::NUM_GROUPS,
+            "There are at most {} groups indexed from 0 to {} (included).",
+            M::NUM_GROUPS,
+            M::NUM_GROUPS - 1
         );
+    }
+
+    #[inline]
+    fn assert_group_mask(group_mask: M) {
+        assert!(
+            group_mask <= M::ALL_GROUPS,
+            "There are at most {} groups indexed from 0 to {} (included).",
+            M::NUM_GROUPS,
+            M::NUM_GROUPS - 1
+        );
+    }
+
+    #[inline]
+    fn modify_mask(mask: &mut M, group_id: usize, add: bool) {
+        Self::assert_group_id(group_id);
 
         if add {
-            *mask = *mask | (1 << group_id)
+            *mask = *mask | M::group(group_id)
         } else {
-            *mask = *mask & !(1 << group_id)
+            *mask = *mask & !M::group(group_id)
         }
     }
 
     #[inline]
-    fn set_mask(mask: &mut u32, groups: &[usize]) {
-        *mask = 0;
+    fn set_mask(mask: &mut M, groups: &[usize]) {
+        *mask = M::NO_GROUP;
         for g in groups.iter() {
-            CollisionGroups::modify_mask(mask, *g, true);
+            Self::modify_mask(mask, *g, true);
         }
     }
 
     #[inline]
-    fn add_mask(cur_mask: u32, new_mask: u32) -> u32 {
-        assert!(
-            new_mask <= ALL_GROUPS,
-            "There are at most 30 groups indexed from 0 to 29 (included)."
-        );
-
+    fn add_mask(cur_mask: M, new_mask: M) -> M {
+        Self::assert_group_mask(new_mask);
         cur_mask | new_mask
     }
 
     #[inline]
-    fn remove_mask(cur_mask: u32, new_mask: u32) -> u32 {
-        assert!(
-            new_mask <= ALL_GROUPS,
-            "There are at most 30 groups indexed from 0 to 29 (included)."
-        );
-
+    fn remove_mask(cur_mask: M, new_mask: M) -> M {
+        Self::assert_group_mask(new_mask);
         cur_mask & !new_mask
     }
 
     #[inline]
     /// adds this entity to the given group by a mask of bits where each bit index represent a group
-    pub fn add_membership_by_mask(mut self, group_mask: u32) -> CollisionGroups {
+    pub fn add_membership_by_mask(mut self, group_mask: M) -> Self {
         self.membership = Self::add_mask(self.membership, group_mask);
         self
     }
 
     #[inline]
     /// removes this entity from the given group by a mask of bits where each bit index represent a group
-    pub fn remove_membership_by_mask(mut self, group_mask: u32) -> CollisionGroups {
+    pub fn remove_membership_by_mask(mut self, group_mask: M) -> Self {
         self.membership = Self::remove_mask(self.membership, group_mask);
         self
     }
 
     #[inline]
     /// Replaces the membership with a mask of bits where each bit index represent a group
-    pub fn with_membership_by_mask(mut self, group_mask: u32) -> CollisionGroups {
-        assert!(
-            group_mask <= ALL_GROUPS,
-            "There are at most 30 groups indexed from 0 to 29 (included)."
-        );
+    pub fn with_membership_by_mask(mut self, group_mask: M) -> Self {
+        Self::assert_group_mask(group_mask);
 
         self.membership = group_mask;
         self
@@ -196,25 +263,22 @@ impl CollisionGroups {
 
     #[inline]
     /// adds this entity to this entity whitelist by a mask of bits where each bit index represent a group
-    pub fn add_whitelist_by_mask(mut self, group_mask: u32) -> CollisionGroups {
+    pub fn add_whitelist_by_mask(mut self, group_mask: M) -> Self {
         self.whitelist = Self::add_mask(self.whitelist, group_mask);
         self
     }
 
     #[inline]
     /// remove this entity from this entity whitelist by a mask of bits where each bit index represent a group
-    pub fn remove_whitelist_by_mask(mut self, group_mask: u32) -> CollisionGroups {
+    pub fn remove_whitelist_by_mask(mut self, group_mask: M) -> Self {
         self.whitelist = Self::remove_mask(self.whitelist, group_mask);
         self
     }
 
     #[inline]
     /// Replaces the whitelist with a mask of bits where each bit index represent a group
-    pub fn with_whitelist_by_mask(mut self, group_mask: u32) -> CollisionGroups {
-        assert!(
-            group_mask <= ALL_GROUPS,
-            "There are at most 30 groups indexed from 0 to 29 (included)."
-        );
+    pub fn with_whitelist_by_mask(mut self, group_mask: M) -> Self {
+        Self::assert_group_mask(group_mask);
 
         self.whitelist = group_mask;
         self
@@ -222,25 +286,22 @@ impl CollisionGroups {
 
     #[inline]
     /// adds this entity to this entity blacklist by a mask of bits where each bit index represent a group
-    pub fn add_blacklist_by_mask(mut self, group_mask: u32) -> CollisionGroups {
+    pub fn add_blacklist_by_mask(mut self, group_mask: M) -> Self {
         self.blacklist = Self::add_mask(self.blacklist, group_mask);
         self
     }
 
     #[inline]
     /// remove this entity from this entity blacklist by a mask of bits where each bit index represent a group
-    pub fn remove_blacklist_by_mask(mut self, group_mask: u32) -> CollisionGroups {
+    pub fn remove_blacklist_by_mask(mut self, group_mask: M) -> Self {
         self.blacklist = Self::remove_mask(self.blacklist, group_mask);
         self
     }
 
     #[inline]
     /// Replaces the blacklist with a mask of bits where each bit index represent a group
-    pub fn with_blacklist_by_mask(mut self, group_mask: u32) -> CollisionGroups {
-        assert!(
-            group_mask <= ALL_GROUPS,
-            "There are at most 30 groups indexed from 0 to 29 (included)."
-        );
+    pub fn with_blacklist_by_mask(mut self, group_mask: M) -> Self {
+        Self::assert_group_mask(group_mask);
 
         self.blacklist = group_mask;
         self
@@ -249,94 +310,91 @@ impl CollisionGroups {
     /// Adds or removes this entity from the given group.
     #[inline]
     pub fn modify_membership(&mut self, group_id: usize, add: bool) {
-        CollisionGroups::modify_mask(&mut self.membership, group_id, add);
+        Self::modify_mask(&mut self.membership, group_id, add);
     }
 
     /// Adds or removes the given group from this entity whitelist.
     #[inline]
     pub fn modify_whitelist(&mut self, group_id: usize, add: bool) {
-        CollisionGroups::modify_mask(&mut self.whitelist, group_id, add);
+        Self::modify_mask(&mut self.whitelist, group_id, add);
     }
 
     /// Adds or removes this entity from the given group.
     #[inline]
     pub fn modify_blacklist(&mut self, group_id: usize, add: bool) {
-        CollisionGroups::modify_mask(&mut self.blacklist, group_id, add);
+        Self::modify_mask(&mut self.blacklist, group_id, add);
     }
 
     /// Make this object member of the given groups only.
     #[inline]
     pub fn set_membership(&mut self, groups: &[usize]) {
-        CollisionGroups::set_mask(&mut self.membership, groups);
+        Self::set_mask(&mut self.membership, groups);
     }
 
     /// Whitelists the given groups only (others will be un-whitelisted).
     #[inline]
     pub fn set_whitelist(&mut self, groups: &[usize]) {
-        CollisionGroups::set_mask(&mut self.whitelist, groups);
+        Self::set_mask(&mut self.whitelist, groups);
     }
 
     /// Blacklists the given groups only (others will be un-blacklisted).
     #[inline]
     pub fn set_blacklist(&mut self, groups: &[usize]) {
-        CollisionGroups::set_mask(&mut self.blacklist, groups);
+        Self::set_mask(&mut self.blacklist, groups);
     }
 
     /// Copies the membership of another collision groups.
     #[inline]
-    pub fn copy_membership(&mut self, other: &CollisionGroups) {
+    pub fn copy_membership(&mut self, other: &Self) {
         self.membership = other.membership
     }
 
     /// Copies the whitelist of another collision groups.
     #[inline]
-    pub fn copy_whitelist(&mut self, other: &CollisionGroups) {
+    pub fn copy_whitelist(&mut self, other: &Self) {
         self.whitelist = other.whitelist
     }
 
     /// Copies the blacklist of another collision groups.
     #[inline]
-    pub fn copy_blacklist(&mut self, other: &CollisionGroups) {
+    pub fn copy_blacklist(&mut self, other: &Self) {
         self.blacklist = other.blacklist
     }
 
     /// Allows the object to interact with itself.
     #[inline]
     pub fn enable_self_interaction(&mut self) {
-        self.whitelist = self.whitelist | SELF_COLLISION;
+        self.whitelist = self.whitelist | M::SELF_COLLISION;
     }
 
     /// Prevents the object from interacting with itself.
     #[inline]
     pub fn disable_self_interaction(&mut self) {
-        self.whitelist = self.whitelist & !SELF_COLLISION;
+        self.whitelist = self.whitelist & !M::SELF_COLLISION;
     }
 
     #[inline]
-    fn is_inside_mask(mask: u32, group_id: usize) -> bool {
-        assert!(
-            group_id < 30,
-            "There are at most 30 groups indexed from 0 to 29 (included)."
-        );
-        mask & (1 << group_id) != 0
+    fn is_inside_mask(mask: M, group_id: usize) -> bool {
+        Self::assert_group_id(group_id);
+        mask & M::group(group_id) != M::NO_GROUP
     }
 
     /// Tests if this entity is part of the given group.
     #[inline]
     pub fn is_member_of(&self, group_id: usize) -> bool {
-        CollisionGroups::is_inside_mask(self.membership, group_id)
+        Self::is_inside_mask(self.membership, group_id)
     }
 
     /// Tests if the given group is whitelisted.
     #[inline]
     pub fn is_group_whitelisted(&self, group_id: usize) -> bool {
-        CollisionGroups::is_inside_mask(self.whitelist, group_id)
+        Self::is_inside_mask(self.whitelist, group_id)
     }
 
     /// Tests if the given group is blacklisted.
     #[inline]
     pub fn is_group_blacklisted(&self, group_id: usize) -> bool {
-        CollisionGroups::is_inside_mask(self.blacklist, group_id)
+        Self::is_inside_mask(self.blacklist, group_id)
     }
 
     /// Tests whether interactions with a given group is possible.
@@ -344,35 +402,47 @@ impl CollisionGroups {
     /// Collision is possible if `group_id` is whitelisted but not blacklisted.
     #[inline]
     pub fn can_interact_with(&self, group_id: usize) -> bool {
-        !CollisionGroups::is_inside_mask(self.blacklist, group_id)
-            && CollisionGroups::is_inside_mask(self.whitelist, group_id)
+        !Self::is_inside_mask(self.blacklist, group_id)
+            && Self::is_inside_mask(self.whitelist, group_id)
     }
 
     /// Tests whether two collision groups have at least one group in common.
     #[inline]
-    pub fn can_interact_with_groups(&self, other: &CollisionGroups) -> bool {
+    pub fn can_interact_with_groups(&self, other: &Self) -> bool {
         // FIXME: is there a more bitwise-y way of doing this?
-        self.membership & other.blacklist == 0
-            && other.membership & self.blacklist == 0
-            && self.membership & other.whitelist != 0
-            && other.membership & self.whitelist != 0
+        self.membership & other.blacklist == M::NO_GROUP
+            && other.membership & self.blacklist == M::NO_GROUP
+            && self.membership & other.whitelist != M::NO_GROUP
+            && other.membership & self.whitelist != M::NO_GROUP
     }
 
     /// Tests whether self-interaction is enabled.
     #[inline]
     pub fn can_interact_with_self(&self) -> bool {
-        self.whitelist & SELF_COLLISION != 0
+        self.whitelist & M::SELF_COLLISION != M::NO_GROUP
     }
 }
 
-impl Default for CollisionGroups {
+impl<M: CollisionGroupsMask> Default for GenericCollisionGroups<M> {
     #[inline]
     fn default() -> Self {
-        CollisionGroups::new()
+        Self::new()
     }
 }
 
-/// A collision filter based collision groups.
+impl<M: CollisionGroupsMask> InteractionGroups for GenericCollisionGroups<M> {
+    #[inline]
+    fn can_interact_with_groups(&self, other: &Self) -> bool {
+        GenericCollisionGroups::can_interact_with_groups(self, other)
+    }
+
+    #[inline]
+    fn can_interact_with_self(&self) -> bool {
+        GenericCollisionGroups::can_interact_with_self(self)
+    }
+}
+
+/// A collision filter based on the `InteractionGroups` of the collision objects.
 pub struct CollisionGroupsPairFilter;
 
 impl CollisionGroupsPairFilter {
@@ -383,6 +453,13 @@ impl CollisionGroupsPairFilter {
     }
 }
 
+impl Default for CollisionGroupsPairFilter {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<N: RealField, Object: CollisionObjectRef<N>, Handle: CollisionObjectHandle>
     BroadPhasePairFilter<N, Object, Handle> for CollisionGroupsPairFilter
 {
@@ -0,0 +1,104 @@
+use crate::pipeline::object::InteractionGroups;
+use std::sync::Arc;
+
+/// A symmetric table that decides which collision layers can interact with each other.
+#[derive(Clone, Debug)]
+pub struct CollisionLayerMatrix {
+    nlayers: usize,
+    interactions: Vec<bool>,
+}
+
+impl CollisionLayerMatrix {
+    /// Creates a new layer matrix where every layer can interact with every other layer.
+    pub fn new(nlayers: usize) -> Self {
+        CollisionLayerMatrix {
+            nlayers,
+            interactions: vec![true; nlayers * nlayers],
+        }
+    }
+
+    /// Creates a new layer matrix where no layer can interact with any other layer.
+    pub fn empty(nlayers: usize) -> Self {
+        CollisionLayerMatrix {
+            nlayers,
+            interactions: vec![false; nlayers * nlayers],
+        }
+    }
+
+    /// The number of layers of this matrix.
+    #[inline]
+    pub fn nlayers(&self) -> usize {
+        self.nlayers
+    }
+
+    /// Enables or disables the interactions between the layers `layer1` and `layer2`.
+    ///
+    /// Panics if any layer is out of bounds.
+    pub fn set_interaction(&mut self, layer1: usize, layer2: usize, enabled: bool) {
+        assert!(
+            layer1 < self.nlayers && layer2 < self.nlayers,
+            "Collision layer out of bounds."
+        );
+        self.interactions[layer1 * self.nlayers + layer2] = enabled;
+        self.interactions[layer2 * self.nlayers + layer1] = enabled;
+    }
+
+    /// Tests whether the layers `layer1` and `layer2` can interact.
+    ///
+    /// Panics if any layer is out of bounds.
+    #[inline]
+    pub fn can_interact(&self, layer1: usize, layer2: usize) -> bool {
+        assert!(
+            layer1 < self.nlayers && layer2 < self.nlayers,
+            "Collision layer out of bounds."
+        );
+        self.interactions[layer1 * self.nlayers + layer2]
+    }
+}
+
+/// Interaction groups based on a collision layer and a shared `CollisionLayerMatrix`.
+///
+/// This is an alternative to `CollisionGroups` where the number of layers is not limited by the size
+/// of a bit mask: two collision objects can interact if their layers can interact according to the
+/// layer matrix. All the collision layers of a collision world are expected to share the same
+/// layer matrix.
+#[derive(Clone, Debug)]
+pub struct CollisionLayer {
+    layer: usize,
+    matrix: Arc<CollisionLayerMatrix>,
+}
+
+impl CollisionLayer {
+    /// Creates a new collision layer.
+    ///
+    /// Panics if `layer` is out of the bounds of the `matrix`.
+    pub fn new(layer: usize, matrix: Arc<CollisionLayerMatrix>) -> Self {
+        assert!(layer < matrix.nlayers(), "Collision layer out of bounds.");
+        CollisionLayer { layer, matrix }
+    }
+
+    /// The layer index.
+    #[inline]
+    pub fn layer(&self) -> usize {
+        self.layer
+    }
+
+    /// The layer matrix used to decide if this layer can interact with other layers.
+    #[inline]
+    pub fn matrix(&self) -> &Arc<CollisionLayerMatrix> {
+        &self.matrix
+    }
+}
+
+impl InteractionGroups for CollisionLayer {
+    #[inline]
+    fn can_interact_with_groups(&self, other: &Self) -> bool {
+        self.matrix.can_interact(self.layer, other.layer)
+    }
+
+    /// A collision object with a collision layer never interacts with itself.
+    #[inline]
+    fn can_interact_with_self(&self) -> bool {
+        false
+    }
+}
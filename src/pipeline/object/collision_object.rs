@@ -2,8 +2,8 @@ use crate::bounding_volume::{self, BoundingVolume, AABB};
 use crate::math::Isometry;
 use crate::pipeline::broad_phase::BroadPhaseProxyHandle;
 use crate::pipeline::narrow_phase::CollisionObjectGraphIndex;
-use crate::pipeline::object::CollisionGroups;
 use crate::pipeline::object::GeometricQueryType;
+use crate::pipeline::object::{CollisionGroups, InteractionGroups};
 use crate::shape::{Shape, ShapeHandle};
 use simba::scalar::RealField;
 
@@ -58,8 +58,46 @@ impl CollisionObjectUpdateFlags {
     }
 }
 
+/// The activation status of a collision object.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ActivationStatus {
+    /// The collision object may move and its interactions are updated by the narrow-phase.
+    Active,
+    /// The collision object is not expected to move until it is woken up.
+    ///
+    /// Interactions between two collision objects that are not active are not updated by the narrow-phase
+    /// and keep their last contacts and proximities. A sleeping collision object is woken up by the
+    /// `CollisionWorld` as soon as a collision object interacting with it moves.
+    Sleeping,
+    /// The collision object never moves and is never woken up.
+    Static,
+}
+
+impl ActivationStatus {
+    /// Checks if this status is `ActivationStatus::Active`.
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        *self == ActivationStatus::Active
+    }
+
+    /// Checks if this status is `ActivationStatus::Sleeping`.
+    #[inline]
+    pub fn is_sleeping(&self) -> bool {
+        *self == ActivationStatus::Sleeping
+    }
+
+    /// Checks if this status is `ActivationStatus::Static`.
+    #[inline]
+    pub fn is_static(&self) -> bool {
+        *self == ActivationStatus::Static
+    }
+}
+
 /// Trait implemented by collision objects.
 pub trait CollisionObjectRef<N: RealField> {
+    /// The type of the interaction groups of this collision object.
+    type Groups: InteractionGroups;
+
     /// The interaction graph index of this collision object, if it has been registered into an interaction graph.
     ///
     /// Se the `glue::create_proxies` for more details.
@@ -79,11 +117,21 @@ pub trait CollisionObjectRef<N: RealField> {
     /// The shape of this collision object.
     fn shape(&self) -> &dyn Shape<N>;
     /// The collision groups of this collision object.
-    fn collision_groups(&self) -> &CollisionGroups;
+    fn collision_groups(&self) -> &Self::Groups;
     /// The type of geometric queries this collision object is subjected to.
     fn query_type(&self) -> GeometricQueryType<N>;
     /// Flags indicating what changed in this collision object.
     fn update_flags(&self) -> CollisionObjectUpdateFlags;
+    /// Whether this collision object is subjected to continuous collision detection.
+    ///
+    /// See `glue::perform_ccd` for details.
+    fn is_ccd_enabled(&self) -> bool {
+        false
+    }
+    /// The activation status of this collision object.
+    fn activation_status(&self) -> ActivationStatus {
+        ActivationStatus::Active
+    }
 
     /// Computes the AABB of this collision object, ignoring `self.predicted_position()`.
     fn compute_aabb(&self) -> AABB<N> {
@@ -113,6 +161,9 @@ pub trait CollisionObjectRef<N: RealField> {
     }
 }
 
+// The bit set on the handles of the collision objects of a `StaticScene`.
+pub(crate) const STATIC_SCENE_BIT: usize = !(usize::MAX >> 1);
+
 /// The unique identifier of a collision object stored in a `CollisionObjectSlab` structure.
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -124,32 +175,42 @@ impl CollisionObjectSlabHandle {
     pub fn uid(&self) -> usize {
         self.0
     }
+
+    /// Checks if this handle identifies a collision object of a `StaticScene`.
+    #[inline]
+    pub fn is_in_static_scene(&self) -> bool {
+        self.0 & STATIC_SCENE_BIT != 0
+    }
 }
 
 /// A stand-alone object that has a position and a shape.
-pub struct CollisionObject<N: RealField, T> {
+///
+/// The type `G` of the interaction groups of this collision object defaults to `CollisionGroups`.
+pub struct CollisionObject<N: RealField, T, G = CollisionGroups> {
     proxy_handle: Option<BroadPhaseProxyHandle>,
     graph_index: Option<CollisionObjectGraphIndex>,
     position: Isometry<N>,
     predicted_position: Option<Isometry<N>>,
     shape: ShapeHandle<N>,
-    collision_groups: CollisionGroups,
+    collision_groups: G,
     query_type: GeometricQueryType<N>,
     update_flags: CollisionObjectUpdateFlags,
+    ccd_enabled: bool,
+    activation_status: ActivationStatus,
     data: T,
 }
 
-impl<N: RealField, T> CollisionObject<N, T> {
+impl<N: RealField, T, G: InteractionGroups> CollisionObject<N, T, G> {
     /// Creates a new collision object.
     pub fn new(
         proxy_handle: Option<BroadPhaseProxyHandle>,
         graph_index: Option<CollisionObjectGraphIndex>,
         position: Isometry<N>,
         shape: ShapeHandle<N>,
-        groups: CollisionGroups,
+        groups: G,
         query_type: GeometricQueryType<N>,
         data: T,
-    ) -> CollisionObject<N, T> {
+    ) -> CollisionObject<N, T, G> {
         CollisionObject {
             proxy_handle,
             graph_index,
@@ -160,6 +221,8 @@ impl<N: RealField, T> CollisionObject<N, T> {
             data,
             query_type,
             update_flags: CollisionObjectUpdateFlags::all(),
+            ccd_enabled: false,
+            activation_status: ActivationStatus::Active,
         }
     }
 
@@ -264,13 +327,13 @@ impl<N: RealField, T> CollisionObject<N, T> {
 
     /// The collision groups of the collision object.
     #[inline]
-    pub fn collision_groups(&self) -> &CollisionGroups {
+    pub fn collision_groups(&self) -> &G {
         &self.collision_groups
     }
 
     /// Sets the collision groups of this collision object.
     #[inline]
-    pub fn set_collision_groups(&mut self, groups: CollisionGroups) {
+    pub fn set_collision_groups(&mut self, groups: G) {
         self.update_flags |= CollisionObjectUpdateFlags::COLLISION_GROUPS_CHANGED;
         self.collision_groups = groups
     }
@@ -289,6 +352,35 @@ impl<N: RealField, T> CollisionObject<N, T> {
         self.query_type = query_type;
     }
 
+    /// Whether this collision object is subjected to continuous collision detection.
+    #[inline]
+    pub fn is_ccd_enabled(&self) -> bool {
+        self.ccd_enabled
+    }
+
+    /// Enables or disables continuous collision detection for this collision object.
+    ///
+    /// Continuous collision detection is only performed if this collision object has a predicted position
+    /// set with `.set_position_with_prediction` or `.set_predicted_position`.
+    #[inline]
+    pub fn enable_ccd(&mut self, enabled: bool) {
+        self.ccd_enabled = enabled
+    }
+
+    /// The activation status of this collision object.
+    #[inline]
+    pub fn activation_status(&self) -> ActivationStatus {
+        self.activation_status
+    }
+
+    /// Sets the activation status of this collision object.
+    ///
+    /// Interactions between two collision objects that are not active are not updated by the narrow-phase.
+    #[inline]
+    pub fn set_activation_status(&mut self, status: ActivationStatus) {
+        self.activation_status = status
+    }
+
     /// Reference to the user-defined data associated to this object.
     #[inline]
     pub fn data(&self) -> &T {
@@ -302,7 +394,9 @@ impl<N: RealField, T> CollisionObject<N, T> {
     }
 }
 
-impl<N: RealField, T> CollisionObjectRef<N> for CollisionObject<N, T> {
+impl<N: RealField, T, G: InteractionGroups> CollisionObjectRef<N> for CollisionObject<N, T, G> {
+    type Groups = G;
+
     fn graph_index(&self) -> Option<CollisionObjectGraphIndex> {
         self.graph_index()
     }
@@ -323,7 +417,7 @@ impl<N: RealField, T> CollisionObjectRef<N> for CollisionObject<N, T> {
         self.shape().as_ref()
     }
 
-    fn collision_groups(&self) -> &CollisionGroups {
+    fn collision_groups(&self) -> &G {
         self.collision_groups()
     }
 
@@ -334,4 +428,12 @@ impl<N: RealField, T> CollisionObjectRef<N> for CollisionObject<N, T> {
     fn update_flags(&self) -> CollisionObjectUpdateFlags {
         self.update_flags
     }
+
+    fn is_ccd_enabled(&self) -> bool {
+        self.is_ccd_enabled()
+    }
+
+    fn activation_status(&self) -> ActivationStatus {
+        self.activation_status()
+    }
 }
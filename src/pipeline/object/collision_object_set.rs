@@ -1,9 +1,15 @@
 use simba::scalar::RealField;
 
-use crate::pipeline::object::{CollisionObject, CollisionObjectRef, CollisionObjectSlabHandle};
+use crate::pipeline::narrow_phase::CollisionObjectGraphIndex;
+use crate::pipeline::object::collision_object::STATIC_SCENE_BIT;
+use crate::pipeline::object::{
+    CollisionGroups, CollisionObject, CollisionObjectRef, CollisionObjectSlabHandle,
+    InteractionGroups, StaticScene,
+};
 use slab::{Iter, IterMut, Slab};
 use std::hash::Hash;
 use std::ops::{Index, IndexMut};
+use std::sync::Arc;
 
 /// Trait implemented by a handle indentifying a collision object.
 pub trait CollisionObjectHandle: Copy + Hash + PartialEq + Eq + 'static + Send + Sync {}
@@ -27,10 +33,22 @@ pub trait CollisionObjectSet<N: RealField> {
     ) -> Option<&Self::CollisionObject>;
     /// Applies a closure to every collision object (and their handle) stored into this set.
     fn foreach(&self, f: impl FnMut(Self::CollisionObjectHandle, &Self::CollisionObject));
+
+    /// The interaction graph index of the collision object identified by the given `handle`.
+    ///
+    /// Defaults to the graph index stored by the collision object itself. Sets sharing their
+    /// collision objects with other sets have to override this to return the graph index
+    /// specific to this set.
+    fn graph_index(
+        &self,
+        handle: Self::CollisionObjectHandle,
+    ) -> Option<CollisionObjectGraphIndex> {
+        self.collision_object(handle)?.graph_index()
+    }
 }
 
-impl<N: RealField, T> CollisionObjectSet<N> for CollisionObjectSlab<N, T> {
-    type CollisionObject = CollisionObject<N, T>;
+impl<N: RealField, T, G: InteractionGroups> CollisionObjectSet<N> for CollisionObjectSlab<N, T, G> {
+    type CollisionObject = CollisionObject<N, T, G>;
     type CollisionObjectHandle = CollisionObjectSlabHandle;
 
     fn collision_object(
@@ -45,24 +63,51 @@ impl<N: RealField, T> CollisionObjectSet<N> for CollisionObjectSlab<N, T> {
             f(CollisionObjectSlabHandle(co.0), co.1)
         }
     }
+
+    fn graph_index(
+        &self,
+        handle: Self::CollisionObjectHandle,
+    ) -> Option<CollisionObjectGraphIndex> {
+        if handle.is_in_static_scene() {
+            self.static_graph_indices
+                .get(handle.0 & !STATIC_SCENE_BIT)
+                .cloned()
+        } else {
+            self.get(handle)?.graph_index()
+        }
+    }
 }
 
 /// A set of collision objects that can be indexed by collision object handles.
-pub struct CollisionObjectSlab<N: RealField, T> {
-    pub(crate) objects: Slab<CollisionObject<N, T>>,
+///
+/// This set may also share the collision objects of a `StaticScene`, which can be read through
+/// this set but are neither modified, removed, nor iterated on by it.
+pub struct CollisionObjectSlab<N: RealField, T, G = CollisionGroups> {
+    pub(crate) objects: Slab<CollisionObject<N, T, G>>,
+    pub(crate) static_scene: Option<Arc<StaticScene<N, T, G>>>,
+    // The graph indices of the collision objects of the static scene.
+    pub(crate) static_graph_indices: Vec<CollisionObjectGraphIndex>,
 }
 
-impl<N: RealField, T> CollisionObjectSlab<N, T> {
+impl<N: RealField, T, G: InteractionGroups> CollisionObjectSlab<N, T, G> {
     /// Creates a new empty collection of collision objects.
-    pub fn new() -> CollisionObjectSlab<N, T> {
+    pub fn new() -> CollisionObjectSlab<N, T, G> {
         CollisionObjectSlab {
             objects: Slab::new(),
+            static_scene: None,
+            static_graph_indices: Vec::new(),
         }
     }
 
+    /// The static scene shared by this collection, if any.
+    #[inline]
+    pub fn static_scene(&self) -> Option<&Arc<StaticScene<N, T, G>>> {
+        self.static_scene.as_ref()
+    }
+
     /// Inserts a new collision object into this collection and returns the corresponding handle.
     #[inline]
-    pub fn insert(&mut self, co: CollisionObject<N, T>) -> CollisionObjectSlabHandle {
+    pub fn insert(&mut self, co: CollisionObject<N, T, G>) -> CollisionObjectSlabHandle {
         CollisionObjectSlabHandle(self.objects.insert(co))
     }
 
@@ -70,22 +115,43 @@ impl<N: RealField, T> CollisionObjectSlab<N, T> {
     ///
     /// The removed collision object structure is returned.
     #[inline]
-    pub fn remove(&mut self, handle: CollisionObjectSlabHandle) -> CollisionObject<N, T> {
+    pub fn remove(&mut self, handle: CollisionObjectSlabHandle) -> CollisionObject<N, T, G> {
         self.objects.remove(handle.0)
     }
 
+    /// Sets the interaction graph index of the collision object identified by the given handle.
+    pub(crate) fn set_graph_index(
+        &mut self,
+        handle: CollisionObjectSlabHandle,
+        graph_index: CollisionObjectGraphIndex,
+    ) {
+        if handle.is_in_static_scene() {
+            self.static_graph_indices[handle.0 & !STATIC_SCENE_BIT] = graph_index
+        } else {
+            self.objects[handle.0].set_graph_index(Some(graph_index))
+        }
+    }
+
     /// If it exists, retrieves a reference to the collision object identified by the given handle.
+    ///
+    /// This includes the collision objects of the static scene.
     #[inline]
-    pub fn get(&self, handle: CollisionObjectSlabHandle) -> Option<&CollisionObject<N, T>> {
-        self.objects.get(handle.0)
+    pub fn get(&self, handle: CollisionObjectSlabHandle) -> Option<&CollisionObject<N, T, G>> {
+        if handle.is_in_static_scene() {
+            self.static_scene.as_ref()?.get(handle)
+        } else {
+            self.objects.get(handle.0)
+        }
     }
 
     /// If it exists, retrieves a mutable reference to the collision object identified by the given handle.
+    ///
+    /// Returns `None` for the collision objects of the static scene, which are read-only.
     #[inline]
     pub fn get_mut(
         &mut self,
         handle: CollisionObjectSlabHandle,
-    ) -> Option<&mut CollisionObject<N, T>> {
+    ) -> Option<&mut CollisionObject<N, T, G>> {
         self.objects.get_mut(handle.0)
     }
 
@@ -98,8 +164,8 @@ impl<N: RealField, T> CollisionObjectSlab<N, T> {
         handle1: CollisionObjectSlabHandle,
         handle2: CollisionObjectSlabHandle,
     ) -> (
-        Option<&mut CollisionObject<N, T>>,
-        Option<&mut CollisionObject<N, T>>,
+        Option<&mut CollisionObject<N, T, G>>,
+        Option<&mut CollisionObject<N, T, G>>,
     ) {
         assert_ne!(handle1, handle2, "The two handles must not be the same.");
         let a = self.objects.get_mut(handle1.0).map(|o| o as *mut _);
@@ -112,42 +178,53 @@ impl<N: RealField, T> CollisionObjectSlab<N, T> {
     /// Returns `true` if the specified handle identifies a collision object stored in this collection.
     #[inline]
     pub fn contains(&self, handle: CollisionObjectSlabHandle) -> bool {
-        self.objects.contains(handle.0)
+        self.get(handle).is_some()
     }
 
     /// Retrieves an iterator yielding references to each collision object.
+    ///
+    /// The collision objects of the static scene are not included.
     #[inline]
-    pub fn iter(&self) -> CollisionObjects<N, T> {
+    pub fn iter(&self) -> CollisionObjects<N, T, G> {
         CollisionObjects {
             iter: self.objects.iter(),
         }
     }
 
     /// Retrieves an iterator yielding references to each collision object.
+    ///
+    /// The collision objects of the static scene are not included.
     #[inline]
-    pub fn iter_mut(&mut self) -> CollisionObjectsMut<N, T> {
+    pub fn iter_mut(&mut self) -> CollisionObjectsMut<N, T, G> {
         CollisionObjectsMut {
             iter_mut: self.objects.iter_mut(),
         }
     }
 
-    /// The number of collision objects on this slab.
+    /// The number of collision objects on this slab, not counting the ones of the static scene.
     #[inline]
     pub fn len(&self) -> usize {
         self.objects.len()
     }
 }
 
-impl<N: RealField, T> Index<CollisionObjectSlabHandle> for CollisionObjectSlab<N, T> {
-    type Output = CollisionObject<N, T>;
+impl<N: RealField, T, G> Index<CollisionObjectSlabHandle> for CollisionObjectSlab<N, T, G> {
+    type Output = CollisionObject<N, T, G>;
 
     #[inline]
     fn index(&self, handle: CollisionObjectSlabHandle) -> &Self::Output {
-        &self.objects[handle.0]
+        if handle.is_in_static_scene() {
+            self.static_scene
+                .as_ref()
+                .and_then(|scene| scene.get(handle))
+                .expect("Invalid static collision object handle.")
+        } else {
+            &self.objects[handle.0]
+        }
     }
 }
 
-impl<N: RealField, T> IndexMut<CollisionObjectSlabHandle> for CollisionObjectSlab<N, T> {
+impl<N: RealField, T, G> IndexMut<CollisionObjectSlabHandle> for CollisionObjectSlab<N, T, G> {
     #[inline]
     fn index_mut(&mut self, handle: CollisionObjectSlabHandle) -> &mut Self::Output {
         &mut self.objects[handle.0]
@@ -155,12 +232,12 @@ impl<N: RealField, T> IndexMut<CollisionObjectSlabHandle> for CollisionObjectSla
 }
 
 /// An iterator yielding references to collision objects.
-pub struct CollisionObjects<'a, N: 'a + RealField, T: 'a> {
-    iter: Iter<'a, CollisionObject<N, T>>,
+pub struct CollisionObjects<'a, N: 'a + RealField, T: 'a, G: 'a = CollisionGroups> {
+    iter: Iter<'a, CollisionObject<N, T, G>>,
 }
 
-impl<'a, N: 'a + RealField, T: 'a> Iterator for CollisionObjects<'a, N, T> {
-    type Item = (CollisionObjectSlabHandle, &'a CollisionObject<N, T>);
+impl<'a, N: 'a + RealField, T: 'a, G: 'a> Iterator for CollisionObjects<'a, N, T, G> {
+    type Item = (CollisionObjectSlabHandle, &'a CollisionObject<N, T, G>);
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
@@ -171,12 +248,12 @@ impl<'a, N: 'a + RealField, T: 'a> Iterator for CollisionObjects<'a, N, T> {
 }
 
 /// An iterator yielding mutable references to collision objects.
-pub struct CollisionObjectsMut<'a, N: 'a + RealField, T: 'a> {
-    iter_mut: IterMut<'a, CollisionObject<N, T>>,
+pub struct CollisionObjectsMut<'a, N: 'a + RealField, T: 'a, G: 'a = CollisionGroups> {
+    iter_mut: IterMut<'a, CollisionObject<N, T, G>>,
 }
 
-impl<'a, N: 'a + RealField, T: 'a> Iterator for CollisionObjectsMut<'a, N, T> {
-    type Item = (CollisionObjectSlabHandle, &'a mut CollisionObject<N, T>);
+impl<'a, N: 'a + RealField, T: 'a, G: 'a> Iterator for CollisionObjectsMut<'a, N, T, G> {
+    type Item = (CollisionObjectSlabHandle, &'a mut CollisionObject<N, T, G>);
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
@@ -1,15 +1,23 @@
 //! Definition of collision objects and some of their properties.
 
-pub use self::collision_groups::{CollisionGroups, CollisionGroupsPairFilter};
+pub use self::collision_groups::{
+    CollisionGroups, CollisionGroupsMask, CollisionGroupsPairFilter, CollisionGroupsU128,
+    CollisionGroupsU64, GenericCollisionGroups, InteractionGroups,
+};
+pub use self::collision_layer::{CollisionLayer, CollisionLayerMatrix};
 pub use self::collision_object::{
-    CollisionObject, CollisionObjectRef, CollisionObjectSlabHandle, CollisionObjectUpdateFlags,
+    ActivationStatus, CollisionObject, CollisionObjectRef, CollisionObjectSlabHandle,
+    CollisionObjectUpdateFlags,
 };
 pub use self::collision_object_set::{
     CollisionObjectHandle, CollisionObjectSet, CollisionObjectSlab, CollisionObjects,
 };
 pub use self::query_type::GeometricQueryType;
+pub use self::static_scene::StaticScene;
 
 mod collision_groups;
+mod collision_layer;
 mod collision_object;
 mod collision_object_set;
 mod query_type;
+mod static_scene;
@@ -8,6 +8,9 @@ use na::RealField;
 ///
 /// * Contacts + Contacts = exact contact point coputation.
 /// * Contacts + Proximity = proximity test only.
+/// * Contacts + Distance = distance and closest points computation.
+/// * Distance + Distance = distance and closest points computation.
+/// * Distance + Proximity = proximity test only.
 /// * Proximity + Proximity = proximity test only.
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum GeometricQueryType<N: RealField> {
@@ -15,7 +18,11 @@ pub enum GeometricQueryType<N: RealField> {
     Contacts(N, N),
     /// This object can respond to proximity tests only.
     Proximity(N),
-    // FIXME: not yet implemented: Distance
+    /// This object can respond to distance and closest points computation only.
+    ///
+    /// The value is the maximum distance at which the closest points with another object will be
+    /// tracked.
+    Distance(N),
 }
 
 impl<N: RealField> GeometricQueryType<N> {
@@ -24,12 +31,14 @@ impl<N: RealField> GeometricQueryType<N> {
     /// If two objects are separated by a distance greater than the sum of their respective
     /// `query_limit`, the corresponding query will not by performed. For proximity queries,
     /// non-intersecting object closer than a distance equal to the sum of their `query_limit` will
-    /// be reported as `Proximity::WithinMargin`.
+    /// be reported as `Proximity::WithinMargin`. For distance queries, objects further than the sum of
+    /// their `query_limit` will be reported as `ClosestPoints::Disjoint`.
     #[inline]
     pub fn query_limit(&self) -> N {
         match *self {
             GeometricQueryType::Contacts(ref val, _) => *val,
             GeometricQueryType::Proximity(ref val) => *val,
+            GeometricQueryType::Distance(ref val) => *val,
         }
     }
 
@@ -69,4 +78,14 @@ impl<N: RealField> GeometricQueryType<N> {
             false
         }
     }
+
+    /// Returns `true` if this is a distance query type.
+    #[inline]
+    pub fn is_distance_query(&self) -> bool {
+        if let GeometricQueryType::Distance(_) = *self {
+            true
+        } else {
+            false
+        }
+    }
 }
@@ -0,0 +1,118 @@
+use simba::scalar::RealField;
+use std::sync::Arc;
+
+use crate::bounding_volume::AABB;
+use crate::math::Isometry;
+use crate::pipeline::broad_phase::DBVTStaticLayer;
+use crate::pipeline::object::collision_object::STATIC_SCENE_BIT;
+use crate::pipeline::object::{
+    ActivationStatus, CollisionGroups, CollisionObject, CollisionObjectRef,
+    CollisionObjectSlabHandle, GeometricQueryType, InteractionGroups,
+};
+use crate::shape::ShapeHandle;
+
+/// A read-only set of static collision objects that can be shared among several collision worlds.
+///
+/// A static scene is built once, e.g., with the level geometry, and then shared with
+/// `CollisionWorld::with_static_scene` by every collision world that needs it. Its collision objects
+/// and their bounding volume tree are not duplicated by those worlds, which only store the
+/// interactions of their own collision objects with the static scene.
+///
+/// The collision objects of a static scene have the `ActivationStatus::Static` status, and are never
+/// moved nor modified once added to the scene.
+pub struct StaticScene<N: RealField, T, G = CollisionGroups> {
+    objects: Vec<CollisionObject<N, T, G>>,
+    broad_phase_layer: Arc<DBVTStaticLayer<N, AABB<N>, CollisionObjectSlabHandle>>,
+}
+
+impl<N: RealField, T, G: InteractionGroups> StaticScene<N, T, G> {
+    /// Creates an empty static scene.
+    pub fn new() -> StaticScene<N, T, G> {
+        StaticScene {
+            objects: Vec::new(),
+            broad_phase_layer: Arc::new(DBVTStaticLayer::new()),
+        }
+    }
+
+    /// Adds a static collision object to this scene.
+    ///
+    /// The returned handle identifies this collision object in every collision world sharing this
+    /// scene.
+    pub fn add(
+        &mut self,
+        position: Isometry<N>,
+        shape: ShapeHandle<N>,
+        collision_groups: G,
+        query_type: GeometricQueryType<N>,
+        data: T,
+    ) -> CollisionObjectSlabHandle {
+        let handle = CollisionObjectSlabHandle(self.objects.len() | STATIC_SCENE_BIT);
+        let mut co = CollisionObject::new(
+            None,
+            None,
+            position,
+            shape,
+            collision_groups,
+            query_type,
+            data,
+        );
+        let layer = Arc::get_mut(&mut self.broad_phase_layer)
+            .expect("Cannot add a collision object to a static scene shared by a collision world.");
+        let proxy_handle = layer.create_proxy(co.compute_aabb(), handle);
+
+        co.set_proxy_handle(Some(proxy_handle));
+        co.set_activation_status(ActivationStatus::Static);
+        co.clear_update_flags();
+        self.objects.push(co);
+        handle
+    }
+}
+
+impl<N: RealField, T, G> StaticScene<N, T, G> {
+    /// Retrieves a reference to the collision object of this scene identified by the given handle.
+    #[inline]
+    pub fn get(&self, handle: CollisionObjectSlabHandle) -> Option<&CollisionObject<N, T, G>> {
+        if handle.is_in_static_scene() {
+            self.objects.get(handle.0 & !STATIC_SCENE_BIT)
+        } else {
+            None
+        }
+    }
+
+    /// Iterates through the collision objects of this scene and their handles.
+    #[inline]
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = (CollisionObjectSlabHandle, &CollisionObject<N, T, G>)> {
+        self.objects
+            .iter()
+            .enumerate()
+            .map(|(i, co)| (CollisionObjectSlabHandle(i | STATIC_SCENE_BIT), co))
+    }
+
+    /// The number of collision objects of this scene.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    /// Returns `true` if this scene does not contain any collision object.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// The static broad phase tree of this scene, shared by the broad phases of the collision worlds.
+    #[inline]
+    pub fn broad_phase_layer(
+        &self,
+    ) -> &Arc<DBVTStaticLayer<N, AABB<N>, CollisionObjectSlabHandle>> {
+        &self.broad_phase_layer
+    }
+}
+
+impl<N: RealField, T, G: InteractionGroups> Default for StaticScene<N, T, G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
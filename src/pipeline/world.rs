@@ -4,30 +4,42 @@ use na::{RealField, Unit};
 
 use crate::bounding_volume::{BoundingVolume, AABB};
 use crate::math::{Isometry, Point, Rotation, Translation, Vector};
-use crate::pipeline::broad_phase::{BroadPhase, BroadPhasePairFilter, DBVTBroadPhase};
+use crate::pipeline::broad_phase::{
+    BroadPhase, BroadPhasePairFilter, BroadPhaseWorkspace, DBVTBroadPhase,
+};
 use crate::pipeline::glue::{
-    self, FirstInterferenceWithRay, InterferencesWithAABB, InterferencesWithPoint,
-    InterferencesWithRay,
+    self, CCDImpact, ContactsWithShape, FirstInterferenceWithRay, InterferencesWithAABB,
+    InterferencesWithConvexVolume, InterferencesWithPoint, InterferencesWithRay,
+    InterferencesWithShape, ProximitiesWithShape,
 };
 use crate::pipeline::narrow_phase::{
-    ContactAlgorithm, ContactEvents, DefaultContactDispatcher, DefaultProximityDispatcher,
-    Interaction, InteractionGraph, NarrowPhase, ProximityDetector, ProximityEvents,
-    TemporaryInteractionIndex,
+    ContactAlgorithm, ContactEvent, ContactEventData, ContactEvents, ContactModifier,
+    DefaultContactDispatcher, DefaultProximityDispatcher, DistanceEvents, Interaction,
+    InteractionGraph, NarrowPhase, NarrowPhasePairFilter, ProximityDetector, ProximityEvents,
+    ShapeRegistry, SpeculativeContacts, TemporaryInteractionIndex,
 };
 use crate::pipeline::object::{
-    CollisionGroups, CollisionObject, CollisionObjectSet, CollisionObjectSlab,
-    CollisionObjectSlabHandle, CollisionObjects, GeometricQueryType,
+    ActivationStatus, CollisionGroups, CollisionGroupsPairFilter, CollisionObject,
+    CollisionObjectSet, CollisionObjectSlab, CollisionObjectSlabHandle, CollisionObjects,
+    GeometricQueryType, InteractionGroups, StaticScene,
+};
+use crate::query::{
+    ClosestPoints, ContactManifold, ContactPrediction, ContactReduction, ConvexVolume,
+    DefaultTOIDispatcher, PointProjection, PointQuery, Proximity, Ray, RayCast, RayIntersection,
+    TOIDispatcher, TrackedContact, TOI,
 };
-use crate::query::{ContactManifold, DefaultTOIDispatcher, Proximity, Ray, TOIDispatcher, TOI};
 use crate::shape::{Shape, ShapeHandle};
+use std::sync::Arc;
 
 /// Type of the broad phase trait-object used by the collision world.
 pub type BroadPhaseObject<N> = Box<dyn BroadPhase<N, AABB<N>, CollisionObjectSlabHandle>>;
 
 /// A world that handles collision objects.
-pub struct CollisionWorld<N: RealField, T> {
+///
+/// The type `G` of the interaction groups of the collision objects defaults to `CollisionGroups`.
+pub struct CollisionWorld<N: RealField, T, G = CollisionGroups> {
     /// The set of objects on this collision world.
-    pub objects: CollisionObjectSlab<N, T>,
+    pub objects: CollisionObjectSlab<N, T, G>,
     /// The broad phase used by this collision world.
     pub broad_phase: BroadPhaseObject<N>,
     /// The narrow-phase used by this collision world.
@@ -37,14 +49,23 @@ pub struct CollisionWorld<N: RealField, T> {
     /// The graph of interactions detected so far.
     pub interactions: InteractionGraph<N, CollisionObjectSlabHandle>,
     /// A user-defined broad-phase pair filter.
-    pub pair_filters:
-        Option<Box<dyn BroadPhasePairFilter<N, CollisionObject<N, T>, CollisionObjectSlabHandle>>>,
+    pub pair_filters: Option<
+        Box<dyn BroadPhasePairFilter<N, CollisionObject<N, T, G>, CollisionObjectSlabHandle>>,
+    >,
+    /// A user-defined narrow-phase pair filter.
+    pub narrow_phase_pair_filter: Option<
+        Box<dyn NarrowPhasePairFilter<N, CollisionObject<N, T, G>, CollisionObjectSlabHandle>>,
+    >,
+    /// A user-defined contact modifier.
+    pub contact_modifier:
+        Option<Box<dyn ContactModifier<N, CollisionObject<N, T, G>, CollisionObjectSlabHandle>>>,
+    ccd_impacts: Vec<CCDImpact<N, CollisionObjectSlabHandle>>,
 }
 
-impl<N: RealField, T> CollisionWorld<N, T> {
+impl<N: RealField, T: 'static, G: InteractionGroups> CollisionWorld<N, T, G> {
     /// Creates a new collision world.
     // FIXME: use default values for `margin` and allow its modification by the user ?
-    pub fn new(margin: N) -> CollisionWorld<N, T> {
+    pub fn new(margin: N) -> CollisionWorld<N, T, G> {
         let objects = CollisionObjectSlab::new();
         let coll_dispatcher = Box::new(DefaultContactDispatcher::new());
         let prox_dispatcher = Box::new(DefaultProximityDispatcher::new());
@@ -60,7 +81,35 @@ impl<N: RealField, T> CollisionWorld<N, T> {
             narrow_phase,
             toi_dispatcher,
             pair_filters: None,
+            narrow_phase_pair_filter: None,
+            contact_modifier: None,
+            ccd_impacts: Vec::new(),
+        }
+    }
+
+    /// Creates a new collision world sharing the collision objects of the given static scene.
+    ///
+    /// The collision objects of the static scene are identified in this world by the handles
+    /// returned by `StaticScene::add`. They are not copied: this world only stores their
+    /// interactions with its own collision objects, and queries the bounding volume tree of the
+    /// scene alongside its own. They cannot be modified nor removed from this world.
+    pub fn with_static_scene(
+        margin: N,
+        scene: Arc<StaticScene<N, T, G>>,
+    ) -> CollisionWorld<N, T, G> {
+        let mut world = Self::new(margin);
+        world.broad_phase = Box::new(DBVTBroadPhase::with_static_layer(
+            margin,
+            scene.broad_phase_layer().clone(),
+        ));
+
+        for (handle, _) in scene.iter() {
+            let graph_index = world.interactions.add_node(handle);
+            world.objects.static_graph_indices.push(graph_index);
         }
+
+        world.objects.static_scene = Some(scene);
+        world
     }
 
     /// Adds a collision object to the world.
@@ -68,10 +117,10 @@ impl<N: RealField, T> CollisionWorld<N, T> {
         &mut self,
         position: Isometry<N>,
         shape: ShapeHandle<N>,
-        collision_groups: CollisionGroups,
+        collision_groups: G,
         query_type: GeometricQueryType<N>,
         data: T,
-    ) -> (CollisionObjectSlabHandle, &mut CollisionObject<N, T>) {
+    ) -> (CollisionObjectSlabHandle, &mut CollisionObject<N, T, G>) {
         let entry = self.objects.objects.vacant_entry();
         let handle = CollisionObjectSlabHandle(entry.key());
         let (proxy_handle, graph_index) = glue::create_proxies(
@@ -101,17 +150,28 @@ impl<N: RealField, T> CollisionWorld<N, T> {
     /// This executes the whole collision detection pipeline:
     /// 1. Clears the event pools.
     /// 2. Executes the broad phase first.
-    /// 3. Executes the narrow phase.
+    /// 3. Wakes up sleeping collision objects interacting with active collision objects that moved.
+    /// 4. Executes the narrow phase.
+    /// 5. Executes the continuous collision detection for collision objects with CCD enabled.
     pub fn update(&mut self) {
         self.narrow_phase.clear_events();
 
-        glue::perform_all_pipeline(
+        glue::perform_broad_phase(
             &self.objects,
             &mut *self.broad_phase,
             &mut self.narrow_phase,
             &mut self.interactions,
-            self.pair_filters.as_ref().map(|f| &**f),
+            self.pair_filters.as_deref(),
+        );
+        self.wake_up_sleeping_objects();
+        glue::perform_narrow_phase(
+            &self.objects,
+            &mut self.narrow_phase,
+            &mut self.interactions,
+            self.narrow_phase_pair_filter.as_deref(),
+            self.contact_modifier.as_deref(),
         );
+        self.perform_ccd();
 
         // Clear update flags.
         for (_, co) in self.objects.iter_mut() {
@@ -126,9 +186,15 @@ impl<N: RealField, T> CollisionWorld<N, T> {
 
     /// Removed the specified set of collision objects from the world.
     ///
-    /// Panics of any handle is invalid, or if the list contains duplicates.
+    /// Panics of any handle is invalid, identifies a collision object of the static scene, or if
+    /// the list contains duplicates.
     pub fn remove(&mut self, handles: &[CollisionObjectSlabHandle]) {
         for handle in handles {
+            assert!(
+                !handle.is_in_static_scene(),
+                "Cannot remove a collision object of the static scene."
+            );
+            self.narrow_phase.include_all_pairs_with(*handle);
             let co = self.objects.remove(*handle);
             let graph_index = co.graph_index().expect(crate::NOT_REGISTERED_ERROR);
             let proxy_handle = co.proxy_handle().expect(crate::NOT_REGISTERED_ERROR);
@@ -139,7 +205,7 @@ impl<N: RealField, T> CollisionWorld<N, T> {
                 proxy_handle,
                 graph_index,
             ) {
-                self.objects[new_handle].set_graph_index(Some(new_index))
+                self.objects.set_graph_index(new_handle, new_index)
             }
         }
     }
@@ -210,17 +276,160 @@ impl<N: RealField, T> CollisionWorld<N, T> {
     /// collision pairs.
     pub fn set_broad_phase_pair_filter<F>(&mut self, filter: Option<F>)
     where
-        F: BroadPhasePairFilter<N, CollisionObject<N, T>, CollisionObjectSlabHandle>,
+        F: BroadPhasePairFilter<N, CollisionObject<N, T, G>, CollisionObjectSlabHandle>,
     {
         self.pair_filters = filter.map(|f| {
             Box::new(f)
                 as Box<
-                    dyn BroadPhasePairFilter<N, CollisionObject<N, T>, CollisionObjectSlabHandle>,
+                    dyn BroadPhasePairFilter<
+                        N,
+                        CollisionObject<N, T, G>,
+                        CollisionObjectSlabHandle,
+                    >,
                 >
         });
         self.broad_phase.deferred_recompute_all_proximities();
     }
 
+    /// Sets the user-defined filter that tells if contacts should be computed between two collision objects.
+    ///
+    /// Unlike the broad-phase pair filter, this filter is evaluated at each update for every pair of
+    /// collision objects with a contact interaction, so it may depend on the user-defined data of the
+    /// collision objects. Pairs rejected by this filter have no contacts, but are still reported by
+    /// the broad phase and keep their interaction.
+    pub fn set_narrow_phase_pair_filter<F>(&mut self, filter: Option<F>)
+    where
+        F: NarrowPhasePairFilter<N, CollisionObject<N, T, G>, CollisionObjectSlabHandle>,
+    {
+        self.narrow_phase_pair_filter = filter.map(|f| {
+            Box::new(f)
+                as Box<
+                    dyn NarrowPhasePairFilter<
+                        N,
+                        CollisionObject<N, T, G>,
+                        CollisionObjectSlabHandle,
+                    >,
+                >
+        });
+    }
+
+    /// Prevents any interaction between the two given collision objects.
+    ///
+    /// Any existing interaction between those collision objects is removed. This exclusion lasts until `self.include_pair(handle1, handle2)` is called or one of the
+    /// collision objects is removed from this world.
+    ///
+    /// Panics if any handle is invalid.
+    pub fn exclude_pair(
+        &mut self,
+        handle1: CollisionObjectSlabHandle,
+        handle2: CollisionObjectSlabHandle,
+    ) {
+        if self.narrow_phase.exclude_pair(handle1, handle2) {
+            self.narrow_phase.handle_interaction(
+                &mut self.interactions,
+                &self.objects,
+                handle1,
+                handle2,
+                false,
+            );
+        }
+    }
+
+    /// Allows again the interactions between two collision objects excluded by `self.exclude_pair`.
+    ///
+    /// Panics if any handle is invalid.
+    pub fn include_pair(
+        &mut self,
+        handle1: CollisionObjectSlabHandle,
+        handle2: CollisionObjectSlabHandle,
+    ) {
+        if !self.narrow_phase.include_pair(handle1, handle2) {
+            return;
+        }
+
+        let co1 = &self.objects[handle1];
+        let co2 = &self.objects[handle2];
+        let proxy1 = co1.proxy_handle().expect(crate::NOT_REGISTERED_ERROR);
+        let proxy2 = co2.proxy_handle().expect(crate::NOT_REGISTERED_ERROR);
+
+        // The broad phase won't report this pair again if their bounding volumes
+        // already intersect, so we have to check this ourselves.
+        if let (Some((bv1, _)), Some((bv2, _))) = (
+            self.broad_phase.proxy(proxy1),
+            self.broad_phase.proxy(proxy2),
+        ) {
+            let filter_by_groups = CollisionGroupsPairFilter;
+
+            if bv1.intersects(bv2)
+                && filter_by_groups.is_pair_valid(co1, co2, handle1, handle2)
+                && self
+                    .pair_filters
+                    .as_ref()
+                    .map(|f| f.is_pair_valid(co1, co2, handle1, handle2))
+                    .unwrap_or(true)
+            {
+                self.narrow_phase.handle_interaction(
+                    &mut self.interactions,
+                    &self.objects,
+                    handle1,
+                    handle2,
+                    true,
+                );
+            }
+        }
+    }
+
+    /// Checks if the interactions between the two given collision objects have been disabled
+    /// by `self.exclude_pair`.
+    pub fn is_pair_excluded(
+        &self,
+        handle1: CollisionObjectSlabHandle,
+        handle2: CollisionObjectSlabHandle,
+    ) -> bool {
+        self.narrow_phase.is_pair_excluded(handle1, handle2)
+    }
+
+    /// Sets the user-defined hook that can modify the contacts computed by the narrow phase.
+    ///
+    /// The contact modifier is called after the contacts between two collision objects have been
+    /// computed, and may edit, remove or add contacts to their contact manifold. This is useful for,
+    /// e.g., one-way platforms or conveyor belts.
+    pub fn set_contact_modifier<M>(&mut self, modifier: Option<M>)
+    where
+        M: ContactModifier<N, CollisionObject<N, T, G>, CollisionObjectSlabHandle>,
+    {
+        self.contact_modifier = modifier.map(|m| {
+            Box::new(m)
+                as Box<dyn ContactModifier<N, CollisionObject<N, T, G>, CollisionObjectSlabHandle>>
+        });
+    }
+
+    /// Sets the parameters used to reduce the number of contacts of each contact manifold.
+    ///
+    /// If `None`, all the contacts computed by the narrow phase are kept.
+    pub fn set_contact_reduction(&mut self, reduction: Option<ContactReduction<N>>) {
+        self.narrow_phase.set_contact_reduction(reduction)
+    }
+
+    /// Uses the given shape registry for contact, proximity, and time-of-impact dispatch.
+    ///
+    /// This replaces the contact and proximity dispatchers of the narrow phase as well as
+    /// `self.toi_dispatcher`. The interactions that already exist keep using the algorithms they
+    /// have been created with, so this should be called before any collision object is added.
+    pub fn set_shape_registry(&mut self, registry: ShapeRegistry<N>) {
+        self.narrow_phase
+            .set_contact_dispatcher(Box::new(registry.clone()));
+        self.narrow_phase
+            .set_proximity_dispatcher(Box::new(registry.clone()));
+        self.toi_dispatcher = Box::new(registry);
+    }
+
+    /// Sets the strategy used by the narrow phase to compute speculative contacts.
+    pub fn set_speculative_contacts(&mut self, speculative_contacts: SpeculativeContacts) {
+        self.narrow_phase
+            .set_speculative_contacts(speculative_contacts)
+    }
+
     /// Executes the broad phase of the collision detection pipeline.
     pub fn perform_broad_phase(&mut self) {
         glue::perform_broad_phase(
@@ -228,7 +437,7 @@ impl<N: RealField, T> CollisionWorld<N, T> {
             &mut *self.broad_phase,
             &mut self.narrow_phase,
             &mut self.interactions,
-            self.pair_filters.as_ref().map(|f| &**f),
+            self.pair_filters.as_deref(),
         )
     }
 
@@ -238,9 +447,53 @@ impl<N: RealField, T> CollisionWorld<N, T> {
             &self.objects,
             &mut self.narrow_phase,
             &mut self.interactions,
+            self.narrow_phase_pair_filter.as_deref(),
+            self.contact_modifier.as_deref(),
+        )
+    }
+
+    /// Executes the continuous collision detection stage of the collision detection pipeline.
+    ///
+    /// This replaces the impacts returned by `.ccd_impacts()` by the earliest impact of each collision
+    /// object with CCD enabled, moving from its current position to its predicted position.
+    /// Pairs of shapes for which the time of impact dispatcher does not support nonlinear time of
+    /// impact computation are skipped by this stage.
+    pub fn perform_ccd(&mut self) {
+        self.ccd_impacts.clear();
+        glue::perform_ccd(
+            &self.objects,
+            &*self.broad_phase,
+            &*self.toi_dispatcher,
+            self.pair_filters.as_deref(),
+            &mut self.ccd_impacts,
         )
     }
 
+    /// Wakes up every sleeping collision object interacting with an active collision object that moved
+    /// since the last update.
+    ///
+    /// Every interaction of the collision objects woken up is updated by the next narrow phase, even
+    /// if those objects did not change while they were sleeping.
+    pub fn wake_up_sleeping_objects(&mut self) {
+        let mut to_wake_up = Vec::new();
+        glue::collect_objects_to_wake_up(&self.objects, &self.interactions, &mut to_wake_up);
+
+        for handle in to_wake_up {
+            let co = &mut self.objects[handle];
+            co.set_activation_status(ActivationStatus::Active);
+            let graph_index = co.graph_index().expect(crate::NOT_REGISTERED_ERROR);
+            self.narrow_phase
+                .update_interactions_with(&self.interactions, graph_index);
+        }
+    }
+
+    /// The earliest impacts found by the last continuous collision detection stage.
+    ///
+    /// There is at most one impact per collision object with CCD enabled.
+    pub fn ccd_impacts(&self) -> &[CCDImpact<N, CollisionObjectSlabHandle>] {
+        &self.ccd_impacts
+    }
+
     /// The broad-phase aabb for the given collision object.
     pub fn broad_phase_aabb(&self, handle: CollisionObjectSlabHandle) -> Option<&AABB<N>> {
         let co = self.objects.collision_object(handle)?;
@@ -250,7 +503,7 @@ impl<N: RealField, T> CollisionWorld<N, T> {
 
     /// Iterates through all collision objects.
     #[inline]
-    pub fn collision_objects(&self) -> CollisionObjects<N, T> {
+    pub fn collision_objects(&self) -> CollisionObjects<N, T, G> {
         self.objects.iter()
     }
 
@@ -259,7 +512,7 @@ impl<N: RealField, T> CollisionWorld<N, T> {
     pub fn collision_object(
         &self,
         handle: CollisionObjectSlabHandle,
-    ) -> Option<&CollisionObject<N, T>> {
+    ) -> Option<&CollisionObject<N, T, G>> {
         self.objects.collision_object(handle)
     }
 
@@ -268,7 +521,7 @@ impl<N: RealField, T> CollisionWorld<N, T> {
     pub fn get_mut(
         &mut self,
         handle: CollisionObjectSlabHandle,
-    ) -> Option<&mut CollisionObject<N, T>> {
+    ) -> Option<&mut CollisionObject<N, T, G>> {
         self.objects.get_mut(handle)
     }
 
@@ -281,8 +534,8 @@ impl<N: RealField, T> CollisionWorld<N, T> {
         handle1: CollisionObjectSlabHandle,
         handle2: CollisionObjectSlabHandle,
     ) -> (
-        Option<&mut CollisionObject<N, T>>,
-        Option<&mut CollisionObject<N, T>>,
+        Option<&mut CollisionObject<N, T, G>>,
+        Option<&mut CollisionObject<N, T, G>>,
     ) {
         self.objects.get_pair_mut(handle1, handle2)
     }
@@ -290,11 +543,7 @@ impl<N: RealField, T> CollisionWorld<N, T> {
     /// Sets the collision groups of the given collision object.
     #[inline]
     #[deprecated = "Call directly the method `.set_collision_groups` on the collision object."]
-    pub fn set_collision_groups(
-        &mut self,
-        handle: CollisionObjectSlabHandle,
-        groups: CollisionGroups,
-    ) {
+    pub fn set_collision_groups(&mut self, handle: CollisionObjectSlabHandle, groups: G) {
         if let Some(co) = self.objects.get_mut(handle) {
             co.set_collision_groups(groups);
         }
@@ -304,6 +553,11 @@ impl<N: RealField, T> CollisionWorld<N, T> {
     /// transformed by `isometry` along `direction` until `maximum_distance` is
     /// reached. The objects are not returned in any particular order. You may
     /// use the `toi` returned for each object to determine the closest object.
+    ///
+    /// Objects with a shape for which the time of impact dispatcher does not support time of
+    /// impact computation with `shape` are not returned.
+    ///
+    /// See `sweep_test_with_workspace` for a version of this query which does not allocate.
     #[inline]
     pub fn sweep_test<'a>(
         &'a self,
@@ -311,35 +565,87 @@ impl<N: RealField, T> CollisionWorld<N, T> {
         isometry: &'a Isometry<N>,
         direction: &'a Unit<Vector<N>>,
         maximum_distance: N,
-        groups: &'a CollisionGroups,
+        groups: &'a G,
     ) -> impl Iterator<Item = (CollisionObjectSlabHandle, TOI<N>)> + 'a {
-        let a = shape.aabb(&isometry);
+        let aabb = Self::sweep_aabb(shape, isometry, direction, maximum_distance);
+        let interferences: Vec<_> = self.interferences_with_aabb(&aabb, groups).collect();
+
+        interferences.into_iter().filter_map(move |(handle, x)| {
+            self.sweep_toi(shape, isometry, direction, x)
+                .map(|toi| (handle, toi))
+        })
+    }
+
+    /// Appends to `out` all objects in the collision world that intersect with the shape
+    /// transformed by `isometry` along `direction` until `maximum_distance` is reached.
+    ///
+    /// This is the same as `sweep_test` except that the temporary buffers of the query are taken
+    /// from `workspace`. Thus, no memory is allocated once the buffers of `workspace` and `out`
+    /// are large enough.
+    #[allow(clippy::too_many_arguments)]
+    pub fn sweep_test_with_workspace(
+        &self,
+        shape: &dyn Shape<N>,
+        isometry: &Isometry<N>,
+        direction: &Unit<Vector<N>>,
+        maximum_distance: N,
+        groups: &G,
+        workspace: &mut BroadPhaseWorkspace<N>,
+        out: &mut Vec<(CollisionObjectSlabHandle, TOI<N>)>,
+    ) {
+        let aabb = Self::sweep_aabb(shape, isometry, direction, maximum_distance);
+
+        self.broad_phase
+            .visit_interferences_with_bounding_volume(&aabb, workspace, &mut |handle| {
+                if let Some(co) = self.objects.collision_object(*handle) {
+                    if co.collision_groups().can_interact_with_groups(groups) {
+                        if let Some(toi) = self.sweep_toi(shape, isometry, direction, co) {
+                            out.push((*handle, toi))
+                        }
+                    }
+                }
+            })
+    }
+
+    // The AABB swept by `shape` during a sweep test.
+    fn sweep_aabb(
+        shape: &dyn Shape<N>,
+        isometry: &Isometry<N>,
+        direction: &Unit<Vector<N>>,
+        maximum_distance: N,
+    ) -> AABB<N> {
+        let a = shape.aabb(isometry);
         let b = shape.aabb(&Isometry::from_parts(
             Translation::from(isometry.translation.vector + direction.as_ref() * maximum_distance),
             Rotation::identity(),
         ));
-        let aabb = a.merged(&b);
-
-        // FIXME: avoid allocation.
-        let interferences: Vec<_> = self.interferences_with_aabb(&aabb, groups).collect();
+        a.merged(&b)
+    }
 
+    // The time of impact between `shape` moving along `direction` and the collision object `co`.
+    //
+    // Returns `None` if the time of impact between those shapes is not supported by the dispatcher.
+    fn sweep_toi(
+        &self,
+        shape: &dyn Shape<N>,
+        isometry: &Isometry<N>,
+        direction: &Unit<Vector<N>>,
+        co: &CollisionObject<N, T, G>,
+    ) -> Option<TOI<N>> {
         let dispatcher = &*self.toi_dispatcher;
-        interferences.into_iter().filter_map(move |(handle, x)| {
-            dispatcher
-                .time_of_impact(
-                    dispatcher,
-                    &isometry,
-                    &direction,
-                    shape,
-                    x.position(),
-                    &Vector::zeros(),
-                    x.shape().as_ref(),
-                    N::max_value(),
-                    N::zero(),
-                )
-                .unwrap_or(None)
-                .map(|toi| (handle, toi))
-        })
+        let toi = dispatcher.time_of_impact(
+            dispatcher,
+            isometry,
+            direction,
+            shape,
+            co.position(),
+            &Vector::zeros(),
+            co.shape().as_ref(),
+            N::max_value(),
+            N::zero(),
+        );
+
+        toi.ok().flatten()
     }
 
     /// Computes the interferences between every rigid bodies on this world and a ray.
@@ -348,42 +654,286 @@ impl<N: RealField, T> CollisionWorld<N, T> {
         &'a self,
         ray: &'b Ray<N>,
         max_toi: N,
-        groups: &'b CollisionGroups,
-    ) -> InterferencesWithRay<'a, 'b, N, CollisionObjectSlab<N, T>> {
+        groups: &'b G,
+    ) -> InterferencesWithRay<'a, 'b, N, CollisionObjectSlab<N, T, G>> {
         glue::interferences_with_ray(&self.objects, &*self.broad_phase, ray, max_toi, groups)
     }
 
+    /// Appends to `out` the collision objects of this world intersecting a ray, with their
+    /// intersection with the ray.
+    ///
+    /// This is the same as `interferences_with_ray` except that the temporary buffers of the query
+    /// are taken from `workspace`. Thus, no memory is allocated once the buffers of `workspace`
+    /// and `out` are large enough.
+    pub fn interferences_with_ray_with_workspace(
+        &self,
+        ray: &Ray<N>,
+        max_toi: N,
+        groups: &G,
+        workspace: &mut BroadPhaseWorkspace<N>,
+        out: &mut Vec<(CollisionObjectSlabHandle, RayIntersection<N>)>,
+    ) {
+        self.broad_phase
+            .visit_interferences_with_ray(ray, max_toi, workspace, &mut |handle| {
+                if let Some(co) = self.objects.collision_object(*handle) {
+                    if co.collision_groups().can_interact_with_groups(groups) {
+                        let inter =
+                            co.shape()
+                                .toi_and_normal_with_ray(co.position(), ray, max_toi, true);
+
+                        if let Some(inter) = inter {
+                            out.push((*handle, inter))
+                        }
+                    }
+                }
+            })
+    }
+
     /// Computes the first interference with `ray` and
     #[inline]
     pub fn first_interference_with_ray<'a, 'b>(
         &'a self,
         ray: &'b Ray<N>,
         max_toi: N,
-        groups: &'b CollisionGroups,
-    ) -> Option<FirstInterferenceWithRay<'a, N, CollisionObjectSlab<N, T>>> {
+        groups: &'b G,
+    ) -> Option<FirstInterferenceWithRay<'a, N, CollisionObjectSlab<N, T, G>>> {
         glue::first_interference_with_ray(&self.objects, &*self.broad_phase, ray, max_toi, groups)
     }
 
+    /// Computes the first interference with `ray`, taking the temporary buffers of the query from
+    /// `workspace`.
+    ///
+    /// This is the same as `first_interference_with_ray` except that no memory is allocated once
+    /// the buffers of `workspace` are large enough.
+    #[inline]
+    pub fn first_interference_with_ray_with_workspace<'a, 'b>(
+        &'a self,
+        ray: &'b Ray<N>,
+        max_toi: N,
+        groups: &'b G,
+        workspace: &mut BroadPhaseWorkspace<N>,
+    ) -> Option<FirstInterferenceWithRay<'a, N, CollisionObjectSlab<N, T, G>>> {
+        glue::first_interference_with_ray_with_workspace(
+            &self.objects,
+            &*self.broad_phase,
+            ray,
+            max_toi,
+            groups,
+            workspace,
+        )
+    }
+
     /// Computes the interferences between every rigid bodies of a given broad phase, and a point.
     #[inline]
     pub fn interferences_with_point<'a, 'b>(
         &'a self,
         point: &'b Point<N>,
-        groups: &'b CollisionGroups,
-    ) -> InterferencesWithPoint<'a, 'b, N, CollisionObjectSlab<N, T>> {
+        groups: &'b G,
+    ) -> InterferencesWithPoint<'a, 'b, N, CollisionObjectSlab<N, T, G>> {
         glue::interferences_with_point(&self.objects, &*self.broad_phase, point, groups)
     }
 
+    /// Appends to `out` the collision objects of this world containing `point`.
+    ///
+    /// This is the same as `interferences_with_point` except that the temporary buffers of the
+    /// query are taken from `workspace`. Thus, no memory is allocated once the buffers of
+    /// `workspace` and `out` are large enough.
+    pub fn interferences_with_point_with_workspace(
+        &self,
+        point: &Point<N>,
+        groups: &G,
+        workspace: &mut BroadPhaseWorkspace<N>,
+        out: &mut Vec<CollisionObjectSlabHandle>,
+    ) {
+        self.broad_phase
+            .visit_interferences_with_point(point, workspace, &mut |handle| {
+                if let Some(co) = self.objects.collision_object(*handle) {
+                    if co.collision_groups().can_interact_with_groups(groups)
+                        && co.shape().contains_point(co.position(), point)
+                    {
+                        out.push(*handle)
+                    }
+                }
+            })
+    }
+
     /// Computes the interferences between every rigid bodies of a given broad phase, and a aabb.
     #[inline]
     pub fn interferences_with_aabb<'a, 'b>(
         &'a self,
         aabb: &'b AABB<N>,
-        groups: &'b CollisionGroups,
-    ) -> InterferencesWithAABB<'a, 'b, N, CollisionObjectSlab<N, T>> {
+        groups: &'b G,
+    ) -> InterferencesWithAABB<'a, 'b, N, CollisionObjectSlab<N, T, G>> {
         glue::interferences_with_aabb(&self.objects, &*self.broad_phase, aabb, groups)
     }
 
+    /// Appends to `out` the collision objects of this world which AABB intersects `aabb`.
+    ///
+    /// This is the same as `interferences_with_aabb` except that the temporary buffers of the
+    /// query are taken from `workspace`. Thus, no memory is allocated once the buffers of
+    /// `workspace` and `out` are large enough.
+    pub fn interferences_with_aabb_with_workspace(
+        &self,
+        aabb: &AABB<N>,
+        groups: &G,
+        workspace: &mut BroadPhaseWorkspace<N>,
+        out: &mut Vec<CollisionObjectSlabHandle>,
+    ) {
+        self.broad_phase
+            .visit_interferences_with_bounding_volume(aabb, workspace, &mut |handle| {
+                if let Some(co) = self.objects.collision_object(*handle) {
+                    if co.collision_groups().can_interact_with_groups(groups) {
+                        out.push(*handle)
+                    }
+                }
+            })
+    }
+
+    /// Computes the interferences between every rigid bodies of a given broad phase, and a
+    /// convex volume.
+    ///
+    /// This yields the collision objects which AABB may intersect `volume`, e.g., the objects
+    /// visible by a camera when `volume` is its frustum.
+    #[inline]
+    pub fn interferences_with_convex_volume<'a, 'b>(
+        &'a self,
+        volume: &'b ConvexVolume<N>,
+        groups: &'b G,
+    ) -> InterferencesWithConvexVolume<'a, 'b, N, CollisionObjectSlab<N, T, G>> {
+        glue::interferences_with_convex_volume(&self.objects, &*self.broad_phase, volume, groups)
+    }
+
+    /// Finds the collision object closest to `point`, if it is not further than `max_dist`.
+    ///
+    /// Returns its handle, the collision object itself, and the projection of `point` on its shape.
+    #[inline]
+    pub fn closest_object_to_point(
+        &self,
+        point: &Point<N>,
+        max_dist: N,
+        groups: &G,
+    ) -> Option<(
+        CollisionObjectSlabHandle,
+        &CollisionObject<N, T, G>,
+        PointProjection<N>,
+    )> {
+        glue::closest_object_to_point(&self.objects, &*self.broad_phase, point, max_dist, groups)
+    }
+
+    /// Finds the `k` collision objects closest to `point` and not further than `max_dist`.
+    ///
+    /// Returns their handles, the collision objects themselves, and the projection of `point` on their
+    /// shapes, sorted by increasing distance.
+    #[inline]
+    pub fn k_nearest_to_point(
+        &self,
+        point: &Point<N>,
+        k: usize,
+        max_dist: N,
+        groups: &G,
+    ) -> Vec<(
+        CollisionObjectSlabHandle,
+        &CollisionObject<N, T, G>,
+        PointProjection<N>,
+    )> {
+        glue::k_nearest_to_point(
+            &self.objects,
+            &*self.broad_phase,
+            point,
+            k,
+            max_dist,
+            groups,
+        )
+    }
+
+    /// Finds the collision object closest to the given shape, if it is not further than `max_dist`.
+    ///
+    /// Returns its handle, the collision object itself, and its distance to the shape.
+    #[inline]
+    pub fn closest_object_to_shape(
+        &self,
+        isometry: &Isometry<N>,
+        shape: &dyn Shape<N>,
+        max_dist: N,
+        groups: &G,
+    ) -> Option<(CollisionObjectSlabHandle, &CollisionObject<N, T, G>, N)> {
+        glue::closest_object_to_shape(
+            &self.objects,
+            &*self.broad_phase,
+            isometry,
+            shape,
+            max_dist,
+            groups,
+        )
+    }
+
+    /// Computes the interferences between every collision objects on this world and a shape.
+    ///
+    /// The exact intersection tests are performed by the proximity dispatcher of the narrow phase.
+    #[inline]
+    pub fn interferences_with_shape<'a, 'b>(
+        &'a self,
+        isometry: &'b Isometry<N>,
+        shape: &'b dyn Shape<N>,
+        groups: &'b G,
+    ) -> InterferencesWithShape<'a, 'b, N, CollisionObjectSlab<N, T, G>> {
+        glue::interferences_with_shape(
+            &self.objects,
+            &*self.broad_phase,
+            self.narrow_phase.proximity_dispatcher(),
+            isometry,
+            shape,
+            groups,
+        )
+    }
+
+    /// Computes the proximities between a shape and every collision objects on this world closer
+    /// to it than `margin`.
+    ///
+    /// The proximities are computed by the proximity dispatcher of the narrow phase.
+    #[inline]
+    pub fn proximities_with_shape<'a, 'b>(
+        &'a self,
+        isometry: &'b Isometry<N>,
+        shape: &'b dyn Shape<N>,
+        margin: N,
+        groups: &'b G,
+    ) -> ProximitiesWithShape<'a, 'b, N, CollisionObjectSlab<N, T, G>> {
+        glue::proximities_with_shape(
+            &self.objects,
+            &*self.broad_phase,
+            self.narrow_phase.proximity_dispatcher(),
+            isometry,
+            shape,
+            margin,
+            groups,
+        )
+    }
+
+    /// Computes the contacts between a shape and every collision objects on this world closer to it
+    /// than `prediction.linear()`.
+    ///
+    /// The contacts are computed by the contact dispatcher of the narrow phase, with the given shape as
+    /// the first shape of each contact manifold.
+    #[inline]
+    pub fn contacts_with_shape<'a, 'b>(
+        &'a self,
+        isometry: &'b Isometry<N>,
+        shape: &'b dyn Shape<N>,
+        prediction: &'b ContactPrediction<N>,
+        groups: &'b G,
+    ) -> ContactsWithShape<'a, 'b, N, CollisionObjectSlab<N, T, G>> {
+        glue::contacts_with_shape(
+            &self.objects,
+            &*self.broad_phase,
+            self.narrow_phase.contact_dispatcher(),
+            isometry,
+            shape,
+            prediction,
+            groups,
+        )
+    }
+
     /// Customize the selection of narrowphase collision detection algorithms
     pub fn set_narrow_phase(&mut self, narrow_phase: NarrowPhase<N, CollisionObjectSlabHandle>) {
         self.narrow_phase = narrow_phase;
@@ -449,6 +999,23 @@ impl<N: RealField, T> CollisionWorld<N, T> {
         self.interactions.proximity_pairs(effective_only)
     }
 
+    /// All the potential distance pairs.
+    ///
+    /// Refer to the official [user guide](https://nphysics.org/interaction_handling_and_sensors/#interaction-iterators)
+    /// for details.
+    pub fn distance_pairs(
+        &self,
+        effective_only: bool,
+    ) -> impl Iterator<
+        Item = (
+            CollisionObjectSlabHandle,
+            CollisionObjectSlabHandle,
+            &ClosestPoints<N>,
+        ),
+    > {
+        self.interactions.distance_pairs(effective_only)
+    }
+
     /// The potential interaction pair between the two specified collision objects.
     ///
     /// Refer to the official [user guide](https://nphysics.org/interaction_handling_and_sensors/#interaction-iterators)
@@ -463,10 +1030,8 @@ impl<N: RealField, T> CollisionWorld<N, T> {
         CollisionObjectSlabHandle,
         &Interaction<N>,
     )> {
-        let co1 = self.objects.collision_object(handle1)?;
-        let co2 = self.objects.collision_object(handle2)?;
-        let id1 = co1.graph_index().expect(crate::NOT_REGISTERED_ERROR);
-        let id2 = co2.graph_index().expect(crate::NOT_REGISTERED_ERROR);
+        let id1 = self.objects.graph_index(handle1)?;
+        let id2 = self.objects.graph_index(handle2)?;
         self.interactions.interaction_pair(id1, id2, effective_only)
     }
 
@@ -485,10 +1050,8 @@ impl<N: RealField, T> CollisionWorld<N, T> {
         &ContactAlgorithm<N>,
         &ContactManifold<N>,
     )> {
-        let co1 = self.objects.collision_object(handle1)?;
-        let co2 = self.objects.collision_object(handle2)?;
-        let id1 = co1.graph_index().expect(crate::NOT_REGISTERED_ERROR);
-        let id2 = co2.graph_index().expect(crate::NOT_REGISTERED_ERROR);
+        let id1 = self.objects.graph_index(handle1)?;
+        let id2 = self.objects.graph_index(handle2)?;
         self.interactions.contact_pair(id1, id2, effective_only)
     }
 
@@ -507,13 +1070,30 @@ impl<N: RealField, T> CollisionWorld<N, T> {
         &dyn ProximityDetector<N>,
         Proximity,
     )> {
-        let co1 = self.objects.collision_object(handle1)?;
-        let co2 = self.objects.collision_object(handle2)?;
-        let id1 = co1.graph_index().expect(crate::NOT_REGISTERED_ERROR);
-        let id2 = co2.graph_index().expect(crate::NOT_REGISTERED_ERROR);
+        let id1 = self.objects.graph_index(handle1)?;
+        let id2 = self.objects.graph_index(handle2)?;
         self.interactions.proximity_pair(id1, id2, effective_only)
     }
 
+    /// The potential distance pair between the two specified collision objects.
+    ///
+    /// Refer to the official [user guide](https://nphysics.org/interaction_handling_and_sensors/#interaction-iterators)
+    /// for details.
+    pub fn distance_pair(
+        &self,
+        handle1: CollisionObjectSlabHandle,
+        handle2: CollisionObjectSlabHandle,
+        effective_only: bool,
+    ) -> Option<(
+        CollisionObjectSlabHandle,
+        CollisionObjectSlabHandle,
+        &ClosestPoints<N>,
+    )> {
+        let id1 = self.objects.graph_index(handle1)?;
+        let id2 = self.objects.graph_index(handle2)?;
+        self.interactions.distance_pair(id1, id2, effective_only)
+    }
+
     /// All the interaction pairs involving the specified collision object.
     ///
     /// Refer to the official [user guide](https://nphysics.org/interaction_handling_and_sensors/#interaction-iterators)
@@ -531,8 +1111,7 @@ impl<N: RealField, T> CollisionWorld<N, T> {
             ),
         >,
     > {
-        let co = self.objects.collision_object(handle)?;
-        let id = co.graph_index().expect(crate::NOT_REGISTERED_ERROR);
+        let id = self.objects.graph_index(handle)?;
         Some(self.interactions.interactions_with(id, effective_only))
     }
 
@@ -554,8 +1133,7 @@ impl<N: RealField, T> CollisionWorld<N, T> {
             ),
         >,
     )> {
-        let co = self.objects.collision_object(handle)?;
-        let id = co.graph_index().expect(crate::NOT_REGISTERED_ERROR);
+        let id = self.objects.graph_index(handle)?;
         Some((
             &mut self.narrow_phase,
             self.interactions.interactions_with_mut(id),
@@ -580,11 +1158,31 @@ impl<N: RealField, T> CollisionWorld<N, T> {
             ),
         >,
     > {
-        let co = self.objects.collision_object(handle)?;
-        let id = co.graph_index().expect(crate::NOT_REGISTERED_ERROR);
+        let id = self.objects.graph_index(handle)?;
         Some(self.interactions.proximities_with(id, effective_only))
     }
 
+    /// All the distance pairs involving the specified collision object.
+    ///
+    /// Refer to the official [user guide](https://nphysics.org/interaction_handling_and_sensors/#interaction-iterators)
+    /// for details.
+    pub fn distances_with(
+        &self,
+        handle: CollisionObjectSlabHandle,
+        effective_only: bool,
+    ) -> Option<
+        impl Iterator<
+            Item = (
+                CollisionObjectSlabHandle,
+                CollisionObjectSlabHandle,
+                &ClosestPoints<N>,
+            ),
+        >,
+    > {
+        let id = self.objects.graph_index(handle)?;
+        Some(self.interactions.distances_with(id, effective_only))
+    }
+
     /// All the contact pairs involving the specified collision object.
     ///
     /// Refer to the official [user guide](https://nphysics.org/interaction_handling_and_sensors/#interaction-iterators)
@@ -603,8 +1201,7 @@ impl<N: RealField, T> CollisionWorld<N, T> {
             ),
         >,
     > {
-        let co = self.objects.collision_object(handle)?;
-        let id = co.graph_index().expect(crate::NOT_REGISTERED_ERROR);
+        let id = self.objects.graph_index(handle)?;
         Some(self.interactions.contacts_with(id, effective_only))
     }
 
@@ -616,8 +1213,7 @@ impl<N: RealField, T> CollisionWorld<N, T> {
         &'a self,
         handle: CollisionObjectSlabHandle,
     ) -> Option<impl Iterator<Item = CollisionObjectSlabHandle> + 'a> {
-        let co = self.objects.collision_object(handle)?;
-        let id = co.graph_index().expect(crate::NOT_REGISTERED_ERROR);
+        let id = self.objects.graph_index(handle)?;
         Some(self.interactions.collision_objects_interacting_with(id))
     }
 
@@ -630,8 +1226,7 @@ impl<N: RealField, T> CollisionWorld<N, T> {
         &'a self,
         handle: CollisionObjectSlabHandle,
     ) -> Option<impl Iterator<Item = CollisionObjectSlabHandle> + 'a> {
-        let co = self.objects.collision_object(handle)?;
-        let id = co.graph_index().expect(crate::NOT_REGISTERED_ERROR);
+        let id = self.objects.graph_index(handle)?;
         Some(self.interactions.collision_objects_in_contact_with(id))
     }
 
@@ -644,8 +1239,7 @@ impl<N: RealField, T> CollisionWorld<N, T> {
         &'a self,
         handle: CollisionObjectSlabHandle,
     ) -> Option<impl Iterator<Item = CollisionObjectSlabHandle> + 'a> {
-        let co = self.objects.collision_object(handle)?;
-        let id = co.graph_index().expect(crate::NOT_REGISTERED_ERROR);
+        let id = self.objects.graph_index(handle)?;
         Some(self.interactions.collision_objects_in_proximity_of(id))
     }
 
@@ -659,8 +1253,33 @@ impl<N: RealField, T> CollisionWorld<N, T> {
         self.narrow_phase.contact_events()
     }
 
+    /// The contact information attached to the given contact event, if any.
+    ///
+    /// See `NarrowPhase::contact_event_data` for details.
+    pub fn contact_event_data(
+        &self,
+        event: &ContactEvent<CollisionObjectSlabHandle>,
+    ) -> Option<&ContactEventData<N>> {
+        self.narrow_phase.contact_event_data(event)
+    }
+
+    /// The contacts involved in the given contact event.
+    ///
+    /// See `NarrowPhase::contact_event_contacts` for details.
+    pub fn contact_event_contacts(
+        &self,
+        event: &ContactEvent<CollisionObjectSlabHandle>,
+    ) -> &[TrackedContact<N>] {
+        self.narrow_phase.contact_event_contacts(event)
+    }
+
     /// The proximity events pool.
     pub fn proximity_events(&self) -> &ProximityEvents<CollisionObjectSlabHandle> {
         self.narrow_phase.proximity_events()
     }
+
+    /// The distance events pool.
+    pub fn distance_events(&self) -> &DistanceEvents<CollisionObjectSlabHandle> {
+        self.narrow_phase.distance_events()
+    }
 }
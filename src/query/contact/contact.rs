@@ -69,6 +69,9 @@ pub struct TrackedContact<N: RealField> {
     pub kinematic: ContactKinematic<N>,
     /// The identifier of this contact.
     pub id: ContactId,
+    /// Whether this is a speculative contact, i.e., a contact computed at the predicted positions
+    /// of the solids and expressed at their current positions.
+    pub speculative: bool,
 }
 
 impl<N: RealField> TrackedContact<N> {
@@ -78,6 +81,7 @@ impl<N: RealField> TrackedContact<N> {
             contact,
             kinematic,
             id: ContactId::null(),
+            speculative: false,
         }
     }
 }
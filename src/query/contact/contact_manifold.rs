@@ -1,10 +1,13 @@
 use crate::math::Point;
+use crate::pipeline::ContactAlgorithm;
 use crate::query::ContactPreprocessor;
 use crate::query::{Contact, ContactKinematic, TrackedContact};
 use crate::shape::FeatureId;
 use na::{self, RealField};
 use slab::Slab;
+use std::cmp::Ordering;
 use std::collections::{hash_map::Entry, HashMap};
+use std::fmt;
 
 /// The technique used for contact tracking.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -17,12 +20,79 @@ pub enum ContactTrackingMode<N: RealField> {
     DistanceBased(N),
 }
 
+/// Parameters of the reduction of the number of contacts of a contact manifold.
+///
+/// The contacts of a manifold are first clustered by normal: two contacts belong to the same
+/// cluster if the angle between their normals is smaller than the normal tolerance. Then, at most
+/// `max_contacts` contacts are kept for the whole manifold, distributed among the clusters.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ContactReduction<N: RealField> {
+    max_contacts: usize,
+    normal_tolerance: N,
+    cos_normal_tolerance: N,
+}
+
+impl<N: RealField> ContactReduction<N> {
+    /// Initialize contact reduction parameters.
+    ///
+    /// The `normal_tolerance` is an angle in radians.
+    pub fn new(max_contacts: usize, normal_tolerance: N) -> Self {
+        assert!(
+            max_contacts > 0,
+            "At least one contact must be kept per manifold."
+        );
+
+        ContactReduction {
+            max_contacts,
+            normal_tolerance,
+            cos_normal_tolerance: normal_tolerance.cos(),
+        }
+    }
+
+    /// The maximum number of contacts kept for each contact manifold.
+    #[inline]
+    pub fn max_contacts(&self) -> usize {
+        self.max_contacts
+    }
+
+    /// The maximum angle between the normals of two contacts of the same cluster.
+    #[inline]
+    pub fn normal_tolerance(&self) -> N {
+        self.normal_tolerance
+    }
+
+    /// The cosine of `self.normal_tolerance()`.
+    #[inline]
+    pub fn cos_normal_tolerance(&self) -> N {
+        self.cos_normal_tolerance
+    }
+}
+
 #[derive(Clone, Debug)]
 enum ContactCache<N: RealField> {
     FeatureBased(HashMap<(FeatureId, FeatureId), usize>),
     DistanceBased(Vec<(Point<N>, usize)>, N),
 }
 
+// The detector used by the narrow phase to compute the speculative contacts of a manifold.
+//
+// This is only a cache, so it is not copied when the manifold is cloned.
+struct SpeculativeDetector<N: RealField>(Option<ContactAlgorithm<N>>);
+
+impl<N: RealField> Clone for SpeculativeDetector<N> {
+    fn clone(&self) -> Self {
+        SpeculativeDetector(None)
+    }
+}
+
+impl<N: RealField> fmt::Debug for SpeculativeDetector<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SpeculativeDetector")
+            .field(&self.0.is_some())
+            .finish()
+    }
+}
+
 /// A contact manifold.
 ///
 /// A contact manifold is a set of contacts between two shapes.
@@ -36,6 +106,7 @@ pub struct ContactManifold<N: RealField> {
     deepest: usize,
     contacts: Slab<(TrackedContact<N>, usize)>,
     cache: ContactCache<N>,
+    speculative_detector: SpeculativeDetector<N>,
 }
 
 impl<N: RealField> ContactManifold<N> {
@@ -49,6 +120,7 @@ impl<N: RealField> ContactManifold<N> {
             persistence: 1,
             contacts: Slab::new(),
             cache: ContactCache::DistanceBased(Vec::new(), na::convert(0.02)),
+            speculative_detector: SpeculativeDetector(None),
         }
     }
 
@@ -77,6 +149,21 @@ impl<N: RealField> ContactManifold<N> {
         })
     }
 
+    /// All the contacts removed by the last call to `save_cache_and_clear` that have not been matched by any
+    /// new contact since.
+    ///
+    /// This is useful to retrieve the last contacts known before a manifold became empty.
+    pub fn stale_contacts(&self) -> impl Iterator<Item = &TrackedContact<N>> {
+        let persistence = self.persistence;
+        self.contacts.iter().filter_map(move |(_, c)| {
+            if c.1 + 1 == persistence {
+                Some(&c.0)
+            } else {
+                None
+            }
+        })
+    }
+
     /// The contact of this manifold with the deepest penetration depth.
     pub fn deepest_contact(&self) -> Option<&TrackedContact<N>> {
         if self.len() != 0 {
@@ -130,6 +217,166 @@ impl<N: RealField> ContactManifold<N> {
         }
     }
 
+    /// Removes from this manifold all the contacts for which `f` returns `false`.
+    ///
+    /// The removed contacts are kept in cache until the next call to `save_cache_and_clear`
+    /// so they are reported by `self.stale_contacts()`.
+    pub fn retain(&mut self, mut f: impl FnMut(&TrackedContact<N>) -> bool) {
+        self.retain_with_key(|_, c| f(c))
+    }
+
+    fn retain_with_key(&mut self, mut f: impl FnMut(usize, &TrackedContact<N>) -> bool) {
+        let persistence = self.persistence;
+        let mut ncontacts = 0;
+        let mut deepest = None::<(usize, N)>;
+
+        for (i, c) in self.contacts.iter_mut() {
+            if c.1 != persistence {
+                continue;
+            }
+
+            if f(i, &c.0) {
+                ncontacts += 1;
+
+                if deepest.map(|d| c.0.contact.depth > d.1).unwrap_or(true) {
+                    deepest = Some((i, c.0.contact.depth));
+                }
+            } else {
+                c.1 = persistence - 1;
+            }
+        }
+
+        self.ncontacts = ncontacts;
+        self.deepest = deepest.map(|d| d.0).unwrap_or(0);
+    }
+
+    /// Groups the contacts of this manifold into clusters of contacts with similar normals.
+    ///
+    /// Two contacts belong to the same cluster if the angle between the normal of the first one and
+    /// the normal of the deepest contact of the cluster is smaller than `normal_tolerance` (in radians).
+    pub fn clusters(&self, normal_tolerance: N) -> Vec<Vec<&TrackedContact<N>>> {
+        self.cluster_keys(normal_tolerance.cos())
+            .into_iter()
+            .map(|cluster| cluster.into_iter().map(|i| &self.contacts[i].0).collect())
+            .collect()
+    }
+
+    // Clusters of contacts, each sorted by decreasing depth.
+    fn cluster_keys(&self, cos_normal_tolerance: N) -> Vec<Vec<usize>> {
+        let persistence = self.persistence;
+        let mut keys: Vec<_> = self
+            .contacts
+            .iter()
+            .filter(|(_, c)| c.1 == persistence)
+            .map(|(i, _)| i)
+            .collect();
+        keys.sort_by(|a, b| {
+            let depth_a = self.contacts[*a].0.contact.depth;
+            let depth_b = self.contacts[*b].0.contact.depth;
+            depth_b.partial_cmp(&depth_a).unwrap_or(Ordering::Equal)
+        });
+
+        let mut clusters: Vec<Vec<usize>> = Vec::new();
+
+        for i in keys {
+            let normal = &self.contacts[i].0.contact.normal;
+            let cluster = clusters.iter_mut().find(|cluster| {
+                let cluster_normal = &self.contacts[cluster[0]].0.contact.normal;
+                normal.dot(cluster_normal) >= cos_normal_tolerance
+            });
+
+            match cluster {
+                Some(cluster) => cluster.push(i),
+                None => clusters.push(vec![i]),
+            }
+        }
+
+        clusters
+    }
+
+    /// Reduces the number of contacts of this manifold.
+    ///
+    /// The contacts are grouped into clusters of similar normals (see `self.clusters`), and at most
+    /// `reduction.max_contacts()` contacts are kept for the whole manifold. Those contacts are
+    /// distributed among the clusters in a round-robin fashion, starting with the cluster containing
+    /// the deepest contact. Thus, the deepest contact of each cluster is kept as long as there are
+    /// fewer clusters than `reduction.max_contacts()`. Within a cluster, the first contact is the
+    /// deepest, the second one is the farthest from the deepest, the third one is the one forming the
+    /// largest triangle with the first two, and the others are selected iteratively as the farthest
+    /// from all the contacts selected so far.
+    ///
+    /// The removed contacts are kept in cache until the next call to `save_cache_and_clear`
+    /// so they are reported by `self.stale_contacts()`.
+    pub fn reduce(&mut self, reduction: &ContactReduction<N>) {
+        let max_contacts = reduction.max_contacts();
+
+        if self.ncontacts <= max_contacts {
+            return;
+        }
+
+        let mut clusters = self.cluster_keys(reduction.cos_normal_tolerance());
+
+        // Distribute the contacts to keep among the clusters.
+        let mut quotas = vec![0; clusters.len()];
+        let mut budget = max_contacts;
+
+        while budget != 0 {
+            for (quota, cluster) in quotas.iter_mut().zip(clusters.iter()) {
+                if budget != 0 && *quota < cluster.len() {
+                    *quota += 1;
+                    budget -= 1;
+                }
+            }
+        }
+
+        let mut kept = Vec::with_capacity(max_contacts);
+
+        for (cluster, quota) in clusters.iter_mut().zip(quotas) {
+            self.select_contacts(cluster, quota);
+            kept.extend_from_slice(cluster);
+        }
+
+        kept.sort_unstable();
+        self.retain_with_key(|i, _| kept.binary_search(&i).is_ok())
+    }
+
+    // Moves the `max_contacts` selected contacts at the beginning of `cluster` and truncates it.
+    fn select_contacts(&self, cluster: &mut Vec<usize>, max_contacts: usize) {
+        let point = |i: usize| &self.contacts[i].0.contact.world1;
+
+        // The cluster is sorted by decreasing depth so the deepest contact is already selected.
+        for k in 1..max_contacts {
+            let mut best = k;
+            let mut best_score = -N::one();
+
+            for (j, candidate) in cluster.iter().enumerate().skip(k) {
+                let pt = point(*candidate);
+                let score = if k == 2 {
+                    // Squared area of the triangle formed with the first two contacts.
+                    let ab = point(cluster[1]) - point(cluster[0]);
+                    let ap = pt - point(cluster[0]);
+                    let dot = ab.dot(&ap);
+                    ab.norm_squared() * ap.norm_squared() - dot * dot
+                } else {
+                    // Squared distance to the closest contact selected so far.
+                    cluster[..k]
+                        .iter()
+                        .map(|s| na::distance_squared(pt, point(*s)))
+                        .fold(N::max_value(), |a, b| a.min(b))
+                };
+
+                if score > best_score {
+                    best = j;
+                    best_score = score;
+                }
+            }
+
+            cluster.swap(k, best);
+        }
+
+        cluster.truncate(max_contacts);
+    }
+
     /// Save the contacts to a cache and empty the manifold.
     pub fn save_cache_and_clear(&mut self) {
         match &mut self.cache {
@@ -160,15 +407,65 @@ impl<N: RealField> ContactManifold<N> {
     ///
     /// The manifold will attempt to match this contact with another one
     /// previously added and added to the cache by the last call to
-    /// `save_cache_and_clear`. The matching is done by spacial proximity, i.e.,
-    /// two contacts that are sufficiently close will be given the same identifier.
+    /// `save_cache_and_clear`. With `ContactTrackingMode::DistanceBased`, the matching is done by
+    /// spacial proximity, i.e., two contacts that are sufficiently close will be given the same
+    /// identifier. With `ContactTrackingMode::FeatureBased`, two contacts on the same features will
+    /// be given the same identifier. If no contact on the same features exists, the closest contact
+    /// on adjacent features (as determined by the preprocessors) is used instead.
     pub fn push(
+        &mut self,
+        contact: Contact<N>,
+        kinematic: ContactKinematic<N>,
+        tracking_pt: Point<N>,
+        preprocessor1: Option<&dyn ContactPreprocessor<N>>,
+        preprocessor2: Option<&dyn ContactPreprocessor<N>>,
+    ) -> bool {
+        self.push_contact(
+            contact,
+            kinematic,
+            tracking_pt,
+            preprocessor1,
+            preprocessor2,
+            false,
+        )
+    }
+
+    // The detector used by the narrow phase to compute the speculative contacts of this manifold,
+    // kept from one update to the next.
+    pub(crate) fn speculative_detector_mut(&mut self) -> &mut Option<ContactAlgorithm<N>> {
+        &mut self.speculative_detector.0
+    }
+
+    /// Add a new speculative contact to the manifold.
+    ///
+    /// This is the same as `self.push` except that the added contact is flagged as speculative. A
+    /// speculative contact matched by a regular contact afterward loses its speculative flag.
+    pub fn push_speculative(
+        &mut self,
+        contact: Contact<N>,
+        kinematic: ContactKinematic<N>,
+        tracking_pt: Point<N>,
+        preprocessor1: Option<&dyn ContactPreprocessor<N>>,
+        preprocessor2: Option<&dyn ContactPreprocessor<N>>,
+    ) -> bool {
+        self.push_contact(
+            contact,
+            kinematic,
+            tracking_pt,
+            preprocessor1,
+            preprocessor2,
+            true,
+        )
+    }
+
+    fn push_contact(
         &mut self,
         mut contact: Contact<N>,
         mut kinematic: ContactKinematic<N>,
         tracking_pt: Point<N>,
         preprocessor1: Option<&dyn ContactPreprocessor<N>>,
         preprocessor2: Option<&dyn ContactPreprocessor<N>>,
+        speculative: bool,
     ) -> bool {
         if let Some(pp) = preprocessor1 {
             if !pp.process_contact(&mut contact, &mut kinematic, true) {
@@ -199,7 +496,8 @@ impl<N: RealField> ContactManifold<N> {
                 }
 
                 if closest == cache.len() {
-                    let tracked = TrackedContact::new(contact, kinematic);
+                    let mut tracked = TrackedContact::new(contact, kinematic);
+                    tracked.speculative = speculative;
                     let i = self.contacts.insert((tracked, self.persistence));
                     cache.push((tracking_pt, i));
                     self.ncontacts += 1;
@@ -218,8 +516,9 @@ impl<N: RealField> ContactManifold<N> {
                     let c = &mut self.contacts[contact_i];
 
                     if c.1 == self.persistence {
-                        if contact.depth <= c.0.contact.depth {
-                            // Keep the contact already in cache because it is deeper.
+                        if contact.depth <= c.0.contact.depth || (speculative && !c.0.speculative) {
+                            // Keep the contact already in cache because it is deeper
+                            // or because it is not speculative.
                             return true;
                         }
                     } else {
@@ -229,15 +528,52 @@ impl<N: RealField> ContactManifold<N> {
 
                     c.0.contact = contact;
                     c.0.kinematic = kinematic;
+                    c.0.speculative = speculative;
                     cache[closest].0 = tracking_pt;
 
                     true
                 }
             }
             ContactCache::FeatureBased(cache) => {
-                match cache.entry((kinematic.feature1(), kinematic.feature2())) {
+                let key = (kinematic.feature1(), kinematic.feature2());
+
+                if !cache.contains_key(&key) {
+                    // Look for a contact of the previous update located on adjacent features
+                    // so that the contact identifier is preserved when the contact moves from
+                    // one feature to its neighbour.
+                    let persistence = self.persistence;
+                    let contacts = &self.contacts;
+                    let is_same_or_adjacent =
+                        |f1: FeatureId, f2: FeatureId, pp: Option<&dyn ContactPreprocessor<N>>| {
+                            f1 == f2 || pp.map(|pp| pp.are_features_adjacent(f1, f2)) == Some(true)
+                        };
+
+                    let adjacent = cache
+                        .iter()
+                        .filter(|(k, i)| {
+                            contacts[**i].1 + 1 == persistence
+                                && is_same_or_adjacent(k.0, key.0, preprocessor1)
+                                && is_same_or_adjacent(k.1, key.1, preprocessor2)
+                        })
+                        .map(|(k, i)| {
+                            let dist = na::distance_squared(
+                                &contacts[*i].0.contact.world1,
+                                &contact.world1,
+                            );
+                            (*k, dist)
+                        })
+                        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
+
+                    if let Some((old_key, _)) = adjacent {
+                        let i = cache.remove(&old_key).unwrap();
+                        let _ = cache.insert(key, i);
+                    }
+                }
+
+                match cache.entry(key) {
                     Entry::Vacant(e) => {
-                        let tracked = TrackedContact::new(contact, kinematic);
+                        let mut tracked = TrackedContact::new(contact, kinematic);
+                        tracked.speculative = speculative;
                         let i = self.contacts.insert((tracked, self.persistence));
                         let _ = e.insert(i);
                         self.ncontacts += 1;
@@ -256,8 +592,11 @@ impl<N: RealField> ContactManifold<N> {
                         let c = &mut self.contacts[*e.get()];
 
                         if c.1 == self.persistence {
-                            if contact.depth <= c.0.contact.depth {
-                                // Keep the contact already in cache because it is deeper.
+                            if contact.depth <= c.0.contact.depth
+                                || (speculative && !c.0.speculative)
+                            {
+                                // Keep the contact already in cache because it is deeper
+                                // or because it is not speculative.
                                 return true;
                             }
                         } else {
@@ -267,6 +606,7 @@ impl<N: RealField> ContactManifold<N> {
 
                         c.0.contact = contact;
                         c.0.kinematic = kinematic;
+                        c.0.speculative = speculative;
 
                         true
                     }
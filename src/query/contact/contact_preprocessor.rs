@@ -1,4 +1,5 @@
 use crate::query::{Contact, ContactKinematic};
+use crate::shape::FeatureId;
 use na::RealField;
 
 /// Pre-process a contact before it is added to a contact manifold.
@@ -12,6 +13,16 @@ pub trait ContactPreprocessor<N: RealField> {
         kinematic: &mut ContactKinematic<N>,
         is_first: bool,
     ) -> bool;
+
+    /// Tests whether two distinct features, as output by `self.process_contact`, are adjacent
+    /// features of the shape this preprocessor is attached to.
+    ///
+    /// This is used by contact manifolds with feature-based tracking to preserve the identifier of
+    /// a contact moving from one feature to an adjacent one, e.g., from one triangle of a mesh to
+    /// its neighbour. Returns `false` by default.
+    fn are_features_adjacent(&self, _feature1: FeatureId, _feature2: FeatureId) -> bool {
+        false
+    }
 }
 
 // FIXME: not sure if there is a more efficient way of doing this.
@@ -36,4 +47,10 @@ where
             self.1.process_contact(c, kinematic, is_first)
         }
     }
+
+    // The features are remapped last by the second preprocessor, so only it can
+    // decide if they are adjacent.
+    fn are_features_adjacent(&self, feature1: FeatureId, feature2: FeatureId) -> bool {
+        self.1.are_features_adjacent(feature1, feature2)
+    }
 }
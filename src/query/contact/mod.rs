@@ -4,7 +4,7 @@ pub use self::contact::{Contact, ContactId, ContactPrediction, TrackedContact};
 pub use self::contact_kinematic::{
     ContactKinematic, LocalShapeApproximation, NeighborhoodGeometry,
 };
-pub use self::contact_manifold::{ContactManifold, ContactTrackingMode};
+pub use self::contact_manifold::{ContactManifold, ContactReduction, ContactTrackingMode};
 pub use self::contact_preprocessor::ContactPreprocessor;
 
 pub use self::contact_ball_ball::contact_ball_ball;
@@ -0,0 +1,207 @@
+//! Convex volumes bounded by planes.
+
+use crate::bounding_volume::{BoundingVolume, AABB};
+use crate::math::{Matrix, Point, Vector, DIM};
+#[cfg(feature = "dim3")]
+use na::Matrix4;
+use na::{RealField, Unit};
+
+/// A convex volume defined as the intersection of half-spaces, e.g., a camera frustum.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvexVolume<N: RealField> {
+    planes: Vec<(Unit<Vector<N>>, N)>,
+}
+
+/// The location of a bounding volume relative to a convex volume.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CullingStatus {
+    /// The bounding volume does not intersect the convex volume.
+    Outside,
+    /// The bounding volume may intersect the boundary of the convex volume.
+    Intersecting,
+    /// The bounding volume is fully inside of the convex volume.
+    Inside,
+}
+
+impl<N: RealField> ConvexVolume<N> {
+    /// Creates the convex volume bounded by the given planes.
+    ///
+    /// Each plane is given by its outward normal `n` and its offset `d`, such that the convex
+    /// volume is the set of points `x` satisfying `n.dot(x) <= d` for every plane.
+    pub fn new(planes: Vec<(Unit<Vector<N>>, N)>) -> ConvexVolume<N> {
+        ConvexVolume { planes }
+    }
+
+    /// Creates the frustum of a camera from its view-projection matrix.
+    ///
+    /// The matrix must follow the OpenGL conventions, i.e., the frustum is mapped to the cube
+    /// `[-1, 1]^3` in normalized device coordinates.
+    #[cfg(feature = "dim3")]
+    pub fn from_view_projection(view_projection: &Matrix4<N>) -> ConvexVolume<N> {
+        let last_row = view_projection.row(3);
+        let mut planes = Vec::with_capacity(6);
+
+        for i in 0..3 {
+            let row = view_projection.row(i);
+
+            // The clip-space coordinates `c` of the inner points satisfy `-c.w <= c[i] <= c.w`.
+            for plane in &[last_row + row, last_row - row] {
+                let normal = -Vector::new(plane[0], plane[1], plane[2]);
+                let (normal, norm) = Unit::new_and_get(normal);
+                planes.push((normal, plane[3] / norm));
+            }
+        }
+
+        ConvexVolume::new(planes)
+    }
+
+    /// The planes bounding this convex volume, given by their outward normals and offsets.
+    #[inline]
+    pub fn planes(&self) -> &[(Unit<Vector<N>>, N)] {
+        &self.planes
+    }
+
+    /// Tests if this convex volume contains the given point.
+    #[inline]
+    pub fn contains_point(&self, point: &Point<N>) -> bool {
+        self.planes
+            .iter()
+            .all(|(normal, offset)| normal.dot(&point.coords) <= *offset)
+    }
+
+    /// The AABB of this convex volume.
+    ///
+    /// If this convex volume is unbounded, the returned AABB contains every point with coordinates
+    /// between `-N::max_value() / 2` and `N::max_value() / 2` instead.
+    pub fn aabb(&self) -> AABB<N> {
+        let eps = N::default_epsilon().sqrt();
+        let mut full_rank = false;
+        let mut aabb: Option<AABB<N>> = None;
+
+        // The vertices are the points at the intersection of `DIM` planes.
+        let mut ids: Vec<usize> = (0..DIM).collect();
+
+        if self.planes.len() >= DIM {
+            loop {
+                let normals = Matrix::from_fn(|r, c| self.planes[ids[r]].0[c]);
+
+                if let Some(inv) = normals.try_inverse() {
+                    full_rank = true;
+                    let offsets = Vector::from_fn(|r, _| self.planes[ids[r]].1);
+                    let vertex = Point::from(inv * offsets);
+                    let is_vertex = self.planes.iter().all(|(normal, offset)| {
+                        normal.dot(&vertex.coords) <= *offset + eps * (N::one() + offset.abs())
+                    });
+
+                    if is_vertex {
+                        let vertex_aabb = AABB::new(vertex, vertex);
+
+                        match &mut aabb {
+                            Some(aabb) => aabb.merge(&vertex_aabb),
+                            None => aabb = Some(vertex_aabb),
+                        }
+                    }
+                }
+
+                if !next_combination(&mut ids, self.planes.len()) {
+                    break;
+                }
+            }
+        }
+
+        // The volume is unbounded if it contains a half-line. Such a half-line is orthogonal to
+        // `DIM - 1` of the normals if they span the whole space.
+        let is_unbounded = !full_rank
+            || self
+                .edge_directions()
+                .into_iter()
+                .filter_map(|dir| Unit::try_new(dir, eps))
+                .any(|dir| {
+                    [dir, -dir]
+                        .iter()
+                        .any(|dir| self.planes.iter().all(|(normal, _)| normal.dot(dir) <= eps))
+                });
+
+        if is_unbounded {
+            let half_max = N::max_value() * na::convert(0.5);
+            AABB::new(
+                Point::from(Vector::repeat(-half_max)),
+                Point::from(Vector::repeat(half_max)),
+            )
+        } else {
+            // There is no vertex if the volume is empty.
+            aabb.unwrap_or_else(|| AABB::new(Point::origin(), Point::origin()))
+        }
+    }
+
+    // The directions orthogonal to `DIM - 1` of the normals of this convex volume.
+    #[cfg(feature = "dim2")]
+    fn edge_directions(&self) -> Vec<Vector<N>> {
+        self.planes
+            .iter()
+            .map(|(normal, _)| Vector::new(-normal.y, normal.x))
+            .collect()
+    }
+
+    // The directions orthogonal to `DIM - 1` of the normals of this convex volume.
+    #[cfg(feature = "dim3")]
+    fn edge_directions(&self) -> Vec<Vector<N>> {
+        let mut result = Vec::new();
+
+        for (i, (normal1, _)) in self.planes.iter().enumerate() {
+            for (normal2, _) in &self.planes[i + 1..] {
+                result.push(normal1.cross(normal2))
+            }
+        }
+
+        result
+    }
+}
+
+// Replaces `ids` by the next increasing sequence of indices smaller than `n`, in lexicographic
+// order. Returns `false` if `ids` is the last one.
+fn next_combination(ids: &mut [usize], n: usize) -> bool {
+    let k = ids.len();
+
+    for i in (0..k).rev() {
+        if ids[i] < n - k + i {
+            ids[i] += 1;
+
+            for j in i + 1..k {
+                ids[j] = ids[j - 1] + 1;
+            }
+
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Trait implemented by bounding volumes which can be culled by a convex volume.
+pub trait ConvexVolumeCulling<N: RealField> {
+    /// An interval containing the dot products between `dir` and all the points of `self`.
+    ///
+    /// The interval does not need to be tight, but a tighter interval leads to better culling.
+    fn interval_along(&self, dir: &Unit<Vector<N>>) -> (N, N);
+
+    /// Locates `self` relative to the given convex volume.
+    ///
+    /// The test is conservative: `self` may be classified as `CullingStatus::Intersecting` while
+    /// being outside of `volume` near one of its edges.
+    fn culling_status(&self, volume: &ConvexVolume<N>) -> CullingStatus {
+        let mut result = CullingStatus::Inside;
+
+        for (normal, offset) in volume.planes() {
+            let (min, max) = self.interval_along(normal);
+
+            if min > *offset {
+                return CullingStatus::Outside;
+            } else if max > *offset {
+                result = CullingStatus::Intersecting;
+            }
+        }
+
+        result
+    }
+}
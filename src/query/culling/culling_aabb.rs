@@ -0,0 +1,13 @@
+use crate::bounding_volume::AABB;
+use crate::math::Vector;
+use crate::query::ConvexVolumeCulling;
+use na::{RealField, Unit};
+
+impl<N: RealField> ConvexVolumeCulling<N> for AABB<N> {
+    #[inline]
+    fn interval_along(&self, dir: &Unit<Vector<N>>) -> (N, N) {
+        let center = dir.dot(&self.center().coords);
+        let radius = dir.abs().dot(&self.half_extents());
+        (center - radius, center + radius)
+    }
+}
@@ -0,0 +1,12 @@
+use crate::bounding_volume::BoundingSphere;
+use crate::math::Vector;
+use crate::query::ConvexVolumeCulling;
+use na::{RealField, Unit};
+
+impl<N: RealField> ConvexVolumeCulling<N> for BoundingSphere<N> {
+    #[inline]
+    fn interval_along(&self, dir: &Unit<Vector<N>>) -> (N, N) {
+        let center = dir.dot(&self.center().coords);
+        (center - self.radius(), center + self.radius())
+    }
+}
@@ -0,0 +1,13 @@
+use crate::bounding_volume::{KDOPAxes, KDOP};
+use crate::math::Vector;
+use crate::query::ConvexVolumeCulling;
+use na::{RealField, Unit};
+
+impl<N: RealField, A: KDOPAxes<N>> ConvexVolumeCulling<N> for KDOP<N, A> {
+    #[inline]
+    fn interval_along(&self, dir: &Unit<Vector<N>>) -> (N, N) {
+        // NOTE: the interval of the AABB of the k-DOP is not tight, but computing the exact
+        // interval would require solving a linear program.
+        self.aabb().interval_along(dir)
+    }
+}
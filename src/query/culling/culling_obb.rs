@@ -0,0 +1,14 @@
+use crate::bounding_volume::OBB;
+use crate::math::Vector;
+use crate::query::ConvexVolumeCulling;
+use na::{RealField, Unit};
+
+impl<N: RealField> ConvexVolumeCulling<N> for OBB<N> {
+    #[inline]
+    fn interval_along(&self, dir: &Unit<Vector<N>>) -> (N, N) {
+        let center = dir.dot(&self.center().coords);
+        let local_dir = self.position().inverse_transform_vector(dir);
+        let radius = local_dir.abs().dot(self.half_extents());
+        (center - radius, center + radius)
+    }
+}
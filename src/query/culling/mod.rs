@@ -0,0 +1,11 @@
+//! Culling of bounding volumes by convex volumes.
+
+#[doc(inline)]
+pub use self::convex_volume::{ConvexVolume, ConvexVolumeCulling, CullingStatus};
+
+#[doc(hidden)]
+pub mod convex_volume;
+mod culling_aabb;
+mod culling_bounding_sphere;
+mod culling_kdop;
+mod culling_obb;
@@ -31,3 +31,49 @@ pub fn distance<N: RealField>(
         panic!("No algorithm known to compute a contact point between the given pair of shapes.")
     }
 }
+
+// The kinds of non-composite shapes a shape is made of.
+#[derive(Default)]
+struct LeafKinds {
+    support_map: bool,
+    plane: bool,
+    unsupported: bool,
+}
+
+impl LeafKinds {
+    fn of<N: RealField>(shape: &dyn Shape<N>) -> Self {
+        let mut kinds = LeafKinds::default();
+        kinds.add(shape);
+        kinds
+    }
+
+    fn add<N: RealField>(&mut self, shape: &dyn Shape<N>) {
+        if shape.is_support_map() {
+            self.support_map = true
+        } else if shape.is_shape::<Plane<N>>() {
+            self.plane = true
+        } else if let Some(composite) = shape.as_composite_shape() {
+            for i in 0..composite.nparts() {
+                composite.map_part_at(i, &Isometry::identity(), &mut |_, part| self.add(part))
+            }
+        } else {
+            self.unsupported = true
+        }
+    }
+
+    fn is_supported(&self) -> bool {
+        !self.unsupported && (self.support_map || self.plane)
+    }
+}
+
+/// Checks if `query::distance` and `query::closest_points` support the given pair of shapes
+/// instead of panicking.
+///
+/// The parts of composite shapes are checked recursively, so this is linear wrt. their number of
+/// parts.
+pub(crate) fn is_distance_supported<N: RealField>(g1: &dyn Shape<N>, g2: &dyn Shape<N>) -> bool {
+    let kinds1 = LeafKinds::of(g1);
+    let kinds2 = LeafKinds::of(g2);
+
+    kinds1.is_supported() && kinds2.is_supported() && !(kinds1.plane && kinds2.plane)
+}
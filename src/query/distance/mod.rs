@@ -1,6 +1,7 @@
 //! Implementation details of the `distance` function.
 
 pub use self::distance::distance;
+pub(crate) use self::distance::is_distance_supported;
 pub use self::distance_ball_ball::distance_ball_ball;
 pub use self::distance_composite_shape_shape::{
     distance_composite_shape_shape, distance_shape_composite_shape,
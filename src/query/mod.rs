@@ -27,6 +27,7 @@
 
 pub use self::closest_points::*;
 pub use self::contact::*;
+pub use self::culling::*;
 pub use self::distance::*;
 pub use self::error::*;
 pub use self::nonlinear_time_of_impact::*;
@@ -39,6 +40,7 @@ pub use self::toi_dispatcher::*;
 pub mod algorithms;
 mod closest_points;
 mod contact;
+mod culling;
 mod distance;
 mod error;
 mod nonlinear_time_of_impact;
@@ -11,6 +11,8 @@ mod point_capsule;
 mod point_compound;
 mod point_cuboid;
 mod point_heightfield;
+mod point_kdop;
+mod point_obb;
 mod point_plane;
 mod point_polyline;
 #[doc(hidden)]
@@ -0,0 +1,184 @@
+use crate::bounding_volume::{KDOPAxes, KDOP};
+use crate::math::{Isometry, Point, Vector, DIM};
+use crate::query::{PointProjection, PointQuery};
+use crate::shape::FeatureId;
+use na::{self, Matrix2, RealField, Vector2};
+#[cfg(feature = "dim3")]
+use na::{Matrix3, Vector3};
+
+impl<N: RealField, A: KDOPAxes<N>> KDOP<N, A> {
+    fn local_point_projection(
+        &self,
+        pt: &Point<N>,
+        solid: bool,
+    ) -> (bool, Point<N>, Option<usize>) {
+        let num_planes = A::AXES.len() * 2;
+        let violation = |i: usize, pt: &Point<N>| {
+            let (normal, offset) = self.plane(i);
+            normal.dot(&pt.coords) - offset
+        };
+
+        if (0..num_planes).all(|i| violation(i, pt) <= N::zero()) {
+            if solid {
+                return (true, *pt, None);
+            }
+
+            // The closest point of the boundary lies on the closest bounding plane.
+            let mut best = N::max_value();
+            let mut best_proj = *pt;
+            let mut best_id = 0;
+
+            for i in 0..num_planes {
+                let (normal, offset) = self.plane(i);
+                let shift = normal * ((offset - normal.dot(&pt.coords)) / normal.norm_squared());
+                let dist = shift.norm();
+
+                if dist < best {
+                    best = dist;
+                    best_proj = pt + shift;
+                    best_id = i;
+                }
+            }
+
+            return (true, best_proj, Some(best_id));
+        }
+
+        // The projection lies on the intersection of at most `DIM` bounding planes. Find them by
+        // enumerating the sets of planes with increasing size until the projection on one of them
+        // satisfies the optimality conditions.
+        let scale = self
+            .mins()
+            .iter()
+            .chain(self.maxs().iter())
+            .fold(pt.coords.amax(), |a, b| a.max(b.abs()));
+        let tolerance = N::default_epsilon() * na::convert(10.0) * (N::one() + scale);
+        let num_axes = A::AXES.len();
+        let distinct_axes = |i: usize, j: usize| i % num_axes != j % num_axes;
+
+        let project_on_planes = |ids: &[usize]| -> Option<Point<N>> {
+            let mut normals = [Vector::zeros(); DIM];
+            let mut violations = [N::zero(); DIM];
+
+            for (k, i) in ids.iter().enumerate() {
+                normals[k] = self.plane(*i).0;
+                violations[k] = violation(*i, pt);
+            }
+
+            let multipliers = solve_gram(&normals[..ids.len()], &violations[..ids.len()])?;
+
+            if multipliers[..ids.len()].iter().any(|l| *l < -tolerance) {
+                return None;
+            }
+
+            let mut proj = *pt;
+
+            for k in 0..ids.len() {
+                proj -= normals[k] * multipliers[k];
+            }
+
+            if (0..num_planes).all(|i| violation(i, &proj) <= tolerance) {
+                Some(proj)
+            } else {
+                None
+            }
+        };
+
+        for i in 0..num_planes {
+            if violation(i, pt) > N::zero() {
+                if let Some(proj) = project_on_planes(&[i]) {
+                    return (false, proj, Some(i));
+                }
+            }
+        }
+
+        // The projection usually lies on planes which are all violated by `pt`, so they are
+        // tried first.
+        for only_violated in &[true, false] {
+            let is_candidate = |i: usize| !*only_violated || violation(i, pt) > N::zero();
+
+            for i in (0..num_planes).filter(|i| is_candidate(*i)) {
+                for j in (i + 1..num_planes).filter(|j| is_candidate(*j)) {
+                    if distinct_axes(i, j) {
+                        if let Some(proj) = project_on_planes(&[i, j]) {
+                            return (false, proj, None);
+                        }
+                    }
+                }
+            }
+
+            #[cfg(feature = "dim3")]
+            {
+                for i in (0..num_planes).filter(|i| is_candidate(*i)) {
+                    for j in (i + 1..num_planes).filter(|j| is_candidate(*j)) {
+                        for k in (j + 1..num_planes).filter(|k| is_candidate(*k)) {
+                            if distinct_axes(i, j) && distinct_axes(i, k) && distinct_axes(j, k) {
+                                if let Some(proj) = project_on_planes(&[i, j, k]) {
+                                    return (false, proj, None);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Only reachable because of rounding errors.
+        let proj = self
+            .aabb()
+            .project_point(&Isometry::identity(), pt, true)
+            .point;
+        (false, proj, None)
+    }
+}
+
+// Solves `G * x = rhs` where `G` is the Gram matrix of the given vectors.
+fn solve_gram<N: RealField>(vectors: &[Vector<N>], rhs: &[N]) -> Option<[N; DIM]> {
+    let mut result = [N::zero(); DIM];
+    let gram = |i: usize, j: usize| vectors[i].dot(&vectors[j]);
+
+    match vectors.len() {
+        1 => {
+            result[0] = rhs[0] / gram(0, 0);
+        }
+        2 => {
+            let mat = Matrix2::new(gram(0, 0), gram(0, 1), gram(1, 0), gram(1, 1));
+            let sol = mat.try_inverse()? * Vector2::new(rhs[0], rhs[1]);
+            result[..2].copy_from_slice(sol.as_slice());
+        }
+        #[cfg(feature = "dim3")]
+        3 => {
+            let mat = Matrix3::from_fn(gram);
+            let sol = mat.try_inverse()? * Vector3::new(rhs[0], rhs[1], rhs[2]);
+            result.copy_from_slice(sol.as_slice());
+        }
+        _ => unreachable!(),
+    }
+
+    Some(result)
+}
+
+impl<N: RealField, A: KDOPAxes<N>> PointQuery<N> for KDOP<N, A> {
+    #[inline]
+    fn project_point(&self, m: &Isometry<N>, pt: &Point<N>, solid: bool) -> PointProjection<N> {
+        let ls_pt = m.inverse_transform_point(pt);
+        let (inside, proj, _) = self.local_point_projection(&ls_pt, solid);
+        PointProjection::new(inside, m * proj)
+    }
+
+    #[inline]
+    fn project_point_with_feature(
+        &self,
+        m: &Isometry<N>,
+        pt: &Point<N>,
+    ) -> (PointProjection<N>, FeatureId) {
+        let ls_pt = m.inverse_transform_point(pt);
+        let (inside, proj, plane) = self.local_point_projection(&ls_pt, false);
+        let feature = plane.map(FeatureId::Face).unwrap_or(FeatureId::Unknown);
+        (PointProjection::new(inside, m * proj), feature)
+    }
+
+    #[inline]
+    fn contains_point(&self, m: &Isometry<N>, pt: &Point<N>) -> bool {
+        self.contains_local_point(&m.inverse_transform_point(pt))
+    }
+}
@@ -0,0 +1,34 @@
+use crate::bounding_volume::OBB;
+use crate::math::{Isometry, Point};
+use crate::query::{PointProjection, PointQuery};
+use crate::shape::FeatureId;
+use na::RealField;
+
+impl<N: RealField> PointQuery<N> for OBB<N> {
+    #[inline]
+    fn project_point(&self, m: &Isometry<N>, pt: &Point<N>, solid: bool) -> PointProjection<N> {
+        self.cuboid()
+            .project_point(&(m * self.position()), pt, solid)
+    }
+
+    #[inline]
+    fn project_point_with_feature(
+        &self,
+        m: &Isometry<N>,
+        pt: &Point<N>,
+    ) -> (PointProjection<N>, FeatureId) {
+        self.cuboid()
+            .project_point_with_feature(&(m * self.position()), pt)
+    }
+
+    #[inline]
+    fn distance_to_point(&self, m: &Isometry<N>, pt: &Point<N>, solid: bool) -> N {
+        self.cuboid()
+            .distance_to_point(&(m * self.position()), pt, solid)
+    }
+
+    #[inline]
+    fn contains_point(&self, m: &Isometry<N>, pt: &Point<N>) -> bool {
+        self.cuboid().contains_point(&(m * self.position()), pt)
+    }
+}
@@ -1,4 +1,3 @@
-use crate::bounding_volume::AABB;
 use crate::math::{Isometry, Point};
 use crate::partitioning::{BestFirstVisitStatus, BestFirstVisitor};
 use crate::query::{
@@ -34,7 +33,7 @@ impl<N: RealField> PointQuery<N> for TriMesh<N> {
             found: false,
         };
 
-        self.bvh().visit(&mut visitor);
+        self.query_bvh().visit(&mut visitor);
 
         visitor.found
     }
@@ -56,7 +55,7 @@ impl<N: RealField> PointQueryWithLocation<N> for TriMesh<N> {
             point: &ls_pt,
         };
 
-        let (mut proj, extra_info) = self.bvh().best_first_search(&mut visitor).unwrap().1;
+        let (mut proj, extra_info) = self.query_bvh().best_first_search(&mut visitor).unwrap();
         proj.point = m * proj.point;
 
         (proj, extra_info)
@@ -71,17 +70,19 @@ struct TriMeshPointProjVisitor<'a, N: 'a + RealField> {
     point: &'a Point<N>,
 }
 
-impl<'a, N: RealField> BestFirstVisitor<N, usize, AABB<N>> for TriMeshPointProjVisitor<'a, N> {
+impl<'a, N: RealField, BV: PointQuery<N>> BestFirstVisitor<N, usize, BV>
+    for TriMeshPointProjVisitor<'a, N>
+{
     type Result = (PointProjection<N>, (usize, TrianglePointLocation<N>));
 
     #[inline]
     fn visit(
         &mut self,
         best: N,
-        aabb: &AABB<N>,
+        bv: &BV,
         data: Option<&usize>,
     ) -> BestFirstVisitStatus<N, Self::Result> {
-        let dist = aabb.distance_to_point(&Isometry::identity(), self.point, true);
+        let dist = bv.distance_to_point(&Isometry::identity(), self.point, true);
 
         let mut res = BestFirstVisitStatus::Continue {
             cost: dist,
@@ -16,6 +16,8 @@ mod ray_bounding_sphere;
 mod ray_compound;
 mod ray_cuboid;
 mod ray_heightfield;
+mod ray_kdop;
+mod ray_obb;
 mod ray_plane;
 mod ray_polyline;
 mod ray_shape;
@@ -0,0 +1,101 @@
+use crate::bounding_volume::{KDOPAxes, KDOP};
+use crate::math::{Isometry, Vector};
+use crate::query::{Ray, RayCast, RayIntersection};
+use crate::shape::FeatureId;
+use na::RealField;
+use std::mem;
+
+impl<N: RealField, A: KDOPAxes<N>> KDOP<N, A> {
+    // The parameters and bounding plane indices of the points where the line supporting `ray`
+    // enters and leaves this k-DOP.
+    fn clip_ray(&self, ray: &Ray<N>) -> Option<((N, usize), (N, usize))> {
+        let num_axes = A::AXES.len();
+        let mut near = (-N::max_value(), 0);
+        let mut far = (N::max_value(), 0);
+
+        for i in 0..num_axes {
+            let origin = Self::project_on_axis(i, &ray.origin.coords);
+            let dir = Self::project_on_axis(i, &ray.dir);
+
+            if dir.is_zero() {
+                if origin < self.mins()[i] || origin > self.maxs()[i] {
+                    return None;
+                }
+            } else {
+                let denom = N::one() / dir;
+                let mut inter_with_near_plane = ((self.mins()[i] - origin) * denom, i + num_axes);
+                let mut inter_with_far_plane = ((self.maxs()[i] - origin) * denom, i);
+
+                if dir < N::zero() {
+                    mem::swap(&mut inter_with_near_plane, &mut inter_with_far_plane)
+                }
+
+                if inter_with_near_plane.0 > near.0 {
+                    near = inter_with_near_plane
+                }
+
+                if inter_with_far_plane.0 < far.0 {
+                    far = inter_with_far_plane
+                }
+
+                if near.0 > far.0 {
+                    return None;
+                }
+            }
+        }
+
+        Some((near, far))
+    }
+
+    fn ray_intersection(
+        &self,
+        ray: &Ray<N>,
+        max_toi: N,
+        solid: bool,
+    ) -> Option<(N, Option<usize>)> {
+        let (near, far) = self.clip_ray(ray)?;
+
+        if near.0 < N::zero() {
+            if solid {
+                Some((N::zero(), None))
+            } else if far.0 >= N::zero() && far.0 <= max_toi {
+                Some((far.0, Some(far.1)))
+            } else {
+                None
+            }
+        } else if near.0 <= max_toi {
+            Some((near.0, Some(near.1)))
+        } else {
+            None
+        }
+    }
+}
+
+impl<N: RealField, A: KDOPAxes<N>> RayCast<N> for KDOP<N, A> {
+    #[inline]
+    fn toi_with_ray(&self, m: &Isometry<N>, ray: &Ray<N>, max_toi: N, solid: bool) -> Option<N> {
+        let ls_ray = ray.inverse_transform_by(m);
+        self.ray_intersection(&ls_ray, max_toi, solid)
+            .map(|(toi, _)| toi)
+    }
+
+    #[inline]
+    fn toi_and_normal_with_ray(
+        &self,
+        m: &Isometry<N>,
+        ray: &Ray<N>,
+        max_toi: N,
+        solid: bool,
+    ) -> Option<RayIntersection<N>> {
+        let ls_ray = ray.inverse_transform_by(m);
+
+        self.ray_intersection(&ls_ray, max_toi, solid)
+            .map(|(toi, plane)| match plane {
+                Some(i) => {
+                    let normal = self.plane(i).0.normalize();
+                    RayIntersection::new(toi, m * normal, FeatureId::Face(i))
+                }
+                None => RayIntersection::new(toi, Vector::zeros(), FeatureId::Unknown),
+            })
+    }
+}
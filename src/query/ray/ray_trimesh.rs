@@ -1,4 +1,3 @@
-use crate::bounding_volume::AABB;
 use crate::math::Isometry;
 use crate::partitioning::{BestFirstVisitStatus, BestFirstVisitor};
 use crate::query::{self, Ray, RayCast, RayIntersection};
@@ -16,7 +15,7 @@ impl<N: RealField> RayCast<N> for TriMesh<N> {
             max_toi,
         };
 
-        self.bvh().best_first_search(&mut visitor).map(|res| res.1)
+        self.query_bvh().best_first_search(&mut visitor)
     }
 
     #[inline]
@@ -35,9 +34,9 @@ impl<N: RealField> RayCast<N> for TriMesh<N> {
             max_toi,
         };
 
-        self.bvh()
+        self.query_bvh()
             .best_first_search(&mut visitor)
-            .map(|(_, (best, mut res))| {
+            .map(|(best, mut res)| {
                 if let FeatureId::Face(1) = res.feature {
                     res.feature = FeatureId::Face(best + self.faces().len());
                 } else {
@@ -67,9 +66,9 @@ impl<N: RealField> RayCast<N> for TriMesh<N> {
             ray: &ls_ray,
             max_toi,
         };
-        let cast = self.bvh().best_first_search(&mut visitor);
+        let cast = self.query_bvh().best_first_search(&mut visitor);
 
-        cast.map(|(_, (best, inter, uv))| {
+        cast.map(|(best, inter, uv)| {
             let toi = inter.toi;
             let n = inter.normal;
 
@@ -103,17 +102,19 @@ struct TriMeshRayToiVisitor<'a, N: 'a + RealField> {
     max_toi: N,
 }
 
-impl<'a, N: RealField> BestFirstVisitor<N, usize, AABB<N>> for TriMeshRayToiVisitor<'a, N> {
+impl<'a, N: RealField, BV: RayCast<N>> BestFirstVisitor<N, usize, BV>
+    for TriMeshRayToiVisitor<'a, N>
+{
     type Result = N;
 
     #[inline]
     fn visit(
         &mut self,
         best: N,
-        aabb: &AABB<N>,
+        bv: &BV,
         data: Option<&usize>,
     ) -> BestFirstVisitStatus<N, Self::Result> {
-        if let Some(toi) = aabb.toi_with_ray(&Isometry::identity(), self.ray, self.max_toi, true) {
+        if let Some(toi) = bv.toi_with_ray(&Isometry::identity(), self.ray, self.max_toi, true) {
             let mut res = BestFirstVisitStatus::Continue {
                 cost: toi,
                 result: None,
@@ -147,7 +148,7 @@ struct TriMeshRayToiAndNormalVisitor<'a, N: 'a + RealField> {
     max_toi: N,
 }
 
-impl<'a, N: RealField> BestFirstVisitor<N, usize, AABB<N>>
+impl<'a, N: RealField, BV: RayCast<N>> BestFirstVisitor<N, usize, BV>
     for TriMeshRayToiAndNormalVisitor<'a, N>
 {
     type Result = (usize, RayIntersection<N>);
@@ -156,10 +157,10 @@ impl<'a, N: RealField> BestFirstVisitor<N, usize, AABB<N>>
     fn visit(
         &mut self,
         best: N,
-        aabb: &AABB<N>,
+        bv: &BV,
         data: Option<&usize>,
     ) -> BestFirstVisitStatus<N, Self::Result> {
-        if let Some(toi) = aabb.toi_with_ray(&Isometry::identity(), self.ray, self.max_toi, true) {
+        if let Some(toi) = bv.toi_with_ray(&Isometry::identity(), self.ray, self.max_toi, true) {
             let mut res = BestFirstVisitStatus::Continue {
                 cost: toi,
                 result: None,
@@ -196,7 +197,7 @@ struct TriMeshRayToiAndNormalAndUVsVisitor<'a, N: 'a + RealField> {
     max_toi: N,
 }
 
-impl<'a, N: RealField> BestFirstVisitor<N, usize, AABB<N>>
+impl<'a, N: RealField, BV: RayCast<N>> BestFirstVisitor<N, usize, BV>
     for TriMeshRayToiAndNormalAndUVsVisitor<'a, N>
 {
     type Result = (usize, RayIntersection<N>, Vector3<N>);
@@ -205,10 +206,10 @@ impl<'a, N: RealField> BestFirstVisitor<N, usize, AABB<N>>
     fn visit(
         &mut self,
         best: N,
-        aabb: &AABB<N>,
+        bv: &BV,
         data: Option<&usize>,
     ) -> BestFirstVisitStatus<N, Self::Result> {
-        if let Some(toi) = aabb.toi_with_ray(&Isometry::identity(), self.ray, self.max_toi, true) {
+        if let Some(toi) = bv.toi_with_ray(&Isometry::identity(), self.ray, self.max_toi, true) {
             let mut res = BestFirstVisitStatus::Continue {
                 cost: toi,
                 result: None,
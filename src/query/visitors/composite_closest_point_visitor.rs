@@ -1,4 +1,3 @@
-use crate::bounding_volume::AABB;
 use crate::math::{Isometry, Point};
 use crate::partitioning::{BestFirstVisitStatus, BestFirstVisitor};
 use crate::query::{PointProjection, PointQuery};
@@ -23,8 +22,11 @@ impl<'a, N: RealField, S: CompositeShape<N>> CompositeClosestPointVisitor<'a, N,
     }
 }
 
-impl<'a, N: RealField, S: CompositeShape<N> + PointQuery<N>> BestFirstVisitor<N, usize, AABB<N>>
-    for CompositeClosestPointVisitor<'a, N, S>
+impl<'a, N, BV, S> BestFirstVisitor<N, usize, BV> for CompositeClosestPointVisitor<'a, N, S>
+where
+    N: RealField,
+    BV: PointQuery<N>,
+    S: CompositeShape<N> + PointQuery<N>,
 {
     type Result = PointProjection<N>;
 
@@ -32,10 +34,10 @@ impl<'a, N: RealField, S: CompositeShape<N> + PointQuery<N>> BestFirstVisitor<N,
     fn visit(
         &mut self,
         best: N,
-        aabb: &AABB<N>,
+        bv: &BV,
         data: Option<&usize>,
     ) -> BestFirstVisitStatus<N, Self::Result> {
-        let dist = aabb.distance_to_point(&Isometry::identity(), self.point, true);
+        let dist = bv.distance_to_point(&Isometry::identity(), self.point, true);
 
         let mut res = BestFirstVisitStatus::Continue {
             cost: dist,
@@ -0,0 +1,66 @@
+use crate::partitioning::BVH;
+use crate::query::{ConvexVolume, ConvexVolumeCulling, CullingStatus};
+use na::RealField;
+
+/// Spatial partitioning structure traversal collecting leaves that may intersect a given convex
+/// volume.
+///
+/// Once a node is found to be fully inside of the convex volume, all the leaves of its subtree are
+/// collected with `BVH::visit_subtree_leaves`, without testing their bounding volumes against the
+/// planes of the convex volume.
+pub struct ConvexVolumeInterferencesCollector<'a, N: 'a + RealField, T: 'a> {
+    /// The convex volume to be tested.
+    pub volume: &'a ConvexVolume<N>,
+    /// The data contained by the leaves which bounding volume is not outside of `self.volume`.
+    pub collector: &'a mut Vec<T>,
+}
+
+impl<'a, N: RealField, T: Clone> ConvexVolumeInterferencesCollector<'a, N, T> {
+    /// Creates a new `ConvexVolumeInterferencesCollector`.
+    #[inline]
+    pub fn new(
+        volume: &'a ConvexVolume<N>,
+        buffer: &'a mut Vec<T>,
+    ) -> ConvexVolumeInterferencesCollector<'a, N, T> {
+        ConvexVolumeInterferencesCollector {
+            volume,
+            collector: buffer,
+        }
+    }
+
+    /// Traverses `bvh` and collects the data of its leaves which bounding volume is not outside of
+    /// `self.volume`.
+    pub fn collect<BV: ConvexVolumeCulling<N>>(&mut self, bvh: &impl BVH<T, BV>) {
+        let mut stack = Vec::new();
+        let mut subtree_stack = Vec::new();
+
+        if let Some(root) = bvh.root() {
+            stack.push(root);
+        }
+
+        while let Some(node) = stack.pop() {
+            let (bv, data) = bvh.content(node);
+
+            match bv.culling_status(self.volume) {
+                CullingStatus::Outside => {}
+                CullingStatus::Intersecting => {
+                    if let Some(data) = data {
+                        self.collector.push(data.clone());
+                    }
+
+                    for i in 0..bvh.num_children(node) {
+                        stack.push(bvh.child(i, node))
+                    }
+                }
+                CullingStatus::Inside => {
+                    let collector = &mut *self.collector;
+                    bvh.visit_subtree_leaves_with_stack(
+                        node,
+                        &mut |data| collector.push(data.clone()),
+                        &mut subtree_stack,
+                    )
+                }
+            }
+        }
+    }
+}
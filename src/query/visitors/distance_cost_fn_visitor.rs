@@ -0,0 +1,106 @@
+use crate::bounding_volume::BoundingVolume;
+use crate::math::{Isometry, Point};
+use crate::partitioning::{BestFirstVisitStatus, BestFirstVisitor};
+use crate::query::{PointQuery, RayCast};
+use na::RealField;
+use std::any::Any;
+
+use crate::pipeline::{BroadPhase, BroadPhaseProxyHandle};
+
+/// Bounding Volume Tree visitor searching for the objects closest to a given ball.
+pub struct DistanceCostFnVisitor<'a, 'b, N: 'a + RealField, T, BV>
+where
+    BV: BoundingVolume<N> + RayCast<N> + PointQuery<N> + Any + Send + Sync + Clone,
+    T: Any + Send + Sync,
+{
+    /// Center of the ball to be tested.
+    center: &'b Point<N>,
+
+    /// Radius of the ball to be tested.
+    radius: N,
+
+    /// Maximum distance between the ball and the objects.
+    max_dist: N,
+
+    /// Used as a lookup to get the underlying data of the tree.
+    broad_phase: &'a dyn BroadPhase<N, BV, T>,
+
+    /// The cost function to apply to each leaf nodes data.
+    ///
+    /// The cost must be greater or equal to the distance between the ball and the leaf bounding volume.
+    cost_fn: &'a dyn Fn(&T) -> Option<N>,
+}
+
+impl<'a, 'b, N: RealField, T, BV> DistanceCostFnVisitor<'a, 'b, N, T, BV>
+where
+    BV: BoundingVolume<N> + RayCast<N> + PointQuery<N> + Any + Send + Sync + Clone,
+    T: Any + Send + Sync,
+{
+    /// Creates a new `DistanceCostFnVisitor`.
+    #[inline]
+    pub fn new(
+        center: &'b Point<N>,
+        radius: N,
+        max_dist: N,
+        broad_phase: &'a dyn BroadPhase<N, BV, T>,
+        cost_fn: &'a dyn Fn(&T) -> Option<N>,
+    ) -> DistanceCostFnVisitor<'a, 'b, N, T, BV> {
+        DistanceCostFnVisitor {
+            center,
+            radius,
+            max_dist,
+            broad_phase,
+            cost_fn,
+        }
+    }
+}
+
+impl<'a, 'b, N, BV, T> BestFirstVisitor<N, BroadPhaseProxyHandle, BV>
+    for DistanceCostFnVisitor<'a, 'b, N, T, BV>
+where
+    N: RealField,
+    BV: BoundingVolume<N> + RayCast<N> + PointQuery<N> + Any + Send + Sync + Clone,
+    T: Any + Send + Sync + Clone,
+{
+    type Result = (T, N);
+
+    #[inline]
+    fn visit(
+        &mut self,
+        best_cost_so_far: N,
+        bv: &BV,
+        data: Option<&BroadPhaseProxyHandle>,
+    ) -> BestFirstVisitStatus<N, Self::Result> {
+        // A lower bound of the distance between the ball and anything inside of the bv.
+        let dist = bv.distance_to_point(&Isometry::identity(), self.center, true) - self.radius;
+        let dist = dist.max(N::zero());
+
+        if dist > self.max_dist {
+            return BestFirstVisitStatus::Stop;
+        }
+
+        if let Some(data_handle) = data {
+            if dist >= best_cost_so_far {
+                return BestFirstVisitStatus::Stop;
+            }
+
+            if let Some((_, leaf_data)) = self.broad_phase.proxy(*data_handle) {
+                if let Some(cost) = (self.cost_fn)(leaf_data) {
+                    if cost <= self.max_dist {
+                        return BestFirstVisitStatus::Continue {
+                            cost,
+                            result: Some((leaf_data.clone(), cost)),
+                        };
+                    }
+                }
+            }
+
+            BestFirstVisitStatus::Stop
+        } else {
+            BestFirstVisitStatus::Continue {
+                cost: dist,
+                result: None,
+            }
+        }
+    }
+}
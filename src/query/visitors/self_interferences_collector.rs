@@ -0,0 +1,63 @@
+use crate::bounding_volume::BoundingVolume;
+use crate::partitioning::{SimultaneousVisitor, VisitStatus};
+use na::RealField;
+use std::marker::PhantomData;
+
+/// Spatial partitioning data structure visitor collecting the pairs of leaves of a single
+/// structure with intersecting bounding volumes.
+///
+/// It is meant to be used with `BVH::visit_self_bvtt` for self-collision detection.
+pub struct SelfInterferencesCollector<'a, N: 'a, T: 'a, F> {
+    /// Predicate returning `false` for the pairs of leaves which must not be reported.
+    ///
+    /// For example, it can reject the pairs of triangles sharing a vertex on a triangle mesh.
+    pub filter: F,
+    /// The pairs of data contained by the leaves with intersecting bounding volumes.
+    pub collector: &'a mut Vec<(T, T)>,
+    _point: PhantomData<N>,
+}
+
+impl<'a, N, T, F> SelfInterferencesCollector<'a, N, T, F>
+where
+    N: RealField,
+    F: FnMut(&T, &T) -> bool,
+{
+    /// Creates a new `SelfInterferencesCollector`.
+    #[inline]
+    pub fn new(filter: F, buffer: &'a mut Vec<(T, T)>) -> SelfInterferencesCollector<'a, N, T, F> {
+        SelfInterferencesCollector {
+            filter,
+            collector: buffer,
+            _point: PhantomData,
+        }
+    }
+}
+
+impl<'a, N, T, BV, F> SimultaneousVisitor<T, BV> for SelfInterferencesCollector<'a, N, T, F>
+where
+    N: RealField,
+    T: Clone,
+    BV: BoundingVolume<N>,
+    F: FnMut(&T, &T) -> bool,
+{
+    #[inline]
+    fn visit(
+        &mut self,
+        left_bv: &BV,
+        left_data: Option<&T>,
+        right_bv: &BV,
+        right_data: Option<&T>,
+    ) -> VisitStatus {
+        if left_bv.intersects(right_bv) {
+            if let (Some(a), Some(b)) = (left_data, right_data) {
+                if (self.filter)(a, b) {
+                    self.collector.push((a.clone(), b.clone()))
+                }
+            }
+
+            VisitStatus::Continue
+        } else {
+            VisitStatus::Stop
+        }
+    }
+}
@@ -1,6 +1,6 @@
-use crate::bounding_volume::AABB;
+use crate::bounding_volume::{BoundingSphere, BoundingVolume, HasBoundingVolume, AABB, OBB};
 use crate::math::Isometry;
-use crate::partitioning::BVHImpl;
+use crate::partitioning::{BVHImpl, BestFirstVisitor, Visitor, BVH, BVT};
 use crate::query::{ContactPrediction, ContactPreprocessor};
 use crate::shape::Shape;
 use na::RealField;
@@ -38,4 +38,186 @@ pub trait CompositeShape<N: RealField> {
 
     /// Gets the acceleration structure of the concave shape.
     fn bvh(&self) -> BVHImpl<N, usize, AABB<N>>;
+
+    /// Gets the acceleration structure traversed by the ray casts and point queries on this shape.
+    ///
+    /// This is `self.bvh()` by default. Shapes may return a hierarchy of other bounding volumes
+    /// instead, e.g., if their sub-shapes are long and not aligned with the coordinate axes.
+    fn query_bvh(&self) -> CompositeShapeBVH<'_, N> {
+        CompositeShapeBVH::AABB(self.bvh())
+    }
+
+    /// Builds a balanced BVT of the sub-shapes of this composite shape, bounded by `BV`s.
+    ///
+    /// The acceleration structure returned by `self.bvh()` always uses AABBs, which are loose for
+    /// sub-shapes that are long and not aligned with the coordinate axes. An `OBB` or
+    /// `BoundingSphere` hierarchy built by this method can be traversed with the visitors of
+    /// `query::visitors` instead, or be returned by `self.query_bvh()`.
+    fn build_bvt<BV>(&self) -> BVT<usize, BV>
+    where
+        Self: Sized,
+        BV: BoundingVolume<N> + Clone,
+        dyn Shape<N>: HasBoundingVolume<N, BV>,
+    {
+        let leaves = (0..self.nparts())
+            .map(|i| {
+                let mut bv = None;
+                self.map_part_at(i, &Isometry::identity(), &mut |m, part| {
+                    bv = Some(part.bounding_volume(m))
+                });
+
+                (
+                    i,
+                    bv.expect("Every part of a composite shape must be mapped."),
+                )
+            })
+            .collect();
+
+        BVT::new_balanced(leaves)
+    }
+}
+
+/// The bounding volume of the hierarchy traversed by the ray casts and point queries on a
+/// composite shape.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum QueryBoundingVolume {
+    /// Axis-aligned bounding boxes.
+    AABB,
+    /// Oriented bounding boxes.
+    OBB,
+    /// Bounding spheres.
+    BoundingSphere,
+}
+
+/// The acceleration structure traversed by the ray casts and point queries on a composite shape.
+pub enum CompositeShapeBVH<'a, N: 'a + RealField> {
+    /// A hierarchy of axis-aligned bounding boxes.
+    AABB(BVHImpl<'a, N, usize, AABB<N>>),
+    /// A hierarchy of oriented bounding boxes.
+    OBB(&'a BVT<usize, OBB<N>>),
+    /// A hierarchy of bounding spheres.
+    BoundingSphere(&'a BVT<usize, BoundingSphere<N>>),
+}
+
+impl<'a, N: RealField> CompositeShapeBVH<'a, N> {
+    /// The bounding volume of this hierarchy.
+    pub fn bounding_volume(&self) -> QueryBoundingVolume {
+        match self {
+            CompositeShapeBVH::AABB(_) => QueryBoundingVolume::AABB,
+            CompositeShapeBVH::OBB(_) => QueryBoundingVolume::OBB,
+            CompositeShapeBVH::BoundingSphere(_) => QueryBoundingVolume::BoundingSphere,
+        }
+    }
+
+    /// Traverses this hierarchy using a visitor.
+    pub fn visit<V>(self, visitor: &mut V)
+    where
+        V: Visitor<usize, AABB<N>> + Visitor<usize, OBB<N>> + Visitor<usize, BoundingSphere<N>>,
+    {
+        match self {
+            CompositeShapeBVH::AABB(bvh) => bvh.visit(visitor),
+            CompositeShapeBVH::OBB(bvt) => bvt.visit(visitor),
+            CompositeShapeBVH::BoundingSphere(bvt) => bvt.visit(visitor),
+        }
+    }
+
+    /// Performs a best-first-search on this hierarchy.
+    ///
+    /// Returns the user-defined result associated to the leaf with the smallest cost.
+    pub fn best_first_search<V, R>(self, visitor: &mut V) -> Option<R>
+    where
+        V: BestFirstVisitor<N, usize, AABB<N>, Result = R>
+            + BestFirstVisitor<N, usize, OBB<N>, Result = R>
+            + BestFirstVisitor<N, usize, BoundingSphere<N>, Result = R>,
+    {
+        match self {
+            CompositeShapeBVH::AABB(bvh) => bvh.best_first_search(visitor).map(|res| res.1),
+            CompositeShapeBVH::OBB(bvt) => bvt.best_first_search(visitor).map(|res| res.1),
+            CompositeShapeBVH::BoundingSphere(bvt) => {
+                bvt.best_first_search(visitor).map(|res| res.1)
+            }
+        }
+    }
+}
+
+// The hierarchy traversed by the ray casts and point queries on a composite shape instead of its
+// AABB tree.
+#[allow(clippy::upper_case_acronyms)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone)]
+enum QueryBVTImpl<N: RealField> {
+    OBB(BVT<usize, OBB<N>>),
+    BoundingSphere(BVT<usize, BoundingSphere<N>>),
+}
+
+/// An OBB or bounding sphere hierarchy built on the parts of a composite shape.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone)]
+pub(crate) struct QueryBVT<N: RealField> {
+    bvt: QueryBVTImpl<N>,
+    // The index of the leaf of each part.
+    part_leaves: Vec<usize>,
+}
+
+impl<N: RealField> QueryBVT<N> {
+    /// Builds the hierarchy bounded by `bv` on the parts of `shape`.
+    ///
+    /// Returns `None` for AABBs since `shape.bvh()` is traversed in this case.
+    pub fn new<S: CompositeShape<N>>(shape: &S, bv: QueryBoundingVolume) -> Option<Self> {
+        let bvt = match bv {
+            QueryBoundingVolume::AABB => return None,
+            QueryBoundingVolume::OBB => QueryBVTImpl::OBB(shape.build_bvt()),
+            QueryBoundingVolume::BoundingSphere => QueryBVTImpl::BoundingSphere(shape.build_bvt()),
+        };
+
+        let mut part_leaves = vec![0; shape.nparts()];
+        let data: Vec<usize> = match &bvt {
+            QueryBVTImpl::OBB(bvt) => bvt.leaves().iter().map(|leaf| *leaf.data()).collect(),
+            QueryBVTImpl::BoundingSphere(bvt) => {
+                bvt.leaves().iter().map(|leaf| *leaf.data()).collect()
+            }
+        };
+
+        for (leaf, part) in data.into_iter().enumerate() {
+            part_leaves[part] = leaf;
+        }
+
+        Some(QueryBVT { bvt, part_leaves })
+    }
+
+    /// The acceleration structure to return from `CompositeShape::query_bvh`.
+    pub fn bvh(&self) -> CompositeShapeBVH<'_, N> {
+        match &self.bvt {
+            QueryBVTImpl::OBB(bvt) => CompositeShapeBVH::OBB(bvt),
+            QueryBVTImpl::BoundingSphere(bvt) => CompositeShapeBVH::BoundingSphere(bvt),
+        }
+    }
+
+    /// Sets the bounding volume of the leaf of the `i`-th part, loosened by `margin`.
+    ///
+    /// The ancestors of this leaf are not updated until `self.refit()` is called.
+    pub fn set_part(&mut self, i: usize, part: &dyn Shape<N>, margin: N) {
+        let leaf = self.part_leaves[i];
+        let m = Isometry::identity();
+
+        match &mut self.bvt {
+            QueryBVTImpl::OBB(bvt) => {
+                let bv: OBB<N> = part.bounding_volume(&m);
+                bvt.set_leaf_bounding_volume(leaf, bv.loosened(margin), false)
+            }
+            QueryBVTImpl::BoundingSphere(bvt) => {
+                let bv: BoundingSphere<N> = part.bounding_volume(&m);
+                bvt.set_leaf_bounding_volume(leaf, bv.loosened(margin), false)
+            }
+        }
+    }
+
+    /// Refits the ancestors of the leaves modified by `self.set_part`.
+    pub fn refit(&mut self) {
+        match &mut self.bvt {
+            QueryBVTImpl::OBB(bvt) => bvt.refit(N::zero()),
+            QueryBVTImpl::BoundingSphere(bvt) => bvt.refit(N::zero()),
+        }
+    }
 }
@@ -6,7 +6,10 @@ use crate::bounding_volume::{BoundingVolume, AABB};
 use crate::math::Isometry;
 use crate::partitioning::{BVHImpl, BVT};
 use crate::query::{Contact, ContactKinematic, ContactPrediction, ContactPreprocessor};
-use crate::shape::{CompositeShape, FeatureId, Shape, ShapeHandle};
+use crate::shape::{
+    composite_shape::QueryBVT, CompositeShape, CompositeShapeBVH, FeatureId, QueryBoundingVolume,
+    Shape, ShapeHandle,
+};
 use na::{self, RealField};
 use std::mem;
 
@@ -19,6 +22,7 @@ use std::mem;
 pub struct Compound<N: RealField> {
     shapes: Vec<(Isometry<N>, ShapeHandle<N>)>,
     bvt: BVT<usize, AABB<N>>,
+    query_bvt: Option<QueryBVT<N>>,
     bvs: Vec<AABB<N>>,
     nbits: usize,
 }
@@ -42,15 +46,66 @@ impl<N: RealField> Compound<N> {
         }
 
         let nbits = mem::size_of::<usize>() * 8 - leaves.len().leading_zeros() as usize;
+        #[cfg(feature = "parallel")]
+        let bvt = BVT::new_balanced_parallel(leaves);
+        #[cfg(not(feature = "parallel"))]
         let bvt = BVT::new_balanced(leaves);
 
         Compound {
             shapes: shapes,
             bvt: bvt,
+            query_bvt: None,
             bvs: bvs,
             nbits,
         }
     }
+
+    /// Builds a new compound shape using a previously built BVT, e.g., decoded with
+    /// `BVT::from_bytes`.
+    ///
+    /// The data of the leaves of `bvt` must be the indices of the parts of this compound, and
+    /// their AABBs must contain those parts. This is the case of the BVT of a compound built
+    /// with the same `shapes`.
+    ///
+    /// Panics if `bvt` does not match the parts of this compound.
+    pub fn with_bvt(
+        shapes: Vec<(Isometry<N>, ShapeHandle<N>)>,
+        bvt: BVT<usize, AABB<N>>,
+    ) -> Compound<N> {
+        if shapes.iter().any(|(_, shape)| shape.is_composite_shape()) {
+            panic!("Nested composite shapes are not allowed.");
+        }
+
+        assert_eq!(
+            bvt.leaves().len(),
+            shapes.len(),
+            "The BVT leaves do not match the parts of the compound."
+        );
+
+        let mut bvs: Vec<Option<AABB<N>>> = vec![None; shapes.len()];
+
+        for leaf in bvt.leaves() {
+            let i = *leaf.data();
+            assert!(
+                matches!(bvs.get(i), Some(None))
+                    && leaf
+                        .bounding_volume()
+                        .contains(&shapes[i].1.aabb(&shapes[i].0)),
+                "The BVT leaves do not match the parts of the compound."
+            );
+            bvs[i] = Some(leaf.bounding_volume().clone());
+        }
+
+        let nbits = size_of::<usize>() * 8 - shapes.len().leading_zeros() as usize;
+
+        Compound {
+            shapes,
+            bvt,
+            query_bvt: None,
+            bvs: bvs.into_iter().map(Option::unwrap).collect(),
+            nbits,
+        }
+    }
 }
 
 impl<N: RealField> Compound<N> {
@@ -66,6 +121,23 @@ impl<N: RealField> Compound<N> {
         &self.bvt
     }
 
+    /// The bounding volume of the hierarchy traversed by the ray casts and point queries on this
+    /// compound shape.
+    #[inline]
+    pub fn query_bounding_volume(&self) -> QueryBoundingVolume {
+        self.query_bvh().bounding_volume()
+    }
+
+    /// Sets the bounding volume of the hierarchy traversed by the ray casts and point queries on
+    /// this compound shape.
+    ///
+    /// By default, those queries traverse `self.bvt()`. OBBs bound parts that are long and not
+    /// aligned with the coordinate axes more tightly than AABBs. Any other hierarchy than the AABB
+    /// one is built in addition to `self.bvt()`.
+    pub fn set_query_bounding_volume(&mut self, bv: QueryBoundingVolume) {
+        self.query_bvt = QueryBVT::new(self, bv)
+    }
+
     /// The AABB of this compound in its local-space.
     #[inline]
     pub fn aabb(&self) -> &AABB<N> {
@@ -150,6 +222,14 @@ impl<N: RealField> CompositeShape<N> for Compound<N> {
     fn bvh(&self) -> BVHImpl<N, usize, AABB<N>> {
         BVHImpl::BVT(&self.bvt)
     }
+
+    #[inline]
+    fn query_bvh(&self) -> CompositeShapeBVH<'_, N> {
+        match &self.query_bvt {
+            Some(query_bvt) => query_bvt.bvh(),
+            None => CompositeShapeBVH::AABB(self.bvh()),
+        }
+    }
 }
 
 struct CompoundContactProcessor<'a, N: RealField> {
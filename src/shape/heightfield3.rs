@@ -72,6 +72,11 @@ impl<N: RealField> HeightField<N> {
         self.heights.ncols() - 1
     }
 
+    /// The number of triangles of this heightfield, including the removed ones.
+    pub fn num_triangles(&self) -> usize {
+        self.num_triangles
+    }
+
     fn triangle_id(&self, i: usize, j: usize, left: bool) -> usize {
         let tid = j * (self.heights.nrows() - 1) + i;
         if left {
@@ -81,6 +86,15 @@ impl<N: RealField> HeightField<N> {
         }
     }
 
+    // The cell `(i, j)` containing the given triangle, and whether it is the left triangle.
+    fn triangle_cell(&self, tid: usize) -> (usize, usize, bool) {
+        let half = self.num_triangles / 2;
+        let left = tid < half;
+        let cell = if left { tid } else { tid - half };
+        let nrows = self.heights.nrows() - 1;
+        (cell % nrows, cell / nrows, left)
+    }
+
     fn face_id(&self, i: usize, j: usize, left: bool, front: bool) -> usize {
         let tid = self.triangle_id(i, j, left);
         if front {
@@ -360,6 +374,72 @@ impl<N: RealField> HeightField<N> {
         }
     }
 
+    // The vertices of the given feature, followed by the number of vertices.
+    pub(crate) fn feature_vertices(&self, fid: FeatureId) -> ([usize; 3], usize) {
+        let (nrows, ncols) = self.heights.shape();
+
+        match fid {
+            FeatureId::Vertex(i) => ([i, i, i], 1),
+            FeatureId::Edge(iedge) => {
+                let hshift = (nrows - 1) * ncols; // First horizontal line index.
+                let dshift = hshift + nrows * (ncols - 1); // First diagonal line index.
+
+                if iedge < hshift {
+                    let (i, j) = (iedge % (nrows - 1), iedge / (nrows - 1));
+                    let ij = i + j * nrows;
+                    ([ij, ij + 1, ij + 1], 2)
+                } else if iedge < dshift {
+                    let ij = iedge - hshift;
+                    ([ij, ij + nrows, ij + nrows], 2)
+                } else {
+                    let idiag = iedge - dshift;
+                    let (i, j) = (idiag % (nrows - 1), idiag / (nrows - 1));
+                    let ij = i + j * nrows;
+
+                    if self.status[(i, j)].contains(HeightFieldCellStatus::ZIGZAG_SUBDIVISION) {
+                        ([ij, ij + 1 + nrows, ij + 1 + nrows], 2)
+                    } else {
+                        ([ij + 1, ij + nrows, ij + nrows], 2)
+                    }
+                }
+            }
+            FeatureId::Face(iface) => {
+                let (i, j, left) = self.triangle_cell(iface % self.num_triangles);
+                let mut vertices = [0; 3];
+
+                for (k, vertex) in vertices.iter_mut().enumerate() {
+                    if let FeatureId::Vertex(id) =
+                        self.convert_triangle_feature_id(i, j, left, FeatureId::Vertex(k))
+                    {
+                        *vertex = id
+                    }
+                }
+
+                (vertices, 3)
+            }
+            FeatureId::Unknown => ([0; 3], 0),
+        }
+    }
+
+    /// Tests whether two distinct features of this heightfield share at least one vertex.
+    ///
+    /// Two faces are adjacent only if they are on the same side of the heightfield.
+    pub fn are_features_adjacent(&self, feature1: FeatureId, feature2: FeatureId) -> bool {
+        if feature1 == feature2 {
+            return false;
+        }
+
+        if let (FeatureId::Face(i1), FeatureId::Face(i2)) = (feature1, feature2) {
+            if (i1 < self.num_triangles) != (i2 < self.num_triangles) {
+                return false;
+            }
+        }
+
+        let (vtx1, n1) = self.feature_vertices(feature1);
+        let (vtx2, n2) = self.feature_vertices(feature2);
+        vtx1[..n1].iter().any(|i| vtx2[..n2].contains(i))
+    }
+
     /// Applies the function `f` to all the triangles of this heightfield intersecting the given AABB.
     pub fn map_elements_in_local_aabb(
         &self,
@@ -455,7 +535,6 @@ impl<N: RealField> HeightField<N> {
     }
 }
 
-#[allow(dead_code)]
 pub struct HeightFieldTriangleContactPreprocessor<'a, N: RealField> {
     heightfield: &'a HeightField<N>,
     triangle: usize,
@@ -474,10 +553,9 @@ impl<'a, N: RealField> ContactPreprocessor<N> for HeightFieldTriangleContactPrep
     fn process_contact(
         &self,
         _c: &mut Contact<N>,
-        _kinematic: &mut ContactKinematic<N>,
-        _is_first: bool,
+        kinematic: &mut ContactKinematic<N>,
+        is_first: bool,
     ) -> bool {
-        /*
         // Fix the feature ID.
         let feature = if is_first {
             kinematic.feature1()
@@ -485,19 +563,10 @@ impl<'a, N: RealField> ContactPreprocessor<N> for HeightFieldTriangleContactPrep
             kinematic.feature2()
         };
 
-        let face = &self.mesh.faces()[self.face_id];
-        let actual_feature = match feature {
-            FeatureId::Vertex(i) => FeatureId::Vertex(face.indices[i]),
-            FeatureId::Edge(i) => FeatureId::Edge(face.edges[i]),
-            FeatureId::Face(i) => {
-                if i == 0 {
-                    FeatureId::Face(self.face_id)
-                } else {
-                    FeatureId::Face(self.face_id + self.mesh.faces().len())
-                }
-            }
-            FeatureId::Unknown => FeatureId::Unknown,
-        };
+        let (i, j, left) = self.heightfield.triangle_cell(self.triangle);
+        let actual_feature = self
+            .heightfield
+            .convert_triangle_feature_id(i, j, left, feature);
 
         if is_first {
             kinematic.set_feature1(actual_feature);
@@ -505,16 +574,13 @@ impl<'a, N: RealField> ContactPreprocessor<N> for HeightFieldTriangleContactPrep
             kinematic.set_feature2(actual_feature);
         }
 
-        // Test the validity of the LMD.
-        if c.depth > N::zero() {
-            true
-        } else {
-            // FIXME
-        }
-        */
-
+        // FIXME: test the validity of the LMD.
         true
     }
+
+    fn are_features_adjacent(&self, feature1: FeatureId, feature2: FeatureId) -> bool {
+        self.heightfield.are_features_adjacent(feature1, feature2)
+    }
 }
 
 struct HeightfieldTriangles<'a, N: RealField> {
@@ -3,7 +3,7 @@
 pub use self::ball::Ball;
 pub use self::capsule::Capsule;
 #[doc(inline)]
-pub use self::composite_shape::CompositeShape;
+pub use self::composite_shape::{CompositeShape, CompositeShapeBVH, QueryBoundingVolume};
 pub use self::compound::Compound;
 #[cfg(feature = "dim3")]
 pub use self::cone::Cone;
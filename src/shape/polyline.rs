@@ -2,12 +2,15 @@
 
 use crate::bounding_volume::{self, BoundingVolume, AABB};
 use crate::math::{Isometry, Point, Vector, DIM};
-use crate::partitioning::{BVHImpl, BVT};
+use crate::partitioning::{BVHImpl, BVTRebuildPolicy, BVT};
 use crate::query::{
     Contact, ContactKinematic, ContactPrediction, ContactPreprocessor, LocalShapeApproximation,
     NeighborhoodGeometry,
 };
-use crate::shape::{CompositeShape, DeformableShape, DeformationsType, FeatureId, Segment, Shape};
+use crate::shape::{
+    composite_shape::QueryBVT, CompositeShape, CompositeShapeBVH, DeformableShape,
+    DeformationsType, FeatureId, QueryBoundingVolume, Segment, Shape,
+};
 use na::{self, Point2, RealField, Unit};
 use std::iter;
 use std::ops::Range;
@@ -21,6 +24,7 @@ struct DeformationInfos<N: RealField> {
     timestamps: Vec<usize>,
     ref_vertices: Vec<Point<N>>,
     seg_to_update: Vec<usize>,
+    bvt_rebuild_policy: Option<BVTRebuildPolicy<N>>,
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -43,6 +47,7 @@ pub struct PolylineVertex {
 #[derive(Clone)]
 pub struct Polyline<N: RealField> {
     bvt: BVT<usize, AABB<N>>,
+    query_bvt: Option<QueryBVT<N>>,
     points: Vec<Point<N>>,
     vertices: Vec<PolylineVertex>,
     edges: Vec<PolylineEdge<N>>,
@@ -91,6 +96,9 @@ impl<N: RealField> Polyline<N> {
             }
         }
 
+        #[cfg(feature = "parallel")]
+        let bvt = BVT::new_balanced_parallel(leaves);
+        #[cfg(not(feature = "parallel"))]
         let bvt = BVT::new_balanced(leaves);
 
         // Set edge.bvt_leaf
@@ -104,10 +112,12 @@ impl<N: RealField> Polyline<N> {
             timestamps: Vec::new(),
             ref_vertices: Vec::new(),
             seg_to_update: Vec::new(),
+            bvt_rebuild_policy: None,
         };
 
         Polyline {
             bvt,
+            query_bvt: None,
             points,
             deformations,
             vertices,
@@ -304,6 +314,39 @@ impl<N: RealField> Polyline<N> {
         &self.bvt
     }
 
+    /// The bounding volume of the hierarchy traversed by the ray casts and point queries on this
+    /// polyline.
+    #[inline]
+    pub fn query_bounding_volume(&self) -> QueryBoundingVolume {
+        self.query_bvh().bounding_volume()
+    }
+
+    /// Sets the bounding volume of the hierarchy traversed by the ray casts and point queries on
+    /// this polyline.
+    ///
+    /// By default, those queries traverse `self.bvt()`. OBBs bound long segments that are not
+    /// aligned with the coordinate axes more tightly than AABBs. Any other hierarchy than the AABB
+    /// one is built in addition to `self.bvt()`, and is refitted like it every time this polyline
+    /// is deformed.
+    pub fn set_query_bounding_volume(&mut self, bv: QueryBoundingVolume) {
+        self.query_bvt = QueryBVT::new(self, bv)
+    }
+
+    /// The policy deciding which parts of the BVT are rebuilt after deformations, if any.
+    #[inline]
+    pub fn bvt_rebuild_policy(&self) -> Option<&BVTRebuildPolicy<N>> {
+        self.deformations.bvt_rebuild_policy.as_ref()
+    }
+
+    /// Sets the policy deciding which parts of the BVT are rebuilt after deformations.
+    ///
+    /// By default, the BVT is only refitted when this polyline is deformed. Create the policy with
+    /// `BVTRebuildPolicy::new(self.bvt(), ...)` to compare future deformations with the current state.
+    #[inline]
+    pub fn set_bvt_rebuild_policy(&mut self, policy: Option<BVTRebuildPolicy<N>>) {
+        self.deformations.bvt_rebuild_policy = policy
+    }
+
     /// Tests that the given `dir` is on the tangent cone of the `i`th vertex
     /// of this polyline.
     #[cfg(feature = "dim3")]
@@ -571,6 +614,14 @@ impl<N: RealField> CompositeShape<N> for Polyline<N> {
     fn bvh(&self) -> BVHImpl<N, usize, AABB<N>> {
         BVHImpl::BVT(&self.bvt)
     }
+
+    #[inline]
+    fn query_bvh(&self) -> CompositeShapeBVH<'_, N> {
+        match &self.query_bvt {
+            Some(query_bvt) => query_bvt.bvh(),
+            None => CompositeShapeBVH::AABB(self.bvh()),
+        }
+    }
 }
 
 impl<N: RealField> DeformableShape<N> for Polyline<N> {
@@ -632,12 +683,26 @@ impl<N: RealField> DeformableShape<N> for Polyline<N> {
                 new_bv.loosen(self.deformations.margin);
                 self.bvt
                     .set_leaf_bounding_volume(self.edges[seg_id].bvt_leaf, new_bv, false);
+
+                if let Some(query_bvt) = &mut self.query_bvt {
+                    let seg = Segment::new(self.points[idx.x], self.points[idx.y]);
+                    query_bvt.set_part(seg_id, &seg, self.deformations.margin);
+                }
+
                 self.deformations.timestamps[seg_id] = self.deformations.curr_timestamp;
             }
         }
 
         // FIXME: measure efficiency with a non-zero margin.
-        self.bvt.refit(N::zero())
+        self.bvt.refit(N::zero());
+
+        if let Some(policy) = &mut self.deformations.bvt_rebuild_policy {
+            let _ = policy.rebuild_degraded(&mut self.bvt);
+        }
+
+        if let Some(query_bvt) = &mut self.query_bvt {
+            query_bvt.refit();
+        }
     }
 
     fn update_local_approximation(&self, coords: &[N], approx: &mut LocalShapeApproximation<N>) {
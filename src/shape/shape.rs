@@ -1,5 +1,5 @@
 // Queries.
-use crate::bounding_volume::{BoundingSphere, AABB};
+use crate::bounding_volume::{BoundingSphere, AABB, OBB};
 use crate::math::{Isometry, Vector};
 use crate::query::{PointQuery, RayCast};
 use crate::shape::{CompositeShape, ConvexPolyhedron, DeformableShape, FeatureId, SupportMap};
@@ -46,6 +46,20 @@ pub trait Shape<N: RealField>: Send + Sync + Downcast + ShapeClone<N> {
         BoundingSphere::new(aabb.center(), aabb.half_extents().norm())
     }
 
+    /// The oriented bounding box of `self` transformed by `m`.
+    #[inline]
+    fn obb(&self, m: &Isometry<N>) -> OBB<N> {
+        self.local_obb().transform_by(m)
+    }
+
+    /// The oriented bounding box of `self`.
+    ///
+    /// By default, this is the local AABB of `self`.
+    #[inline]
+    fn local_obb(&self) -> OBB<N> {
+        OBB::from_aabb(&Isometry::identity(), &self.local_aabb())
+    }
+
     /// Check if if the feature `_feature` of the `i-th` subshape of `self` transformed by `m` has a tangent
     /// cone that contains `dir` at the point `pt`.
     // NOTE: for the moment, we assume the tangent cone is the same for the whole feature.
@@ -1,4 +1,4 @@
-use crate::bounding_volume::{self, BoundingSphere, AABB};
+use crate::bounding_volume::{self, BoundingSphere, AABB, OBB};
 use crate::math::{Isometry, Vector};
 use crate::query::{PointQuery, RayCast};
 #[cfg(feature = "dim2")]
@@ -112,12 +112,20 @@ impl<N: RealField> Shape<N> for Triangle<N> {
     impl_shape_common!();
     impl_as_support_map!();
     impl_as_convex_polyhedron!();
+    #[inline]
+    fn local_obb(&self) -> OBB<N> {
+        bounding_volume::local_obb(self)
+    }
 }
 
 impl<N: RealField> Shape<N> for Segment<N> {
     impl_shape_common!();
     impl_as_support_map!();
     impl_as_convex_polyhedron!();
+    #[inline]
+    fn local_obb(&self) -> OBB<N> {
+        bounding_volume::local_obb(self)
+    }
 }
 
 impl<N: RealField> Shape<N> for Ball<N> {
@@ -2,16 +2,17 @@
 
 use crate::bounding_volume::{self, BoundingVolume, AABB};
 use crate::math::{Isometry, Point, Vector, DIM};
-use crate::partitioning::{BVHImpl, BVT};
+use crate::partitioning::{BVHImpl, BVTRebuildPolicy, BVT};
 use crate::procedural;
 use crate::query::{
     Contact, ContactKinematic, ContactPrediction, ContactPreprocessor, LocalShapeApproximation,
     NeighborhoodGeometry,
 };
 use crate::shape::{
-    CompositeShape, DeformableShape, DeformationsType, FeatureId, Segment, Shape, Triangle,
+    composite_shape::QueryBVT, CompositeShape, CompositeShapeBVH, DeformableShape,
+    DeformationsType, FeatureId, QueryBoundingVolume, Segment, Shape, Triangle,
 };
-use crate::utils::{DeterministicState, IsometryOps};
+use crate::utils::{self, BinaryCodec, DecodingError, DeterministicState, IsometryOps};
 use na::{self, Point2, Point3, RealField, Unit};
 use std::collections::{hash_map::Entry, HashMap};
 use std::iter;
@@ -26,6 +27,7 @@ struct DeformationInfos<N: RealField> {
     timestamps: Vec<usize>,
     ref_vertices: Vec<Point<N>>,
     tri_to_update: Vec<usize>,
+    bvt_rebuild_policy: Option<BVTRebuildPolicy<N>>,
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -79,11 +81,21 @@ pub struct TriMeshVertex {
     pub adj_vertices: Range<usize>,
 }
 
+// The adjacency information of the vertices, edges, and faces of a mesh.
+struct TriMeshTopology {
+    vertices: Vec<TriMeshVertex>,
+    edges: Vec<TriMeshEdge>,
+    face_edges: Vec<Point3<usize>>,
+    adj_face_list: Vec<usize>,
+    adj_vertex_list: Vec<usize>,
+}
+
 /// A 3d triangle mesh.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone)]
 pub struct TriMesh<N: RealField> {
     bvt: BVT<usize, AABB<N>>,
+    query_bvt: Option<QueryBVT<N>>,
     uvs: Option<Vec<Point2<N>>>,
     points: Vec<Point<N>>,
     vertices: Vec<TriMeshVertex>,
@@ -102,53 +114,139 @@ impl<N: RealField> TriMesh<N> {
         indices: Vec<Point3<usize>>,
         uvs: Option<Vec<Point2<N>>>,
     ) -> TriMesh<N> {
-        let mut leaves = Vec::with_capacity(indices.len());
-        let mut vertices: Vec<TriMeshVertex> = iter::repeat(TriMeshVertex {
-            adj_faces: 0..0,
-            adj_vertices: 0..0,
-        })
-        .take(points.len())
-        .collect();
-        let mut faces = Vec::with_capacity(indices.len());
+        Self::from_parts(points, indices, uvs, None, None)
+    }
 
-        let edges = Self::edges_list(&indices);
-        let adj_face_list = Self::adj_face_list(&indices, &mut vertices);
-        let adj_vertex_list = Self::adj_vertex_list(&edges, &mut vertices);
+    /// Builds a new mesh using a previously built BVT, e.g., decoded with `BVT::from_bytes`.
+    ///
+    /// This is much faster than `TriMesh::new` for large meshes. The data of the leaves of `bvt`
+    /// must be the indices of the faces of this mesh, and their AABBs must contain those faces.
+    /// This is the case of the BVT of a mesh built with the same `points` and `indices`.
+    ///
+    /// Panics if `bvt` does not match the faces of this mesh.
+    pub fn with_bvt(
+        points: Vec<Point<N>>,
+        indices: Vec<Point3<usize>>,
+        uvs: Option<Vec<Point2<N>>>,
+        bvt: BVT<usize, AABB<N>>,
+    ) -> TriMesh<N> {
+        assert!(
+            Self::bvt_matches_faces(&points, &indices, &bvt),
+            "The BVT leaves do not match the faces of the mesh."
+        );
+        Self::from_parts(points, indices, uvs, Some(bvt), None)
+    }
+
+    // Checks that each leaf of `bvt` bounds a different face.
+    fn bvt_matches_faces(
+        points: &[Point<N>],
+        indices: &[Point3<usize>],
+        bvt: &BVT<usize, AABB<N>>,
+    ) -> bool {
+        if bvt.leaves().len() != indices.len() {
+            return false;
+        }
 
-        {
-            let is = &*indices;
+        let mut bounded = vec![false; indices.len()];
 
-            for (i, is) in is.iter().enumerate() {
-                let triangle = Triangle::new(points[is.x], points[is.y], points[is.z]);
-                let normal = triangle.normal();
-                let side_normals = normal.map(|n| {
-                    [
-                        Unit::new_normalize((triangle.b() - triangle.a()).cross(&n)),
-                        Unit::new_normalize((triangle.c() - triangle.b()).cross(&n)),
-                        Unit::new_normalize((triangle.a() - triangle.c()).cross(&n)),
-                    ]
-                });
+        bvt.leaves().iter().all(|leaf| {
+            let i = *leaf.data();
 
-                let bv = triangle.local_aabb();
-                leaves.push((i, bv.clone()));
-                faces.push(TriMeshFace {
-                    indices: *is,
-                    edges: Point3::origin(), // Will be set later.
-                    bvt_leaf: 0,             // Will be set later.
-                    normal,
-                    side_normals,
-                })
+            match bounded.get_mut(i) {
+                Some(bounded) if !*bounded => *bounded = true,
+                _ => return false,
             }
+
+            let is = indices[i];
+            let triangle = Triangle::new(points[is.x], points[is.y], points[is.z]);
+            leaf.bounding_volume().contains(&triangle.local_aabb())
+        })
+    }
+
+    // Builds a mesh, and its BVT and topology if they are not given.
+    fn from_parts(
+        points: Vec<Point<N>>,
+        indices: Vec<Point3<usize>>,
+        uvs: Option<Vec<Point2<N>>>,
+        bvt: Option<BVT<usize, AABB<N>>>,
+        topology: Option<TriMeshTopology>,
+    ) -> TriMesh<N> {
+        let mut leaves = Vec::with_capacity(if bvt.is_none() { indices.len() } else { 0 });
+        let topology = topology.unwrap_or_else(|| Self::topology(points.len(), &indices));
+        let mut faces = Vec::with_capacity(indices.len());
+
+        for (i, (is, edges)) in indices.iter().zip(topology.face_edges).enumerate() {
+            let triangle = Triangle::new(points[is.x], points[is.y], points[is.z]);
+            let normal = triangle.normal();
+            let side_normals = normal.map(|n| {
+                [
+                    Unit::new_normalize((triangle.b() - triangle.a()).cross(&n)),
+                    Unit::new_normalize((triangle.c() - triangle.b()).cross(&n)),
+                    Unit::new_normalize((triangle.a() - triangle.c()).cross(&n)),
+                ]
+            });
+
+            if bvt.is_none() {
+                leaves.push((i, triangle.local_aabb()));
+            }
+
+            faces.push(TriMeshFace {
+                indices: *is,
+                edges,
+                bvt_leaf: 0, // Will be set later.
+                normal,
+                side_normals,
+            })
         }
 
-        let bvt = BVT::new_balanced(leaves);
+        #[cfg(feature = "parallel")]
+        let bvt = bvt.unwrap_or_else(|| BVT::new_balanced_parallel(leaves));
+        #[cfg(not(feature = "parallel"))]
+        let bvt = bvt.unwrap_or_else(|| BVT::new_balanced(leaves));
 
         // Set face.bvt_leaf
         for (i, leaf) in bvt.leaves().iter().enumerate() {
             faces[*leaf.data()].bvt_leaf = i;
         }
 
-        // Set face.edges
+        let deformations = DeformationInfos {
+            margin: na::convert(0.1), // FIXME: find a better way to define the margin.
+            curr_timestamp: 0,
+            timestamps: Vec::new(),
+            ref_vertices: Vec::new(),
+            tri_to_update: Vec::new(),
+            bvt_rebuild_policy: None,
+        };
+
+        TriMesh {
+            bvt,
+            query_bvt: None,
+            points,
+            uvs,
+            deformations,
+            vertices: topology.vertices,
+            edges: topology.edges,
+            faces,
+            adj_face_list: topology.adj_face_list,
+            adj_vertex_list: topology.adj_vertex_list,
+            oriented: false,
+        }
+    }
+
+    // Computes the adjacency information of the given faces.
+    fn topology(num_points: usize, indices: &[Point3<usize>]) -> TriMeshTopology {
+        let vertex = TriMeshVertex {
+            adj_faces: 0..0,
+            adj_vertices: 0..0,
+        };
+        let mut vertices = vec![vertex; num_points];
+
+        let edges = Self::edges_list(indices);
+        let adj_face_list = Self::adj_face_list(indices, &mut vertices);
+        let adj_vertex_list = Self::adj_vertex_list(&edges, &mut vertices);
+        let mut face_edges = vec![Point3::origin(); indices.len()];
+
+        // Set the edges of each face.
         for (i, e) in edges.iter().enumerate() {
             let fid1 = e.adj_faces.0.face_id;
             let fid2 = e.adj_faces.1.face_id;
@@ -156,43 +254,26 @@ impl<N: RealField> TriMesh<N> {
             for k1 in 0..3 {
                 let k2 = (k1 + 1) % 3;
 
-                if (faces[fid1].indices[k1] == e.indices.x
-                    && faces[fid1].indices[k2] == e.indices.y)
-                    || (faces[fid1].indices[k1] == e.indices.y
-                        && faces[fid1].indices[k2] == e.indices.x)
+                if (indices[fid1][k1] == e.indices.x && indices[fid1][k2] == e.indices.y)
+                    || (indices[fid1][k1] == e.indices.y && indices[fid1][k2] == e.indices.x)
                 {
-                    faces[fid1].edges[k1] = i;
+                    face_edges[fid1][k1] = i;
                 }
 
-                if (faces[fid2].indices[k1] == e.indices.x
-                    && faces[fid2].indices[k2] == e.indices.y)
-                    || (faces[fid2].indices[k1] == e.indices.y
-                        && faces[fid2].indices[k2] == e.indices.x)
+                if (indices[fid2][k1] == e.indices.x && indices[fid2][k2] == e.indices.y)
+                    || (indices[fid2][k1] == e.indices.y && indices[fid2][k2] == e.indices.x)
                 {
-                    faces[fid2].edges[k1] = i;
+                    face_edges[fid2][k1] = i;
                 }
             }
         }
 
-        let deformations = DeformationInfos {
-            margin: na::convert(0.1), // FIXME: find a better way to define the margin.
-            curr_timestamp: 0,
-            timestamps: Vec::new(),
-            ref_vertices: Vec::new(),
-            tri_to_update: Vec::new(),
-        };
-
-        TriMesh {
-            bvt,
-            points,
-            uvs,
-            deformations,
+        TriMeshTopology {
             vertices,
             edges,
-            faces,
+            face_edges,
             adj_face_list,
             adj_vertex_list,
-            oriented: false,
         }
     }
 
@@ -413,6 +494,42 @@ impl<N: RealField> TriMesh<N> {
         }
     }
 
+    // The vertices of the given feature, followed by the number of vertices.
+    pub(crate) fn feature_vertices(&self, id: FeatureId) -> ([usize; 3], usize) {
+        match id {
+            FeatureId::Vertex(i) => ([i, i, i], 1),
+            FeatureId::Edge(i) => {
+                let idx = self.edges[i].indices;
+                ([idx.x, idx.y, idx.y], 2)
+            }
+            FeatureId::Face(i) => {
+                let idx = self.faces[i % self.faces.len()].indices;
+                ([idx.x, idx.y, idx.z], 3)
+            }
+            FeatureId::Unknown => ([0; 3], 0),
+        }
+    }
+
+    /// Tests whether two distinct features of this trimesh share at least one vertex.
+    ///
+    /// Two faces are adjacent only if they are on the same side of the trimesh, i.e., if they are
+    /// both backfaces or both front faces.
+    pub fn are_features_adjacent(&self, feature1: FeatureId, feature2: FeatureId) -> bool {
+        if feature1 == feature2 {
+            return false;
+        }
+
+        if let (FeatureId::Face(_), FeatureId::Face(_)) = (feature1, feature2) {
+            if self.is_backface(feature1) != self.is_backface(feature2) {
+                return false;
+            }
+        }
+
+        let (vtx1, n1) = self.feature_vertices(feature1);
+        let (vtx2, n2) = self.feature_vertices(feature2);
+        vtx1[..n1].iter().any(|i| vtx2[..n2].contains(i))
+    }
+
     /// The segment of the `i`-th edge on this triangle mesh.
     #[inline]
     pub fn edge_segment(&self, i: usize) -> Segment<N> {
@@ -451,6 +568,39 @@ impl<N: RealField> TriMesh<N> {
         &self.bvt
     }
 
+    /// The bounding volume of the hierarchy traversed by the ray casts and point queries on this
+    /// triangle mesh.
+    #[inline]
+    pub fn query_bounding_volume(&self) -> QueryBoundingVolume {
+        self.query_bvh().bounding_volume()
+    }
+
+    /// Sets the bounding volume of the hierarchy traversed by the ray casts and point queries on
+    /// this triangle mesh.
+    ///
+    /// By default, those queries traverse `self.bvt()`. OBBs bound long triangles that are not
+    /// aligned with the coordinate axes more tightly than AABBs. Any other hierarchy than the AABB
+    /// one is built in addition to `self.bvt()`, and is refitted like it every time this triangle
+    /// mesh is deformed.
+    pub fn set_query_bounding_volume(&mut self, bv: QueryBoundingVolume) {
+        self.query_bvt = QueryBVT::new(self, bv)
+    }
+
+    /// The policy deciding which parts of the BVT are rebuilt after deformations, if any.
+    #[inline]
+    pub fn bvt_rebuild_policy(&self) -> Option<&BVTRebuildPolicy<N>> {
+        self.deformations.bvt_rebuild_policy.as_ref()
+    }
+
+    /// Sets the policy deciding which parts of the BVT are rebuilt after deformations.
+    ///
+    /// By default, the BVT is only refitted when this triangle mesh is deformed. Create the policy with
+    /// `BVTRebuildPolicy::new(self.bvt(), ...)` to compare future deformations with the current state.
+    #[inline]
+    pub fn set_bvt_rebuild_policy(&mut self, policy: Option<BVTRebuildPolicy<N>>) {
+        self.deformations.bvt_rebuild_policy = policy
+    }
+
     /// Tests that the given `dir` is on the tangent cone of the `i`th vertex
     /// of this mesh.
     pub fn vertex_tangent_cone_contains_dir(
@@ -709,6 +859,244 @@ impl<N: RealField> TriMesh<N> {
     }
 }
 
+// The magic number starting the binary encoding of a triangle mesh.
+const TRIMESH_MAGIC: &[u8; 4] = b"NTRI";
+
+impl<N: RealField + BinaryCodec> TriMesh<N> {
+    /// Encodes the geometry of this mesh, its topology, and its BVT into a compact and versioned
+    /// binary buffer.
+    ///
+    /// The mesh can then be loaded with `TriMesh::from_bytes` without rebuilding its BVT nor its
+    /// topology. Its deformation-tracking data are not encoded.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        utils::encode_header(TRIMESH_MAGIC, &[utils::layout_of::<N>()], &mut out);
+        (self.oriented as u8).encode(&mut out);
+
+        self.points.len().encode(&mut out);
+        for pt in &self.points {
+            pt.encode(&mut out);
+        }
+
+        self.faces.len().encode(&mut out);
+        for face in &self.faces {
+            face.indices.encode(&mut out);
+        }
+
+        match &self.uvs {
+            Some(uvs) => {
+                1u8.encode(&mut out);
+                uvs.len().encode(&mut out);
+
+                for uv in uvs {
+                    uv.encode(&mut out);
+                }
+            }
+            None => 0u8.encode(&mut out),
+        }
+
+        for vtx in &self.vertices {
+            encode_range(&vtx.adj_faces, &mut out);
+            encode_range(&vtx.adj_vertices, &mut out);
+        }
+
+        encode_indices(&self.adj_face_list, &mut out);
+        encode_indices(&self.adj_vertex_list, &mut out);
+
+        self.edges.len().encode(&mut out);
+        for edge in &self.edges {
+            edge.indices.encode(&mut out);
+            edge.adj_faces.0.face_id.encode(&mut out);
+            edge.adj_faces.0.edge_id.encode(&mut out);
+            edge.adj_faces.1.face_id.encode(&mut out);
+            edge.adj_faces.1.edge_id.encode(&mut out);
+        }
+
+        for face in &self.faces {
+            face.edges.encode(&mut out);
+        }
+
+        self.bvt.encode(&mut out);
+        out
+    }
+
+    /// Decodes a mesh encoded with `TriMesh::to_bytes`.
+    ///
+    /// The BVT and the topology of the mesh are decoded instead of being rebuilt, so no hashing
+    /// nor tree construction is performed. The decoded data are still copied into the returned
+    /// mesh and checked in time linear in their size, and the face normals are recomputed. Fails
+    /// if `bytes` does not contain exactly one mesh with the same scalar type, or if its data are
+    /// inconsistent.
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<TriMesh<N>, DecodingError> {
+        let bytes = &mut bytes;
+        utils::decode_header(TRIMESH_MAGIC, &[utils::layout_of::<N>()], bytes)?;
+        let oriented = match u8::decode(bytes)? {
+            0 => false,
+            1 => true,
+            _ => return Err(DecodingError::InvalidData),
+        };
+
+        let num_points = utils::decode_len(bytes, Point::<N>::ENCODED_SIZE)?;
+        let mut points = Vec::with_capacity(num_points);
+        for _ in 0..num_points {
+            points.push(Point::decode(bytes)?);
+        }
+
+        let num_faces = utils::decode_len(bytes, Point3::<usize>::ENCODED_SIZE)?;
+        let mut indices = Vec::with_capacity(num_faces);
+        for _ in 0..num_faces {
+            let is = Point3::<usize>::decode(bytes)?;
+
+            if is.iter().any(|i| *i >= num_points) {
+                return Err(DecodingError::InvalidData);
+            }
+
+            indices.push(is);
+        }
+
+        let uvs = match u8::decode(bytes)? {
+            0 => None,
+            1 => {
+                let num_uvs = utils::decode_len(bytes, Point2::<N>::ENCODED_SIZE)?;
+                let mut uvs = Vec::with_capacity(num_uvs);
+                for _ in 0..num_uvs {
+                    uvs.push(Point2::decode(bytes)?);
+                }
+
+                Some(uvs)
+            }
+            _ => return Err(DecodingError::InvalidData),
+        };
+
+        let topology = Self::decode_topology(bytes, num_points, num_faces)?;
+        let bvt = BVT::decode(bytes)?;
+
+        if !bytes.is_empty() || !Self::bvt_matches_faces(&points, &indices, &bvt) {
+            return Err(DecodingError::InvalidData);
+        }
+
+        let mut result = Self::from_parts(points, indices, uvs, Some(bvt), Some(topology));
+        result.oriented = oriented;
+        Ok(result)
+    }
+
+    // Decodes the topology written by `to_bytes`, and checks that all its indices are in bounds.
+    fn decode_topology(
+        bytes: &mut &[u8],
+        num_points: usize,
+        num_faces: usize,
+    ) -> Result<TriMeshTopology, DecodingError> {
+        let mut vertices = Vec::with_capacity(num_points);
+        for _ in 0..num_points {
+            let adj_faces = decode_range(bytes)?;
+            let adj_vertices = decode_range(bytes)?;
+            vertices.push(TriMeshVertex {
+                adj_faces,
+                adj_vertices,
+            });
+        }
+
+        let adj_face_list = decode_indices(bytes, num_faces)?;
+        let adj_vertex_list = decode_indices(bytes, num_points)?;
+
+        if vertices.iter().any(|vtx| {
+            vtx.adj_faces.end > adj_face_list.len() || vtx.adj_vertices.end > adj_vertex_list.len()
+        }) {
+            return Err(DecodingError::InvalidData);
+        }
+
+        let num_edges = utils::decode_len(
+            bytes,
+            Point2::<usize>::ENCODED_SIZE + usize::ENCODED_SIZE * 4,
+        )?;
+        let mut edges = Vec::with_capacity(num_edges);
+        for _ in 0..num_edges {
+            let indices = Point2::<usize>::decode(bytes)?;
+            let mut adj_faces = [FaceAdjacentToEdge::new(0, 0), FaceAdjacentToEdge::new(0, 0)];
+
+            for adj_face in &mut adj_faces {
+                adj_face.face_id = usize::decode(bytes)?;
+                adj_face.edge_id = usize::decode(bytes)?;
+
+                if adj_face.face_id >= num_faces || adj_face.edge_id >= 3 {
+                    return Err(DecodingError::InvalidData);
+                }
+            }
+
+            if indices.iter().any(|i| *i >= num_points) {
+                return Err(DecodingError::InvalidData);
+            }
+
+            let [adj_face1, adj_face2] = adj_faces;
+            edges.push(TriMeshEdge {
+                indices,
+                adj_faces: (adj_face1, adj_face2),
+            });
+        }
+
+        let mut face_edges = Vec::with_capacity(num_faces);
+        for _ in 0..num_faces {
+            let es = Point3::<usize>::decode(bytes)?;
+
+            if es.iter().any(|e| *e >= num_edges) {
+                return Err(DecodingError::InvalidData);
+            }
+
+            face_edges.push(es);
+        }
+
+        Ok(TriMeshTopology {
+            vertices,
+            edges,
+            face_edges,
+            adj_face_list,
+            adj_vertex_list,
+        })
+    }
+}
+
+fn encode_range(range: &Range<usize>, out: &mut Vec<u8>) {
+    range.start.encode(out);
+    range.end.encode(out);
+}
+
+fn decode_range(bytes: &mut &[u8]) -> Result<Range<usize>, DecodingError> {
+    let start = usize::decode(bytes)?;
+    let end = usize::decode(bytes)?;
+
+    if start <= end {
+        Ok(start..end)
+    } else {
+        Err(DecodingError::InvalidData)
+    }
+}
+
+fn encode_indices(indices: &[usize], out: &mut Vec<u8>) {
+    indices.len().encode(out);
+
+    for i in indices {
+        i.encode(out);
+    }
+}
+
+// Decodes a list of indices, and checks that they are all smaller than `bound`.
+fn decode_indices(bytes: &mut &[u8], bound: usize) -> Result<Vec<usize>, DecodingError> {
+    let len = utils::decode_len(bytes, usize::ENCODED_SIZE)?;
+    let mut indices = Vec::with_capacity(len);
+
+    for _ in 0..len {
+        let i = usize::decode(bytes)?;
+
+        if i >= bound {
+            return Err(DecodingError::InvalidData);
+        }
+
+        indices.push(i);
+    }
+
+    Ok(indices)
+}
+
 impl<N: RealField> CompositeShape<N> for TriMesh<N> {
     #[inline]
     fn nparts(&self) -> usize {
@@ -750,6 +1138,14 @@ impl<N: RealField> CompositeShape<N> for TriMesh<N> {
     fn bvh(&self) -> BVHImpl<N, usize, AABB<N>> {
         BVHImpl::BVT(&self.bvt)
     }
+
+    #[inline]
+    fn query_bvh(&self) -> CompositeShapeBVH<'_, N> {
+        match &self.query_bvt {
+            Some(query_bvt) => query_bvt.bvh(),
+            None => CompositeShapeBVH::AABB(self.bvh()),
+        }
+    }
 }
 
 impl<N: RealField> DeformableShape<N> for TriMesh<N> {
@@ -826,12 +1222,27 @@ impl<N: RealField> DeformableShape<N> for TriMesh<N> {
                 new_bv.loosen(self.deformations.margin);
                 self.bvt
                     .set_leaf_bounding_volume(self.faces[tri_id].bvt_leaf, new_bv, false);
+
+                if let Some(query_bvt) = &mut self.query_bvt {
+                    let tri =
+                        Triangle::new(self.points[idx.x], self.points[idx.y], self.points[idx.z]);
+                    query_bvt.set_part(tri_id, &tri, self.deformations.margin);
+                }
+
                 self.deformations.timestamps[tri_id] = self.deformations.curr_timestamp;
             }
         }
 
         // FIXME: measure efficiency with a non-zero margin.
-        self.bvt.refit(N::zero())
+        self.bvt.refit(N::zero());
+
+        if let Some(policy) = &mut self.deformations.bvt_rebuild_policy {
+            let _ = policy.rebuild_degraded(&mut self.bvt);
+        }
+
+        if let Some(query_bvt) = &mut self.query_bvt {
+            query_bvt.refit();
+        }
     }
 
     fn update_local_approximation(&self, coords: &[N], approx: &mut LocalShapeApproximation<N>) {
@@ -983,4 +1394,8 @@ impl<'a, N: RealField> ContactPreprocessor<N> for TriMeshContactProcessor<'a, N>
             }
         }
     }
+
+    fn are_features_adjacent(&self, feature1: FeatureId, feature2: FeatureId) -> bool {
+        self.mesh.are_features_adjacent(feature1, feature2)
+    }
 }
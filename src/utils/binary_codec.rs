@@ -0,0 +1,402 @@
+//! A compact little-endian binary encoding of geometric data.
+
+use crate::bounding_volume::{BoundingSphere, KDOPAxes, AABB, KDOP, OBB};
+use crate::math::{Isometry, Rotation, Translation, Vector, DIM};
+use na::{Point2, Point3, RealField, Scalar, Unit, Vector2, Vector3};
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::mem;
+
+/// The version of the binary format written by `BinaryCodec` and the `to_bytes` methods.
+///
+/// It is increased whenever the layout of the encoded data changes.
+pub const BINARY_FORMAT_VERSION: u32 = 1;
+
+/// Error occurring when decoding data from a binary buffer.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DecodingError {
+    /// The buffer ended before all the data could be read.
+    UnexpectedEnd,
+    /// The buffer does not start with the expected magic number.
+    InvalidMagic,
+    /// The buffer was written with an unsupported version of the binary format.
+    UnsupportedVersion(u32),
+    /// The buffer was written for another dimension, or with other scalar, bounding volume, or
+    /// data types.
+    IncompatibleLayout,
+    /// The decoded data are inconsistent, e.g., an index is out of bounds.
+    InvalidData,
+}
+
+impl fmt::Display for DecodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            DecodingError::UnexpectedEnd => f.pad("unexpected end of the binary data"),
+            DecodingError::InvalidMagic => f.pad("invalid magic number"),
+            DecodingError::UnsupportedVersion(v) => {
+                write!(f, "unsupported binary format version {}", v)
+            }
+            DecodingError::IncompatibleLayout => f.pad("incompatible binary data layout"),
+            DecodingError::InvalidData => f.pad("inconsistent binary data"),
+        }
+    }
+}
+
+impl Error for DecodingError {}
+
+/// A value with a fixed-size little-endian binary encoding.
+pub trait BinaryCodec: Sized {
+    /// The number of bytes written by `encode`.
+    const ENCODED_SIZE: usize;
+
+    /// A tag identifying the encoded type, written in the headers of the encoded buffers.
+    ///
+    /// Types with different encodings must have different tags, even if they have the same
+    /// `ENCODED_SIZE`. Tags are usually computed with `type_tag`.
+    const TYPE_TAG: u64;
+
+    /// Appends the binary encoding of `self` to `out`.
+    fn encode(&self, out: &mut Vec<u8>);
+
+    /// Decodes a value from the beginning of `bytes`, and advances `bytes` past it.
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodingError>;
+}
+
+/// Removes the first `len` bytes from `bytes` and returns them.
+pub(crate) fn take_bytes<'a>(bytes: &mut &'a [u8], len: usize) -> Result<&'a [u8], DecodingError> {
+    if bytes.len() < len {
+        return Err(DecodingError::UnexpectedEnd);
+    }
+
+    let (head, tail) = bytes.split_at(len);
+    *bytes = tail;
+    Ok(head)
+}
+
+// The parameters of the FNV-1a hash used to compute type tags.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// Computes the type tag of the type with the given name and generic parameters' tags.
+///
+/// Use this to implement `BinaryCodec::TYPE_TAG`, e.g.,
+/// `type_tag("MyVector", &[N::TYPE_TAG])` for a vector with components of type `N`.
+pub const fn type_tag(name: &str, params: &[u64]) -> u64 {
+    let name = name.as_bytes();
+    let mut hash = FNV_OFFSET_BASIS;
+    let mut i = 0;
+
+    while i < name.len() {
+        hash = (hash ^ name[i] as u64).wrapping_mul(FNV_PRIME);
+        i += 1;
+    }
+
+    let mut i = 0;
+
+    while i < params.len() {
+        hash = (hash ^ params[i]).wrapping_mul(FNV_PRIME);
+        i += 1;
+    }
+
+    hash
+}
+
+/// The encoded size and type tag of `T`, as written in the binary headers.
+pub(crate) fn layout_of<T: BinaryCodec>() -> (usize, u64) {
+    (T::ENCODED_SIZE, T::TYPE_TAG)
+}
+
+/// Writes the header identifying data encoded with the given magic number and layout.
+///
+/// The layout is a set of encoded sizes and type tags, e.g., given by `layout_of`, which must
+/// match when decoding.
+pub(crate) fn encode_header(magic: &[u8; 4], layout: &[(usize, u64)], out: &mut Vec<u8>) {
+    out.extend_from_slice(magic);
+    BINARY_FORMAT_VERSION.encode(out);
+    (DIM as u32).encode(out);
+
+    for (size, tag) in layout {
+        size.encode(out);
+        tag.encode(out);
+    }
+}
+
+/// Reads and checks a header written by `encode_header`.
+pub(crate) fn decode_header(
+    magic: &[u8; 4],
+    layout: &[(usize, u64)],
+    bytes: &mut &[u8],
+) -> Result<(), DecodingError> {
+    if take_bytes(bytes, magic.len())? != magic {
+        return Err(DecodingError::InvalidMagic);
+    }
+
+    let version = u32::decode(bytes)?;
+
+    if version != BINARY_FORMAT_VERSION {
+        return Err(DecodingError::UnsupportedVersion(version));
+    }
+
+    if u32::decode(bytes)? as usize != DIM {
+        return Err(DecodingError::IncompatibleLayout);
+    }
+
+    for (size, tag) in layout {
+        if usize::decode(bytes)? != *size || u64::decode(bytes)? != *tag {
+            return Err(DecodingError::IncompatibleLayout);
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads the number of elements of an array, and checks that `bytes` is large enough to contain
+/// them if each of them is encoded on `element_size` bytes.
+///
+/// This avoids allocating huge buffers when decoding corrupted data.
+pub(crate) fn decode_len(bytes: &mut &[u8], element_size: usize) -> Result<usize, DecodingError> {
+    let len = usize::decode(bytes)?;
+
+    match len.checked_mul(element_size) {
+        Some(size) if size <= bytes.len() => Ok(len),
+        _ => Err(DecodingError::UnexpectedEnd),
+    }
+}
+
+macro_rules! primitive_binary_codec(
+    ($($t: ident),*) => {$(
+        impl BinaryCodec for $t {
+            const ENCODED_SIZE: usize = mem::size_of::<$t>();
+            const TYPE_TAG: u64 = type_tag(stringify!($t), &[]);
+
+            #[inline]
+            fn encode(&self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes())
+            }
+
+            #[inline]
+            fn decode(bytes: &mut &[u8]) -> Result<Self, DecodingError> {
+                let mut buf = [0; mem::size_of::<$t>()];
+                buf.copy_from_slice(take_bytes(bytes, mem::size_of::<$t>())?);
+                Ok(<$t>::from_le_bytes(buf))
+            }
+        }
+    )*}
+);
+
+primitive_binary_codec!(u8, u32, u64, f32, f64);
+
+// `usize` is encoded on 64 bits so that the data don't depend on the platform. It is thus
+// interchangeable with `u64`.
+impl BinaryCodec for usize {
+    const ENCODED_SIZE: usize = 8;
+    const TYPE_TAG: u64 = u64::TYPE_TAG;
+
+    #[inline]
+    fn encode(&self, out: &mut Vec<u8>) {
+        (*self as u64).encode(out)
+    }
+
+    #[inline]
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodingError> {
+        usize::try_from(u64::decode(bytes)?).map_err(|_| DecodingError::InvalidData)
+    }
+}
+
+macro_rules! vector_binary_codec(
+    ($t: ident, $dimension: expr) => {
+        impl<T: Scalar + BinaryCodec> BinaryCodec for $t<T> {
+            const ENCODED_SIZE: usize = T::ENCODED_SIZE * $dimension;
+            const TYPE_TAG: u64 = type_tag(stringify!($t), &[T::TYPE_TAG]);
+
+            #[inline]
+            fn encode(&self, out: &mut Vec<u8>) {
+                for i in 0..$dimension {
+                    self[i].encode(out)
+                }
+            }
+
+            #[inline]
+            fn decode(bytes: &mut &[u8]) -> Result<Self, DecodingError> {
+                let mut coords = Vec::with_capacity($dimension);
+
+                for _ in 0..$dimension {
+                    coords.push(T::decode(bytes)?);
+                }
+
+                Ok($t::from_iterator(coords))
+            }
+        }
+    }
+);
+
+vector_binary_codec!(Vector2, 2);
+vector_binary_codec!(Vector3, 3);
+
+macro_rules! point_binary_codec(
+    ($t: ident, $vector: ident) => {
+        impl<T: Scalar + BinaryCodec> BinaryCodec for $t<T> {
+            const ENCODED_SIZE: usize = $vector::<T>::ENCODED_SIZE;
+            const TYPE_TAG: u64 = type_tag(stringify!($t), &[T::TYPE_TAG]);
+
+            #[inline]
+            fn encode(&self, out: &mut Vec<u8>) {
+                self.coords.encode(out)
+            }
+
+            #[inline]
+            fn decode(bytes: &mut &[u8]) -> Result<Self, DecodingError> {
+                $vector::decode(bytes).map($t::from)
+            }
+        }
+    }
+);
+
+point_binary_codec!(Point2, Vector2);
+point_binary_codec!(Point3, Vector3);
+
+impl<N: RealField + BinaryCodec> BinaryCodec for Isometry<N> {
+    #[cfg(feature = "dim2")]
+    const ENCODED_SIZE: usize = N::ENCODED_SIZE * 4;
+    #[cfg(feature = "dim3")]
+    const ENCODED_SIZE: usize = N::ENCODED_SIZE * 7;
+    const TYPE_TAG: u64 = type_tag("Isometry", &[N::TYPE_TAG, DIM as u64]);
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.translation.vector.encode(out);
+
+        #[cfg(feature = "dim2")]
+        {
+            self.rotation.re.encode(out);
+            self.rotation.im.encode(out);
+        }
+
+        #[cfg(feature = "dim3")]
+        {
+            for coord in self.rotation.coords.iter() {
+                coord.encode(out)
+            }
+        }
+    }
+
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodingError> {
+        let translation = Translation::from(Vector::decode(bytes)?);
+
+        #[cfg(feature = "dim2")]
+        let rotation: Rotation<N> = {
+            let re = N::decode(bytes)?;
+            let im = N::decode(bytes)?;
+            Unit::new_unchecked(na::Complex::new(re, im))
+        };
+
+        #[cfg(feature = "dim3")]
+        let rotation: Rotation<N> = {
+            let mut coords = [N::zero(); 4];
+
+            for coord in &mut coords {
+                *coord = N::decode(bytes)?;
+            }
+
+            let [i, j, k, w] = coords;
+            Unit::new_unchecked(na::Quaternion::new(w, i, j, k))
+        };
+
+        Ok(Isometry::from_parts(translation, rotation))
+    }
+}
+
+impl<N: RealField + BinaryCodec> BinaryCodec for AABB<N> {
+    const ENCODED_SIZE: usize = Vector::<N>::ENCODED_SIZE * 2;
+    const TYPE_TAG: u64 = type_tag("AABB", &[N::TYPE_TAG, DIM as u64]);
+
+    #[inline]
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.mins().encode(out);
+        self.maxs().encode(out);
+    }
+
+    #[inline]
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodingError> {
+        let mins = BinaryCodec::decode(bytes)?;
+        let maxs = BinaryCodec::decode(bytes)?;
+        Ok(AABB::new(mins, maxs))
+    }
+}
+
+impl<N: RealField + BinaryCodec> BinaryCodec for BoundingSphere<N> {
+    const ENCODED_SIZE: usize = Vector::<N>::ENCODED_SIZE + N::ENCODED_SIZE;
+    const TYPE_TAG: u64 = type_tag("BoundingSphere", &[N::TYPE_TAG, DIM as u64]);
+
+    #[inline]
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.center().encode(out);
+        self.radius().encode(out);
+    }
+
+    #[inline]
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodingError> {
+        let center = BinaryCodec::decode(bytes)?;
+        let radius = N::decode(bytes)?;
+        Ok(BoundingSphere::new(center, radius))
+    }
+}
+
+impl<N: RealField + BinaryCodec> BinaryCodec for OBB<N> {
+    const ENCODED_SIZE: usize = Isometry::<N>::ENCODED_SIZE + Vector::<N>::ENCODED_SIZE;
+    const TYPE_TAG: u64 = type_tag("OBB", &[N::TYPE_TAG, DIM as u64]);
+
+    #[inline]
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.position().encode(out);
+        self.half_extents().encode(out);
+    }
+
+    #[inline]
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodingError> {
+        let position = Isometry::decode(bytes)?;
+        let half_extents = Vector::decode(bytes)?;
+        Ok(OBB::new(position, half_extents))
+    }
+}
+
+impl<N: RealField + BinaryCodec, A: KDOPAxes<N>> BinaryCodec for KDOP<N, A> {
+    const ENCODED_SIZE: usize = N::ENCODED_SIZE * A::AXES.len() * 2;
+    const TYPE_TAG: u64 = type_tag("KDOP", &[N::TYPE_TAG, kdop_axes_tag(A::AXES)]);
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        for bound in self.mins().iter().chain(self.maxs().iter()) {
+            bound.encode(out)
+        }
+    }
+
+    fn decode(bytes: &mut &[u8]) -> Result<Self, DecodingError> {
+        let mut mins = A::bounds(N::zero());
+        let mut maxs = A::bounds(N::zero());
+
+        for bound in mins.as_mut().iter_mut().chain(maxs.as_mut().iter_mut()) {
+            *bound = N::decode(bytes)?;
+        }
+
+        Ok(KDOP::new(mins, maxs))
+    }
+}
+
+// The tag identifying the axes of a k-DOP.
+const fn kdop_axes_tag(axes: &[[i8; DIM]]) -> u64 {
+    let mut hash = type_tag("KDOPAxes", &[axes.len() as u64]);
+    let mut i = 0;
+
+    while i < axes.len() {
+        let mut k = 0;
+
+        while k < DIM {
+            hash = (hash ^ axes[i][k] as u8 as u64).wrapping_mul(FNV_PRIME);
+            k += 1;
+        }
+
+        i += 1;
+    }
+
+    hash
+}
@@ -2,6 +2,8 @@
 
 #[doc(inline)]
 pub use self::as_bytes::AsBytes;
+pub(crate) use self::binary_codec::{decode_header, decode_len, encode_header, layout_of};
+pub use self::binary_codec::{type_tag, BinaryCodec, DecodingError, BINARY_FORMAT_VERSION};
 pub use self::ccw_face_normal::ccw_face_normal;
 pub use self::center::center;
 #[cfg(feature = "dim3")]
@@ -27,6 +29,7 @@ pub use self::triangle::{
 
 #[doc(hidden)]
 pub mod as_bytes;
+mod binary_codec;
 mod ccw_face_normal;
 mod center;
 #[cfg(feature = "dim3")]